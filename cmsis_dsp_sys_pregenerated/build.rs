@@ -67,7 +67,7 @@ fn download_and_link_library(library_name: &str) -> Result<(), Box<dyn Error>> {
 }
 
 fn download_library(out_dir: &Path, library_name: &str) -> Result<(), Box<dyn Error>> {
-    let pack_file = download_cmsis_pack(&out_dir)?;
+    let pack_file = download_cmsis_pack(out_dir)?;
     let mut zip = ZipArchive::new(pack_file)?;
     // Extract one library file
     let library_archive_path = format!("CMSIS/DSP/Lib/GCC/lib{}.a", library_name);
@@ -106,7 +106,7 @@ fn download_cmsis_pack(out_dir: &Path) -> Result<File, Box<dyn Error>> {
 /// on a server that handles HEAD requests, and the local and remote files have the same size,
 /// or false otherwise
 fn fully_downloaded(url: &str, path: &Path) -> bool {
-    file_size(&path)
+    file_size(path)
         .and_then(|local_size| {
             get_remote_file_size(url)
                 .ok()
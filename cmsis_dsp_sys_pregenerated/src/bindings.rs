@@ -53,7 +53,7 @@ fn bindgen_test_layout_arm_fir_instance_q7() {
         concat!("Alignment of ", stringify!(arm_fir_instance_q7))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_fir_instance_q7>())).numTaps as *const _ as usize },
+        ::core::mem::offset_of!(arm_fir_instance_q7, numTaps),
         0usize,
         concat!(
             "Offset of field: ",
@@ -63,7 +63,7 @@ fn bindgen_test_layout_arm_fir_instance_q7() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_fir_instance_q7>())).pState as *const _ as usize },
+        ::core::mem::offset_of!(arm_fir_instance_q7, pState),
         8usize,
         concat!(
             "Offset of field: ",
@@ -73,7 +73,7 @@ fn bindgen_test_layout_arm_fir_instance_q7() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_fir_instance_q7>())).pCoeffs as *const _ as usize },
+        ::core::mem::offset_of!(arm_fir_instance_q7, pCoeffs),
         16usize,
         concat!(
             "Offset of field: ",
@@ -106,7 +106,7 @@ fn bindgen_test_layout_arm_fir_instance_q15() {
         concat!("Alignment of ", stringify!(arm_fir_instance_q15))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_fir_instance_q15>())).numTaps as *const _ as usize },
+        ::core::mem::offset_of!(arm_fir_instance_q15, numTaps),
         0usize,
         concat!(
             "Offset of field: ",
@@ -116,7 +116,7 @@ fn bindgen_test_layout_arm_fir_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_fir_instance_q15>())).pState as *const _ as usize },
+        ::core::mem::offset_of!(arm_fir_instance_q15, pState),
         8usize,
         concat!(
             "Offset of field: ",
@@ -126,7 +126,7 @@ fn bindgen_test_layout_arm_fir_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_fir_instance_q15>())).pCoeffs as *const _ as usize },
+        ::core::mem::offset_of!(arm_fir_instance_q15, pCoeffs),
         16usize,
         concat!(
             "Offset of field: ",
@@ -159,7 +159,7 @@ fn bindgen_test_layout_arm_fir_instance_q31() {
         concat!("Alignment of ", stringify!(arm_fir_instance_q31))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_fir_instance_q31>())).numTaps as *const _ as usize },
+        ::core::mem::offset_of!(arm_fir_instance_q31, numTaps),
         0usize,
         concat!(
             "Offset of field: ",
@@ -169,7 +169,7 @@ fn bindgen_test_layout_arm_fir_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_fir_instance_q31>())).pState as *const _ as usize },
+        ::core::mem::offset_of!(arm_fir_instance_q31, pState),
         8usize,
         concat!(
             "Offset of field: ",
@@ -179,7 +179,7 @@ fn bindgen_test_layout_arm_fir_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_fir_instance_q31>())).pCoeffs as *const _ as usize },
+        ::core::mem::offset_of!(arm_fir_instance_q31, pCoeffs),
         16usize,
         concat!(
             "Offset of field: ",
@@ -212,7 +212,7 @@ fn bindgen_test_layout_arm_fir_instance_f32() {
         concat!("Alignment of ", stringify!(arm_fir_instance_f32))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_fir_instance_f32>())).numTaps as *const _ as usize },
+        ::core::mem::offset_of!(arm_fir_instance_f32, numTaps),
         0usize,
         concat!(
             "Offset of field: ",
@@ -222,7 +222,7 @@ fn bindgen_test_layout_arm_fir_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_fir_instance_f32>())).pState as *const _ as usize },
+        ::core::mem::offset_of!(arm_fir_instance_f32, pState),
         8usize,
         concat!(
             "Offset of field: ",
@@ -232,7 +232,7 @@ fn bindgen_test_layout_arm_fir_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_fir_instance_f32>())).pCoeffs as *const _ as usize },
+        ::core::mem::offset_of!(arm_fir_instance_f32, pCoeffs),
         16usize,
         concat!(
             "Offset of field: ",
@@ -408,9 +408,7 @@ fn bindgen_test_layout_arm_biquad_casd_df1_inst_q15() {
         concat!("Alignment of ", stringify!(arm_biquad_casd_df1_inst_q15))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_biquad_casd_df1_inst_q15>())).numStages as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_biquad_casd_df1_inst_q15, numStages),
         0usize,
         concat!(
             "Offset of field: ",
@@ -420,9 +418,7 @@ fn bindgen_test_layout_arm_biquad_casd_df1_inst_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_biquad_casd_df1_inst_q15>())).pState as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_biquad_casd_df1_inst_q15, pState),
         8usize,
         concat!(
             "Offset of field: ",
@@ -432,9 +428,7 @@ fn bindgen_test_layout_arm_biquad_casd_df1_inst_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_biquad_casd_df1_inst_q15>())).pCoeffs as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_biquad_casd_df1_inst_q15, pCoeffs),
         16usize,
         concat!(
             "Offset of field: ",
@@ -444,9 +438,7 @@ fn bindgen_test_layout_arm_biquad_casd_df1_inst_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_biquad_casd_df1_inst_q15>())).postShift as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_biquad_casd_df1_inst_q15, postShift),
         24usize,
         concat!(
             "Offset of field: ",
@@ -481,9 +473,7 @@ fn bindgen_test_layout_arm_biquad_casd_df1_inst_q31() {
         concat!("Alignment of ", stringify!(arm_biquad_casd_df1_inst_q31))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_biquad_casd_df1_inst_q31>())).numStages as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_biquad_casd_df1_inst_q31, numStages),
         0usize,
         concat!(
             "Offset of field: ",
@@ -493,9 +483,7 @@ fn bindgen_test_layout_arm_biquad_casd_df1_inst_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_biquad_casd_df1_inst_q31>())).pState as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_biquad_casd_df1_inst_q31, pState),
         8usize,
         concat!(
             "Offset of field: ",
@@ -505,9 +493,7 @@ fn bindgen_test_layout_arm_biquad_casd_df1_inst_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_biquad_casd_df1_inst_q31>())).pCoeffs as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_biquad_casd_df1_inst_q31, pCoeffs),
         16usize,
         concat!(
             "Offset of field: ",
@@ -517,9 +503,7 @@ fn bindgen_test_layout_arm_biquad_casd_df1_inst_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_biquad_casd_df1_inst_q31>())).postShift as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_biquad_casd_df1_inst_q31, postShift),
         24usize,
         concat!(
             "Offset of field: ",
@@ -552,9 +536,7 @@ fn bindgen_test_layout_arm_biquad_casd_df1_inst_f32() {
         concat!("Alignment of ", stringify!(arm_biquad_casd_df1_inst_f32))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_biquad_casd_df1_inst_f32>())).numStages as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_biquad_casd_df1_inst_f32, numStages),
         0usize,
         concat!(
             "Offset of field: ",
@@ -564,9 +546,7 @@ fn bindgen_test_layout_arm_biquad_casd_df1_inst_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_biquad_casd_df1_inst_f32>())).pState as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_biquad_casd_df1_inst_f32, pState),
         8usize,
         concat!(
             "Offset of field: ",
@@ -576,9 +556,7 @@ fn bindgen_test_layout_arm_biquad_casd_df1_inst_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_biquad_casd_df1_inst_f32>())).pCoeffs as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_biquad_casd_df1_inst_f32, pCoeffs),
         16usize,
         concat!(
             "Offset of field: ",
@@ -834,7 +812,7 @@ fn bindgen_test_layout_arm_sort_instance_f32() {
         concat!("Alignment of ", stringify!(arm_sort_instance_f32))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_sort_instance_f32>())).alg as *const _ as usize },
+        ::core::mem::offset_of!(arm_sort_instance_f32, alg),
         0usize,
         concat!(
             "Offset of field: ",
@@ -844,7 +822,7 @@ fn bindgen_test_layout_arm_sort_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_sort_instance_f32>())).dir as *const _ as usize },
+        ::core::mem::offset_of!(arm_sort_instance_f32, dir),
         4usize,
         concat!(
             "Offset of field: ",
@@ -898,9 +876,7 @@ fn bindgen_test_layout_arm_merge_sort_instance_f32() {
         concat!("Alignment of ", stringify!(arm_merge_sort_instance_f32))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_merge_sort_instance_f32>())).dir as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_merge_sort_instance_f32, dir),
         0usize,
         concat!(
             "Offset of field: ",
@@ -910,9 +886,7 @@ fn bindgen_test_layout_arm_merge_sort_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_merge_sort_instance_f32>())).buffer as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_merge_sort_instance_f32, buffer),
         8usize,
         concat!(
             "Offset of field: ",
@@ -979,7 +953,7 @@ fn bindgen_test_layout_arm_spline_instance_f32() {
         concat!("Alignment of ", stringify!(arm_spline_instance_f32))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_spline_instance_f32>())).type_ as *const _ as usize },
+        ::core::mem::offset_of!(arm_spline_instance_f32, type_),
         0usize,
         concat!(
             "Offset of field: ",
@@ -989,7 +963,7 @@ fn bindgen_test_layout_arm_spline_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_spline_instance_f32>())).x as *const _ as usize },
+        ::core::mem::offset_of!(arm_spline_instance_f32, x),
         8usize,
         concat!(
             "Offset of field: ",
@@ -999,7 +973,7 @@ fn bindgen_test_layout_arm_spline_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_spline_instance_f32>())).y as *const _ as usize },
+        ::core::mem::offset_of!(arm_spline_instance_f32, y),
         16usize,
         concat!(
             "Offset of field: ",
@@ -1009,7 +983,7 @@ fn bindgen_test_layout_arm_spline_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_spline_instance_f32>())).n_x as *const _ as usize },
+        ::core::mem::offset_of!(arm_spline_instance_f32, n_x),
         24usize,
         concat!(
             "Offset of field: ",
@@ -1019,7 +993,7 @@ fn bindgen_test_layout_arm_spline_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_spline_instance_f32>())).coeffs as *const _ as usize },
+        ::core::mem::offset_of!(arm_spline_instance_f32, coeffs),
         32usize,
         concat!(
             "Offset of field: ",
@@ -1084,9 +1058,7 @@ fn bindgen_test_layout_arm_matrix_instance_f32() {
         concat!("Alignment of ", stringify!(arm_matrix_instance_f32))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_matrix_instance_f32>())).numRows as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_matrix_instance_f32, numRows),
         0usize,
         concat!(
             "Offset of field: ",
@@ -1096,9 +1068,7 @@ fn bindgen_test_layout_arm_matrix_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_matrix_instance_f32>())).numCols as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_matrix_instance_f32, numCols),
         2usize,
         concat!(
             "Offset of field: ",
@@ -1108,7 +1078,7 @@ fn bindgen_test_layout_arm_matrix_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_matrix_instance_f32>())).pData as *const _ as usize },
+        ::core::mem::offset_of!(arm_matrix_instance_f32, pData),
         8usize,
         concat!(
             "Offset of field: ",
@@ -1141,9 +1111,7 @@ fn bindgen_test_layout_arm_matrix_instance_f64() {
         concat!("Alignment of ", stringify!(arm_matrix_instance_f64))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_matrix_instance_f64>())).numRows as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_matrix_instance_f64, numRows),
         0usize,
         concat!(
             "Offset of field: ",
@@ -1153,9 +1121,7 @@ fn bindgen_test_layout_arm_matrix_instance_f64() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_matrix_instance_f64>())).numCols as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_matrix_instance_f64, numCols),
         2usize,
         concat!(
             "Offset of field: ",
@@ -1165,7 +1131,7 @@ fn bindgen_test_layout_arm_matrix_instance_f64() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_matrix_instance_f64>())).pData as *const _ as usize },
+        ::core::mem::offset_of!(arm_matrix_instance_f64, pData),
         8usize,
         concat!(
             "Offset of field: ",
@@ -1198,9 +1164,7 @@ fn bindgen_test_layout_arm_matrix_instance_q15() {
         concat!("Alignment of ", stringify!(arm_matrix_instance_q15))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_matrix_instance_q15>())).numRows as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_matrix_instance_q15, numRows),
         0usize,
         concat!(
             "Offset of field: ",
@@ -1210,9 +1174,7 @@ fn bindgen_test_layout_arm_matrix_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_matrix_instance_q15>())).numCols as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_matrix_instance_q15, numCols),
         2usize,
         concat!(
             "Offset of field: ",
@@ -1222,7 +1184,7 @@ fn bindgen_test_layout_arm_matrix_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_matrix_instance_q15>())).pData as *const _ as usize },
+        ::core::mem::offset_of!(arm_matrix_instance_q15, pData),
         8usize,
         concat!(
             "Offset of field: ",
@@ -1255,9 +1217,7 @@ fn bindgen_test_layout_arm_matrix_instance_q31() {
         concat!("Alignment of ", stringify!(arm_matrix_instance_q31))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_matrix_instance_q31>())).numRows as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_matrix_instance_q31, numRows),
         0usize,
         concat!(
             "Offset of field: ",
@@ -1267,9 +1227,7 @@ fn bindgen_test_layout_arm_matrix_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_matrix_instance_q31>())).numCols as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_matrix_instance_q31, numCols),
         2usize,
         concat!(
             "Offset of field: ",
@@ -1279,7 +1237,7 @@ fn bindgen_test_layout_arm_matrix_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_matrix_instance_q31>())).pData as *const _ as usize },
+        ::core::mem::offset_of!(arm_matrix_instance_q31, pData),
         8usize,
         concat!(
             "Offset of field: ",
@@ -1620,7 +1578,7 @@ fn bindgen_test_layout_arm_pid_instance_q15() {
         concat!("Alignment of ", stringify!(arm_pid_instance_q15))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_pid_instance_q15>())).A0 as *const _ as usize },
+        ::core::mem::offset_of!(arm_pid_instance_q15, A0),
         0usize,
         concat!(
             "Offset of field: ",
@@ -1630,7 +1588,7 @@ fn bindgen_test_layout_arm_pid_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_pid_instance_q15>())).A1 as *const _ as usize },
+        ::core::mem::offset_of!(arm_pid_instance_q15, A1),
         2usize,
         concat!(
             "Offset of field: ",
@@ -1640,7 +1598,7 @@ fn bindgen_test_layout_arm_pid_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_pid_instance_q15>())).A2 as *const _ as usize },
+        ::core::mem::offset_of!(arm_pid_instance_q15, A2),
         4usize,
         concat!(
             "Offset of field: ",
@@ -1650,7 +1608,7 @@ fn bindgen_test_layout_arm_pid_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_pid_instance_q15>())).state as *const _ as usize },
+        ::core::mem::offset_of!(arm_pid_instance_q15, state),
         6usize,
         concat!(
             "Offset of field: ",
@@ -1660,7 +1618,7 @@ fn bindgen_test_layout_arm_pid_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_pid_instance_q15>())).Kp as *const _ as usize },
+        ::core::mem::offset_of!(arm_pid_instance_q15, Kp),
         12usize,
         concat!(
             "Offset of field: ",
@@ -1670,7 +1628,7 @@ fn bindgen_test_layout_arm_pid_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_pid_instance_q15>())).Ki as *const _ as usize },
+        ::core::mem::offset_of!(arm_pid_instance_q15, Ki),
         14usize,
         concat!(
             "Offset of field: ",
@@ -1680,7 +1638,7 @@ fn bindgen_test_layout_arm_pid_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_pid_instance_q15>())).Kd as *const _ as usize },
+        ::core::mem::offset_of!(arm_pid_instance_q15, Kd),
         16usize,
         concat!(
             "Offset of field: ",
@@ -1721,7 +1679,7 @@ fn bindgen_test_layout_arm_pid_instance_q31() {
         concat!("Alignment of ", stringify!(arm_pid_instance_q31))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_pid_instance_q31>())).A0 as *const _ as usize },
+        ::core::mem::offset_of!(arm_pid_instance_q31, A0),
         0usize,
         concat!(
             "Offset of field: ",
@@ -1731,7 +1689,7 @@ fn bindgen_test_layout_arm_pid_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_pid_instance_q31>())).A1 as *const _ as usize },
+        ::core::mem::offset_of!(arm_pid_instance_q31, A1),
         4usize,
         concat!(
             "Offset of field: ",
@@ -1741,7 +1699,7 @@ fn bindgen_test_layout_arm_pid_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_pid_instance_q31>())).A2 as *const _ as usize },
+        ::core::mem::offset_of!(arm_pid_instance_q31, A2),
         8usize,
         concat!(
             "Offset of field: ",
@@ -1751,7 +1709,7 @@ fn bindgen_test_layout_arm_pid_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_pid_instance_q31>())).state as *const _ as usize },
+        ::core::mem::offset_of!(arm_pid_instance_q31, state),
         12usize,
         concat!(
             "Offset of field: ",
@@ -1761,7 +1719,7 @@ fn bindgen_test_layout_arm_pid_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_pid_instance_q31>())).Kp as *const _ as usize },
+        ::core::mem::offset_of!(arm_pid_instance_q31, Kp),
         24usize,
         concat!(
             "Offset of field: ",
@@ -1771,7 +1729,7 @@ fn bindgen_test_layout_arm_pid_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_pid_instance_q31>())).Ki as *const _ as usize },
+        ::core::mem::offset_of!(arm_pid_instance_q31, Ki),
         28usize,
         concat!(
             "Offset of field: ",
@@ -1781,7 +1739,7 @@ fn bindgen_test_layout_arm_pid_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_pid_instance_q31>())).Kd as *const _ as usize },
+        ::core::mem::offset_of!(arm_pid_instance_q31, Kd),
         32usize,
         concat!(
             "Offset of field: ",
@@ -1823,7 +1781,7 @@ fn bindgen_test_layout_arm_pid_instance_f32() {
         concat!("Alignment of ", stringify!(arm_pid_instance_f32))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_pid_instance_f32>())).A0 as *const _ as usize },
+        ::core::mem::offset_of!(arm_pid_instance_f32, A0),
         0usize,
         concat!(
             "Offset of field: ",
@@ -1833,7 +1791,7 @@ fn bindgen_test_layout_arm_pid_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_pid_instance_f32>())).A1 as *const _ as usize },
+        ::core::mem::offset_of!(arm_pid_instance_f32, A1),
         4usize,
         concat!(
             "Offset of field: ",
@@ -1843,7 +1801,7 @@ fn bindgen_test_layout_arm_pid_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_pid_instance_f32>())).A2 as *const _ as usize },
+        ::core::mem::offset_of!(arm_pid_instance_f32, A2),
         8usize,
         concat!(
             "Offset of field: ",
@@ -1853,7 +1811,7 @@ fn bindgen_test_layout_arm_pid_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_pid_instance_f32>())).state as *const _ as usize },
+        ::core::mem::offset_of!(arm_pid_instance_f32, state),
         12usize,
         concat!(
             "Offset of field: ",
@@ -1863,7 +1821,7 @@ fn bindgen_test_layout_arm_pid_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_pid_instance_f32>())).Kp as *const _ as usize },
+        ::core::mem::offset_of!(arm_pid_instance_f32, Kp),
         24usize,
         concat!(
             "Offset of field: ",
@@ -1873,7 +1831,7 @@ fn bindgen_test_layout_arm_pid_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_pid_instance_f32>())).Ki as *const _ as usize },
+        ::core::mem::offset_of!(arm_pid_instance_f32, Ki),
         28usize,
         concat!(
             "Offset of field: ",
@@ -1883,7 +1841,7 @@ fn bindgen_test_layout_arm_pid_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_pid_instance_f32>())).Kd as *const _ as usize },
+        ::core::mem::offset_of!(arm_pid_instance_f32, Kd),
         32usize,
         concat!(
             "Offset of field: ",
@@ -1994,9 +1952,7 @@ fn bindgen_test_layout_arm_cfft_radix2_instance_q15() {
         concat!("Alignment of ", stringify!(arm_cfft_radix2_instance_q15))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix2_instance_q15>())).fftLen as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix2_instance_q15, fftLen),
         0usize,
         concat!(
             "Offset of field: ",
@@ -2006,9 +1962,7 @@ fn bindgen_test_layout_arm_cfft_radix2_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix2_instance_q15>())).ifftFlag as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix2_instance_q15, ifftFlag),
         2usize,
         concat!(
             "Offset of field: ",
@@ -2018,10 +1972,7 @@ fn bindgen_test_layout_arm_cfft_radix2_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix2_instance_q15>())).bitReverseFlag as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix2_instance_q15, bitReverseFlag),
         3usize,
         concat!(
             "Offset of field: ",
@@ -2031,9 +1982,7 @@ fn bindgen_test_layout_arm_cfft_radix2_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix2_instance_q15>())).pTwiddle as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix2_instance_q15, pTwiddle),
         8usize,
         concat!(
             "Offset of field: ",
@@ -2043,10 +1992,7 @@ fn bindgen_test_layout_arm_cfft_radix2_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix2_instance_q15>())).pBitRevTable as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix2_instance_q15, pBitRevTable),
         16usize,
         concat!(
             "Offset of field: ",
@@ -2056,10 +2002,7 @@ fn bindgen_test_layout_arm_cfft_radix2_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix2_instance_q15>())).twidCoefModifier as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix2_instance_q15, twidCoefModifier),
         24usize,
         concat!(
             "Offset of field: ",
@@ -2069,10 +2012,7 @@ fn bindgen_test_layout_arm_cfft_radix2_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix2_instance_q15>())).bitRevFactor as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix2_instance_q15, bitRevFactor),
         26usize,
         concat!(
             "Offset of field: ",
@@ -2124,9 +2064,7 @@ fn bindgen_test_layout_arm_cfft_radix4_instance_q15() {
         concat!("Alignment of ", stringify!(arm_cfft_radix4_instance_q15))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix4_instance_q15>())).fftLen as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix4_instance_q15, fftLen),
         0usize,
         concat!(
             "Offset of field: ",
@@ -2136,9 +2074,7 @@ fn bindgen_test_layout_arm_cfft_radix4_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix4_instance_q15>())).ifftFlag as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix4_instance_q15, ifftFlag),
         2usize,
         concat!(
             "Offset of field: ",
@@ -2148,10 +2084,7 @@ fn bindgen_test_layout_arm_cfft_radix4_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix4_instance_q15>())).bitReverseFlag as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix4_instance_q15, bitReverseFlag),
         3usize,
         concat!(
             "Offset of field: ",
@@ -2161,9 +2094,7 @@ fn bindgen_test_layout_arm_cfft_radix4_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix4_instance_q15>())).pTwiddle as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix4_instance_q15, pTwiddle),
         8usize,
         concat!(
             "Offset of field: ",
@@ -2173,10 +2104,7 @@ fn bindgen_test_layout_arm_cfft_radix4_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix4_instance_q15>())).pBitRevTable as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix4_instance_q15, pBitRevTable),
         16usize,
         concat!(
             "Offset of field: ",
@@ -2186,10 +2114,7 @@ fn bindgen_test_layout_arm_cfft_radix4_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix4_instance_q15>())).twidCoefModifier as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix4_instance_q15, twidCoefModifier),
         24usize,
         concat!(
             "Offset of field: ",
@@ -2199,10 +2124,7 @@ fn bindgen_test_layout_arm_cfft_radix4_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix4_instance_q15>())).bitRevFactor as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix4_instance_q15, bitRevFactor),
         26usize,
         concat!(
             "Offset of field: ",
@@ -2254,9 +2176,7 @@ fn bindgen_test_layout_arm_cfft_radix2_instance_q31() {
         concat!("Alignment of ", stringify!(arm_cfft_radix2_instance_q31))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix2_instance_q31>())).fftLen as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix2_instance_q31, fftLen),
         0usize,
         concat!(
             "Offset of field: ",
@@ -2266,9 +2186,7 @@ fn bindgen_test_layout_arm_cfft_radix2_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix2_instance_q31>())).ifftFlag as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix2_instance_q31, ifftFlag),
         2usize,
         concat!(
             "Offset of field: ",
@@ -2278,10 +2196,7 @@ fn bindgen_test_layout_arm_cfft_radix2_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix2_instance_q31>())).bitReverseFlag as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix2_instance_q31, bitReverseFlag),
         3usize,
         concat!(
             "Offset of field: ",
@@ -2291,9 +2206,7 @@ fn bindgen_test_layout_arm_cfft_radix2_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix2_instance_q31>())).pTwiddle as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix2_instance_q31, pTwiddle),
         8usize,
         concat!(
             "Offset of field: ",
@@ -2303,10 +2216,7 @@ fn bindgen_test_layout_arm_cfft_radix2_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix2_instance_q31>())).pBitRevTable as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix2_instance_q31, pBitRevTable),
         16usize,
         concat!(
             "Offset of field: ",
@@ -2316,10 +2226,7 @@ fn bindgen_test_layout_arm_cfft_radix2_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix2_instance_q31>())).twidCoefModifier as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix2_instance_q31, twidCoefModifier),
         24usize,
         concat!(
             "Offset of field: ",
@@ -2329,10 +2236,7 @@ fn bindgen_test_layout_arm_cfft_radix2_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix2_instance_q31>())).bitRevFactor as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix2_instance_q31, bitRevFactor),
         26usize,
         concat!(
             "Offset of field: ",
@@ -2384,9 +2288,7 @@ fn bindgen_test_layout_arm_cfft_radix4_instance_q31() {
         concat!("Alignment of ", stringify!(arm_cfft_radix4_instance_q31))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix4_instance_q31>())).fftLen as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix4_instance_q31, fftLen),
         0usize,
         concat!(
             "Offset of field: ",
@@ -2396,9 +2298,7 @@ fn bindgen_test_layout_arm_cfft_radix4_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix4_instance_q31>())).ifftFlag as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix4_instance_q31, ifftFlag),
         2usize,
         concat!(
             "Offset of field: ",
@@ -2408,10 +2308,7 @@ fn bindgen_test_layout_arm_cfft_radix4_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix4_instance_q31>())).bitReverseFlag as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix4_instance_q31, bitReverseFlag),
         3usize,
         concat!(
             "Offset of field: ",
@@ -2421,9 +2318,7 @@ fn bindgen_test_layout_arm_cfft_radix4_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix4_instance_q31>())).pTwiddle as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix4_instance_q31, pTwiddle),
         8usize,
         concat!(
             "Offset of field: ",
@@ -2433,10 +2328,7 @@ fn bindgen_test_layout_arm_cfft_radix4_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix4_instance_q31>())).pBitRevTable as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix4_instance_q31, pBitRevTable),
         16usize,
         concat!(
             "Offset of field: ",
@@ -2446,10 +2338,7 @@ fn bindgen_test_layout_arm_cfft_radix4_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix4_instance_q31>())).twidCoefModifier as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix4_instance_q31, twidCoefModifier),
         24usize,
         concat!(
             "Offset of field: ",
@@ -2459,10 +2348,7 @@ fn bindgen_test_layout_arm_cfft_radix4_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix4_instance_q31>())).bitRevFactor as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix4_instance_q31, bitRevFactor),
         26usize,
         concat!(
             "Offset of field: ",
@@ -2516,9 +2402,7 @@ fn bindgen_test_layout_arm_cfft_radix2_instance_f32() {
         concat!("Alignment of ", stringify!(arm_cfft_radix2_instance_f32))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix2_instance_f32>())).fftLen as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix2_instance_f32, fftLen),
         0usize,
         concat!(
             "Offset of field: ",
@@ -2528,9 +2412,7 @@ fn bindgen_test_layout_arm_cfft_radix2_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix2_instance_f32>())).ifftFlag as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix2_instance_f32, ifftFlag),
         2usize,
         concat!(
             "Offset of field: ",
@@ -2540,10 +2422,7 @@ fn bindgen_test_layout_arm_cfft_radix2_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix2_instance_f32>())).bitReverseFlag as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix2_instance_f32, bitReverseFlag),
         3usize,
         concat!(
             "Offset of field: ",
@@ -2553,9 +2432,7 @@ fn bindgen_test_layout_arm_cfft_radix2_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix2_instance_f32>())).pTwiddle as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix2_instance_f32, pTwiddle),
         8usize,
         concat!(
             "Offset of field: ",
@@ -2565,10 +2442,7 @@ fn bindgen_test_layout_arm_cfft_radix2_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix2_instance_f32>())).pBitRevTable as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix2_instance_f32, pBitRevTable),
         16usize,
         concat!(
             "Offset of field: ",
@@ -2578,10 +2452,7 @@ fn bindgen_test_layout_arm_cfft_radix2_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix2_instance_f32>())).twidCoefModifier as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix2_instance_f32, twidCoefModifier),
         24usize,
         concat!(
             "Offset of field: ",
@@ -2591,10 +2462,7 @@ fn bindgen_test_layout_arm_cfft_radix2_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix2_instance_f32>())).bitRevFactor as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix2_instance_f32, bitRevFactor),
         26usize,
         concat!(
             "Offset of field: ",
@@ -2604,10 +2472,7 @@ fn bindgen_test_layout_arm_cfft_radix2_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix2_instance_f32>())).onebyfftLen as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix2_instance_f32, onebyfftLen),
         28usize,
         concat!(
             "Offset of field: ",
@@ -2661,9 +2526,7 @@ fn bindgen_test_layout_arm_cfft_radix4_instance_f32() {
         concat!("Alignment of ", stringify!(arm_cfft_radix4_instance_f32))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix4_instance_f32>())).fftLen as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix4_instance_f32, fftLen),
         0usize,
         concat!(
             "Offset of field: ",
@@ -2673,9 +2536,7 @@ fn bindgen_test_layout_arm_cfft_radix4_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix4_instance_f32>())).ifftFlag as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix4_instance_f32, ifftFlag),
         2usize,
         concat!(
             "Offset of field: ",
@@ -2685,10 +2546,7 @@ fn bindgen_test_layout_arm_cfft_radix4_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix4_instance_f32>())).bitReverseFlag as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix4_instance_f32, bitReverseFlag),
         3usize,
         concat!(
             "Offset of field: ",
@@ -2698,9 +2556,7 @@ fn bindgen_test_layout_arm_cfft_radix4_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix4_instance_f32>())).pTwiddle as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix4_instance_f32, pTwiddle),
         8usize,
         concat!(
             "Offset of field: ",
@@ -2710,10 +2566,7 @@ fn bindgen_test_layout_arm_cfft_radix4_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix4_instance_f32>())).pBitRevTable as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix4_instance_f32, pBitRevTable),
         16usize,
         concat!(
             "Offset of field: ",
@@ -2723,10 +2576,7 @@ fn bindgen_test_layout_arm_cfft_radix4_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix4_instance_f32>())).twidCoefModifier as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix4_instance_f32, twidCoefModifier),
         24usize,
         concat!(
             "Offset of field: ",
@@ -2736,10 +2586,7 @@ fn bindgen_test_layout_arm_cfft_radix4_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix4_instance_f32>())).bitRevFactor as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix4_instance_f32, bitRevFactor),
         26usize,
         concat!(
             "Offset of field: ",
@@ -2749,10 +2596,7 @@ fn bindgen_test_layout_arm_cfft_radix4_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_radix4_instance_f32>())).onebyfftLen as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_radix4_instance_f32, onebyfftLen),
         28usize,
         concat!(
             "Offset of field: ",
@@ -2798,7 +2642,7 @@ fn bindgen_test_layout_arm_cfft_instance_q15() {
         concat!("Alignment of ", stringify!(arm_cfft_instance_q15))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_cfft_instance_q15>())).fftLen as *const _ as usize },
+        ::core::mem::offset_of!(arm_cfft_instance_q15, fftLen),
         0usize,
         concat!(
             "Offset of field: ",
@@ -2808,7 +2652,7 @@ fn bindgen_test_layout_arm_cfft_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_cfft_instance_q15>())).pTwiddle as *const _ as usize },
+        ::core::mem::offset_of!(arm_cfft_instance_q15, pTwiddle),
         8usize,
         concat!(
             "Offset of field: ",
@@ -2818,9 +2662,7 @@ fn bindgen_test_layout_arm_cfft_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_instance_q15>())).pBitRevTable as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_instance_q15, pBitRevTable),
         16usize,
         concat!(
             "Offset of field: ",
@@ -2830,9 +2672,7 @@ fn bindgen_test_layout_arm_cfft_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_instance_q15>())).bitRevLength as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_instance_q15, bitRevLength),
         24usize,
         concat!(
             "Offset of field: ",
@@ -2878,7 +2718,7 @@ fn bindgen_test_layout_arm_cfft_instance_q31() {
         concat!("Alignment of ", stringify!(arm_cfft_instance_q31))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_cfft_instance_q31>())).fftLen as *const _ as usize },
+        ::core::mem::offset_of!(arm_cfft_instance_q31, fftLen),
         0usize,
         concat!(
             "Offset of field: ",
@@ -2888,7 +2728,7 @@ fn bindgen_test_layout_arm_cfft_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_cfft_instance_q31>())).pTwiddle as *const _ as usize },
+        ::core::mem::offset_of!(arm_cfft_instance_q31, pTwiddle),
         8usize,
         concat!(
             "Offset of field: ",
@@ -2898,9 +2738,7 @@ fn bindgen_test_layout_arm_cfft_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_instance_q31>())).pBitRevTable as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_instance_q31, pBitRevTable),
         16usize,
         concat!(
             "Offset of field: ",
@@ -2910,9 +2748,7 @@ fn bindgen_test_layout_arm_cfft_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_instance_q31>())).bitRevLength as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_instance_q31, bitRevLength),
         24usize,
         concat!(
             "Offset of field: ",
@@ -2958,7 +2794,7 @@ fn bindgen_test_layout_arm_cfft_instance_f32() {
         concat!("Alignment of ", stringify!(arm_cfft_instance_f32))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_cfft_instance_f32>())).fftLen as *const _ as usize },
+        ::core::mem::offset_of!(arm_cfft_instance_f32, fftLen),
         0usize,
         concat!(
             "Offset of field: ",
@@ -2968,7 +2804,7 @@ fn bindgen_test_layout_arm_cfft_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_cfft_instance_f32>())).pTwiddle as *const _ as usize },
+        ::core::mem::offset_of!(arm_cfft_instance_f32, pTwiddle),
         8usize,
         concat!(
             "Offset of field: ",
@@ -2978,9 +2814,7 @@ fn bindgen_test_layout_arm_cfft_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_instance_f32>())).pBitRevTable as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_instance_f32, pBitRevTable),
         16usize,
         concat!(
             "Offset of field: ",
@@ -2990,9 +2824,7 @@ fn bindgen_test_layout_arm_cfft_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_instance_f32>())).bitRevLength as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_instance_f32, bitRevLength),
         24usize,
         concat!(
             "Offset of field: ",
@@ -3038,7 +2870,7 @@ fn bindgen_test_layout_arm_cfft_instance_f64() {
         concat!("Alignment of ", stringify!(arm_cfft_instance_f64))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_cfft_instance_f64>())).fftLen as *const _ as usize },
+        ::core::mem::offset_of!(arm_cfft_instance_f64, fftLen),
         0usize,
         concat!(
             "Offset of field: ",
@@ -3048,7 +2880,7 @@ fn bindgen_test_layout_arm_cfft_instance_f64() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_cfft_instance_f64>())).pTwiddle as *const _ as usize },
+        ::core::mem::offset_of!(arm_cfft_instance_f64, pTwiddle),
         8usize,
         concat!(
             "Offset of field: ",
@@ -3058,9 +2890,7 @@ fn bindgen_test_layout_arm_cfft_instance_f64() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_instance_f64>())).pBitRevTable as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_instance_f64, pBitRevTable),
         16usize,
         concat!(
             "Offset of field: ",
@@ -3070,9 +2900,7 @@ fn bindgen_test_layout_arm_cfft_instance_f64() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_cfft_instance_f64>())).bitRevLength as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_cfft_instance_f64, bitRevLength),
         24usize,
         concat!(
             "Offset of field: ",
@@ -3121,9 +2949,7 @@ fn bindgen_test_layout_arm_rfft_instance_q15() {
         concat!("Alignment of ", stringify!(arm_rfft_instance_q15))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_rfft_instance_q15>())).fftLenReal as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_rfft_instance_q15, fftLenReal),
         0usize,
         concat!(
             "Offset of field: ",
@@ -3133,9 +2959,7 @@ fn bindgen_test_layout_arm_rfft_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_rfft_instance_q15>())).ifftFlagR as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_rfft_instance_q15, ifftFlagR),
         4usize,
         concat!(
             "Offset of field: ",
@@ -3145,9 +2969,7 @@ fn bindgen_test_layout_arm_rfft_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_rfft_instance_q15>())).bitReverseFlagR as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_rfft_instance_q15, bitReverseFlagR),
         5usize,
         concat!(
             "Offset of field: ",
@@ -3157,10 +2979,7 @@ fn bindgen_test_layout_arm_rfft_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_rfft_instance_q15>())).twidCoefRModifier as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_rfft_instance_q15, twidCoefRModifier),
         8usize,
         concat!(
             "Offset of field: ",
@@ -3170,9 +2989,7 @@ fn bindgen_test_layout_arm_rfft_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_rfft_instance_q15>())).pTwiddleAReal as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_rfft_instance_q15, pTwiddleAReal),
         16usize,
         concat!(
             "Offset of field: ",
@@ -3182,9 +2999,7 @@ fn bindgen_test_layout_arm_rfft_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_rfft_instance_q15>())).pTwiddleBReal as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_rfft_instance_q15, pTwiddleBReal),
         24usize,
         concat!(
             "Offset of field: ",
@@ -3194,7 +3009,7 @@ fn bindgen_test_layout_arm_rfft_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_rfft_instance_q15>())).pCfft as *const _ as usize },
+        ::core::mem::offset_of!(arm_rfft_instance_q15, pCfft),
         32usize,
         concat!(
             "Offset of field: ",
@@ -3246,9 +3061,7 @@ fn bindgen_test_layout_arm_rfft_instance_q31() {
         concat!("Alignment of ", stringify!(arm_rfft_instance_q31))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_rfft_instance_q31>())).fftLenReal as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_rfft_instance_q31, fftLenReal),
         0usize,
         concat!(
             "Offset of field: ",
@@ -3258,9 +3071,7 @@ fn bindgen_test_layout_arm_rfft_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_rfft_instance_q31>())).ifftFlagR as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_rfft_instance_q31, ifftFlagR),
         4usize,
         concat!(
             "Offset of field: ",
@@ -3270,9 +3081,7 @@ fn bindgen_test_layout_arm_rfft_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_rfft_instance_q31>())).bitReverseFlagR as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_rfft_instance_q31, bitReverseFlagR),
         5usize,
         concat!(
             "Offset of field: ",
@@ -3282,10 +3091,7 @@ fn bindgen_test_layout_arm_rfft_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_rfft_instance_q31>())).twidCoefRModifier as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_rfft_instance_q31, twidCoefRModifier),
         8usize,
         concat!(
             "Offset of field: ",
@@ -3295,9 +3101,7 @@ fn bindgen_test_layout_arm_rfft_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_rfft_instance_q31>())).pTwiddleAReal as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_rfft_instance_q31, pTwiddleAReal),
         16usize,
         concat!(
             "Offset of field: ",
@@ -3307,9 +3111,7 @@ fn bindgen_test_layout_arm_rfft_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_rfft_instance_q31>())).pTwiddleBReal as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_rfft_instance_q31, pTwiddleBReal),
         24usize,
         concat!(
             "Offset of field: ",
@@ -3319,7 +3121,7 @@ fn bindgen_test_layout_arm_rfft_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_rfft_instance_q31>())).pCfft as *const _ as usize },
+        ::core::mem::offset_of!(arm_rfft_instance_q31, pCfft),
         32usize,
         concat!(
             "Offset of field: ",
@@ -3373,9 +3175,7 @@ fn bindgen_test_layout_arm_rfft_instance_f32() {
         concat!("Alignment of ", stringify!(arm_rfft_instance_f32))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_rfft_instance_f32>())).fftLenReal as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_rfft_instance_f32, fftLenReal),
         0usize,
         concat!(
             "Offset of field: ",
@@ -3385,9 +3185,7 @@ fn bindgen_test_layout_arm_rfft_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_rfft_instance_f32>())).fftLenBy2 as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_rfft_instance_f32, fftLenBy2),
         4usize,
         concat!(
             "Offset of field: ",
@@ -3397,9 +3195,7 @@ fn bindgen_test_layout_arm_rfft_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_rfft_instance_f32>())).ifftFlagR as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_rfft_instance_f32, ifftFlagR),
         6usize,
         concat!(
             "Offset of field: ",
@@ -3409,9 +3205,7 @@ fn bindgen_test_layout_arm_rfft_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_rfft_instance_f32>())).bitReverseFlagR as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_rfft_instance_f32, bitReverseFlagR),
         7usize,
         concat!(
             "Offset of field: ",
@@ -3421,10 +3215,7 @@ fn bindgen_test_layout_arm_rfft_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_rfft_instance_f32>())).twidCoefRModifier as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_rfft_instance_f32, twidCoefRModifier),
         8usize,
         concat!(
             "Offset of field: ",
@@ -3434,9 +3225,7 @@ fn bindgen_test_layout_arm_rfft_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_rfft_instance_f32>())).pTwiddleAReal as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_rfft_instance_f32, pTwiddleAReal),
         16usize,
         concat!(
             "Offset of field: ",
@@ -3446,9 +3235,7 @@ fn bindgen_test_layout_arm_rfft_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_rfft_instance_f32>())).pTwiddleBReal as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_rfft_instance_f32, pTwiddleBReal),
         24usize,
         concat!(
             "Offset of field: ",
@@ -3458,7 +3245,7 @@ fn bindgen_test_layout_arm_rfft_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_rfft_instance_f32>())).pCfft as *const _ as usize },
+        ::core::mem::offset_of!(arm_rfft_instance_f32, pCfft),
         32usize,
         concat!(
             "Offset of field: ",
@@ -3507,9 +3294,7 @@ fn bindgen_test_layout_arm_rfft_fast_instance_f64() {
         concat!("Alignment of ", stringify!(arm_rfft_fast_instance_f64))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_rfft_fast_instance_f64>())).Sint as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_rfft_fast_instance_f64, Sint),
         0usize,
         concat!(
             "Offset of field: ",
@@ -3519,9 +3304,7 @@ fn bindgen_test_layout_arm_rfft_fast_instance_f64() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_rfft_fast_instance_f64>())).fftLenRFFT as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_rfft_fast_instance_f64, fftLenRFFT),
         32usize,
         concat!(
             "Offset of field: ",
@@ -3531,10 +3314,7 @@ fn bindgen_test_layout_arm_rfft_fast_instance_f64() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_rfft_fast_instance_f64>())).pTwiddleRFFT as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_rfft_fast_instance_f64, pTwiddleRFFT),
         40usize,
         concat!(
             "Offset of field: ",
@@ -3581,9 +3361,7 @@ fn bindgen_test_layout_arm_rfft_fast_instance_f32() {
         concat!("Alignment of ", stringify!(arm_rfft_fast_instance_f32))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_rfft_fast_instance_f32>())).Sint as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_rfft_fast_instance_f32, Sint),
         0usize,
         concat!(
             "Offset of field: ",
@@ -3593,9 +3371,7 @@ fn bindgen_test_layout_arm_rfft_fast_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_rfft_fast_instance_f32>())).fftLenRFFT as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_rfft_fast_instance_f32, fftLenRFFT),
         32usize,
         concat!(
             "Offset of field: ",
@@ -3605,10 +3381,7 @@ fn bindgen_test_layout_arm_rfft_fast_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_rfft_fast_instance_f32>())).pTwiddleRFFT as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_rfft_fast_instance_f32, pTwiddleRFFT),
         40usize,
         concat!(
             "Offset of field: ",
@@ -3663,7 +3436,7 @@ fn bindgen_test_layout_arm_dct4_instance_f32() {
         concat!("Alignment of ", stringify!(arm_dct4_instance_f32))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_dct4_instance_f32>())).N as *const _ as usize },
+        ::core::mem::offset_of!(arm_dct4_instance_f32, N),
         0usize,
         concat!(
             "Offset of field: ",
@@ -3673,7 +3446,7 @@ fn bindgen_test_layout_arm_dct4_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_dct4_instance_f32>())).Nby2 as *const _ as usize },
+        ::core::mem::offset_of!(arm_dct4_instance_f32, Nby2),
         2usize,
         concat!(
             "Offset of field: ",
@@ -3683,9 +3456,7 @@ fn bindgen_test_layout_arm_dct4_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_dct4_instance_f32>())).normalize as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_dct4_instance_f32, normalize),
         4usize,
         concat!(
             "Offset of field: ",
@@ -3695,7 +3466,7 @@ fn bindgen_test_layout_arm_dct4_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_dct4_instance_f32>())).pTwiddle as *const _ as usize },
+        ::core::mem::offset_of!(arm_dct4_instance_f32, pTwiddle),
         8usize,
         concat!(
             "Offset of field: ",
@@ -3705,9 +3476,7 @@ fn bindgen_test_layout_arm_dct4_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_dct4_instance_f32>())).pCosFactor as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_dct4_instance_f32, pCosFactor),
         16usize,
         concat!(
             "Offset of field: ",
@@ -3717,7 +3486,7 @@ fn bindgen_test_layout_arm_dct4_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_dct4_instance_f32>())).pRfft as *const _ as usize },
+        ::core::mem::offset_of!(arm_dct4_instance_f32, pRfft),
         24usize,
         concat!(
             "Offset of field: ",
@@ -3727,7 +3496,7 @@ fn bindgen_test_layout_arm_dct4_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_dct4_instance_f32>())).pCfft as *const _ as usize },
+        ::core::mem::offset_of!(arm_dct4_instance_f32, pCfft),
         32usize,
         concat!(
             "Offset of field: ",
@@ -3797,7 +3566,7 @@ fn bindgen_test_layout_arm_dct4_instance_q31() {
         concat!("Alignment of ", stringify!(arm_dct4_instance_q31))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_dct4_instance_q31>())).N as *const _ as usize },
+        ::core::mem::offset_of!(arm_dct4_instance_q31, N),
         0usize,
         concat!(
             "Offset of field: ",
@@ -3807,7 +3576,7 @@ fn bindgen_test_layout_arm_dct4_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_dct4_instance_q31>())).Nby2 as *const _ as usize },
+        ::core::mem::offset_of!(arm_dct4_instance_q31, Nby2),
         2usize,
         concat!(
             "Offset of field: ",
@@ -3817,9 +3586,7 @@ fn bindgen_test_layout_arm_dct4_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_dct4_instance_q31>())).normalize as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_dct4_instance_q31, normalize),
         4usize,
         concat!(
             "Offset of field: ",
@@ -3829,7 +3596,7 @@ fn bindgen_test_layout_arm_dct4_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_dct4_instance_q31>())).pTwiddle as *const _ as usize },
+        ::core::mem::offset_of!(arm_dct4_instance_q31, pTwiddle),
         8usize,
         concat!(
             "Offset of field: ",
@@ -3839,9 +3606,7 @@ fn bindgen_test_layout_arm_dct4_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_dct4_instance_q31>())).pCosFactor as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_dct4_instance_q31, pCosFactor),
         16usize,
         concat!(
             "Offset of field: ",
@@ -3851,7 +3616,7 @@ fn bindgen_test_layout_arm_dct4_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_dct4_instance_q31>())).pRfft as *const _ as usize },
+        ::core::mem::offset_of!(arm_dct4_instance_q31, pRfft),
         24usize,
         concat!(
             "Offset of field: ",
@@ -3861,7 +3626,7 @@ fn bindgen_test_layout_arm_dct4_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_dct4_instance_q31>())).pCfft as *const _ as usize },
+        ::core::mem::offset_of!(arm_dct4_instance_q31, pCfft),
         32usize,
         concat!(
             "Offset of field: ",
@@ -3931,7 +3696,7 @@ fn bindgen_test_layout_arm_dct4_instance_q15() {
         concat!("Alignment of ", stringify!(arm_dct4_instance_q15))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_dct4_instance_q15>())).N as *const _ as usize },
+        ::core::mem::offset_of!(arm_dct4_instance_q15, N),
         0usize,
         concat!(
             "Offset of field: ",
@@ -3941,7 +3706,7 @@ fn bindgen_test_layout_arm_dct4_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_dct4_instance_q15>())).Nby2 as *const _ as usize },
+        ::core::mem::offset_of!(arm_dct4_instance_q15, Nby2),
         2usize,
         concat!(
             "Offset of field: ",
@@ -3951,9 +3716,7 @@ fn bindgen_test_layout_arm_dct4_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_dct4_instance_q15>())).normalize as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_dct4_instance_q15, normalize),
         4usize,
         concat!(
             "Offset of field: ",
@@ -3963,7 +3726,7 @@ fn bindgen_test_layout_arm_dct4_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_dct4_instance_q15>())).pTwiddle as *const _ as usize },
+        ::core::mem::offset_of!(arm_dct4_instance_q15, pTwiddle),
         8usize,
         concat!(
             "Offset of field: ",
@@ -3973,9 +3736,7 @@ fn bindgen_test_layout_arm_dct4_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_dct4_instance_q15>())).pCosFactor as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_dct4_instance_q15, pCosFactor),
         16usize,
         concat!(
             "Offset of field: ",
@@ -3985,7 +3746,7 @@ fn bindgen_test_layout_arm_dct4_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_dct4_instance_q15>())).pRfft as *const _ as usize },
+        ::core::mem::offset_of!(arm_dct4_instance_q15, pRfft),
         24usize,
         concat!(
             "Offset of field: ",
@@ -3995,7 +3756,7 @@ fn bindgen_test_layout_arm_dct4_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_dct4_instance_q15>())).pCfft as *const _ as usize },
+        ::core::mem::offset_of!(arm_dct4_instance_q15, pCfft),
         32usize,
         concat!(
             "Offset of field: ",
@@ -4755,9 +4516,7 @@ fn bindgen_test_layout_arm_fir_decimate_instance_q15() {
         concat!("Alignment of ", stringify!(arm_fir_decimate_instance_q15))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_decimate_instance_q15>())).M as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_decimate_instance_q15, M),
         0usize,
         concat!(
             "Offset of field: ",
@@ -4767,9 +4526,7 @@ fn bindgen_test_layout_arm_fir_decimate_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_decimate_instance_q15>())).numTaps as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_decimate_instance_q15, numTaps),
         2usize,
         concat!(
             "Offset of field: ",
@@ -4779,9 +4536,7 @@ fn bindgen_test_layout_arm_fir_decimate_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_decimate_instance_q15>())).pCoeffs as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_decimate_instance_q15, pCoeffs),
         8usize,
         concat!(
             "Offset of field: ",
@@ -4791,9 +4546,7 @@ fn bindgen_test_layout_arm_fir_decimate_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_decimate_instance_q15>())).pState as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_decimate_instance_q15, pState),
         16usize,
         concat!(
             "Offset of field: ",
@@ -4828,9 +4581,7 @@ fn bindgen_test_layout_arm_fir_decimate_instance_q31() {
         concat!("Alignment of ", stringify!(arm_fir_decimate_instance_q31))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_decimate_instance_q31>())).M as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_decimate_instance_q31, M),
         0usize,
         concat!(
             "Offset of field: ",
@@ -4840,9 +4591,7 @@ fn bindgen_test_layout_arm_fir_decimate_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_decimate_instance_q31>())).numTaps as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_decimate_instance_q31, numTaps),
         2usize,
         concat!(
             "Offset of field: ",
@@ -4852,9 +4601,7 @@ fn bindgen_test_layout_arm_fir_decimate_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_decimate_instance_q31>())).pCoeffs as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_decimate_instance_q31, pCoeffs),
         8usize,
         concat!(
             "Offset of field: ",
@@ -4864,9 +4611,7 @@ fn bindgen_test_layout_arm_fir_decimate_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_decimate_instance_q31>())).pState as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_decimate_instance_q31, pState),
         16usize,
         concat!(
             "Offset of field: ",
@@ -4901,9 +4646,7 @@ fn bindgen_test_layout_arm_fir_decimate_instance_f32() {
         concat!("Alignment of ", stringify!(arm_fir_decimate_instance_f32))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_decimate_instance_f32>())).M as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_decimate_instance_f32, M),
         0usize,
         concat!(
             "Offset of field: ",
@@ -4913,9 +4656,7 @@ fn bindgen_test_layout_arm_fir_decimate_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_decimate_instance_f32>())).numTaps as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_decimate_instance_f32, numTaps),
         2usize,
         concat!(
             "Offset of field: ",
@@ -4925,9 +4666,7 @@ fn bindgen_test_layout_arm_fir_decimate_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_decimate_instance_f32>())).pCoeffs as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_decimate_instance_f32, pCoeffs),
         8usize,
         concat!(
             "Offset of field: ",
@@ -4937,9 +4676,7 @@ fn bindgen_test_layout_arm_fir_decimate_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_decimate_instance_f32>())).pState as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_decimate_instance_f32, pState),
         16usize,
         concat!(
             "Offset of field: ",
@@ -5100,9 +4837,7 @@ fn bindgen_test_layout_arm_fir_interpolate_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_interpolate_instance_q15>())).L as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_interpolate_instance_q15, L),
         0usize,
         concat!(
             "Offset of field: ",
@@ -5112,10 +4847,7 @@ fn bindgen_test_layout_arm_fir_interpolate_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_interpolate_instance_q15>())).phaseLength as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_fir_interpolate_instance_q15, phaseLength),
         2usize,
         concat!(
             "Offset of field: ",
@@ -5125,10 +4857,7 @@ fn bindgen_test_layout_arm_fir_interpolate_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_interpolate_instance_q15>())).pCoeffs as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_fir_interpolate_instance_q15, pCoeffs),
         8usize,
         concat!(
             "Offset of field: ",
@@ -5138,10 +4867,7 @@ fn bindgen_test_layout_arm_fir_interpolate_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_interpolate_instance_q15>())).pState as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_fir_interpolate_instance_q15, pState),
         16usize,
         concat!(
             "Offset of field: ",
@@ -5179,9 +4905,7 @@ fn bindgen_test_layout_arm_fir_interpolate_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_interpolate_instance_q31>())).L as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_interpolate_instance_q31, L),
         0usize,
         concat!(
             "Offset of field: ",
@@ -5191,10 +4915,7 @@ fn bindgen_test_layout_arm_fir_interpolate_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_interpolate_instance_q31>())).phaseLength as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_fir_interpolate_instance_q31, phaseLength),
         2usize,
         concat!(
             "Offset of field: ",
@@ -5204,10 +4925,7 @@ fn bindgen_test_layout_arm_fir_interpolate_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_interpolate_instance_q31>())).pCoeffs as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_fir_interpolate_instance_q31, pCoeffs),
         8usize,
         concat!(
             "Offset of field: ",
@@ -5217,10 +4935,7 @@ fn bindgen_test_layout_arm_fir_interpolate_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_interpolate_instance_q31>())).pState as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_fir_interpolate_instance_q31, pState),
         16usize,
         concat!(
             "Offset of field: ",
@@ -5258,9 +4973,7 @@ fn bindgen_test_layout_arm_fir_interpolate_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_interpolate_instance_f32>())).L as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_interpolate_instance_f32, L),
         0usize,
         concat!(
             "Offset of field: ",
@@ -5270,10 +4983,7 @@ fn bindgen_test_layout_arm_fir_interpolate_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_interpolate_instance_f32>())).phaseLength as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_fir_interpolate_instance_f32, phaseLength),
         2usize,
         concat!(
             "Offset of field: ",
@@ -5283,10 +4993,7 @@ fn bindgen_test_layout_arm_fir_interpolate_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_interpolate_instance_f32>())).pCoeffs as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_fir_interpolate_instance_f32, pCoeffs),
         8usize,
         concat!(
             "Offset of field: ",
@@ -5296,10 +5003,7 @@ fn bindgen_test_layout_arm_fir_interpolate_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_interpolate_instance_f32>())).pState as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_fir_interpolate_instance_f32, pState),
         16usize,
         concat!(
             "Offset of field: ",
@@ -5433,10 +5137,7 @@ fn bindgen_test_layout_arm_biquad_cas_df1_32x64_ins_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_biquad_cas_df1_32x64_ins_q31>())).numStages as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_biquad_cas_df1_32x64_ins_q31, numStages),
         0usize,
         concat!(
             "Offset of field: ",
@@ -5446,10 +5147,7 @@ fn bindgen_test_layout_arm_biquad_cas_df1_32x64_ins_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_biquad_cas_df1_32x64_ins_q31>())).pState as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_biquad_cas_df1_32x64_ins_q31, pState),
         8usize,
         concat!(
             "Offset of field: ",
@@ -5459,10 +5157,7 @@ fn bindgen_test_layout_arm_biquad_cas_df1_32x64_ins_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_biquad_cas_df1_32x64_ins_q31>())).pCoeffs as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_biquad_cas_df1_32x64_ins_q31, pCoeffs),
         16usize,
         concat!(
             "Offset of field: ",
@@ -5472,10 +5167,7 @@ fn bindgen_test_layout_arm_biquad_cas_df1_32x64_ins_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_biquad_cas_df1_32x64_ins_q31>())).postShift as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_biquad_cas_df1_32x64_ins_q31, postShift),
         24usize,
         concat!(
             "Offset of field: ",
@@ -5540,10 +5232,7 @@ fn bindgen_test_layout_arm_biquad_cascade_df2T_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_biquad_cascade_df2T_instance_f32>())).numStages as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_biquad_cascade_df2T_instance_f32, numStages),
         0usize,
         concat!(
             "Offset of field: ",
@@ -5553,10 +5242,7 @@ fn bindgen_test_layout_arm_biquad_cascade_df2T_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_biquad_cascade_df2T_instance_f32>())).pState as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_biquad_cascade_df2T_instance_f32, pState),
         8usize,
         concat!(
             "Offset of field: ",
@@ -5566,10 +5252,7 @@ fn bindgen_test_layout_arm_biquad_cascade_df2T_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_biquad_cascade_df2T_instance_f32>())).pCoeffs as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_biquad_cascade_df2T_instance_f32, pCoeffs),
         16usize,
         concat!(
             "Offset of field: ",
@@ -5608,10 +5291,7 @@ fn bindgen_test_layout_arm_biquad_cascade_stereo_df2T_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_biquad_cascade_stereo_df2T_instance_f32>())).numStages
-                as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_biquad_cascade_stereo_df2T_instance_f32, numStages),
         0usize,
         concat!(
             "Offset of field: ",
@@ -5621,10 +5301,7 @@ fn bindgen_test_layout_arm_biquad_cascade_stereo_df2T_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_biquad_cascade_stereo_df2T_instance_f32>())).pState
-                as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_biquad_cascade_stereo_df2T_instance_f32, pState),
         8usize,
         concat!(
             "Offset of field: ",
@@ -5634,10 +5311,7 @@ fn bindgen_test_layout_arm_biquad_cascade_stereo_df2T_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_biquad_cascade_stereo_df2T_instance_f32>())).pCoeffs
-                as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_biquad_cascade_stereo_df2T_instance_f32, pCoeffs),
         16usize,
         concat!(
             "Offset of field: ",
@@ -5676,10 +5350,7 @@ fn bindgen_test_layout_arm_biquad_cascade_df2T_instance_f64() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_biquad_cascade_df2T_instance_f64>())).numStages as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_biquad_cascade_df2T_instance_f64, numStages),
         0usize,
         concat!(
             "Offset of field: ",
@@ -5689,10 +5360,7 @@ fn bindgen_test_layout_arm_biquad_cascade_df2T_instance_f64() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_biquad_cascade_df2T_instance_f64>())).pState as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_biquad_cascade_df2T_instance_f64, pState),
         8usize,
         concat!(
             "Offset of field: ",
@@ -5702,10 +5370,7 @@ fn bindgen_test_layout_arm_biquad_cascade_df2T_instance_f64() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_biquad_cascade_df2T_instance_f64>())).pCoeffs as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_biquad_cascade_df2T_instance_f64, pCoeffs),
         16usize,
         concat!(
             "Offset of field: ",
@@ -5816,9 +5481,7 @@ fn bindgen_test_layout_arm_fir_lattice_instance_q15() {
         concat!("Alignment of ", stringify!(arm_fir_lattice_instance_q15))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_lattice_instance_q15>())).numStages as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_lattice_instance_q15, numStages),
         0usize,
         concat!(
             "Offset of field: ",
@@ -5828,9 +5491,7 @@ fn bindgen_test_layout_arm_fir_lattice_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_lattice_instance_q15>())).pState as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_lattice_instance_q15, pState),
         8usize,
         concat!(
             "Offset of field: ",
@@ -5840,9 +5501,7 @@ fn bindgen_test_layout_arm_fir_lattice_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_lattice_instance_q15>())).pCoeffs as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_lattice_instance_q15, pCoeffs),
         16usize,
         concat!(
             "Offset of field: ",
@@ -5875,9 +5534,7 @@ fn bindgen_test_layout_arm_fir_lattice_instance_q31() {
         concat!("Alignment of ", stringify!(arm_fir_lattice_instance_q31))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_lattice_instance_q31>())).numStages as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_lattice_instance_q31, numStages),
         0usize,
         concat!(
             "Offset of field: ",
@@ -5887,9 +5544,7 @@ fn bindgen_test_layout_arm_fir_lattice_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_lattice_instance_q31>())).pState as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_lattice_instance_q31, pState),
         8usize,
         concat!(
             "Offset of field: ",
@@ -5899,9 +5554,7 @@ fn bindgen_test_layout_arm_fir_lattice_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_lattice_instance_q31>())).pCoeffs as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_lattice_instance_q31, pCoeffs),
         16usize,
         concat!(
             "Offset of field: ",
@@ -5934,9 +5587,7 @@ fn bindgen_test_layout_arm_fir_lattice_instance_f32() {
         concat!("Alignment of ", stringify!(arm_fir_lattice_instance_f32))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_lattice_instance_f32>())).numStages as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_lattice_instance_f32, numStages),
         0usize,
         concat!(
             "Offset of field: ",
@@ -5946,9 +5597,7 @@ fn bindgen_test_layout_arm_fir_lattice_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_lattice_instance_f32>())).pState as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_lattice_instance_f32, pState),
         8usize,
         concat!(
             "Offset of field: ",
@@ -5958,9 +5607,7 @@ fn bindgen_test_layout_arm_fir_lattice_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_lattice_instance_f32>())).pCoeffs as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_lattice_instance_f32, pCoeffs),
         16usize,
         concat!(
             "Offset of field: ",
@@ -6073,9 +5720,7 @@ fn bindgen_test_layout_arm_iir_lattice_instance_q15() {
         concat!("Alignment of ", stringify!(arm_iir_lattice_instance_q15))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_iir_lattice_instance_q15>())).numStages as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_iir_lattice_instance_q15, numStages),
         0usize,
         concat!(
             "Offset of field: ",
@@ -6085,9 +5730,7 @@ fn bindgen_test_layout_arm_iir_lattice_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_iir_lattice_instance_q15>())).pState as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_iir_lattice_instance_q15, pState),
         8usize,
         concat!(
             "Offset of field: ",
@@ -6097,9 +5740,7 @@ fn bindgen_test_layout_arm_iir_lattice_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_iir_lattice_instance_q15>())).pkCoeffs as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_iir_lattice_instance_q15, pkCoeffs),
         16usize,
         concat!(
             "Offset of field: ",
@@ -6109,9 +5750,7 @@ fn bindgen_test_layout_arm_iir_lattice_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_iir_lattice_instance_q15>())).pvCoeffs as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_iir_lattice_instance_q15, pvCoeffs),
         24usize,
         concat!(
             "Offset of field: ",
@@ -6146,9 +5785,7 @@ fn bindgen_test_layout_arm_iir_lattice_instance_q31() {
         concat!("Alignment of ", stringify!(arm_iir_lattice_instance_q31))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_iir_lattice_instance_q31>())).numStages as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_iir_lattice_instance_q31, numStages),
         0usize,
         concat!(
             "Offset of field: ",
@@ -6158,9 +5795,7 @@ fn bindgen_test_layout_arm_iir_lattice_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_iir_lattice_instance_q31>())).pState as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_iir_lattice_instance_q31, pState),
         8usize,
         concat!(
             "Offset of field: ",
@@ -6170,9 +5805,7 @@ fn bindgen_test_layout_arm_iir_lattice_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_iir_lattice_instance_q31>())).pkCoeffs as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_iir_lattice_instance_q31, pkCoeffs),
         16usize,
         concat!(
             "Offset of field: ",
@@ -6182,9 +5815,7 @@ fn bindgen_test_layout_arm_iir_lattice_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_iir_lattice_instance_q31>())).pvCoeffs as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_iir_lattice_instance_q31, pvCoeffs),
         24usize,
         concat!(
             "Offset of field: ",
@@ -6219,9 +5850,7 @@ fn bindgen_test_layout_arm_iir_lattice_instance_f32() {
         concat!("Alignment of ", stringify!(arm_iir_lattice_instance_f32))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_iir_lattice_instance_f32>())).numStages as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_iir_lattice_instance_f32, numStages),
         0usize,
         concat!(
             "Offset of field: ",
@@ -6231,9 +5860,7 @@ fn bindgen_test_layout_arm_iir_lattice_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_iir_lattice_instance_f32>())).pState as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_iir_lattice_instance_f32, pState),
         8usize,
         concat!(
             "Offset of field: ",
@@ -6243,9 +5870,7 @@ fn bindgen_test_layout_arm_iir_lattice_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_iir_lattice_instance_f32>())).pkCoeffs as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_iir_lattice_instance_f32, pkCoeffs),
         16usize,
         concat!(
             "Offset of field: ",
@@ -6255,9 +5880,7 @@ fn bindgen_test_layout_arm_iir_lattice_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_iir_lattice_instance_f32>())).pvCoeffs as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_iir_lattice_instance_f32, pvCoeffs),
         24usize,
         concat!(
             "Offset of field: ",
@@ -6382,7 +6005,7 @@ fn bindgen_test_layout_arm_lms_instance_f32() {
         concat!("Alignment of ", stringify!(arm_lms_instance_f32))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_lms_instance_f32>())).numTaps as *const _ as usize },
+        ::core::mem::offset_of!(arm_lms_instance_f32, numTaps),
         0usize,
         concat!(
             "Offset of field: ",
@@ -6392,7 +6015,7 @@ fn bindgen_test_layout_arm_lms_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_lms_instance_f32>())).pState as *const _ as usize },
+        ::core::mem::offset_of!(arm_lms_instance_f32, pState),
         8usize,
         concat!(
             "Offset of field: ",
@@ -6402,7 +6025,7 @@ fn bindgen_test_layout_arm_lms_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_lms_instance_f32>())).pCoeffs as *const _ as usize },
+        ::core::mem::offset_of!(arm_lms_instance_f32, pCoeffs),
         16usize,
         concat!(
             "Offset of field: ",
@@ -6412,7 +6035,7 @@ fn bindgen_test_layout_arm_lms_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_lms_instance_f32>())).mu as *const _ as usize },
+        ::core::mem::offset_of!(arm_lms_instance_f32, mu),
         24usize,
         concat!(
             "Offset of field: ",
@@ -6483,7 +6106,7 @@ fn bindgen_test_layout_arm_lms_instance_q15() {
         concat!("Alignment of ", stringify!(arm_lms_instance_q15))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_lms_instance_q15>())).numTaps as *const _ as usize },
+        ::core::mem::offset_of!(arm_lms_instance_q15, numTaps),
         0usize,
         concat!(
             "Offset of field: ",
@@ -6493,7 +6116,7 @@ fn bindgen_test_layout_arm_lms_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_lms_instance_q15>())).pState as *const _ as usize },
+        ::core::mem::offset_of!(arm_lms_instance_q15, pState),
         8usize,
         concat!(
             "Offset of field: ",
@@ -6503,7 +6126,7 @@ fn bindgen_test_layout_arm_lms_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_lms_instance_q15>())).pCoeffs as *const _ as usize },
+        ::core::mem::offset_of!(arm_lms_instance_q15, pCoeffs),
         16usize,
         concat!(
             "Offset of field: ",
@@ -6513,7 +6136,7 @@ fn bindgen_test_layout_arm_lms_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_lms_instance_q15>())).mu as *const _ as usize },
+        ::core::mem::offset_of!(arm_lms_instance_q15, mu),
         24usize,
         concat!(
             "Offset of field: ",
@@ -6523,7 +6146,7 @@ fn bindgen_test_layout_arm_lms_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_lms_instance_q15>())).postShift as *const _ as usize },
+        ::core::mem::offset_of!(arm_lms_instance_q15, postShift),
         28usize,
         concat!(
             "Offset of field: ",
@@ -6596,7 +6219,7 @@ fn bindgen_test_layout_arm_lms_instance_q31() {
         concat!("Alignment of ", stringify!(arm_lms_instance_q31))
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_lms_instance_q31>())).numTaps as *const _ as usize },
+        ::core::mem::offset_of!(arm_lms_instance_q31, numTaps),
         0usize,
         concat!(
             "Offset of field: ",
@@ -6606,7 +6229,7 @@ fn bindgen_test_layout_arm_lms_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_lms_instance_q31>())).pState as *const _ as usize },
+        ::core::mem::offset_of!(arm_lms_instance_q31, pState),
         8usize,
         concat!(
             "Offset of field: ",
@@ -6616,7 +6239,7 @@ fn bindgen_test_layout_arm_lms_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_lms_instance_q31>())).pCoeffs as *const _ as usize },
+        ::core::mem::offset_of!(arm_lms_instance_q31, pCoeffs),
         16usize,
         concat!(
             "Offset of field: ",
@@ -6626,7 +6249,7 @@ fn bindgen_test_layout_arm_lms_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_lms_instance_q31>())).mu as *const _ as usize },
+        ::core::mem::offset_of!(arm_lms_instance_q31, mu),
         24usize,
         concat!(
             "Offset of field: ",
@@ -6636,7 +6259,7 @@ fn bindgen_test_layout_arm_lms_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_lms_instance_q31>())).postShift as *const _ as usize },
+        ::core::mem::offset_of!(arm_lms_instance_q31, postShift),
         28usize,
         concat!(
             "Offset of field: ",
@@ -6711,9 +6334,7 @@ fn bindgen_test_layout_arm_lms_norm_instance_f32() {
         concat!("Alignment of ", stringify!(arm_lms_norm_instance_f32))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_lms_norm_instance_f32>())).numTaps as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_lms_norm_instance_f32, numTaps),
         0usize,
         concat!(
             "Offset of field: ",
@@ -6723,9 +6344,7 @@ fn bindgen_test_layout_arm_lms_norm_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_lms_norm_instance_f32>())).pState as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_lms_norm_instance_f32, pState),
         8usize,
         concat!(
             "Offset of field: ",
@@ -6735,9 +6354,7 @@ fn bindgen_test_layout_arm_lms_norm_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_lms_norm_instance_f32>())).pCoeffs as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_lms_norm_instance_f32, pCoeffs),
         16usize,
         concat!(
             "Offset of field: ",
@@ -6747,7 +6364,7 @@ fn bindgen_test_layout_arm_lms_norm_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_lms_norm_instance_f32>())).mu as *const _ as usize },
+        ::core::mem::offset_of!(arm_lms_norm_instance_f32, mu),
         24usize,
         concat!(
             "Offset of field: ",
@@ -6757,9 +6374,7 @@ fn bindgen_test_layout_arm_lms_norm_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_lms_norm_instance_f32>())).energy as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_lms_norm_instance_f32, energy),
         28usize,
         concat!(
             "Offset of field: ",
@@ -6769,7 +6384,7 @@ fn bindgen_test_layout_arm_lms_norm_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_lms_norm_instance_f32>())).x0 as *const _ as usize },
+        ::core::mem::offset_of!(arm_lms_norm_instance_f32, x0),
         32usize,
         concat!(
             "Offset of field: ",
@@ -6846,9 +6461,7 @@ fn bindgen_test_layout_arm_lms_norm_instance_q31() {
         concat!("Alignment of ", stringify!(arm_lms_norm_instance_q31))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_lms_norm_instance_q31>())).numTaps as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_lms_norm_instance_q31, numTaps),
         0usize,
         concat!(
             "Offset of field: ",
@@ -6858,9 +6471,7 @@ fn bindgen_test_layout_arm_lms_norm_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_lms_norm_instance_q31>())).pState as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_lms_norm_instance_q31, pState),
         8usize,
         concat!(
             "Offset of field: ",
@@ -6870,9 +6481,7 @@ fn bindgen_test_layout_arm_lms_norm_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_lms_norm_instance_q31>())).pCoeffs as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_lms_norm_instance_q31, pCoeffs),
         16usize,
         concat!(
             "Offset of field: ",
@@ -6882,7 +6491,7 @@ fn bindgen_test_layout_arm_lms_norm_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_lms_norm_instance_q31>())).mu as *const _ as usize },
+        ::core::mem::offset_of!(arm_lms_norm_instance_q31, mu),
         24usize,
         concat!(
             "Offset of field: ",
@@ -6892,9 +6501,7 @@ fn bindgen_test_layout_arm_lms_norm_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_lms_norm_instance_q31>())).postShift as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_lms_norm_instance_q31, postShift),
         28usize,
         concat!(
             "Offset of field: ",
@@ -6904,9 +6511,7 @@ fn bindgen_test_layout_arm_lms_norm_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_lms_norm_instance_q31>())).recipTable as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_lms_norm_instance_q31, recipTable),
         32usize,
         concat!(
             "Offset of field: ",
@@ -6916,9 +6521,7 @@ fn bindgen_test_layout_arm_lms_norm_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_lms_norm_instance_q31>())).energy as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_lms_norm_instance_q31, energy),
         40usize,
         concat!(
             "Offset of field: ",
@@ -6928,7 +6531,7 @@ fn bindgen_test_layout_arm_lms_norm_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_lms_norm_instance_q31>())).x0 as *const _ as usize },
+        ::core::mem::offset_of!(arm_lms_norm_instance_q31, x0),
         44usize,
         concat!(
             "Offset of field: ",
@@ -7007,9 +6610,7 @@ fn bindgen_test_layout_arm_lms_norm_instance_q15() {
         concat!("Alignment of ", stringify!(arm_lms_norm_instance_q15))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_lms_norm_instance_q15>())).numTaps as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_lms_norm_instance_q15, numTaps),
         0usize,
         concat!(
             "Offset of field: ",
@@ -7019,9 +6620,7 @@ fn bindgen_test_layout_arm_lms_norm_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_lms_norm_instance_q15>())).pState as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_lms_norm_instance_q15, pState),
         8usize,
         concat!(
             "Offset of field: ",
@@ -7031,9 +6630,7 @@ fn bindgen_test_layout_arm_lms_norm_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_lms_norm_instance_q15>())).pCoeffs as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_lms_norm_instance_q15, pCoeffs),
         16usize,
         concat!(
             "Offset of field: ",
@@ -7043,7 +6640,7 @@ fn bindgen_test_layout_arm_lms_norm_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_lms_norm_instance_q15>())).mu as *const _ as usize },
+        ::core::mem::offset_of!(arm_lms_norm_instance_q15, mu),
         24usize,
         concat!(
             "Offset of field: ",
@@ -7053,9 +6650,7 @@ fn bindgen_test_layout_arm_lms_norm_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_lms_norm_instance_q15>())).postShift as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_lms_norm_instance_q15, postShift),
         26usize,
         concat!(
             "Offset of field: ",
@@ -7065,9 +6660,7 @@ fn bindgen_test_layout_arm_lms_norm_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_lms_norm_instance_q15>())).recipTable as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_lms_norm_instance_q15, recipTable),
         32usize,
         concat!(
             "Offset of field: ",
@@ -7077,9 +6670,7 @@ fn bindgen_test_layout_arm_lms_norm_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_lms_norm_instance_q15>())).energy as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_lms_norm_instance_q15, energy),
         40usize,
         concat!(
             "Offset of field: ",
@@ -7089,7 +6680,7 @@ fn bindgen_test_layout_arm_lms_norm_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_lms_norm_instance_q15>())).x0 as *const _ as usize },
+        ::core::mem::offset_of!(arm_lms_norm_instance_q15, x0),
         42usize,
         concat!(
             "Offset of field: ",
@@ -7308,9 +6899,7 @@ fn bindgen_test_layout_arm_fir_sparse_instance_f32() {
         concat!("Alignment of ", stringify!(arm_fir_sparse_instance_f32))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_sparse_instance_f32>())).numTaps as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_sparse_instance_f32, numTaps),
         0usize,
         concat!(
             "Offset of field: ",
@@ -7320,9 +6909,7 @@ fn bindgen_test_layout_arm_fir_sparse_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_sparse_instance_f32>())).stateIndex as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_sparse_instance_f32, stateIndex),
         2usize,
         concat!(
             "Offset of field: ",
@@ -7332,9 +6919,7 @@ fn bindgen_test_layout_arm_fir_sparse_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_sparse_instance_f32>())).pState as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_sparse_instance_f32, pState),
         8usize,
         concat!(
             "Offset of field: ",
@@ -7344,9 +6929,7 @@ fn bindgen_test_layout_arm_fir_sparse_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_sparse_instance_f32>())).pCoeffs as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_sparse_instance_f32, pCoeffs),
         16usize,
         concat!(
             "Offset of field: ",
@@ -7356,9 +6939,7 @@ fn bindgen_test_layout_arm_fir_sparse_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_sparse_instance_f32>())).maxDelay as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_sparse_instance_f32, maxDelay),
         24usize,
         concat!(
             "Offset of field: ",
@@ -7368,9 +6949,7 @@ fn bindgen_test_layout_arm_fir_sparse_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_sparse_instance_f32>())).pTapDelay as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_sparse_instance_f32, pTapDelay),
         32usize,
         concat!(
             "Offset of field: ",
@@ -7409,9 +6988,7 @@ fn bindgen_test_layout_arm_fir_sparse_instance_q31() {
         concat!("Alignment of ", stringify!(arm_fir_sparse_instance_q31))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_sparse_instance_q31>())).numTaps as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_sparse_instance_q31, numTaps),
         0usize,
         concat!(
             "Offset of field: ",
@@ -7421,9 +6998,7 @@ fn bindgen_test_layout_arm_fir_sparse_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_sparse_instance_q31>())).stateIndex as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_sparse_instance_q31, stateIndex),
         2usize,
         concat!(
             "Offset of field: ",
@@ -7433,9 +7008,7 @@ fn bindgen_test_layout_arm_fir_sparse_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_sparse_instance_q31>())).pState as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_sparse_instance_q31, pState),
         8usize,
         concat!(
             "Offset of field: ",
@@ -7445,9 +7018,7 @@ fn bindgen_test_layout_arm_fir_sparse_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_sparse_instance_q31>())).pCoeffs as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_sparse_instance_q31, pCoeffs),
         16usize,
         concat!(
             "Offset of field: ",
@@ -7457,9 +7028,7 @@ fn bindgen_test_layout_arm_fir_sparse_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_sparse_instance_q31>())).maxDelay as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_sparse_instance_q31, maxDelay),
         24usize,
         concat!(
             "Offset of field: ",
@@ -7469,9 +7038,7 @@ fn bindgen_test_layout_arm_fir_sparse_instance_q31() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_sparse_instance_q31>())).pTapDelay as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_sparse_instance_q31, pTapDelay),
         32usize,
         concat!(
             "Offset of field: ",
@@ -7510,9 +7077,7 @@ fn bindgen_test_layout_arm_fir_sparse_instance_q15() {
         concat!("Alignment of ", stringify!(arm_fir_sparse_instance_q15))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_sparse_instance_q15>())).numTaps as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_sparse_instance_q15, numTaps),
         0usize,
         concat!(
             "Offset of field: ",
@@ -7522,9 +7087,7 @@ fn bindgen_test_layout_arm_fir_sparse_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_sparse_instance_q15>())).stateIndex as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_sparse_instance_q15, stateIndex),
         2usize,
         concat!(
             "Offset of field: ",
@@ -7534,9 +7097,7 @@ fn bindgen_test_layout_arm_fir_sparse_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_sparse_instance_q15>())).pState as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_sparse_instance_q15, pState),
         8usize,
         concat!(
             "Offset of field: ",
@@ -7546,9 +7107,7 @@ fn bindgen_test_layout_arm_fir_sparse_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_sparse_instance_q15>())).pCoeffs as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_sparse_instance_q15, pCoeffs),
         16usize,
         concat!(
             "Offset of field: ",
@@ -7558,9 +7117,7 @@ fn bindgen_test_layout_arm_fir_sparse_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_sparse_instance_q15>())).maxDelay as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_sparse_instance_q15, maxDelay),
         24usize,
         concat!(
             "Offset of field: ",
@@ -7570,9 +7127,7 @@ fn bindgen_test_layout_arm_fir_sparse_instance_q15() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_sparse_instance_q15>())).pTapDelay as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_sparse_instance_q15, pTapDelay),
         32usize,
         concat!(
             "Offset of field: ",
@@ -7611,9 +7166,7 @@ fn bindgen_test_layout_arm_fir_sparse_instance_q7() {
         concat!("Alignment of ", stringify!(arm_fir_sparse_instance_q7))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_sparse_instance_q7>())).numTaps as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_sparse_instance_q7, numTaps),
         0usize,
         concat!(
             "Offset of field: ",
@@ -7623,9 +7176,7 @@ fn bindgen_test_layout_arm_fir_sparse_instance_q7() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_sparse_instance_q7>())).stateIndex as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_sparse_instance_q7, stateIndex),
         2usize,
         concat!(
             "Offset of field: ",
@@ -7635,9 +7186,7 @@ fn bindgen_test_layout_arm_fir_sparse_instance_q7() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_sparse_instance_q7>())).pState as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_sparse_instance_q7, pState),
         8usize,
         concat!(
             "Offset of field: ",
@@ -7647,9 +7196,7 @@ fn bindgen_test_layout_arm_fir_sparse_instance_q7() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_sparse_instance_q7>())).pCoeffs as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_sparse_instance_q7, pCoeffs),
         16usize,
         concat!(
             "Offset of field: ",
@@ -7659,9 +7206,7 @@ fn bindgen_test_layout_arm_fir_sparse_instance_q7() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_sparse_instance_q7>())).maxDelay as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_sparse_instance_q7, maxDelay),
         24usize,
         concat!(
             "Offset of field: ",
@@ -7671,9 +7216,7 @@ fn bindgen_test_layout_arm_fir_sparse_instance_q7() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_fir_sparse_instance_q7>())).pTapDelay as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_fir_sparse_instance_q7, pTapDelay),
         32usize,
         concat!(
             "Offset of field: ",
@@ -8444,10 +7987,7 @@ fn bindgen_test_layout_arm_svm_linear_instance_f32() {
         concat!("Alignment of ", stringify!(arm_svm_linear_instance_f32))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_svm_linear_instance_f32>())).nbOfSupportVectors as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_svm_linear_instance_f32, nbOfSupportVectors),
         0usize,
         concat!(
             "Offset of field: ",
@@ -8457,10 +7997,7 @@ fn bindgen_test_layout_arm_svm_linear_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_svm_linear_instance_f32>())).vectorDimension as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_svm_linear_instance_f32, vectorDimension),
         4usize,
         concat!(
             "Offset of field: ",
@@ -8470,9 +8007,7 @@ fn bindgen_test_layout_arm_svm_linear_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_svm_linear_instance_f32>())).intercept as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_svm_linear_instance_f32, intercept),
         8usize,
         concat!(
             "Offset of field: ",
@@ -8482,10 +8017,7 @@ fn bindgen_test_layout_arm_svm_linear_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_svm_linear_instance_f32>())).dualCoefficients as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_svm_linear_instance_f32, dualCoefficients),
         16usize,
         concat!(
             "Offset of field: ",
@@ -8495,10 +8027,7 @@ fn bindgen_test_layout_arm_svm_linear_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_svm_linear_instance_f32>())).supportVectors as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_svm_linear_instance_f32, supportVectors),
         24usize,
         concat!(
             "Offset of field: ",
@@ -8508,9 +8037,7 @@ fn bindgen_test_layout_arm_svm_linear_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_svm_linear_instance_f32>())).classes as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_svm_linear_instance_f32, classes),
         32usize,
         concat!(
             "Offset of field: ",
@@ -8555,10 +8082,7 @@ fn bindgen_test_layout_arm_svm_polynomial_instance_f32() {
         concat!("Alignment of ", stringify!(arm_svm_polynomial_instance_f32))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_svm_polynomial_instance_f32>())).nbOfSupportVectors
-                as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_svm_polynomial_instance_f32, nbOfSupportVectors),
         0usize,
         concat!(
             "Offset of field: ",
@@ -8568,10 +8092,7 @@ fn bindgen_test_layout_arm_svm_polynomial_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_svm_polynomial_instance_f32>())).vectorDimension as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_svm_polynomial_instance_f32, vectorDimension),
         4usize,
         concat!(
             "Offset of field: ",
@@ -8581,10 +8102,7 @@ fn bindgen_test_layout_arm_svm_polynomial_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_svm_polynomial_instance_f32>())).intercept as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_svm_polynomial_instance_f32, intercept),
         8usize,
         concat!(
             "Offset of field: ",
@@ -8594,10 +8112,7 @@ fn bindgen_test_layout_arm_svm_polynomial_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_svm_polynomial_instance_f32>())).dualCoefficients
-                as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_svm_polynomial_instance_f32, dualCoefficients),
         16usize,
         concat!(
             "Offset of field: ",
@@ -8607,10 +8122,7 @@ fn bindgen_test_layout_arm_svm_polynomial_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_svm_polynomial_instance_f32>())).supportVectors as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_svm_polynomial_instance_f32, supportVectors),
         24usize,
         concat!(
             "Offset of field: ",
@@ -8620,10 +8132,7 @@ fn bindgen_test_layout_arm_svm_polynomial_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_svm_polynomial_instance_f32>())).classes as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_svm_polynomial_instance_f32, classes),
         32usize,
         concat!(
             "Offset of field: ",
@@ -8633,9 +8142,7 @@ fn bindgen_test_layout_arm_svm_polynomial_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_svm_polynomial_instance_f32>())).degree as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_svm_polynomial_instance_f32, degree),
         40usize,
         concat!(
             "Offset of field: ",
@@ -8645,9 +8152,7 @@ fn bindgen_test_layout_arm_svm_polynomial_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_svm_polynomial_instance_f32>())).coef0 as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_svm_polynomial_instance_f32, coef0),
         44usize,
         concat!(
             "Offset of field: ",
@@ -8657,9 +8162,7 @@ fn bindgen_test_layout_arm_svm_polynomial_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_svm_polynomial_instance_f32>())).gamma as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_svm_polynomial_instance_f32, gamma),
         48usize,
         concat!(
             "Offset of field: ",
@@ -8700,10 +8203,7 @@ fn bindgen_test_layout_arm_svm_rbf_instance_f32() {
         concat!("Alignment of ", stringify!(arm_svm_rbf_instance_f32))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_svm_rbf_instance_f32>())).nbOfSupportVectors as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_svm_rbf_instance_f32, nbOfSupportVectors),
         0usize,
         concat!(
             "Offset of field: ",
@@ -8713,10 +8213,7 @@ fn bindgen_test_layout_arm_svm_rbf_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_svm_rbf_instance_f32>())).vectorDimension as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_svm_rbf_instance_f32, vectorDimension),
         4usize,
         concat!(
             "Offset of field: ",
@@ -8726,9 +8223,7 @@ fn bindgen_test_layout_arm_svm_rbf_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_svm_rbf_instance_f32>())).intercept as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_svm_rbf_instance_f32, intercept),
         8usize,
         concat!(
             "Offset of field: ",
@@ -8738,10 +8233,7 @@ fn bindgen_test_layout_arm_svm_rbf_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_svm_rbf_instance_f32>())).dualCoefficients as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_svm_rbf_instance_f32, dualCoefficients),
         16usize,
         concat!(
             "Offset of field: ",
@@ -8751,10 +8243,7 @@ fn bindgen_test_layout_arm_svm_rbf_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_svm_rbf_instance_f32>())).supportVectors as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_svm_rbf_instance_f32, supportVectors),
         24usize,
         concat!(
             "Offset of field: ",
@@ -8764,9 +8253,7 @@ fn bindgen_test_layout_arm_svm_rbf_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_svm_rbf_instance_f32>())).classes as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_svm_rbf_instance_f32, classes),
         32usize,
         concat!(
             "Offset of field: ",
@@ -8776,7 +8263,7 @@ fn bindgen_test_layout_arm_svm_rbf_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe { &(*(::core::ptr::null::<arm_svm_rbf_instance_f32>())).gamma as *const _ as usize },
+        ::core::mem::offset_of!(arm_svm_rbf_instance_f32, gamma),
         40usize,
         concat!(
             "Offset of field: ",
@@ -8819,10 +8306,7 @@ fn bindgen_test_layout_arm_svm_sigmoid_instance_f32() {
         concat!("Alignment of ", stringify!(arm_svm_sigmoid_instance_f32))
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_svm_sigmoid_instance_f32>())).nbOfSupportVectors as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_svm_sigmoid_instance_f32, nbOfSupportVectors),
         0usize,
         concat!(
             "Offset of field: ",
@@ -8832,10 +8316,7 @@ fn bindgen_test_layout_arm_svm_sigmoid_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_svm_sigmoid_instance_f32>())).vectorDimension as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_svm_sigmoid_instance_f32, vectorDimension),
         4usize,
         concat!(
             "Offset of field: ",
@@ -8845,9 +8326,7 @@ fn bindgen_test_layout_arm_svm_sigmoid_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_svm_sigmoid_instance_f32>())).intercept as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_svm_sigmoid_instance_f32, intercept),
         8usize,
         concat!(
             "Offset of field: ",
@@ -8857,10 +8336,7 @@ fn bindgen_test_layout_arm_svm_sigmoid_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_svm_sigmoid_instance_f32>())).dualCoefficients as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_svm_sigmoid_instance_f32, dualCoefficients),
         16usize,
         concat!(
             "Offset of field: ",
@@ -8870,10 +8346,7 @@ fn bindgen_test_layout_arm_svm_sigmoid_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_svm_sigmoid_instance_f32>())).supportVectors as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_svm_sigmoid_instance_f32, supportVectors),
         24usize,
         concat!(
             "Offset of field: ",
@@ -8883,9 +8356,7 @@ fn bindgen_test_layout_arm_svm_sigmoid_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_svm_sigmoid_instance_f32>())).classes as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_svm_sigmoid_instance_f32, classes),
         32usize,
         concat!(
             "Offset of field: ",
@@ -8895,9 +8366,7 @@ fn bindgen_test_layout_arm_svm_sigmoid_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_svm_sigmoid_instance_f32>())).coef0 as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_svm_sigmoid_instance_f32, coef0),
         40usize,
         concat!(
             "Offset of field: ",
@@ -8907,9 +8376,7 @@ fn bindgen_test_layout_arm_svm_sigmoid_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_svm_sigmoid_instance_f32>())).gamma as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_svm_sigmoid_instance_f32, gamma),
         44usize,
         concat!(
             "Offset of field: ",
@@ -9102,10 +8569,7 @@ fn bindgen_test_layout_arm_gaussian_naive_bayes_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_gaussian_naive_bayes_instance_f32>())).vectorDimension
-                as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_gaussian_naive_bayes_instance_f32, vectorDimension),
         0usize,
         concat!(
             "Offset of field: ",
@@ -9115,10 +8579,7 @@ fn bindgen_test_layout_arm_gaussian_naive_bayes_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_gaussian_naive_bayes_instance_f32>())).numberOfClasses
-                as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_gaussian_naive_bayes_instance_f32, numberOfClasses),
         4usize,
         concat!(
             "Offset of field: ",
@@ -9128,10 +8589,7 @@ fn bindgen_test_layout_arm_gaussian_naive_bayes_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_gaussian_naive_bayes_instance_f32>())).theta as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_gaussian_naive_bayes_instance_f32, theta),
         8usize,
         concat!(
             "Offset of field: ",
@@ -9141,10 +8599,7 @@ fn bindgen_test_layout_arm_gaussian_naive_bayes_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_gaussian_naive_bayes_instance_f32>())).sigma as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_gaussian_naive_bayes_instance_f32, sigma),
         16usize,
         concat!(
             "Offset of field: ",
@@ -9154,10 +8609,7 @@ fn bindgen_test_layout_arm_gaussian_naive_bayes_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_gaussian_naive_bayes_instance_f32>())).classPriors
-                as *const _ as usize
-        },
+        ::core::mem::offset_of!(arm_gaussian_naive_bayes_instance_f32, classPriors),
         24usize,
         concat!(
             "Offset of field: ",
@@ -9167,10 +8619,7 @@ fn bindgen_test_layout_arm_gaussian_naive_bayes_instance_f32() {
         )
     );
     assert_eq!(
-        unsafe {
-            &(*(::core::ptr::null::<arm_gaussian_naive_bayes_instance_f32>())).epsilon as *const _
-                as usize
-        },
+        ::core::mem::offset_of!(arm_gaussian_naive_bayes_instance_f32, epsilon),
         32usize,
         concat!(
             "Offset of field: ",
@@ -0,0 +1,83 @@
+//! This crate uses the filter and statistics APIs of cmsis_dsp from a `no_std` crate. It only
+//! needs to compile.
+//!
+//! To check that cmsis_dsp does not depend on the standard library, build this crate for a
+//! target that does not have one:
+//!
+//! ```text
+//! cargo build -p compile_test --target thumbv7em-none-eabihf
+//! ```
+
+#![no_std]
+
+use cmsis_dsp::filter::{
+    BiquadCascadeDF2TFilter, FirFilter, FirLatticeFilter, IirLatticeFilter, StaticFirFilter,
+};
+use cmsis_dsp::statistics::{max_f32, mean, mean_f32, min_f32, power, rms, std, std_f32, var};
+use cmsis_dsp::Result;
+
+/// Runs a signal through a biquad cascade and a FIR filter, one after the other
+pub fn filter_chain(src: &[f32; 32], dst: &mut [f32; 32]) -> Result<()> {
+    let biquad_coeffs = [0.2, 0.4, 0.2, 0.5, -0.3];
+    let mut biquad_state = [0.0; 2];
+    let mut biquad = BiquadCascadeDF2TFilter::new(1, &biquad_coeffs, &mut biquad_state)?;
+    let mut intermediate = [0.0; 32];
+    biquad.process(src, &mut intermediate, 32);
+    let _ = biquad.process_scalar(0.0);
+
+    let fir_coeffs = [0.25; 4];
+    let mut fir_state = [0.0; 4 + 32 - 1];
+    let mut fir = FirFilter::new(4, &fir_coeffs, &mut fir_state, 32)?;
+    fir.process(&intermediate, dst, 32);
+    Ok(())
+}
+
+/// A filter that is initialized at compile time
+static mut STATIC_FIR: StaticFirFilter<4, 35> = StaticFirFilter::new([0.25; 4]);
+
+/// Runs a signal through a statically allocated FIR filter, as an interrupt handler would
+///
+/// # Safety
+///
+/// This function must not be called from more than one context at a time.
+pub unsafe fn static_filter(src: &[f32; 32], dst: &mut [f32; 32]) {
+    let filter = &mut *core::ptr::addr_of_mut!(STATIC_FIR);
+    filter.process(src, dst);
+}
+
+/// Runs a signal through FIR and IIR lattice filters
+pub fn lattice_chain(src: &[f32; 16], dst: &mut [f32; 16]) {
+    let reflection = [0.5, -0.25];
+    let mut fir_state = [0.0; 2];
+    let mut fir = FirLatticeFilter::new(2, &reflection, &mut fir_state);
+    let mut intermediate = [0.0; 16];
+    fir.process(src, &mut intermediate);
+
+    let ladder = [1.0, 0.5, 0.25];
+    let mut iir_state = [0.0; 2 + 16];
+    let mut iir = IirLatticeFilter::new(2, &reflection, &ladder, &mut iir_state, 16);
+    iir.process(&intermediate, dst);
+}
+
+/// Calculates statistics of a signal with both the reference and returning functions
+pub fn statistics(src: &[f32]) -> [f32; 8] {
+    let mut mean_value = 0.0;
+    mean_f32(src, src.len(), &mut mean_value);
+    let mut std_value = 0.0;
+    std_f32(src, src.len(), &mut std_value);
+    let mut max_value = 0.0;
+    let mut min_value = 0.0;
+    let mut index = 0;
+    max_f32(src, src.len(), &mut max_value, &mut index);
+    min_f32(src, src.len(), &mut min_value, &mut index);
+    [
+        mean_value,
+        std_value,
+        max_value,
+        min_value,
+        mean(src) + std(src),
+        var(src),
+        rms(src),
+        power(src),
+    ]
+}
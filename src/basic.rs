@@ -1,8 +1,20 @@
 //! Basic math functions
+//!
+//! The element-wise CMSIS-DSP functions, where each output depends only on the inputs at the same
+//! index, support using the same buffer as a source and the destination. Because a slice cannot
+//! be borrowed as both `&[T]` and `&mut [T]`, the functions with `in_place` in their names
+//! provide this: they read from and write to a single slice. The thresholding functions also
+//! work in place. Functions that read inputs at other indices, such as convolution and
+//! correlation, cannot work in place and always take separate source and destination slices.
 
 use fixed::types::{I16F48, I18F14, I1F15, I1F31, I1F7, I34F30};
 
-use crate::check_length;
+use crate::check_length_or_panic;
+use crate::support::{
+    as_q15_mut_ptr, as_q15_ptr, as_q31_mut_ptr, as_q31_ptr, as_q7_mut_ptr, as_q7_ptr,
+};
+
+pub mod bits;
 
 /// Calculates the absolute value of multiple values
 ///
@@ -13,7 +25,7 @@ use crate::check_length;
 ///
 /// This function panics if src and dst do not have the same length.
 pub fn abs_f32(src: &[f32], dst: &mut [f32]) {
-    let length = check_length((src.len(), dst.len()));
+    let length = check_length_or_panic((src.len(), dst.len()));
     unsafe {
         cmsis_dsp_sys::arm_abs_f32(src.as_ptr(), dst.as_mut_ptr(), length);
     }
@@ -28,9 +40,9 @@ pub fn abs_f32(src: &[f32], dst: &mut [f32]) {
 ///
 /// This function panics if src and dst do not have the same length.
 pub fn abs_q31(src: &[I1F31], dst: &mut [I1F31]) {
-    let length = check_length((src.len(), dst.len()));
+    let length = check_length_or_panic((src.len(), dst.len()));
     unsafe {
-        cmsis_dsp_sys::arm_abs_q31(src.as_ptr() as *const _, dst.as_mut_ptr() as *mut _, length);
+        cmsis_dsp_sys::arm_abs_q31(as_q31_ptr(src), as_q31_mut_ptr(dst), length);
     }
 }
 
@@ -43,9 +55,9 @@ pub fn abs_q31(src: &[I1F31], dst: &mut [I1F31]) {
 ///
 /// This function panics if src and dst do not have the same length.
 pub fn abs_q15(src: &[I1F15], dst: &mut [I1F15]) {
-    let length = check_length((src.len(), dst.len()));
+    let length = check_length_or_panic((src.len(), dst.len()));
     unsafe {
-        cmsis_dsp_sys::arm_abs_q15(src.as_ptr() as *const _, dst.as_mut_ptr() as *mut _, length);
+        cmsis_dsp_sys::arm_abs_q15(as_q15_ptr(src), as_q15_mut_ptr(dst), length);
     }
 }
 
@@ -58,9 +70,9 @@ pub fn abs_q15(src: &[I1F15], dst: &mut [I1F15]) {
 ///
 /// This function panics if src and dst do not have the same length.
 pub fn abs_q7(src: &[I1F7], dst: &mut [I1F7]) {
-    let length = check_length((src.len(), dst.len()));
+    let length = check_length_or_panic((src.len(), dst.len()));
     unsafe {
-        cmsis_dsp_sys::arm_abs_q7(src.as_ptr() as *const _, dst.as_mut_ptr() as *mut _, length);
+        cmsis_dsp_sys::arm_abs_q7(as_q7_ptr(src), as_q7_mut_ptr(dst), length);
     }
 }
 
@@ -69,7 +81,7 @@ pub fn abs_q7(src: &[I1F7], dst: &mut [I1F7]) {
 /// This is functionally equivalent to performing `values[i] = abs(values[i])` for all values of i
 /// in range.
 pub fn abs_in_place_f32(values: &mut [f32]) {
-    let length = check_length(values.len());
+    let length = check_length_or_panic(values.len());
     // The CMSIS DSP function specifically does support argument aliasing. Is this really safe
     // from the Rust perspective?
     unsafe {
@@ -83,10 +95,10 @@ pub fn abs_in_place_f32(values: &mut [f32]) {
 /// This is functionally equivalent to performing `values[i] = abs(values[i])` for all values of i
 /// in range.
 pub fn abs_in_place_q31(values: &mut [I1F31]) {
-    let length = check_length(values.len());
+    let length = check_length_or_panic(values.len());
     unsafe {
-        let ptr = values.as_mut_ptr();
-        cmsis_dsp_sys::arm_abs_q31(ptr as *const _, ptr as *mut _, length);
+        let ptr = as_q31_mut_ptr(values);
+        cmsis_dsp_sys::arm_abs_q31(ptr, ptr, length);
     }
 }
 
@@ -95,10 +107,10 @@ pub fn abs_in_place_q31(values: &mut [I1F31]) {
 /// This is functionally equivalent to performing `values[i] = abs(values[i])` for all values of i
 /// in range.
 pub fn abs_in_place_q15(values: &mut [I1F15]) {
-    let length = check_length(values.len());
+    let length = check_length_or_panic(values.len());
     unsafe {
-        let ptr = values.as_mut_ptr();
-        cmsis_dsp_sys::arm_abs_q15(ptr as *const _, ptr as *mut _, length);
+        let ptr = as_q15_mut_ptr(values);
+        cmsis_dsp_sys::arm_abs_q15(ptr, ptr, length);
     }
 }
 
@@ -107,10 +119,10 @@ pub fn abs_in_place_q15(values: &mut [I1F15]) {
 /// This is functionally equivalent to performing `values[i] = abs(values[i])` for all values of i
 /// in range.
 pub fn abs_in_place_q7(values: &mut [I1F7]) {
-    let length = check_length(values.len());
+    let length = check_length_or_panic(values.len());
     unsafe {
-        let ptr = values.as_mut_ptr();
-        cmsis_dsp_sys::arm_abs_q7(ptr as *const _, ptr as *mut _, length);
+        let ptr = as_q7_mut_ptr(values);
+        cmsis_dsp_sys::arm_abs_q7(ptr, ptr, length);
     }
 }
 
@@ -123,7 +135,7 @@ pub fn abs_in_place_q7(values: &mut [I1F7]) {
 ///
 /// This function panics if src1, src2, and dst do not have the same length.
 pub fn add_f32(src1: &[f32], src2: &[f32], dst: &mut [f32]) {
-    let length = check_length((src1.len(), src2.len(), dst.len()));
+    let length = check_length_or_panic((src1.len(), src2.len(), dst.len()));
     unsafe {
         cmsis_dsp_sys::arm_add_f32(src1.as_ptr(), src2.as_ptr(), dst.as_mut_ptr(), length);
     }
@@ -138,12 +150,12 @@ pub fn add_f32(src1: &[f32], src2: &[f32], dst: &mut [f32]) {
 ///
 /// This function panics if src1, src2, and dst do not have the same length.
 pub fn add_q31(src1: &[I1F31], src2: &[I1F31], dst: &mut [I1F31]) {
-    let length = check_length((src1.len(), src2.len(), dst.len()));
+    let length = check_length_or_panic((src1.len(), src2.len(), dst.len()));
     unsafe {
         cmsis_dsp_sys::arm_add_q31(
-            src1.as_ptr() as *const _,
-            src2.as_ptr() as *const _,
-            dst.as_mut_ptr() as *mut _,
+            as_q31_ptr(src1),
+            as_q31_ptr(src2),
+            as_q31_mut_ptr(dst),
             length,
         );
     }
@@ -158,12 +170,12 @@ pub fn add_q31(src1: &[I1F31], src2: &[I1F31], dst: &mut [I1F31]) {
 ///
 /// This function panics if src1, src2, and dst do not have the same length.
 pub fn add_q15(src1: &[I1F15], src2: &[I1F15], dst: &mut [I1F15]) {
-    let length = check_length((src1.len(), src2.len(), dst.len()));
+    let length = check_length_or_panic((src1.len(), src2.len(), dst.len()));
     unsafe {
         cmsis_dsp_sys::arm_add_q15(
-            src1.as_ptr() as *const _,
-            src2.as_ptr() as *const _,
-            dst.as_mut_ptr() as *mut _,
+            as_q15_ptr(src1),
+            as_q15_ptr(src2),
+            as_q15_mut_ptr(dst),
             length,
         );
     }
@@ -178,14 +190,9 @@ pub fn add_q15(src1: &[I1F15], src2: &[I1F15], dst: &mut [I1F15]) {
 ///
 /// This function panics if src1, src2, and dst do not have the same length.
 pub fn add_q7(src1: &[I1F7], src2: &[I1F7], dst: &mut [I1F7]) {
-    let length = check_length((src1.len(), src2.len(), dst.len()));
+    let length = check_length_or_panic((src1.len(), src2.len(), dst.len()));
     unsafe {
-        cmsis_dsp_sys::arm_add_q7(
-            src1.as_ptr() as *const _,
-            src2.as_ptr() as *const _,
-            dst.as_mut_ptr() as *mut _,
-            length,
-        );
+        cmsis_dsp_sys::arm_add_q7(as_q7_ptr(src1), as_q7_ptr(src2), as_q7_mut_ptr(dst), length);
     }
 }
 
@@ -198,7 +205,7 @@ pub fn add_q7(src1: &[I1F7], src2: &[I1F7], dst: &mut [I1F7]) {
 ///
 /// This function panics if src1 and src2 do not have the same length.
 pub fn dot_product_f32(src1: &[f32], src2: &[f32]) -> f32 {
-    let length = check_length((src1.len(), src2.len()));
+    let length = check_length_or_panic((src1.len(), src2.len()));
     let mut result = 0.0;
     unsafe {
         cmsis_dsp_sys::arm_dot_prod_f32(src1.as_ptr(), src2.as_ptr(), length, &mut result);
@@ -206,26 +213,41 @@ pub fn dot_product_f32(src1: &[f32], src2: &[f32]) -> f32 {
     result
 }
 
+/// Calculates the dot product of two vectors of f64 values
+///
+/// The returned value is the sum of `src1[i] * src2[i]` over all values of i
+/// in range. This is equivalent to `arm_dot_prod_f64`.
+///
+/// This function is available when the `f64` feature is enabled.
+///
+/// # Panics
+///
+/// This function panics if src1 and src2 do not have the same length.
+#[cfg(feature = "f64")]
+pub fn dot_product_f64(src1: &[f64], src2: &[f64]) -> f64 {
+    check_length_or_panic::<(usize, usize), usize>((src1.len(), src2.len()));
+    src1.iter().zip(src2).map(|(a, b)| a * b).sum()
+}
+
 /// Calculates the dot product of two vectors
 ///
 /// The returned value is the sum of `src1[i] * src2[i]` over all values of i
 /// in range.
 ///
+/// `arm_dot_prod_q31` shifts each 2.62 product right by 14 bits and adds it to a 64-bit
+/// accumulator, so the result has 16 integer bits and 48 fractional bits. The sum of up to
+/// 2^14 products cannot overflow.
+///
 /// # Panics
 ///
 /// This function panics if src1 and src2 do not have the same length.
 pub fn dot_product_q31(src1: &[I1F31], src2: &[I1F31]) -> I16F48 {
-    let length = check_length((src1.len(), src2.len()));
-    let mut result = I16F48::from_bits(0);
+    let length = check_length_or_panic((src1.len(), src2.len()));
+    let mut result = 0;
     unsafe {
-        cmsis_dsp_sys::arm_dot_prod_q31(
-            src1.as_ptr() as *const _,
-            src2.as_ptr() as *const _,
-            length,
-            &mut result as *mut _ as *mut _,
-        );
+        cmsis_dsp_sys::arm_dot_prod_q31(as_q31_ptr(src1), as_q31_ptr(src2), length, &mut result);
     }
-    result
+    I16F48::from_bits(result)
 }
 
 /// Calculates the dot product of two vectors
@@ -233,21 +255,19 @@ pub fn dot_product_q31(src1: &[I1F31], src2: &[I1F31]) -> I16F48 {
 /// The returned value is the sum of `src1[i] * src2[i]` over all values of i
 /// in range.
 ///
+/// `arm_dot_prod_q15` adds each 2.30 product to a 64-bit accumulator without shifting, so
+/// the result has 34 integer bits and 30 fractional bits, and cannot overflow in practice.
+///
 /// # Panics
 ///
 /// This function panics if src1 and src2 do not have the same length.
 pub fn dot_product_q15(src1: &[I1F15], src2: &[I1F15]) -> I34F30 {
-    let length = check_length((src1.len(), src2.len()));
-    let mut result = I34F30::from_bits(0);
+    let length = check_length_or_panic((src1.len(), src2.len()));
+    let mut result = 0;
     unsafe {
-        cmsis_dsp_sys::arm_dot_prod_q15(
-            src1.as_ptr() as *const _,
-            src2.as_ptr() as *const _,
-            length,
-            &mut result as *mut _ as *mut _,
-        );
+        cmsis_dsp_sys::arm_dot_prod_q15(as_q15_ptr(src1), as_q15_ptr(src2), length, &mut result);
     }
-    result
+    I34F30::from_bits(result)
 }
 
 /// Calculates the dot product of two vectors
@@ -259,17 +279,12 @@ pub fn dot_product_q15(src1: &[I1F15], src2: &[I1F15]) -> I34F30 {
 ///
 /// This function panics if src1 and src2 do not have the same length.
 pub fn dot_product_q7(src1: &[I1F7], src2: &[I1F7]) -> I18F14 {
-    let length = check_length((src1.len(), src2.len()));
-    let mut result = I18F14::from_bits(0);
+    let length = check_length_or_panic((src1.len(), src2.len()));
+    let mut result = 0;
     unsafe {
-        cmsis_dsp_sys::arm_dot_prod_q7(
-            src1.as_ptr() as *const _,
-            src2.as_ptr() as *const _,
-            length,
-            &mut result as *mut _ as *mut _,
-        );
+        cmsis_dsp_sys::arm_dot_prod_q7(as_q7_ptr(src1), as_q7_ptr(src2), length, &mut result);
     }
-    result
+    I18F14::from_bits(result)
 }
 
 /// Multiplies multiple values
@@ -281,7 +296,7 @@ pub fn dot_product_q7(src1: &[I1F7], src2: &[I1F7]) -> I18F14 {
 ///
 /// This function panics if src1, src2, and dst do not have the same length.
 pub fn multiply_f32(src1: &[f32], src2: &[f32], dst: &mut [f32]) {
-    let length = check_length((src1.len(), src2.len(), dst.len()));
+    let length = check_length_or_panic((src1.len(), src2.len(), dst.len()));
     unsafe {
         cmsis_dsp_sys::arm_mult_f32(src1.as_ptr(), src2.as_ptr(), dst.as_mut_ptr(), length);
     }
@@ -296,12 +311,12 @@ pub fn multiply_f32(src1: &[f32], src2: &[f32], dst: &mut [f32]) {
 ///
 /// This function panics if src1, src2, and dst do not have the same length.
 pub fn multiply_q31(src1: &[I1F31], src2: &[I1F31], dst: &mut [I1F31]) {
-    let length = check_length((src1.len(), src2.len(), dst.len()));
+    let length = check_length_or_panic((src1.len(), src2.len(), dst.len()));
     unsafe {
         cmsis_dsp_sys::arm_mult_q31(
-            src1.as_ptr() as *const _,
-            src2.as_ptr() as *const _,
-            dst.as_mut_ptr() as *mut _,
+            as_q31_ptr(src1),
+            as_q31_ptr(src2),
+            as_q31_mut_ptr(dst),
             length,
         );
     }
@@ -316,12 +331,12 @@ pub fn multiply_q31(src1: &[I1F31], src2: &[I1F31], dst: &mut [I1F31]) {
 ///
 /// This function panics if src1, src2, and dst do not have the same length.
 pub fn multiply_q15(src1: &[I1F15], src2: &[I1F15], dst: &mut [I1F15]) {
-    let length = check_length((src1.len(), src2.len(), dst.len()));
+    let length = check_length_or_panic((src1.len(), src2.len(), dst.len()));
     unsafe {
         cmsis_dsp_sys::arm_mult_q15(
-            src1.as_ptr() as *const _,
-            src2.as_ptr() as *const _,
-            dst.as_mut_ptr() as *mut _,
+            as_q15_ptr(src1),
+            as_q15_ptr(src2),
+            as_q15_mut_ptr(dst),
             length,
         );
     }
@@ -336,13 +351,660 @@ pub fn multiply_q15(src1: &[I1F15], src2: &[I1F15], dst: &mut [I1F15]) {
 ///
 /// This function panics if src1, src2, and dst do not have the same length.
 pub fn multiply_q7(src1: &[I1F7], src2: &[I1F7], dst: &mut [I1F7]) {
-    let length = check_length((src1.len(), src2.len(), dst.len()));
+    let length = check_length_or_panic((src1.len(), src2.len(), dst.len()));
+    unsafe {
+        cmsis_dsp_sys::arm_mult_q7(as_q7_ptr(src1), as_q7_ptr(src2), as_q7_mut_ptr(dst), length);
+    }
+}
+
+/// Multiplies multiple values by a scale factor
+///
+/// This is functionally equivalent to performing `dst[i] = src[i] * scale` for all values of i
+/// in range.
+///
+/// # Panics
+///
+/// This function panics if src and dst do not have the same length.
+pub fn scale_f32(src: &[f32], scale: f32, dst: &mut [f32]) {
+    let length = check_length_or_panic((src.len(), dst.len()));
+    unsafe {
+        cmsis_dsp_sys::arm_scale_f32(src.as_ptr(), scale, dst.as_mut_ptr(), length);
+    }
+}
+
+/// Multiplies multiple values by a scale factor in place
+///
+/// This is functionally equivalent to performing `values[i] = values[i] * scale` for all values
+/// of i in range.
+pub fn scale_in_place_f32(values: &mut [f32], scale: f32) {
+    let length = check_length_or_panic(values.len());
+    unsafe {
+        let ptr = values.as_mut_ptr();
+        cmsis_dsp_sys::arm_scale_f32(ptr, scale, ptr, length);
+    }
+}
+
+/// Scales multiple values by a fraction and a power of two
+///
+/// This is functionally equivalent to performing `dst[i] = src[i] * scale_fract * 2^shift` for
+/// all values of i in range. Results saturate to the range of Q1.31.
+///
+/// A scale factor outside the range [-1, 1) is expressed with a positive shift. For example,
+/// a scale factor of 3.0 is `scale_fract = 0.75` and `shift = 2`.
+///
+/// # Panics
+///
+/// This function panics if src and dst do not have the same length.
+pub fn scale_q31(src: &[I1F31], scale_fract: I1F31, shift: i8, dst: &mut [I1F31]) {
+    let length = check_length_or_panic((src.len(), dst.len()));
+    unsafe {
+        cmsis_dsp_sys::arm_scale_q31(
+            as_q31_ptr(src),
+            scale_fract.to_bits(),
+            shift,
+            as_q31_mut_ptr(dst),
+            length,
+        );
+    }
+}
+
+/// Scales multiple values by a fraction and a power of two
+///
+/// This is functionally equivalent to performing `dst[i] = src[i] * scale_fract * 2^shift` for
+/// all values of i in range. Results saturate to the range of Q1.15.
+///
+/// A scale factor outside the range [-1, 1) is expressed with a positive shift. For example,
+/// a scale factor of 3.0 is `scale_fract = 0.75` and `shift = 2`.
+///
+/// # Panics
+///
+/// This function panics if src and dst do not have the same length.
+pub fn scale_q15(src: &[I1F15], scale_fract: I1F15, shift: i8, dst: &mut [I1F15]) {
+    let length = check_length_or_panic((src.len(), dst.len()));
     unsafe {
-        cmsis_dsp_sys::arm_mult_q7(
-            src1.as_ptr() as *const _,
-            src2.as_ptr() as *const _,
-            dst.as_mut_ptr() as *mut _,
+        cmsis_dsp_sys::arm_scale_q15(
+            as_q15_ptr(src),
+            scale_fract.to_bits(),
+            shift,
+            as_q15_mut_ptr(dst),
             length,
         );
     }
 }
+
+/// Adds an offset to multiple values
+///
+/// This is functionally equivalent to performing `dst[i] = src[i] + offset` for all values of i
+/// in range.
+///
+/// # Panics
+///
+/// This function panics if src and dst do not have the same length.
+pub fn offset_f32(src: &[f32], offset: f32, dst: &mut [f32]) {
+    let length = check_length_or_panic((src.len(), dst.len()));
+    unsafe {
+        cmsis_dsp_sys::arm_offset_f32(src.as_ptr(), offset, dst.as_mut_ptr(), length);
+    }
+}
+
+/// Adds an offset to multiple values in place
+///
+/// This is functionally equivalent to performing `values[i] = values[i] + offset` for all values
+/// of i in range.
+pub fn offset_in_place_f32(values: &mut [f32], offset: f32) {
+    let length = check_length_or_panic(values.len());
+    unsafe {
+        let ptr = values.as_mut_ptr();
+        cmsis_dsp_sys::arm_offset_f32(ptr, offset, ptr, length);
+    }
+}
+
+/// Shifts multiple values left or right by a number of bits
+///
+/// This is functionally equivalent to performing `dst[i] = src[i] * 2^shift_bits` for all
+/// values of i in range. A positive shift_bits shifts left, and results saturate to the range
+/// of Q1.31. A negative shift_bits shifts right.
+///
+/// # Panics
+///
+/// This function panics if src and dst do not have the same length.
+pub fn shift_q31(src: &[I1F31], shift_bits: i8, dst: &mut [I1F31]) {
+    let length = check_length_or_panic((src.len(), dst.len()));
+    unsafe {
+        cmsis_dsp_sys::arm_shift_q31(as_q31_ptr(src), shift_bits, as_q31_mut_ptr(dst), length);
+    }
+}
+
+/// Shifts multiple values left or right by a number of bits
+///
+/// This is functionally equivalent to performing `dst[i] = src[i] * 2^shift_bits` for all
+/// values of i in range. A positive shift_bits shifts left, and results saturate to the range
+/// of Q1.15. A negative shift_bits shifts right.
+///
+/// # Panics
+///
+/// This function panics if src and dst do not have the same length.
+pub fn shift_q15(src: &[I1F15], shift_bits: i8, dst: &mut [I1F15]) {
+    let length = check_length_or_panic((src.len(), dst.len()));
+    unsafe {
+        cmsis_dsp_sys::arm_shift_q15(as_q15_ptr(src), shift_bits, as_q15_mut_ptr(dst), length);
+    }
+}
+
+/// Shifts multiple values left or right by a number of bits
+///
+/// This is functionally equivalent to performing `dst[i] = src[i] * 2^shift_bits` for all
+/// values of i in range. A positive shift_bits shifts left, and results saturate to the range
+/// of Q1.7. A negative shift_bits shifts right.
+///
+/// # Panics
+///
+/// This function panics if src and dst do not have the same length.
+pub fn shift_q7(src: &[I1F7], shift_bits: i8, dst: &mut [I1F7]) {
+    let length = check_length_or_panic((src.len(), dst.len()));
+    unsafe {
+        cmsis_dsp_sys::arm_shift_q7(as_q7_ptr(src), shift_bits, as_q7_mut_ptr(dst), length);
+    }
+}
+
+/// Scales multiple values so that the largest absolute value is equal to target_peak
+///
+/// This is functionally equivalent to performing `dst[i] = src[i] * (target_peak / peak)`
+/// for all values of i in range, where peak is the largest absolute value in src. If all values
+/// in src are zero, they are copied to dst without scaling.
+///
+/// # Panics
+///
+/// This function panics if src and dst do not have the same length.
+pub fn normalize_peak_f32(src: &[f32], dst: &mut [f32], target_peak: f32) {
+    let length = check_length_or_panic((src.len(), dst.len()));
+    if length == 0 {
+        return;
+    }
+    let mut max = 0.0;
+    let mut min = 0.0;
+    let mut index = 0;
+    unsafe {
+        cmsis_dsp_sys::arm_max_f32(src.as_ptr(), length, &mut max, &mut index);
+        cmsis_dsp_sys::arm_min_f32(src.as_ptr(), length, &mut min, &mut index);
+    }
+    let peak = if -min > max { -min } else { max };
+    let scale = if peak == 0.0 { 1.0 } else { target_peak / peak };
+    unsafe {
+        cmsis_dsp_sys::arm_scale_f32(src.as_ptr(), scale, dst.as_mut_ptr(), length);
+    }
+}
+
+/// Applies soft thresholding to multiple values in place
+///
+/// Values with magnitudes at or below threshold become zero, and all other values move toward
+/// zero by threshold: `values[i] = sign(values[i]) * max(abs(values[i]) - threshold, 0)`. This
+/// is commonly used to denoise the detail coefficients of a wavelet transform.
+pub fn soft_threshold_f32(values: &mut [f32], threshold: f32) {
+    for value in values.iter_mut() {
+        *value = if *value > threshold {
+            *value - threshold
+        } else if *value < -threshold {
+            *value + threshold
+        } else {
+            0.0
+        };
+    }
+}
+
+/// Applies hard thresholding to multiple values in place
+///
+/// Values with magnitudes at or below threshold become zero, and all other values are not
+/// changed.
+pub fn hard_threshold_f32(values: &mut [f32], threshold: f32) {
+    for value in values.iter_mut() {
+        if *value <= threshold && *value >= -threshold {
+            *value = 0.0;
+        }
+    }
+}
+
+/// Limits multiple values to the range [low, high]
+///
+/// Values below low become low, values above high become high, and all other values are not
+/// changed.
+///
+/// This is equivalent to `arm_clip_f32`.
+///
+/// # Panics
+///
+/// This function panics if src and dst do not have the same length, or if low is greater than
+/// high.
+pub fn clip_f32(src: &[f32], low: f32, high: f32, dst: &mut [f32]) {
+    check_length_or_panic::<(usize, usize), usize>((src.len(), dst.len()));
+    assert!(low <= high, "low must be less than or equal to high");
+    for (value, out) in src.iter().zip(dst.iter_mut()) {
+        *out = if *value < low {
+            low
+        } else if *value > high {
+            high
+        } else {
+            *value
+        };
+    }
+}
+
+/// Limits multiple values to the range [low, high]
+///
+/// This is equivalent to `arm_clip_q31`.
+///
+/// # Panics
+///
+/// This function panics if src and dst do not have the same length, or if low is greater than
+/// high.
+pub fn clip_q31(src: &[I1F31], low: I1F31, high: I1F31, dst: &mut [I1F31]) {
+    check_length_or_panic::<(usize, usize), usize>((src.len(), dst.len()));
+    assert!(low <= high, "low must be less than or equal to high");
+    for (value, out) in src.iter().zip(dst.iter_mut()) {
+        *out = (*value).max(low).min(high);
+    }
+}
+
+/// Limits multiple values to the range [low, high]
+///
+/// This is equivalent to `arm_clip_q15`.
+///
+/// # Panics
+///
+/// This function panics if src and dst do not have the same length, or if low is greater than
+/// high.
+pub fn clip_q15(src: &[I1F15], low: I1F15, high: I1F15, dst: &mut [I1F15]) {
+    check_length_or_panic::<(usize, usize), usize>((src.len(), dst.len()));
+    assert!(low <= high, "low must be less than or equal to high");
+    for (value, out) in src.iter().zip(dst.iter_mut()) {
+        *out = (*value).max(low).min(high);
+    }
+}
+
+/// Calculates the sign of multiple values
+///
+/// Each output is -1.0 for a negative value, 1.0 for a positive value, and 0.0 for zero
+/// (positive or negative). NaN values stay NaN.
+///
+/// # Panics
+///
+/// This function panics if src and dst do not have the same length.
+pub fn sign_f32(src: &[f32], dst: &mut [f32]) {
+    check_length_or_panic::<(usize, usize), usize>((src.len(), dst.len()));
+    for (value, out) in src.iter().zip(dst.iter_mut()) {
+        *out = if *value > 0.0 {
+            1.0
+        } else if *value < 0.0 {
+            -1.0
+        } else if *value == 0.0 {
+            0.0
+        } else {
+            *value
+        };
+    }
+}
+
+/// Combines the magnitudes of one vector with the signs of another
+///
+/// The returned value `dst[i]` has the absolute value of `mag[i]` and the sign bit of
+/// `sign[i]`, like `f32::copysign`. Because the sign bit is used, a sign of -0.0 makes the
+/// result negative.
+///
+/// # Panics
+///
+/// This function panics if mag, sign, and dst do not all have the same length.
+pub fn copysign_f32(mag: &[f32], sign: &[f32], dst: &mut [f32]) {
+    check_length_or_panic::<(usize, usize, usize), usize>((mag.len(), sign.len(), dst.len()));
+    const SIGN_BIT: u32 = 0x8000_0000;
+    for ((magnitude, sign), out) in mag.iter().zip(sign.iter()).zip(dst.iter_mut()) {
+        *out = f32::from_bits((magnitude.to_bits() & !SIGN_BIT) | (sign.to_bits() & SIGN_BIT));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn abs() {
+        let mut dst = [0.0; 3];
+        abs_f32(&[-1.0, 2.0, -3.0], &mut dst);
+        assert_eq!(dst, [1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn abs_checks_length() {
+        abs_f32(&[-1.0, 2.0, -3.0], &mut [0.0; 2]);
+    }
+
+    #[test]
+    fn sign() {
+        let src = [
+            2.5,
+            -0.25,
+            0.0,
+            -0.0,
+            f32::INFINITY,
+            f32::NEG_INFINITY,
+            f32::NAN,
+        ];
+        let mut dst = [0.0; 7];
+        sign_f32(&src, &mut dst);
+        assert_eq!(dst[..6], [1.0, -1.0, 0.0, 0.0, 1.0, -1.0]);
+        // Both zeros give positive zero
+        assert!(dst[2].is_sign_positive() && dst[3].is_sign_positive());
+        assert!(dst[6].is_nan());
+    }
+
+    #[test]
+    #[should_panic]
+    fn sign_checks_length() {
+        sign_f32(&[1.0, 2.0], &mut [0.0; 3]);
+    }
+
+    #[test]
+    fn copysign() {
+        let mag = [1.5, -2.0, 3.0, -4.0, 0.0, f32::NAN];
+        let sign = [1.0, 1.0, -1.0, -0.0, -5.0, -1.0];
+        let mut dst = [0.0; 6];
+        copysign_f32(&mag, &sign, &mut dst);
+        assert_eq!(dst[..5], [1.5, 2.0, -3.0, -4.0, -0.0]);
+        assert!(dst[4].is_sign_negative());
+        assert!(dst[5].is_nan() && dst[5].is_sign_negative());
+    }
+
+    #[test]
+    #[should_panic]
+    fn copysign_checks_length() {
+        copysign_f32(&[1.0, 2.0], &[1.0], &mut [0.0; 2]);
+    }
+
+    #[test]
+    fn dot_product_q31_format() {
+        // 0.5 * 0.5 = 0.25, with 48 fractional bits
+        let half = [I1F31::from_num(0.5)];
+        let product = dot_product_q31(&half, &half);
+        assert_eq!(product, I16F48::from_num(0.25));
+        assert_eq!(product.to_bits(), 1 << 46);
+
+        // Sums larger than 1 do not overflow
+        let values = [I1F31::MIN; 4];
+        assert_eq!(dot_product_q31(&values, &values), I16F48::from_num(4));
+    }
+
+    #[test]
+    fn fixed_point_dot_products_match_f32() {
+        let a = [0.5, -0.25, 0.125, 0.9, -0.7, 0.3];
+        let b = [0.75, 0.5, -0.6, 0.2, -0.1, 0.4];
+        let expected: f32 = a.iter().zip(b.iter()).map(|(a, b)| a * b).sum();
+
+        let a_q31: [I1F31; 6] = a.map(I1F31::from_num);
+        let b_q31: [I1F31; 6] = b.map(I1F31::from_num);
+        let result = dot_product_q31(&a_q31, &b_q31).to_num::<f32>();
+        assert!(
+            (result - expected).abs() < 1e-6,
+            "{} != {}",
+            result,
+            expected
+        );
+
+        let a_q15: [I1F15; 6] = a.map(I1F15::from_num);
+        let b_q15: [I1F15; 6] = b.map(I1F15::from_num);
+        let result = dot_product_q15(&a_q15, &b_q15).to_num::<f32>();
+        assert!(
+            (result - expected).abs() < 1e-3,
+            "{} != {}",
+            result,
+            expected
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn dot_product_q31_checks_length() {
+        dot_product_q31(&[I1F31::ZERO; 2], &[I1F31::ZERO; 3]);
+    }
+
+    #[test]
+    fn clip() {
+        let mut dst = [0.0; 5];
+        clip_f32(&[-2.0, -0.5, 0.25, 0.5, 3.0], -0.5, 0.5, &mut dst);
+        assert_eq!(dst, [-0.5, -0.5, 0.25, 0.5, 0.5]);
+        clip_f32(&[-2.0, 1.0, 3.0, 0.0, 1.5], 1.0, 1.0, &mut dst);
+        assert_eq!(dst, [1.0; 5]);
+    }
+
+    #[test]
+    fn clip_fixed_point() {
+        let (low, high) = (I1F31::from_num(-0.25), I1F31::from_num(0.5));
+        let src = [
+            I1F31::MIN,
+            I1F31::from_num(-0.25),
+            I1F31::from_num(0.125),
+            I1F31::MAX,
+        ];
+        let mut dst = [I1F31::ZERO; 4];
+        clip_q31(&src, low, high, &mut dst);
+        assert_eq!(dst, [low, low, I1F31::from_num(0.125), high]);
+
+        let (low, high) = (I1F15::from_num(-0.25), I1F15::from_num(0.5));
+        let src = [
+            I1F15::MIN,
+            I1F15::from_num(-0.25),
+            I1F15::from_num(0.125),
+            I1F15::MAX,
+        ];
+        let mut dst = [I1F15::ZERO; 4];
+        clip_q15(&src, low, high, &mut dst);
+        assert_eq!(dst, [low, low, I1F15::from_num(0.125), high]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn clip_rejects_reversed_range() {
+        clip_f32(&[0.0], 1.0, -1.0, &mut [0.0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn clip_checks_length() {
+        clip_q15(
+            &[I1F15::ZERO; 2],
+            I1F15::ZERO,
+            I1F15::ZERO,
+            &mut [I1F15::ZERO; 3],
+        );
+    }
+
+    #[test]
+    fn scale_and_offset_in_place() {
+        let mut values = [1.0, -2.0, 0.5];
+        scale_in_place_f32(&mut values, 2.0);
+        assert_eq!(values, [2.0, -4.0, 1.0]);
+        offset_in_place_f32(&mut values, -1.0);
+        assert_eq!(values, [1.0, -5.0, 0.0]);
+    }
+
+    const COEFFICIENTS: [f32; 7] = [-2.0, -0.5, -0.25, 0.0, 0.25, 0.375, 1.5];
+
+    #[test]
+    fn soft_threshold() {
+        let mut values = COEFFICIENTS;
+        soft_threshold_f32(&mut values, 0.25);
+        // Values at the threshold become zero, and the others move toward zero
+        assert_eq!(values, [-1.75, -0.25, 0.0, 0.0, 0.0, 0.125, 1.25]);
+    }
+
+    #[test]
+    fn hard_threshold() {
+        let mut values = COEFFICIENTS;
+        hard_threshold_f32(&mut values, 0.25);
+        assert_eq!(values, [-2.0, -0.5, 0.0, 0.0, 0.0, 0.375, 1.5]);
+    }
+
+    #[test]
+    fn zero_threshold_keeps_values() {
+        let mut soft = COEFFICIENTS;
+        soft_threshold_f32(&mut soft, 0.0);
+        assert_eq!(soft, COEFFICIENTS);
+        let mut hard = COEFFICIENTS;
+        hard_threshold_f32(&mut hard, 0.0);
+        assert_eq!(hard, COEFFICIENTS);
+    }
+
+    #[test]
+    fn normalize_peak() {
+        let mut dst = [0.0; 4];
+        // The negative value has the largest magnitude
+        normalize_peak_f32(&[0.5, -2.0, 1.0, 0.0], &mut dst, 0.5);
+        assert_eq!(dst, [0.125, -0.5, 0.25, 0.0]);
+        normalize_peak_f32(&[0.5, -0.25, 1.0, 0.0], &mut dst, 2.0);
+        assert_eq!(dst, [1.0, -0.5, 2.0, 0.0]);
+    }
+
+    #[test]
+    fn normalize_peak_of_zeros() {
+        let mut dst = [1.0; 3];
+        normalize_peak_f32(&[0.0; 3], &mut dst, 1.0);
+        assert_eq!(dst, [0.0; 3]);
+        normalize_peak_f32(&[], &mut [], 1.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn normalize_peak_checks_length() {
+        normalize_peak_f32(&[1.0; 3], &mut [0.0; 2], 1.0);
+    }
+
+    #[test]
+    fn scale_q31_known_values() {
+        let src = [0.5, -0.25, 0.125, 0.0].map(I1F31::from_num);
+        let mut dst = [I1F31::ZERO; 4];
+        scale_q31(&src, I1F31::from_num(0.5), 0, &mut dst);
+        assert_eq!(dst, [0.25, -0.125, 0.0625, 0.0].map(I1F31::from_num));
+        // A scale factor of 3 is 0.75 * 2^2
+        scale_q31(&src[1..], I1F31::from_num(0.75), 2, &mut dst[..3]);
+        assert_eq!(dst[..3], [-0.75, 0.375, 0.0].map(I1F31::from_num));
+        scale_q31(&src, I1F31::from_num(0.5), -2, &mut dst);
+        assert_eq!(dst, [0.0625, -0.03125, 0.015625, 0.0].map(I1F31::from_num));
+    }
+
+    #[test]
+    fn scale_q31_saturates() {
+        let src = [
+            I1F31::from_num(0.5),
+            I1F31::from_num(-0.5),
+            I1F31::MAX,
+            I1F31::MIN,
+        ];
+        let mut dst = [I1F31::ZERO; 4];
+        scale_q31(&src, I1F31::from_num(0.75), 2, &mut dst);
+        assert_eq!(dst, [I1F31::MAX, I1F31::MIN, I1F31::MAX, I1F31::MIN]);
+
+        // -0.5 * 0.5 * 2^2 is exactly -1, the minimum value, so it is not saturated
+        let mut dst = [I1F31::ZERO; 1];
+        scale_q31(&[I1F31::from_num(-0.5)], I1F31::from_num(0.5), 2, &mut dst);
+        assert_eq!(dst, [I1F31::MIN]);
+    }
+
+    #[test]
+    fn scale_q15_saturates() {
+        let src = [0.5, -0.5, 0.125, 0.0].map(I1F15::from_num);
+        let mut dst = [I1F15::ZERO; 4];
+        scale_q15(&src, I1F15::from_num(0.75), 2, &mut dst);
+        assert_eq!(
+            dst,
+            [I1F15::MAX, I1F15::MIN, I1F15::from_num(0.375), I1F15::ZERO]
+        );
+        scale_q15(&src, I1F15::from_num(0.5), -1, &mut dst);
+        assert_eq!(dst, [0.125, -0.125, 0.03125, 0.0].map(I1F15::from_num));
+    }
+
+    #[test]
+    fn shift_q31_saturates_left_shifts() {
+        let src = [
+            I1F31::from_num(0.25),
+            I1F31::from_num(-0.25),
+            I1F31::from_num(0.5),
+            I1F31::from_num(-0.5),
+            I1F31::from_bits(1),
+        ];
+        let mut dst = [I1F31::ZERO; 5];
+        // 0.25 * 2 and -0.5 * 2 stay in range; 0.5 * 2 saturates
+        shift_q31(&src, 1, &mut dst);
+        assert_eq!(
+            dst,
+            [
+                I1F31::from_num(0.5),
+                I1F31::from_num(-0.5),
+                I1F31::MAX,
+                I1F31::MIN,
+                I1F31::from_bits(2)
+            ]
+        );
+        shift_q31(&src, 30, &mut dst);
+        assert_eq!(
+            dst,
+            [
+                I1F31::MAX,
+                I1F31::MIN,
+                I1F31::MAX,
+                I1F31::MIN,
+                I1F31::from_num(0.5)
+            ]
+        );
+        shift_q31(&[I1F31::MIN, I1F31::MAX], 31, &mut dst[..2]);
+        assert_eq!(dst[..2], [I1F31::MIN, I1F31::MAX]);
+    }
+
+    #[test]
+    fn shift_right_rounds_toward_negative_infinity() {
+        let src = [I1F31::from_num(0.5), I1F31::MIN, I1F31::from_bits(-1)];
+        let mut dst = [I1F31::ZERO; 3];
+        shift_q31(&src, -2, &mut dst);
+        assert_eq!(
+            dst,
+            [
+                I1F31::from_num(0.125),
+                I1F31::from_num(-0.25),
+                I1F31::from_bits(-1)
+            ]
+        );
+        shift_q31(&src, 0, &mut dst);
+        assert_eq!(dst, src);
+    }
+
+    #[test]
+    fn shift_q15_and_q7_saturate() {
+        let src = [0.25, -0.25, 0.5, -0.75].map(I1F15::from_num);
+        let mut dst = [I1F15::ZERO; 4];
+        shift_q15(&src, 1, &mut dst);
+        assert_eq!(
+            dst,
+            [
+                I1F15::from_num(0.5),
+                I1F15::from_num(-0.5),
+                I1F15::MAX,
+                I1F15::MIN
+            ]
+        );
+        shift_q15(&src, -1, &mut dst);
+        assert_eq!(dst, [0.125, -0.125, 0.25, -0.375].map(I1F15::from_num));
+
+        let src = [0.25, -0.25, 0.5, -0.75].map(I1F7::from_num);
+        let mut dst = [I1F7::ZERO; 4];
+        shift_q7(&src, 1, &mut dst);
+        assert_eq!(
+            dst,
+            [
+                I1F7::from_num(0.5),
+                I1F7::from_num(-0.5),
+                I1F7::MAX,
+                I1F7::MIN
+            ]
+        );
+        shift_q7(&src, 7, &mut dst);
+        assert_eq!(dst, [I1F7::MAX, I1F7::MIN, I1F7::MAX, I1F7::MIN]);
+    }
+}
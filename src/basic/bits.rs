@@ -0,0 +1,92 @@
+//! Bitwise operations on vectors of unsigned integers
+//!
+//! These functions are useful for masks, event flags, and packed data that are processed along
+//! with a signal.
+
+use crate::check_length_or_panic;
+
+/// Calculates the bitwise AND of multiple pairs of values
+///
+/// This is functionally equivalent to performing `dst[i] = a[i] & b[i]` for all values of i in
+/// range.
+///
+/// # Panics
+///
+/// This function panics if a, b, and dst do not have the same length.
+pub fn and_u32(a: &[u32], b: &[u32], dst: &mut [u32]) {
+    let length = check_length_or_panic((a.len(), b.len(), dst.len()));
+    unsafe {
+        cmsis_dsp_sys::arm_and_u32(a.as_ptr(), b.as_ptr(), dst.as_mut_ptr(), length);
+    }
+}
+
+/// Calculates the bitwise OR of multiple pairs of values
+///
+/// This is functionally equivalent to performing `dst[i] = a[i] | b[i]` for all values of i in
+/// range.
+///
+/// # Panics
+///
+/// This function panics if a, b, and dst do not have the same length.
+pub fn or_u32(a: &[u32], b: &[u32], dst: &mut [u32]) {
+    let length = check_length_or_panic((a.len(), b.len(), dst.len()));
+    unsafe {
+        cmsis_dsp_sys::arm_or_u32(a.as_ptr(), b.as_ptr(), dst.as_mut_ptr(), length);
+    }
+}
+
+/// Calculates the bitwise exclusive OR of multiple pairs of values
+///
+/// This is functionally equivalent to performing `dst[i] = a[i] ^ b[i]` for all values of i in
+/// range.
+///
+/// # Panics
+///
+/// This function panics if a, b, and dst do not have the same length.
+pub fn xor_u32(a: &[u32], b: &[u32], dst: &mut [u32]) {
+    let length = check_length_or_panic((a.len(), b.len(), dst.len()));
+    unsafe {
+        cmsis_dsp_sys::arm_xor_u32(a.as_ptr(), b.as_ptr(), dst.as_mut_ptr(), length);
+    }
+}
+
+/// Calculates the bitwise NOT of multiple values
+///
+/// This is functionally equivalent to performing `dst[i] = !src[i]` for all values of i in
+/// range.
+///
+/// # Panics
+///
+/// This function panics if src and dst do not have the same length.
+pub fn not_u32(src: &[u32], dst: &mut [u32]) {
+    let length = check_length_or_panic((src.len(), dst.len()));
+    unsafe {
+        cmsis_dsp_sys::arm_not_u32(src.as_ptr(), dst.as_mut_ptr(), length);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bitwise_operations() {
+        let a = [0b1100, 0xffff_0000, 0];
+        let b = [0b1010, 0x00ff_ff00, u32::MAX];
+        let mut dst = [0; 3];
+        and_u32(&a, &b, &mut dst);
+        assert_eq!(dst, [0b1000, 0x00ff_0000, 0]);
+        or_u32(&a, &b, &mut dst);
+        assert_eq!(dst, [0b1110, 0xffff_ff00, u32::MAX]);
+        xor_u32(&a, &b, &mut dst);
+        assert_eq!(dst, [0b0110, 0xff00_ff00, u32::MAX]);
+        not_u32(&a, &mut dst);
+        assert_eq!(dst, [!0b1100, 0x0000_ffff, u32::MAX]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn bitwise_operations_check_length() {
+        and_u32(&[0; 2], &[0; 3], &mut [0; 2]);
+    }
+}
@@ -0,0 +1,128 @@
+//! Gaussian naive Bayes classifier
+//!
+//! The classifier uses a model trained elsewhere (for example, with scikit-learn). For each
+//! class, the model contains the mean and variance of each dimension of the input vectors and
+//! the prior probability of the class.
+
+use core::marker::PhantomData;
+
+use crate::check_length_or_panic;
+
+/// A Gaussian naive Bayes classifier
+pub struct GaussianNaiveBayes<'a> {
+    instance: cmsis_dsp_sys::arm_gaussian_naive_bayes_instance_f32,
+    _model: PhantomData<&'a [f32]>,
+}
+
+impl<'a> GaussianNaiveBayes<'a> {
+    /// Creates a classifier
+    ///
+    /// classes: number of classes
+    ///
+    /// dimension: number of elements in each input vector
+    ///
+    /// means: mean of each dimension for each class. The mean of dimension j for class i is at
+    /// `means[i * dimension + j]`.
+    ///
+    /// variances: variance of each dimension for each class, in the same order as means
+    ///
+    /// priors: prior probability of each class
+    ///
+    /// epsilon: value added to each variance, which prevents division by zero
+    ///
+    /// # Panics
+    ///
+    /// This function panics if means or variances does not have `classes * dimension`
+    /// elements, or if priors does not have classes elements.
+    pub fn new(
+        classes: u32,
+        dimension: u32,
+        means: &'a [f32],
+        variances: &'a [f32],
+        priors: &'a [f32],
+        epsilon: f32,
+    ) -> Self {
+        let model_length = classes as usize * dimension as usize;
+        check_length_or_panic::<(usize, usize, usize), u32>((
+            means.len(),
+            variances.len(),
+            model_length,
+        ));
+        check_length_or_panic::<(usize, usize), u32>((priors.len(), classes as usize));
+        GaussianNaiveBayes {
+            instance: cmsis_dsp_sys::arm_gaussian_naive_bayes_instance_f32 {
+                vectorDimension: dimension,
+                numberOfClasses: classes,
+                theta: means.as_ptr(),
+                sigma: variances.as_ptr(),
+                classPriors: priors.as_ptr(),
+                epsilon,
+            },
+            _model: PhantomData,
+        }
+    }
+
+    /// Classifies an input vector and returns the index of the most probable class
+    ///
+    /// The log-likelihood of each class is written to scores.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if input does not have the number of dimensions of the model, or
+    /// if scores does not have one element for each class.
+    pub fn predict(&self, input: &[f32], scores: &mut [f32]) -> u32 {
+        check_length_or_panic::<(usize, usize), u32>((
+            input.len(),
+            self.instance.vectorDimension as usize,
+        ));
+        check_length_or_panic::<(usize, usize), u32>((
+            scores.len(),
+            self.instance.numberOfClasses as usize,
+        ));
+        unsafe {
+            cmsis_dsp_sys::arm_gaussian_naive_bayes_predict_f32(
+                &self.instance,
+                input.as_ptr(),
+                scores.as_mut_ptr(),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MEANS: [f32; 4] = [0.0, 0.0, 5.0, 5.0];
+    const VARIANCES: [f32; 4] = [1.0, 1.0, 2.0, 2.0];
+
+    #[test]
+    fn predict_nearest_class() {
+        let priors = [0.5, 0.5];
+        let bayes = GaussianNaiveBayes::new(2, 2, &MEANS, &VARIANCES, &priors, 0.0);
+        let mut scores = [0.0; 2];
+        assert_eq!(bayes.predict(&[0.5, -0.5], &mut scores), 0);
+        assert_eq!(bayes.predict(&[4.0, 5.5], &mut scores), 1);
+        // log(0.5) - log(2 pi 2) - (1 + 0.25) / 4
+        let expected = 0.5f32.ln() - (4.0 * core::f32::consts::PI).ln() - 1.25 / 4.0;
+        assert!((scores[1] - expected).abs() < 1e-5);
+    }
+
+    #[test]
+    fn priors_decide_between_equal_likelihoods() {
+        let means = [1.0; 4];
+        let variances = [1.0; 4];
+        let mut scores = [0.0; 2];
+        for &(priors, class) in &[([0.25, 0.75], 1), ([0.75, 0.25], 0)] {
+            let bayes = GaussianNaiveBayes::new(2, 2, &means, &variances, &priors, 0.0);
+            assert_eq!(bayes.predict(&[3.0, -2.0], &mut scores), class);
+            assert!((scores[1] - scores[0] - (priors[1] / priors[0]).ln()).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_checks_model_length() {
+        GaussianNaiveBayes::new(2, 3, &MEANS, &VARIANCES, &[0.5, 0.5], 0.0);
+    }
+}
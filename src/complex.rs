@@ -1,8 +1,12 @@
 //! Complex number operations
+//!
+//! The element-wise functions in this module support processing a buffer in place, and the
+//! functions with `in_place` in their names do so. Correlation cannot work in place.
 
 use num_complex::Complex32;
 
-use crate::check_length;
+use crate::check_length_or_panic;
+use crate::fast_math::{atan2_f32, cos_f32, magnitude_to_db_in_place, sin_f32};
 
 /// Calculates the magnitude of each complex number in a provided source slice, and stores
 /// each result in the corresponding position in the destination slice
@@ -12,12 +16,28 @@ use crate::check_length;
 /// This function panics if source.len() is not equal to destination.len(), or if either length
 /// is too large to fit into a 32-bit integer
 pub fn complex_magnitude_f32(source: &[Complex32], destination: &mut [f32]) {
-    let length = check_length((source.len(), destination.len()));
+    let length = check_length_or_panic((source.len(), destination.len()));
     unsafe {
         cmsis_dsp_sys::arm_cmplx_mag_f32(source.as_ptr() as *const f32, destination.as_mut_ptr(), length);
     }
 }
 
+/// Calculates the magnitude of each complex number in a provided source slice in decibels, and
+/// stores each result in the corresponding position in the destination slice
+///
+/// This is functionally equivalent to `20 * log10(|source[i]|)`, so a magnitude of 1 is 0 dB.
+/// Results below floor_db, including those for zero values, are replaced with floor_db. The
+/// conversion is the same as that of `fast_math::magnitude_to_db_f32`.
+///
+/// # Panics
+///
+/// This function panics if source.len() is not equal to destination.len(), or if either length
+/// is too large to fit into a 32-bit integer
+pub fn complex_magnitude_db_f32(source: &[Complex32], destination: &mut [f32], floor_db: f32) {
+    complex_magnitude_f32(source, destination);
+    magnitude_to_db_in_place(destination, 1.0, floor_db);
+}
+
 /// Multiplies two vectors of complex numbers element-wise, storing the results in the destination slice
 /// 
 /// # Panics
@@ -25,7 +45,7 @@ pub fn complex_magnitude_f32(source: &[Complex32], destination: &mut [f32]) {
 /// This function panics if any of the slices do not have the same length, or if that length
 /// is too large to fit into a 32-bit integer
 pub fn complex_mult_complex_f32(source_a: &[Complex32], source_b: &[Complex32], destination: &mut [Complex32]) {
-    let length = check_length((source_a.len(), source_b.len(), destination.len()));
+    let length = check_length_or_panic((source_a.len(), source_b.len(), destination.len()));
     unsafe {
         cmsis_dsp_sys::arm_cmplx_mult_cmplx_f32(
             source_a.as_ptr() as *const f32,
@@ -43,7 +63,7 @@ pub fn complex_mult_complex_f32(source_a: &[Complex32], source_b: &[Complex32],
 /// This function panics if any of the slices do not have the same length, or if that length
 /// is too large to fit into a 32-bit integer
 pub fn complex_mult_real_f32(source_a: &[Complex32], source_b: &[f32], destination: &mut [Complex32]) {
-    let length = check_length((source_a.len(), source_b.len(), destination.len()));
+    let length = check_length_or_panic((source_a.len(), source_b.len(), destination.len()));
     unsafe {
         cmsis_dsp_sys::arm_cmplx_mult_real_f32(
             source_a.as_ptr() as *const f32,
@@ -52,4 +72,330 @@ pub fn complex_mult_real_f32(source_a: &[Complex32], source_b: &[f32], destinati
             length
         );
     }
-}
\ No newline at end of file
+}
+
+/// Adds two vectors of complex numbers element-wise, storing the results in the destination slice
+///
+/// # Panics
+///
+/// This function panics if any of the slices do not have the same length, or if twice that
+/// length is too large to fit into a 32-bit integer
+pub fn complex_add_f32(source_a: &[Complex32], source_b: &[Complex32], destination: &mut [Complex32]) {
+    // Complex32 is layout-compatible with two f32 values, so the real and imaginary parts are
+    // processed as one interleaved vector
+    let length = check_length_or_panic((2 * source_a.len(), 2 * source_b.len(), 2 * destination.len()));
+    unsafe {
+        cmsis_dsp_sys::arm_add_f32(
+            source_a.as_ptr() as *const f32,
+            source_b.as_ptr() as *const f32,
+            destination.as_mut_ptr() as *mut f32,
+            length
+        );
+    }
+}
+
+/// Subtracts source_b from source_a element-wise, storing the results in the destination slice
+///
+/// # Panics
+///
+/// This function panics if any of the slices do not have the same length, or if twice that
+/// length is too large to fit into a 32-bit integer
+pub fn complex_sub_f32(source_a: &[Complex32], source_b: &[Complex32], destination: &mut [Complex32]) {
+    // Complex32 is layout-compatible with two f32 values, so the real and imaginary parts are
+    // processed as one interleaved vector
+    let length = check_length_or_panic((2 * source_a.len(), 2 * source_b.len(), 2 * destination.len()));
+    unsafe {
+        cmsis_dsp_sys::arm_sub_f32(
+            source_a.as_ptr() as *const f32,
+            source_b.as_ptr() as *const f32,
+            destination.as_mut_ptr() as *mut f32,
+            length
+        );
+    }
+}
+
+/// Multiplies each complex number in the source slice by a real scale factor, storing the results
+/// in the destination slice
+///
+/// # Panics
+///
+/// This function panics if source.len() is not equal to destination.len(), or if twice that
+/// length is too large to fit into a 32-bit integer
+pub fn complex_scale_f32(source: &[Complex32], scale: f32, destination: &mut [Complex32]) {
+    // Scaling by a real number scales the real and imaginary parts independently, so they are
+    // processed as one interleaved vector
+    let length = check_length_or_panic((2 * source.len(), 2 * destination.len()));
+    unsafe {
+        cmsis_dsp_sys::arm_scale_f32(
+            source.as_ptr() as *const f32,
+            scale,
+            destination.as_mut_ptr() as *mut f32,
+            length
+        );
+    }
+}
+
+/// Calculates the cross-correlation of two sequences of complex numbers
+///
+/// Each output value is `dst[k] = sum(src_a[n + m] * conj(src_b[n]))` over all n for which both
+/// indices are in range, with the lag `m = k - (L - 1)` and `L = max(src_a.len(), src_b.len())`.
+/// The output therefore contains lags from `-(L - 1)` to `L - 1`, and zero lag is at index `L - 1`.
+///
+/// # Panics
+///
+/// This function panics if src_a or src_b is empty, or if the length of dst is not
+/// `2 * max(src_a.len(), src_b.len()) - 1`.
+pub fn complex_correlate_f32(src_a: &[Complex32], src_b: &[Complex32], dst: &mut [Complex32]) {
+    assert!(!src_a.is_empty() && !src_b.is_empty());
+    let max_length = src_a.len().max(src_b.len());
+    check_length_or_panic::<(usize, usize), usize>((dst.len(), 2 * max_length - 1));
+
+    for (k, out) in dst.iter_mut().enumerate() {
+        // Lag of src_a relative to src_b
+        let lag = k as isize - (max_length as isize - 1);
+        let n_min = if lag < 0 { (-lag) as usize } else { 0 };
+        let n_max = src_b.len().min((src_a.len() as isize - lag).max(0) as usize);
+        let mut sum = Complex32::new(0.0, 0.0);
+        for n in n_min..n_max {
+            sum += src_a[(n as isize + lag) as usize] * src_b[n].conj();
+        }
+        *out = sum;
+    }
+}
+
+/// Converts complex numbers into polar form
+///
+/// For each complex number `src[i]`, this stores its magnitude in `magnitude[i]` and its phase
+/// in `phase[i]`. Phases are in radians, in the range (-pi, pi].
+///
+/// # Panics
+///
+/// This function panics if src, magnitude, and phase do not have the same length.
+pub fn complex_to_polar_f32(src: &[Complex32], magnitude: &mut [f32], phase: &mut [f32]) {
+    check_length_or_panic::<(usize, usize, usize), u32>((src.len(), magnitude.len(), phase.len()));
+    complex_magnitude_f32(src, magnitude);
+    for (value, phase) in src.iter().zip(phase.iter_mut()) {
+        *phase = atan2_f32(value.im, value.re);
+    }
+}
+
+/// Converts polar coordinates into complex numbers
+///
+/// This is the inverse of `complex_to_polar_f32`: `dst[i]` is set to
+/// `magnitude[i] * (cos(phase[i]) + i sin(phase[i]))`. Phases are in radians.
+///
+/// # Panics
+///
+/// This function panics if magnitude, phase, and dst do not have the same length.
+pub fn polar_to_complex_f32(magnitude: &[f32], phase: &[f32], dst: &mut [Complex32]) {
+    check_length_or_panic::<(usize, usize, usize), u32>((magnitude.len(), phase.len(), dst.len()));
+    for ((magnitude, phase), value) in magnitude.iter().zip(phase.iter()).zip(dst.iter_mut()) {
+        *value = Complex32::new(magnitude * cos_f32(*phase), magnitude * sin_f32(*phase));
+    }
+}
+
+/// Calculates a power spectral density from a complex spectrum
+///
+/// Each output value is `psd[i] = |spectrum[i]|^2 * normalization`. For the FFT of N samples
+/// taken at a sample rate fs, a normalization of `1 / (fs * N)` gives a two-sided density in
+/// units of power per hertz.
+///
+/// # Panics
+///
+/// This function panics if spectrum and psd do not have the same length, or if that length
+/// is too large to fit into a 32-bit integer
+pub fn power_spectral_density_f32(spectrum: &[Complex32], psd: &mut [f32], normalization: f32) {
+    let length = check_length_or_panic((spectrum.len(), psd.len()));
+    unsafe {
+        cmsis_dsp_sys::arm_cmplx_mag_squared_f32(spectrum.as_ptr() as *const f32, psd.as_mut_ptr(), length);
+        cmsis_dsp_sys::arm_scale_f32(psd.as_ptr(), normalization, psd.as_mut_ptr(), length);
+    }
+}
+
+/// Calculates the complex conjugate of each complex number in a provided source slice, and stores
+/// each result in the corresponding position in the destination slice
+///
+/// # Panics
+///
+/// This function panics if source.len() is not equal to destination.len(), or if either length
+/// is too large to fit into a 32-bit integer
+pub fn complex_conjugate_f32(source: &[Complex32], destination: &mut [Complex32]) {
+    let length = check_length_or_panic((source.len(), destination.len()));
+    unsafe {
+        cmsis_dsp_sys::arm_cmplx_conj_f32(source.as_ptr() as *const f32, destination.as_mut_ptr() as *mut f32, length);
+    }
+}
+
+/// Replaces each complex number in a slice with its complex conjugate
+///
+/// # Panics
+///
+/// This function panics if the length of values is too large to fit into a 32-bit integer
+pub fn complex_conjugate_in_place_f32(values: &mut [Complex32]) {
+    let length = check_length_or_panic(values.len());
+    unsafe {
+        let ptr = values.as_mut_ptr() as *mut f32;
+        cmsis_dsp_sys::arm_cmplx_conj_f32(ptr, ptr, length);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::f32::consts::{FRAC_PI_2, PI};
+
+    fn c(re: f32, im: f32) -> Complex32 {
+        Complex32::new(re, im)
+    }
+
+    #[test]
+    fn correlate_lags() {
+        let a = [c(1.0, 0.0), c(0.0, 1.0), c(2.0, 0.0)];
+        let b = [c(1.0, 1.0), c(0.0, 2.0)];
+        let mut dst = [c(9.0, 9.0); 5];
+        complex_correlate_f32(&a, &b, &mut dst);
+        // Lags -2 to 2, calculated from the definition
+        let expected = [
+            c(0.0, 0.0),
+            a[0] * b[1].conj(),
+            a[0] * b[0].conj() + a[1] * b[1].conj(),
+            a[1] * b[0].conj() + a[2] * b[1].conj(),
+            a[2] * b[0].conj(),
+        ];
+        assert_eq!(dst, expected);
+        // Swapping the sources reverses and conjugates the result, also when src_a is shorter
+        let mut swapped = [c(0.0, 0.0); 5];
+        complex_correlate_f32(&b, &a, &mut swapped);
+        for (value, other) in swapped.iter().zip(dst.iter().rev()) {
+            assert_eq!(*value, other.conj());
+        }
+    }
+
+    #[test]
+    fn autocorrelation_peaks_at_zero_lag() {
+        let a = [c(1.0, 2.0), c(-1.0, 0.5), c(0.0, -1.0), c(2.0, 1.0)];
+        let mut dst = [c(0.0, 0.0); 7];
+        complex_correlate_f32(&a, &a, &mut dst);
+        let energy: f32 = a.iter().map(|value| value.norm_sqr()).sum();
+        assert_eq!(dst[3], c(energy, 0.0));
+        for (k, value) in dst.iter().enumerate() {
+            // The autocorrelation is conjugate symmetric
+            assert_eq!(*value, dst[6 - k].conj());
+            assert!(value.norm_sqr() <= energy * energy);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn correlate_checks_length() {
+        complex_correlate_f32(&[c(1.0, 0.0); 3], &[c(1.0, 0.0); 2], &mut [c(0.0, 0.0); 4]);
+    }
+
+    #[test]
+    fn polar_round_trip() {
+        let values = [c(3.0, 4.0), c(-1.0, 0.0), c(0.0, -2.0), c(0.0, 0.0)];
+        let (mut magnitude, mut phase) = ([0.0; 4], [0.0; 4]);
+        complex_to_polar_f32(&values, &mut magnitude, &mut phase);
+        assert_eq!(magnitude, [5.0, 1.0, 2.0, 0.0]);
+        let expected_phase = [(4.0f32).atan2(3.0), PI, -FRAC_PI_2, 0.0];
+        for (phase, expected) in phase.iter().zip(expected_phase.iter()) {
+            assert!((phase - expected).abs() < 1e-6);
+        }
+        let mut round_trip = [c(9.0, 9.0); 4];
+        polar_to_complex_f32(&magnitude, &phase, &mut round_trip);
+        for (value, original) in round_trip.iter().zip(values.iter()) {
+            assert!((value - original).norm_sqr() < 1e-10);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn polar_checks_length() {
+        complex_to_polar_f32(&[c(1.0, 0.0); 2], &mut [0.0; 2], &mut [0.0; 3]);
+    }
+
+    #[test]
+    fn add_and_sub() {
+        let a = [c(1.0, 2.0), c(-3.0, 0.5)];
+        let b = [c(0.5, -1.0), c(2.0, 2.0)];
+        let mut dst = [c(0.0, 0.0); 2];
+        complex_add_f32(&a, &b, &mut dst);
+        assert_eq!(dst, [c(1.5, 1.0), c(-1.0, 2.5)]);
+        complex_sub_f32(&a, &b, &mut dst);
+        assert_eq!(dst, [c(0.5, 3.0), c(-5.0, -1.5)]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn add_checks_length() {
+        complex_add_f32(&[c(0.0, 0.0); 2], &[c(0.0, 0.0); 2], &mut [c(0.0, 0.0); 3]);
+    }
+
+    #[test]
+    fn power_spectral_density_scales_squared_magnitude() {
+        let spectrum = [c(3.0, 4.0), c(0.0, -2.0), c(0.0, 0.0)];
+        let mut psd = [9.0; 3];
+        power_spectral_density_f32(&spectrum, &mut psd, 0.5);
+        assert_eq!(psd, [12.5, 2.0, 0.0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn power_spectral_density_checks_length() {
+        power_spectral_density_f32(&[c(0.0, 0.0); 2], &mut [0.0; 3], 1.0);
+    }
+
+    #[test]
+    fn conjugate() {
+        let src = [c(1.0, 2.0), c(-3.0, -0.5)];
+        let mut dst = [c(0.0, 0.0); 2];
+        complex_conjugate_f32(&src, &mut dst);
+        assert_eq!(dst, [c(1.0, -2.0), c(-3.0, 0.5)]);
+
+        let mut values = src;
+        complex_conjugate_in_place_f32(&mut values);
+        assert_eq!(values, dst);
+    }
+
+    #[test]
+    #[should_panic]
+    fn conjugate_checks_length() {
+        complex_conjugate_f32(&[c(0.0, 0.0); 2], &mut [c(0.0, 0.0); 1]);
+    }
+
+    #[test]
+    fn scale() {
+        let src = [c(1.0, -2.0), c(0.5, 4.0)];
+        let mut dst = [c(0.0, 0.0); 2];
+        complex_scale_f32(&src, -2.0, &mut dst);
+        assert_eq!(dst, [c(-2.0, 4.0), c(-1.0, -8.0)]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn scale_checks_length() {
+        complex_scale_f32(&[c(0.0, 0.0); 2], 1.0, &mut [c(0.0, 0.0); 3]);
+    }
+
+    #[test]
+    fn magnitude_db() {
+        let src = [
+            c(0.0, 10.0),
+            c(3.0, 4.0),
+            c(1.0, 0.0),
+            c(1e-5, 0.0),
+            c(0.0, 0.0),
+        ];
+        let mut dst = [0.0; 5];
+        complex_magnitude_db_f32(&src, &mut dst, -60.0);
+        let expected = [20.0, 13.9794, 0.0, -60.0, -60.0];
+        for (value, expected) in dst.iter().zip(expected.iter()) {
+            assert!(
+                (value - expected).abs() < 1e-3,
+                "{:?} != {:?}",
+                dst,
+                expected
+            );
+        }
+    }
+}
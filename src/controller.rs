@@ -0,0 +1,211 @@
+//! Motor control functions: Clarke and Park transforms, and sine and cosine calculation
+//!
+//! The Clarke and Park transforms are inline functions in the CMSIS-DSP headers
+//! (`arm_clarke_f32` and others), so the bindings do not contain them. These functions
+//! implement the same calculations, and the Q1.31 versions round and saturate in the same way
+//! as the CMSIS-DSP versions.
+//!
+//! The Clarke transform converts the currents of two phases of a balanced three-phase system
+//! (`a` and `b`) into two orthogonal components (`alpha` and `beta`). The Park transform rotates
+//! those components by the rotor angle, producing components in the rotating reference frame
+//! (`d` and `q`). The Park functions take the sine and cosine of the angle, which can be
+//! calculated with `sin_cos_f32` or `sin_cos_q31`.
+
+use fixed::types::I1F31;
+
+/// 1 / sqrt(3) in Q2.30
+const ONE_BY_SQRT3_Q30: i64 = 0x24F3_4E8B;
+/// 2 / sqrt(3) in Q2.30
+const TWO_BY_SQRT3_Q30: i64 = 0x49E6_9D16;
+/// sqrt(3) / 2 in Q1.31
+const SQRT3_BY_TWO_Q31: i64 = 0x6ED9_EBA1;
+
+/// Calculates the sine and cosine of an angle in degrees
+///
+/// This is equivalent to `arm_sin_cos_f32`. The angle must be in the range [-180, 180].
+/// This function returns (sine, cosine).
+pub fn sin_cos_f32(degrees: f32) -> (f32, f32) {
+    let (mut sin, mut cos) = (0.0, 0.0);
+    unsafe {
+        cmsis_dsp_sys::arm_sin_cos_f32(degrees, &mut sin, &mut cos);
+    }
+    (sin, cos)
+}
+
+/// Calculates the sine and cosine of an angle
+///
+/// This is equivalent to `arm_sin_cos_q31`. The angle is scaled so that the range [-1, 1)
+/// corresponds to [-180, 180) degrees. This function returns (sine, cosine).
+pub fn sin_cos_q31(angle: I1F31) -> (I1F31, I1F31) {
+    let (mut sin, mut cos) = (0, 0);
+    unsafe {
+        cmsis_dsp_sys::arm_sin_cos_q31(angle.to_bits(), &mut sin, &mut cos);
+    }
+    (I1F31::from_bits(sin), I1F31::from_bits(cos))
+}
+
+/// Converts the currents of phases a and b into the components alpha and beta
+///
+/// This is equivalent to `arm_clarke_f32`. This function returns (alpha, beta).
+pub fn clarke_f32(a: f32, b: f32) -> (f32, f32) {
+    let alpha = a;
+    let beta = 0.577_350_26 * a + 1.154_700_5 * b;
+    (alpha, beta)
+}
+
+/// Converts the currents of phases a and b into the components alpha and beta
+///
+/// This is equivalent to `arm_clarke_q31`. The sum that gives beta saturates to the range of
+/// Q1.31. As in CMSIS-DSP, the product of b and 2 / sqrt(3) is truncated to 32 bits without
+/// saturation, so the magnitude of b must be less than sqrt(3) / 2. This function returns
+/// (alpha, beta).
+pub fn clarke_q31(a: I1F31, b: I1F31) -> (I1F31, I1F31) {
+    let product1 = ((i64::from(a.to_bits()) * ONE_BY_SQRT3_Q30) >> 30) as i32;
+    let product2 = ((i64::from(b.to_bits()) * TWO_BY_SQRT3_Q30) >> 30) as i32;
+    (a, I1F31::from_bits(product1.saturating_add(product2)))
+}
+
+/// Converts the components alpha and beta into the currents of phases a and b
+///
+/// This is equivalent to `arm_inv_clarke_f32`. This function returns (a, b).
+pub fn inverse_clarke_f32(alpha: f32, beta: f32) -> (f32, f32) {
+    let a = alpha;
+    let b = -0.5 * alpha + 0.866_025_4 * beta;
+    (a, b)
+}
+
+/// Converts the components alpha and beta into the currents of phases a and b
+///
+/// This is equivalent to `arm_inv_clarke_q31`. Results saturate to the range of Q1.31. This
+/// function returns (a, b).
+pub fn inverse_clarke_q31(alpha: I1F31, beta: I1F31) -> (I1F31, I1F31) {
+    let product1 = ((i64::from(alpha.to_bits()) * 0x4000_0000) >> 31) as i32;
+    let product2 = ((i64::from(beta.to_bits()) * SQRT3_BY_TWO_Q31) >> 31) as i32;
+    (alpha, I1F31::from_bits(product2.saturating_sub(product1)))
+}
+
+/// Rotates the components alpha and beta into the components d and q
+///
+/// This is equivalent to `arm_park_f32`. sin and cos are the sine and cosine of the rotor
+/// angle. This function returns (d, q).
+pub fn park_f32(alpha: f32, beta: f32, sin: f32, cos: f32) -> (f32, f32) {
+    let d = alpha * cos + beta * sin;
+    let q = -alpha * sin + beta * cos;
+    (d, q)
+}
+
+/// Rotates the components alpha and beta into the components d and q
+///
+/// This is equivalent to `arm_park_q31`. sin and cos are the sine and cosine of the rotor
+/// angle. Results saturate to the range of Q1.31. This function returns (d, q).
+pub fn park_q31(alpha: I1F31, beta: I1F31, sin: I1F31, cos: I1F31) -> (I1F31, I1F31) {
+    let d = mul_q31(alpha, cos).saturating_add(mul_q31(beta, sin));
+    let q = mul_q31(beta, cos).saturating_sub(mul_q31(alpha, sin));
+    (I1F31::from_bits(d), I1F31::from_bits(q))
+}
+
+/// Rotates the components d and q back into the components alpha and beta
+///
+/// This is equivalent to `arm_inv_park_f32`. sin and cos are the sine and cosine of the rotor
+/// angle. This function returns (alpha, beta).
+pub fn inverse_park_f32(d: f32, q: f32, sin: f32, cos: f32) -> (f32, f32) {
+    let alpha = d * cos - q * sin;
+    let beta = d * sin + q * cos;
+    (alpha, beta)
+}
+
+/// Rotates the components d and q back into the components alpha and beta
+///
+/// This is equivalent to `arm_inv_park_q31`. sin and cos are the sine and cosine of the rotor
+/// angle. Results saturate to the range of Q1.31. This function returns (alpha, beta).
+pub fn inverse_park_q31(d: I1F31, q: I1F31, sin: I1F31, cos: I1F31) -> (I1F31, I1F31) {
+    let alpha = mul_q31(d, cos).saturating_sub(mul_q31(q, sin));
+    let beta = mul_q31(d, sin).saturating_add(mul_q31(q, cos));
+    (I1F31::from_bits(alpha), I1F31::from_bits(beta))
+}
+
+/// Multiplies two Q1.31 values and truncates the product to Q1.31, like the CMSIS-DSP
+/// controller functions
+fn mul_q31(a: I1F31, b: I1F31) -> i32 {
+    ((i64::from(a.to_bits()) * i64::from(b.to_bits())) >> 31) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::f32::consts::PI;
+
+    /// Rotor angles in degrees
+    const ANGLES: [f32; 8] = [0.0, 30.0, 45.0, 90.0, 135.0, -60.0, -150.0, 179.0];
+    /// Largest difference between the Q1.31 and f32 results, which is set by the precision of
+    /// f32
+    const TOLERANCE: f32 = 1e-6;
+
+    fn q31(value: f32) -> I1F31 {
+        I1F31::saturating_from_num(value)
+    }
+
+    fn assert_close(fixed: (I1F31, I1F31), float: (f32, f32)) {
+        let fixed = (fixed.0.to_num::<f32>(), fixed.1.to_num::<f32>());
+        assert!(
+            (fixed.0 - float.0).abs() < TOLERANCE && (fixed.1 - float.1).abs() < TOLERANCE,
+            "{:?} != {:?}",
+            fixed,
+            float
+        );
+    }
+
+    /// Returns the currents of phases a and b and the sine and cosine of the angle
+    fn phases(degrees: f32) -> (f32, f32, f32, f32) {
+        let angle = degrees * PI / 180.0;
+        let a = 0.4 * angle.cos();
+        let b = 0.4 * (angle - 2.0 * PI / 3.0).cos();
+        (a, b, angle.sin(), angle.cos())
+    }
+
+    #[test]
+    fn clarke_q31_matches_f32() {
+        for &degrees in ANGLES.iter() {
+            let (a, b, _, _) = phases(degrees);
+            let (alpha, beta) = clarke_f32(a, b);
+            assert_close(clarke_q31(q31(a), q31(b)), (alpha, beta));
+            assert_close(
+                inverse_clarke_q31(q31(alpha), q31(beta)),
+                inverse_clarke_f32(alpha, beta),
+            );
+            // The inverse recovers the phase currents
+            let (a2, b2) = inverse_clarke_f32(alpha, beta);
+            assert!((a2 - a).abs() < TOLERANCE && (b2 - b).abs() < TOLERANCE);
+        }
+    }
+
+    #[test]
+    fn park_q31_matches_f32() {
+        for &degrees in ANGLES.iter() {
+            let (a, b, sin, cos) = phases(degrees);
+            let (alpha, beta) = clarke_f32(a, b);
+            let (d, q) = park_f32(alpha, beta, sin, cos);
+            // Balanced currents in phase with the rotor have only a d component
+            assert!((d - 0.4).abs() < TOLERANCE && q.abs() < TOLERANCE);
+            let (sin_q31, cos_q31) = (q31(sin), q31(cos));
+            assert_close(park_q31(q31(alpha), q31(beta), sin_q31, cos_q31), (d, q));
+            assert_close(
+                inverse_park_q31(q31(d), q31(q), sin_q31, cos_q31),
+                inverse_park_f32(d, q, sin, cos),
+            );
+        }
+    }
+
+    #[test]
+    fn q31_results_saturate() {
+        let (_, beta) = clarke_q31(q31(0.5), q31(0.7));
+        assert_eq!(beta, I1F31::MAX);
+        let (_, beta) = clarke_q31(q31(-0.5), q31(-0.7));
+        assert_eq!(beta, I1F31::MIN);
+        let (_, b) = inverse_clarke_q31(I1F31::MAX, I1F31::MIN);
+        assert_eq!(b, I1F31::MIN);
+        let (d, q) = park_q31(I1F31::MAX, I1F31::MAX, I1F31::MAX, I1F31::MAX);
+        assert_eq!(d, I1F31::MAX);
+        assert!(q.abs() < q31(TOLERANCE));
+    }
+}
@@ -0,0 +1,83 @@
+//! Distance functions
+//!
+//! These functions measure the distance between two vectors with the same number of
+//! dimensions, which is useful for nearest-neighbor classification.
+
+use crate::check_length_or_panic;
+
+/// Calculates the Euclidean distance between two vectors
+///
+/// This is the square root of the sum of `(a[i] - b[i])^2` over all values of i in range.
+///
+/// # Panics
+///
+/// This function panics if a and b do not have the same length.
+pub fn euclidean_distance_f32(a: &[f32], b: &[f32]) -> f32 {
+    let length = check_length_or_panic((a.len(), b.len()));
+    unsafe { cmsis_dsp_sys::arm_euclidean_distance_f32(a.as_ptr(), b.as_ptr(), length) }
+}
+
+/// Calculates the Manhattan (city block) distance between two vectors
+///
+/// This is the sum of `|a[i] - b[i]|` over all values of i in range.
+///
+/// # Panics
+///
+/// This function panics if a and b do not have the same length.
+pub fn cityblock_distance_f32(a: &[f32], b: &[f32]) -> f32 {
+    let length = check_length_or_panic((a.len(), b.len()));
+    unsafe { cmsis_dsp_sys::arm_cityblock_distance_f32(a.as_ptr(), b.as_ptr(), length) }
+}
+
+/// Calculates the cosine distance between two vectors
+///
+/// This is 1 minus the cosine of the angle between the vectors, so it is 0 for vectors that
+/// point in the same direction and 2 for vectors that point in opposite directions.
+///
+/// # Panics
+///
+/// This function panics if a and b do not have the same length.
+pub fn cosine_distance_f32(a: &[f32], b: &[f32]) -> f32 {
+    let length = check_length_or_panic((a.len(), b.len()));
+    unsafe { cmsis_dsp_sys::arm_cosine_distance_f32(a.as_ptr(), b.as_ptr(), length) }
+}
+
+/// Calculates the Chebyshev distance between two vectors
+///
+/// This is the maximum of `|a[i] - b[i]|` over all values of i in range.
+///
+/// # Panics
+///
+/// This function panics if a and b do not have the same length.
+pub fn chebyshev_distance_f32(a: &[f32], b: &[f32]) -> f32 {
+    let length = check_length_or_panic((a.len(), b.len()));
+    unsafe { cmsis_dsp_sys::arm_chebyshev_distance_f32(a.as_ptr(), b.as_ptr(), length) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distances() {
+        let (a, b) = ([1.0, 2.0, 3.0], [4.0, 6.0, 3.0]);
+        assert!((euclidean_distance_f32(&a, &b) - 5.0).abs() < 1e-6);
+        assert!((cityblock_distance_f32(&a, &b) - 7.0).abs() < 1e-6);
+        assert!((chebyshev_distance_f32(&a, &b) - 4.0).abs() < 1e-6);
+        assert_eq!(euclidean_distance_f32(&a, &a), 0.0);
+    }
+
+    #[test]
+    fn cosine_distance() {
+        let a = [1.0, 2.0, 2.0];
+        assert!(cosine_distance_f32(&a, &[2.0, 4.0, 4.0]).abs() < 1e-6);
+        assert!((cosine_distance_f32(&a, &[-1.0, -2.0, -2.0]) - 2.0).abs() < 1e-6);
+        assert!((cosine_distance_f32(&a, &[2.0, -1.0, 0.0]) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    #[should_panic]
+    fn distance_checks_length() {
+        euclidean_distance_f32(&[0.0; 2], &[0.0; 3]);
+    }
+}
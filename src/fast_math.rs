@@ -0,0 +1,296 @@
+//! Fast math functions
+//!
+//! `arm_sqrt_f32` is defined inline in the CMSIS-DSP header files, so `sqrt_f32` is a Rust
+//! implementation of the same function. Like the C version, it calls the C standard library
+//! function `sqrtf` (see the README for ways to provide it).
+//!
+//! `atan2_f32` is a Rust implementation of `arm_atan2_f32`, with a similar accuracy.
+
+use core::f32::consts::{FRAC_PI_2, FRAC_PI_4, LN_10, LOG10_E, PI};
+
+use crate::{check_length_or_panic, Error, Result};
+
+extern "C" {
+    fn sqrtf(value: f32) -> f32;
+}
+
+/// Calculates an approximation of the sine of x (in radians)
+pub fn sin_f32(x: f32) -> f32 {
+    unsafe { cmsis_dsp_sys::arm_sin_f32(x) }
+}
+
+/// Calculates an approximation of the cosine of x (in radians)
+pub fn cos_f32(x: f32) -> f32 {
+    unsafe { cmsis_dsp_sys::arm_cos_f32(x) }
+}
+
+/// Calculates the square root of x
+///
+/// If x is negative, this function returns zero.
+pub fn sqrt_f32(x: f32) -> f32 {
+    if x >= 0.0 {
+        unsafe { sqrtf(x) }
+    } else {
+        0.0
+    }
+}
+
+/// Calculates the four-quadrant arctangent of y / x, in radians
+///
+/// The result is in the range (-pi, pi]. If x and y are both zero, the result is zero.
+pub fn atan2_f32(y: f32, x: f32) -> f32 {
+    if x == 0.0 && y == 0.0 {
+        return 0.0;
+    }
+    let (x_abs, y_abs) = (x.abs(), y.abs());
+    // Angle of (x_abs, y_abs), in the range [0, pi / 2]
+    let angle = if y_abs <= x_abs {
+        atan_unit(y_abs / x_abs)
+    } else {
+        FRAC_PI_2 - atan_unit(x_abs / y_abs)
+    };
+    let angle = if x < 0.0 { PI - angle } else { angle };
+    if y < 0.0 {
+        -angle
+    } else {
+        angle
+    }
+}
+
+/// Calculates the four-quadrant arctangent of multiple pairs of values, in radians
+///
+/// This is functionally equivalent to performing `dst[i] = atan2_f32(y[i], x[i])` for all
+/// values of i in range.
+///
+/// Like `arm_atan2_f32`, this function reports the pairs where x and y are both zero, for
+/// which the angle is undefined. The corresponding results are zero, all other results are
+/// calculated, and this function returns `Error::NanInf`.
+///
+/// # Panics
+///
+/// This function panics if y, x, and dst do not have the same length.
+pub fn vatan2_f32(y: &[f32], x: &[f32], dst: &mut [f32]) -> Result<()> {
+    check_length_or_panic::<(usize, usize, usize), usize>((y.len(), x.len(), dst.len()));
+    let mut undefined = false;
+    for ((y, x), out) in y.iter().zip(x.iter()).zip(dst.iter_mut()) {
+        undefined |= *x == 0.0 && *y == 0.0;
+        *out = atan2_f32(*y, *x);
+    }
+    if undefined {
+        Err(Error::NanInf)
+    } else {
+        Ok(())
+    }
+}
+
+/// Calculates the natural logarithm of multiple values
+///
+/// This is functionally equivalent to performing `dst[i] = ln(src[i])` for all values of i
+/// in range. Without vector instructions, `arm_vlog_f32` calls the C standard library function
+/// `logf`, so the logarithm of zero is negative infinity and the logarithm of a negative value
+/// is NaN.
+///
+/// # Panics
+///
+/// This function panics if src and dst do not have the same length.
+pub fn vlog_f32(src: &[f32], dst: &mut [f32]) {
+    let length = check_length_or_panic((src.len(), dst.len()));
+    unsafe {
+        cmsis_dsp_sys::arm_vlog_f32(src.as_ptr(), dst.as_mut_ptr(), length);
+    }
+}
+
+/// Calculates e raised to the power of multiple values
+///
+/// This is functionally equivalent to performing `dst[i] = e^src[i]` for all values of i in
+/// range.
+///
+/// # Panics
+///
+/// This function panics if src and dst do not have the same length.
+pub fn vexp_f32(src: &[f32], dst: &mut [f32]) {
+    let length = check_length_or_panic((src.len(), dst.len()));
+    unsafe {
+        cmsis_dsp_sys::arm_vexp_f32(src.as_ptr(), dst.as_mut_ptr(), length);
+    }
+}
+
+/// The lowest level in decibels that `magnitude_to_db_f32` produces
+pub const DB_FLOOR: f32 = -120.0;
+
+/// Converts multiple magnitudes into decibels relative to a reference magnitude
+///
+/// This is functionally equivalent to performing `dst[i] = 20 * log10(src[i] / reference)` for
+/// all values of i in range, except that results below `DB_FLOOR` (-120 dB) are replaced with
+/// `DB_FLOOR`. This keeps magnitudes of zero from producing negative infinity. The logarithms
+/// are calculated with `arm_vlog_f32`.
+///
+/// reference: The magnitude that corresponds to 0 dB, which must be positive. For example, the
+/// full-scale magnitude of a spectrum.
+///
+/// # Panics
+///
+/// This function panics if src and dst do not have the same length.
+pub fn magnitude_to_db_f32(src: &[f32], dst: &mut [f32], reference: f32) {
+    check_length_or_panic::<(usize, usize), usize>((src.len(), dst.len()));
+    dst.copy_from_slice(src);
+    magnitude_to_db_in_place(dst, reference, DB_FLOOR);
+}
+
+/// Replaces each magnitude in values with its level in decibels relative to reference, with
+/// levels below floor_db replaced with floor_db
+pub(crate) fn magnitude_to_db_in_place(values: &mut [f32], reference: f32, floor_db: f32) {
+    let length = check_length_or_panic(values.len());
+    // 10^(floor_db / 20), the ratio at the floor
+    let floor_ratio = exp_f32(floor_db / 20.0 * LN_10);
+    for value in values.iter_mut() {
+        // max also replaces NaN with the floor
+        *value = (*value / reference).max(floor_ratio);
+    }
+    unsafe {
+        // arm_vlog_f32 supports in-place operation
+        cmsis_dsp_sys::arm_vlog_f32(values.as_ptr(), values.as_mut_ptr(), length);
+    }
+    for value in values.iter_mut() {
+        // The rounding of the floor ratio and its logarithm can give a level just below the floor
+        *value = (*value * 20.0 * LOG10_E).max(floor_db);
+    }
+}
+
+/// Calculates the arctangent of a value in the range [0, 1]
+fn atan_unit(x: f32) -> f32 {
+    // Use atan(x) = pi / 4 + atan((x - 1) / (x + 1)) to keep the series argument below
+    // tan(pi / 8), where six terms give an error below 1e-6.
+    const TAN_PI_8: f32 = 0.414_213_57;
+    let (offset, t) = if x > TAN_PI_8 {
+        (FRAC_PI_4, (x - 1.0) / (x + 1.0))
+    } else {
+        (0.0, x)
+    };
+    let t2 = t * t;
+    let series = t
+        * (1.0
+            - t2 * (1.0 / 3.0
+                - t2 * (1.0 / 5.0 - t2 * (1.0 / 7.0 - t2 * (1.0 / 9.0 - t2 * (1.0 / 11.0))))));
+    offset + series
+}
+
+/// Calculates the tangent of x (in radians)
+pub(crate) fn tan_f32(x: f32) -> f32 {
+    sin_f32(x) / cos_f32(x)
+}
+
+/// Calculates the natural logarithm of x
+pub(crate) fn ln_f32(x: f32) -> f32 {
+    let mut result = 0.0;
+    unsafe {
+        cmsis_dsp_sys::arm_vlog_f32(&x, &mut result, 1);
+    }
+    result
+}
+
+/// Calculates e raised to the power x
+pub(crate) fn exp_f32(x: f32) -> f32 {
+    let mut result = 0.0;
+    unsafe {
+        cmsis_dsp_sys::arm_vexp_f32(&x, &mut result, 1);
+    }
+    result
+}
+
+/// Calculates the base-10 logarithm of x
+pub(crate) fn log10_f32(x: f32) -> f32 {
+    ln_f32(x) * LOG10_E
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn atan2_matches_std() {
+        for i in 0..720 {
+            let angle = -PI + (i as f32 + 0.5) * PI / 360.0;
+            for &radius in &[1e-3, 1.0, 250.0] {
+                let (y, x) = (radius * angle.sin(), radius * angle.cos());
+                let error = (atan2_f32(y, x) - y.atan2(x)).abs();
+                assert!(error < 2e-6, "atan2({}, {}) error {}", y, x, error);
+            }
+        }
+    }
+
+    #[test]
+    fn atan2_axes() {
+        assert_eq!(atan2_f32(0.0, 0.0), 0.0);
+        assert_eq!(atan2_f32(0.0, 2.0), 0.0);
+        assert_eq!(atan2_f32(0.0, -2.0), PI);
+        assert_eq!(atan2_f32(2.0, 0.0), FRAC_PI_2);
+        assert_eq!(atan2_f32(-2.0, 0.0), -FRAC_PI_2);
+        assert!((atan2_f32(1.0, 1.0) - FRAC_PI_4).abs() < 1e-7);
+        assert!((atan2_f32(-1.0, -1.0) + 3.0 * FRAC_PI_4).abs() < 1e-6);
+    }
+
+    #[test]
+    fn vatan2_reports_undefined_angles() {
+        let mut dst = [1.0; 3];
+        assert!(vatan2_f32(&[1.0, 0.0], &[0.0, -1.0], &mut dst[..2]).is_ok());
+        assert_eq!(dst[..2], [FRAC_PI_2, PI]);
+        assert!(matches!(
+            vatan2_f32(&[1.0, 0.0, -1.0], &[0.0, 0.0, 0.0], &mut dst),
+            Err(Error::NanInf)
+        ));
+        assert_eq!(dst, [FRAC_PI_2, 0.0, -FRAC_PI_2]);
+    }
+
+    #[test]
+    fn vlog_and_vexp_known_values() {
+        let src = [1.0, core::f32::consts::E, 10.0, 0.5];
+        let mut logs = [0.0; 4];
+        vlog_f32(&src, &mut logs);
+        let expected = [0.0, 1.0, LN_10, -core::f32::consts::LN_2];
+        for (y, expected) in logs.iter().zip(expected.iter()) {
+            assert!((y - expected).abs() < 1e-6, "{} != {}", y, expected);
+        }
+
+        let mut restored = [0.0; 4];
+        vexp_f32(&logs, &mut restored);
+        for (y, x) in restored.iter().zip(src.iter()) {
+            assert!((y - x).abs() < 1e-5 * x, "{} != {}", y, x);
+        }
+    }
+
+    #[test]
+    fn vlog_and_vexp_of_zero() {
+        let mut dst = [0.0; 3];
+        vlog_f32(&[0.0, -0.0, -1.0], &mut dst);
+        assert_eq!(dst[..2], [f32::NEG_INFINITY; 2]);
+        assert!(dst[2].is_nan());
+        vexp_f32(&[0.0, f32::NEG_INFINITY, -200.0], &mut dst);
+        assert_eq!(dst, [1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn magnitude_to_db_known_values() {
+        let src = [1.0, 10.0, 0.1, 2.0, 0.001];
+        let mut dst = [0.0; 5];
+        magnitude_to_db_f32(&src, &mut dst, 1.0);
+        let expected = [0.0, 20.0, -20.0, 6.0206, -60.0];
+        for (y, expected) in dst.iter().zip(expected.iter()) {
+            assert!((y - expected).abs() < 1e-4, "{} != {}", y, expected);
+        }
+
+        // The reference magnitude is 0 dB
+        magnitude_to_db_f32(&[4.0, 0.4], &mut dst[..2], 4.0);
+        assert!(dst[0].abs() < 1e-5);
+        assert!((dst[1] + 20.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn magnitude_to_db_of_zero_is_the_floor() {
+        let mut dst = [0.0; 4];
+        magnitude_to_db_f32(&[0.0, 1e-9, f32::NAN, 1e-6], &mut dst, 1.0);
+        assert_eq!(dst[..3], [DB_FLOOR; 3]);
+        // -120 dB itself is not changed by the floor
+        assert!((dst[3] - DB_FLOOR).abs() < 1e-3, "{}", dst[3]);
+    }
+}
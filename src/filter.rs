@@ -1,18 +1,46 @@
+use core::f32::consts::PI;
+use core::marker::PhantomData;
 use core::mem::MaybeUninit;
-use core::num;
 
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use fixed::types::{I1F15, I1F31};
+use num_complex::Complex32;
+#[cfg(feature = "alloc")]
+use num_complex::Complex;
+
+use crate::{check_length, check_length_or_panic};
+use crate::support::{as_q15_mut_ptr, as_q15_ptr, as_q31_mut_ptr, as_q31_ptr};
+use crate::fast_math::{atan2_f32, cos_f32, sqrt_f32};
+#[cfg(feature = "alloc")]
+use crate::fast_math::{log10_f32, sin_f32};
+#[cfg(feature = "alloc")]
+use crate::transform::FloatRealFft;
+use crate::{Error, Result};
+#[cfg(feature = "alloc")]
+use crate::{window::Window, StatusCode};
+
+pub mod biquad;
+pub mod fir_design;
 
-use crate::check_length;
-use crate::Error;
-use crate::Result;
 /// Calculates the convolution of two input sequences.
 ///
+/// The output has `src_a.len() + src_b.len() - 1` elements. The convolution with an empty
+/// sequence is empty, so if src_a or src_b is empty, dst must also be empty.
+///
+/// `try_conv_f32` returns an error instead of panicking.
+///
 /// # Panics
 /// 
-/// This function will panic if the length of the output buffer is not equal to `srcA.len() + srcB.len() - 1`.
+/// This function will panic if the length of the output buffer is not equal to
+/// `src_a.len() + src_b.len() - 1`, or to zero if src_a or src_b is empty.
 pub fn conv_f32(src_a: &[f32], src_b: &[f32], dst: &mut [f32]) {
-    let lengths = (dst.len(), (src_a.len() + src_b.len() - 1) as usize);
-    check_length::<(usize, usize), usize>(lengths);
+    check_length_or_panic::<(usize, usize), usize>((dst.len(), conv_length(src_a, src_b)));
+    if dst.is_empty() {
+        return;
+    }
 
     for n in 0..dst.len() {
         dst[n] = 0.0;
@@ -24,10 +52,384 @@ pub fn conv_f32(src_a: &[f32], src_b: &[f32], dst: &mut [f32]) {
     }
 }
 
+/// Calculates the convolution of two input sequences, returning an error instead of panicking
+/// if the lengths are not valid
+///
+/// This function returns `Error::LengthMismatch` if the length of the output buffer is not
+/// equal to `src_a.len() + src_b.len() - 1`, or to zero if src_a or src_b is empty.
+pub fn try_conv_f32(src_a: &[f32], src_b: &[f32], dst: &mut [f32]) -> Result<()> {
+    check_length::<(usize, usize), usize>((conv_length(src_a, src_b), dst.len()))?;
+    conv_f32(src_a, src_b, dst);
+    Ok(())
+}
+
+/// Returns the length of the convolution of two sequences
+fn conv_length(src_a: &[f32], src_b: &[f32]) -> usize {
+    if src_a.is_empty() || src_b.is_empty() {
+        0
+    } else {
+        src_a.len() + src_b.len() - 1
+    }
+}
+
+/// Calculates the cross-correlation of two input sequences
+///
+/// Each output value is `dst[k] = sum(src_a[n + m] * src_b[n])` over all n for which both indices
+/// are in range, with the lag `m = k - (L - 1)` and `L = max(src_a.len(), src_b.len())`. The
+/// output therefore contains lags from `-(L - 1)` to `L - 1`, and zero lag is at index `L - 1`.
+///
+/// # Panics
+///
+/// This function panics if src_a or src_b is empty, or if the length of dst is not
+/// `2 * max(src_a.len(), src_b.len()) - 1`.
+pub fn correlate_f32(src_a: &[f32], src_b: &[f32], dst: &mut [f32]) {
+    assert!(!src_a.is_empty() && !src_b.is_empty());
+    let max_length = src_a.len().max(src_b.len());
+    check_length_or_panic::<(usize, usize), usize>((dst.len(), 2 * max_length - 1));
+    unsafe {
+        cmsis_dsp_sys::arm_correlate_f32(
+            src_a.as_ptr(),
+            src_a.len() as u32,
+            src_b.as_ptr(),
+            src_b.len() as u32,
+            dst.as_mut_ptr(),
+        );
+    }
+}
+
+/// Estimates the delay of a signal relative to a reference
+///
+/// This function calculates the cross-correlation of signal and reference with `correlate_f32`
+/// and finds its largest value at a lag from 0 to `signal.len() - 1`. It returns the lag, in
+/// samples, and the correlation at that lag. If signal is reference delayed by d samples, the
+/// lag is d.
+///
+/// Only non-negative lags are searched, so a signal that leads the reference gives a lag of 0.
+/// The peak may be at either end of the searched range. A peak at the last lag only overlaps
+/// one sample of each sequence, so callers that need a reliable estimate should check that the
+/// lag is well inside the range.
+///
+/// This function is available when the `alloc` feature is enabled.
+///
+/// # Panics
+///
+/// This function panics if reference or signal is empty.
+#[cfg(feature = "alloc")]
+pub fn estimate_lag_f32(reference: &[f32], signal: &[f32]) -> (usize, f32) {
+    assert!(!reference.is_empty() && !signal.is_empty());
+    let max_length = reference.len().max(signal.len());
+    let mut correlation = vec![0.0; 2 * max_length - 1];
+    correlate_f32(signal, reference, &mut correlation);
+
+    let lags = &correlation[max_length - 1..max_length - 1 + signal.len()];
+    let mut peak = 0.0;
+    let mut index = 0;
+    unsafe {
+        cmsis_dsp_sys::arm_max_f32(lags.as_ptr(), lags.len() as u32, &mut peak, &mut index);
+    }
+    (index as usize, peak)
+}
+
+/// Finds the position in a signal that best matches a template
+///
+/// For each offset at which the template fits entirely in the signal, this function calculates
+/// the normalized cross-correlation between the template and the part of the signal that it
+/// overlaps: `sum(signal[offset + i] * template[i]) / sqrt(sum(signal[offset + i]^2) *
+/// sum(template[i]^2))`. Because the correlation is normalized by the local energy of the
+/// signal, a scaled copy of the template matches as well as an exact copy.
+///
+/// This function returns the offset with the highest correlation, and the correlation as a
+/// confidence in the range [0, 1]. A confidence of 1 means that the signal at the offset is a
+/// positively scaled copy of the template. Negative correlations are reported as 0. If there
+/// is no offset with energy in both the signal and the template, this function returns
+/// `(0, 0.0)`.
+///
+/// # Panics
+///
+/// This function panics if template is empty or longer than signal.
+pub fn match_template_f32(signal: &[f32], template: &[f32]) -> (usize, f32) {
+    assert!(!template.is_empty(), "template must not be empty");
+    assert!(template.len() <= signal.len(), "template must not be longer than signal");
+    let length = template.len() as u32;
+    let mut template_energy = 0.0;
+    unsafe {
+        cmsis_dsp_sys::arm_power_f32(template.as_ptr(), length, &mut template_energy);
+    }
+
+    let mut best = (0, 0.0);
+    for (offset, window) in signal.windows(template.len()).enumerate() {
+        let mut dot = 0.0;
+        let mut window_energy = 0.0;
+        unsafe {
+            cmsis_dsp_sys::arm_dot_prod_f32(window.as_ptr(), template.as_ptr(), length, &mut dot);
+            cmsis_dsp_sys::arm_power_f32(window.as_ptr(), length, &mut window_energy);
+        }
+        let energy = window_energy * template_energy;
+        if energy > 0.0 {
+            let correlation = dot / sqrt_f32(energy);
+            if correlation > best.1 {
+                best = (offset, correlation);
+            }
+        }
+    }
+    (best.0, best.1.min(1.0))
+}
+
+
+/// Applies a first-order pre-emphasis filter, `y[n] = x[n] - coeff * x[n-1]`
+///
+/// state holds the last input sample from the previous call (`x[-1]`), and is updated so that a
+/// signal can be processed in several blocks. Initialize it to 0.0 before the first block.
+///
+/// Typical values for coeff are between 0.9 and 1.0.
+///
+/// # Panics
+///
+/// This function panics if src and dst do not have the same length.
+pub fn pre_emphasis_f32(src: &[f32], dst: &mut [f32], coeff: f32, state: &mut f32) {
+    check_length_or_panic::<(usize, usize), usize>((src.len(), dst.len()));
+    let mut previous = *state;
+    for (x, y) in src.iter().zip(dst.iter_mut()) {
+        *y = *x - coeff * previous;
+        previous = *x;
+    }
+    *state = previous;
+}
+
+/// Applies a first-order de-emphasis filter, `y[n] = x[n] + coeff * y[n-1]`
+///
+/// This is the inverse of `pre_emphasis_f32` with the same coefficient.
+///
+/// state holds the last output sample from the previous call (`y[-1]`), and is updated so that a
+/// signal can be processed in several blocks. Initialize it to 0.0 before the first block.
+///
+/// # Panics
+///
+/// This function panics if src and dst do not have the same length.
+pub fn de_emphasis_f32(src: &[f32], dst: &mut [f32], coeff: f32, state: &mut f32) {
+    check_length_or_panic::<(usize, usize), usize>((src.len(), dst.len()));
+    let mut previous = *state;
+    for (x, y) in src.iter().zip(dst.iter_mut()) {
+        previous = *x + coeff * previous;
+        *y = previous;
+    }
+    *state = previous;
+}
+
+/// State for `remove_dc_f32`
+///
+/// The state can be copied to save a checkpoint, and copied back to resume from it.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct DcState {
+    /// Current estimate of the DC offset
+    pub offset: f32,
+    /// Weight of each new sample in the offset estimate, in the range (0, 1]
+    pub smoothing: f32,
+}
+
+impl DcState {
+    /// Creates a state with an offset estimate of zero
+    ///
+    /// smoothing: weight of each new sample in the offset estimate. Smaller values track the
+    /// offset more slowly, and remove less of the low-frequency content of the signal. With a
+    /// sample rate fs, the cutoff frequency is about `smoothing * fs / (2 * pi)`. A typical
+    /// value is 0.001.
+    pub fn new(smoothing: f32) -> Self {
+        DcState {
+            offset: 0.0,
+            smoothing,
+        }
+    }
+}
+
+/// Removes a slowly varying DC offset from a signal
+///
+/// For each sample, the offset estimate moves toward the sample by `state.smoothing` times the
+/// difference between them, and the output is the sample minus the updated estimate:
+/// `offset += smoothing * (x[n] - offset)`, `y[n] = x[n] - offset`.
+///
+/// state is updated so that a signal can be processed in several blocks.
+///
+/// # Panics
+///
+/// This function panics if src and dst do not have the same length.
+pub fn remove_dc_f32(src: &[f32], dst: &mut [f32], state: &mut DcState) {
+    check_length_or_panic::<(usize, usize), usize>((src.len(), dst.len()));
+    let mut offset = state.offset;
+    for (x, y) in src.iter().zip(dst.iter_mut()) {
+        offset += state.smoothing * (*x - offset);
+        *y = *x - offset;
+    }
+    state.offset = offset;
+}
+
+/// A DC blocking filter, which is a first-order highpass filter with a zero at DC
+///
+/// The filter implements `y[n] = x[n] - x[n-1] + r * y[n-1]`, where r is the pole, with a
+/// one-stage `arm_biquad_cascade_df2T_f32`. A constant input decays to zero, and the speed of
+/// the decay depends on r: values of r closer to 1 give a lower cutoff frequency and a slower
+/// decay, and remove less of the low-frequency signal content.
+///
+/// The filter stores its coefficients and state, so it does not need external buffers and can
+/// be moved freely.
+#[derive(Debug, Clone)]
+pub struct DcBlocker {
+    /// Coefficients in the CMSIS-DSP format
+    coeffs: [f32; 5],
+    state: [f32; 2],
+}
+
+impl DcBlocker {
+    /// Creates a DC blocker with the provided pole
+    ///
+    /// pole: pole r of the filter, in the range [0, 1). Typical values are between 0.99 and
+    /// 0.999.
+    ///
+    /// This function returns an error if pole is not in the range [0, 1).
+    pub fn new(pole: f32) -> Result<Self> {
+        if !(0.0..1.0).contains(&pole) {
+            return Err(Error::Argument);
+        }
+        Ok(DcBlocker {
+            coeffs: [1.0, -1.0, 0.0, pole, 0.0],
+            state: [0.0; 2],
+        })
+    }
+
+    /// Creates a DC blocker with an approximate -3 dB cutoff frequency
+    ///
+    /// The pole is `1 - 2 * pi * cutoff / sample_rate`, which is accurate when the cutoff is much
+    /// lower than the sample rate.
+    ///
+    /// This function returns an error if cutoff is not positive or if it gives a pole outside
+    /// the range [0, 1).
+    pub fn from_cutoff(cutoff: f32, sample_rate: f32) -> Result<Self> {
+        if cutoff.is_nan() || cutoff <= 0.0 {
+            return Err(Error::Argument);
+        }
+        Self::new(1.0 - 2.0 * PI * cutoff / sample_rate)
+    }
+
+    /// Returns the pole of the filter
+    pub fn pole(&self) -> f32 {
+        self.coeffs[3]
+    }
+
+    /// Processes a block of samples through the filter
+    ///
+    /// # Panics
+    ///
+    /// This function panics if src and dst do not have the same length.
+    pub fn process(&mut self, src: &[f32], dst: &mut [f32]) {
+        let length = check_length_or_panic((src.len(), dst.len()));
+        // The instance refers to the coefficients and state inside this struct, so it is
+        // created for each call instead of being stored
+        let instance = cmsis_dsp_sys::arm_biquad_cascade_df2T_instance_f32 {
+            numStages: 1,
+            pState: self.state.as_mut_ptr(),
+            pCoeffs: self.coeffs.as_ptr(),
+        };
+        unsafe {
+            cmsis_dsp_sys::arm_biquad_cascade_df2T_f32(&instance, src.as_ptr(), dst.as_mut_ptr(), length);
+        }
+    }
+
+    /// Processes a block of samples through the filter, returning an error instead of
+    /// panicking if the lengths are not valid
+    ///
+    /// This function returns `Error::LengthMismatch` if src and dst do not have the same length.
+    pub fn try_process(&mut self, src: &[f32], dst: &mut [f32]) -> Result<()> {
+        check_length::<(usize, usize), u32>((src.len(), dst.len()))?;
+        self.process(src, dst);
+        Ok(())
+    }
+
+    /// Clears the filter state
+    pub fn reset(&mut self) {
+        self.state = [0.0; 2];
+    }
+}
+
+/// An exponential moving average, which is a first-order lowpass filter
+///
+/// The filter implements `y[n] = alpha * x[n] + (1 - alpha) * y[n-1]`. After a step in the
+/// input, the output covers `1 - 1/e` (63%) of the step in about `1 / alpha` samples when alpha
+/// is small, so with a sample rate fs the time constant is about `1 / (alpha * fs)`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ExponentialMovingAverage {
+    alpha: f32,
+    /// Most recent output
+    value: f32,
+}
+
+impl ExponentialMovingAverage {
+    /// Creates a moving average with an initial value of zero
+    ///
+    /// alpha: weight of each new sample, in the range (0, 1]. An alpha of 1 passes the input
+    /// through unchanged.
+    ///
+    /// This function returns an error if alpha is not in the range (0, 1].
+    pub fn new(alpha: f32) -> Result<Self> {
+        if alpha.is_nan() || alpha <= 0.0 || alpha > 1.0 {
+            return Err(Error::Argument);
+        }
+        Ok(ExponentialMovingAverage { alpha, value: 0.0 })
+    }
+
+    /// Returns the weight of each new sample
+    pub fn alpha(&self) -> f32 {
+        self.alpha
+    }
+
+    /// Returns the current average, which is the most recent output
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    /// Processes one sample and returns the updated average
+    pub fn process_sample(&mut self, x: f32) -> f32 {
+        self.value += self.alpha * (x - self.value);
+        self.value
+    }
+
+    /// Processes a block of samples
+    ///
+    /// # Panics
+    ///
+    /// This function panics if src and dst do not have the same length.
+    pub fn process(&mut self, src: &[f32], dst: &mut [f32]) {
+        check_length_or_panic::<(usize, usize), usize>((src.len(), dst.len()));
+        for (x, y) in src.iter().zip(dst.iter_mut()) {
+            *y = self.process_sample(*x);
+        }
+    }
 
-pub struct BiquadCascadeDF2TFilter(cmsis_dsp_sys::arm_biquad_cascade_df2T_instance_f32);
+    /// Processes a block of samples, returning an error instead of panicking if the lengths are
+    /// not valid
+    ///
+    /// This function returns `Error::LengthMismatch` if src and dst do not have the same length.
+    pub fn try_process(&mut self, src: &[f32], dst: &mut [f32]) -> Result<()> {
+        check_length::<(usize, usize), usize>((src.len(), dst.len()))?;
+        self.process(src, dst);
+        Ok(())
+    }
+
+    /// Sets the current average, for example to the first sample of a signal so that the output
+    /// does not start at zero
+    pub fn reset(&mut self, value: f32) {
+        self.value = value;
+    }
+}
+
+/// A biquad cascade IIR filter using a direct form II transposed structure
+///
+/// The filter borrows its coefficients and state buffer for the lifetime 'a.
+pub struct BiquadCascadeDF2TFilter<'a>(
+    cmsis_dsp_sys::arm_biquad_cascade_df2T_instance_f32,
+    PhantomData<&'a mut [f32]>,
+);
 
-impl BiquadCascadeDF2TFilter {
+impl<'a> BiquadCascadeDF2TFilter<'a> {
     /// Initializes a Biquad Cascade IIR Filters Using a Direct Form II Transposed Structure
     /// 
     /// num_stages: number of 2nd order stages in the filter.
@@ -38,14 +440,86 @@ impl BiquadCascadeDF2TFilter {
     /// 
     /// state: State buffer. The array is of length 2 * num_stages.
     /// 
-    pub fn new(num_stages: u8, coeffs: &[f32], state: &mut [f32]) -> Result<Self> {
-        check_length::<(usize, usize), usize>((coeffs.len(), (5 * num_stages) as usize));
-        check_length::<(usize, usize), usize>((state.len(), (2 * num_stages) as usize));
+    pub fn new(num_stages: u8, coeffs: &'a [f32], state: &'a mut [f32]) -> Result<Self> {
+        check_length_or_panic::<(usize, usize), usize>((coeffs.len(), (5 * num_stages) as usize));
+        check_length_or_panic::<(usize, usize), usize>((state.len(), (2 * num_stages) as usize));
         let mut data = MaybeUninit::<cmsis_dsp_sys::arm_biquad_cascade_df2T_instance_f32>::uninit();
         unsafe {
             cmsis_dsp_sys::arm_biquad_cascade_df2T_init_f32 (data.as_mut_ptr(), num_stages, coeffs.as_ptr(), state.as_mut_ptr());
-            Ok(BiquadCascadeDF2TFilter(data.assume_init()))
+            Ok(BiquadCascadeDF2TFilter(data.assume_init(), PhantomData))
+        }
+    }
+
+    /// Initializes a filter from second-order sections in the format that scipy uses
+    ///
+    /// sos: One row {b0, b1, b2, a0, a1, a2} for each stage, like the output of
+    /// `scipy.signal.butter(..., output='sos')`. The transfer function of each stage is
+    /// `(b0 + b1 z^-1 + b2 z^-2) / (a0 + a1 z^-1 + a2 z^-2)`.
+    ///
+    /// coeffs: Coefficient array, which this function fills with the coefficients in the CMSIS-DSP
+    /// format. The array is of length 5 * sos.len().
+    ///
+    /// state: State buffer. The array is of length 2 * sos.len().
+    ///
+    /// This function returns an error if sos has more than 255 stages, or if any a0 is zero.
+    pub fn from_sos(sos: &[[f32; 6]], coeffs: &'a mut [f32], state: &'a mut [f32]) -> Result<Self> {
+        if sos.len() > usize::from(u8::MAX) || sos.iter().any(|section| section[3] == 0.0) {
+            return Err(Error::Argument);
         }
+        check_length_or_panic::<(usize, usize), usize>((coeffs.len(), 5 * sos.len()));
+        for (section, stage) in sos.iter().zip(coeffs.chunks_exact_mut(5)) {
+            let a0 = section[3];
+            // CMSIS-DSP adds the feedback terms, so the signs of a1 and a2 are reversed
+            stage.copy_from_slice(&[
+                section[0] / a0,
+                section[1] / a0,
+                section[2] / a0,
+                -section[4] / a0,
+                -section[5] / a0,
+            ]);
+        }
+        Self::new(sos.len() as u8, coeffs, state)
+    }
+
+    /// Returns the coefficients of each stage in the (b, a) format that scipy uses
+    ///
+    /// Each stage has the transfer function `(b0 + b1 z^-1 + b2 z^-2) / (a0 + a1 z^-1 + a2 z^-2)`
+    /// with `a0 = 1`. This is the inverse of `from_sos` for sections that have `a0 = 1`.
+    ///
+    /// This function is available when the `alloc` feature is enabled.
+    #[cfg(feature = "alloc")]
+    pub fn to_ba(&self) -> Vec<([f32; 3], [f32; 3])> {
+        self.coeffs()
+            .chunks_exact(5)
+            .map(|stage| ([stage[0], stage[1], stage[2]], [1.0, -stage[3], -stage[4]]))
+            .collect()
+    }
+
+    /// Replaces the coefficients of all stages while the filter is running
+    ///
+    /// coeffs: Coefficient array, in the same format as for `new`. The array is of length
+    /// 5 * num_stages, and the filter uses it instead of the previous coefficients from now on.
+    /// Like the coefficients passed to new, it is borrowed for the lifetime of the filter.
+    ///
+    /// The state buffer is not cleared, so the output continues smoothly from the previous
+    /// samples instead of restarting from zero, which would cause a click in audio signals.
+    /// `arm_biquad_cascade_df2T_init_f32` clears the state, so this function only replaces the
+    /// coefficient pointer of the instance.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the length of coeffs is not 5 times the number of stages.
+    pub fn update_coeffs(&mut self, coeffs: &'a [f32]) {
+        check_length_or_panic::<(usize, usize), usize>((coeffs.len(), 5 * usize::from(self.0.numStages)));
+        self.0.pCoeffs = coeffs.as_ptr();
+    }
+
+    /// Returns the coefficients of all stages, in the CMSIS-DSP format
+    #[cfg(feature = "alloc")]
+    fn coeffs(&self) -> &[f32] {
+        // The coefficients passed to new or update_coeffs are borrowed for 'a, and new checked
+        // that they have 5 * numStages elements
+        unsafe { core::slice::from_raw_parts(self.0.pCoeffs, 5 * usize::from(self.0.numStages)) }
     }
 
     /// Process the input data through the filter.
@@ -55,88 +529,3536 @@ impl BiquadCascadeDF2TFilter {
     /// 
     /// The length of the input and output buffers must be the same.
     pub fn process(&mut self, src: &[f32], dst: &mut [f32], block_size: u32) {
-        check_length::<(usize, usize), usize>((src.len(), dst.len()));
+        check_length_or_panic::<(usize, usize), usize>((src.len(), dst.len()));
         assert!(block_size as usize <= src.len() && block_size as usize <= dst.len());
         unsafe {
-            cmsis_dsp_sys::arm_biquad_cascade_df2T_f32(&mut self.0, src.as_ptr(), dst.as_mut_ptr(), block_size as u32);
+            cmsis_dsp_sys::arm_biquad_cascade_df2T_f32(&self.0, src.as_ptr(), dst.as_mut_ptr(), block_size);
         }
     }
-}
 
-pub struct FirFilter(cmsis_dsp_sys::arm_fir_instance_f32);
+    /// Processes the input data through the filter, returning an error instead of panicking
+    /// if the lengths are not valid
+    ///
+    /// This function returns `Error::LengthMismatch` if src and dst do not have the same length,
+    /// or `Error::Length` if block_size is larger than their length.
+    pub fn try_process(&mut self, src: &[f32], dst: &mut [f32], block_size: u32) -> Result<()> {
+        check_length::<(usize, usize), usize>((src.len(), dst.len()))?;
+        if block_size as usize > src.len() {
+            return Err(Error::Length);
+        }
+        self.process(src, dst, block_size);
+        Ok(())
+    }
 
-impl FirFilter {
-    /// Initializes a FIR Filter
-    /// 
-    /// num_taps: number of filter coefficients in the filter 
+    /// Processes the input data through the filter, limiting block_size to the length of the
+    /// buffers instead of panicking
     ///
-    /// coeffs points to the array of filter coefficients stored in time reversed order: 
-    /// {b[numTaps-1], b[numTaps-2], b[N-2], ..., b[1], b[0]}
-    /// 
-    /// state: State buffer. The array is of length num_taps + block_size - 1.
-    /// 
-    /// block_size: number of samples processed per call 
-    /// 
-    pub fn new(num_taps: u16, coeffs: &[f32], state: &mut [f32], block_size: u32) -> Result<Self> {
-        check_length::<(usize, usize), usize>((num_taps as usize, coeffs.len()));
-        check_length::<(usize, usize), usize>((((num_taps as u32)+block_size-1) as usize, state.len()));
-        let mut data = MaybeUninit::<cmsis_dsp_sys::arm_fir_instance_f32>::uninit();
-        unsafe {
-            cmsis_dsp_sys::arm_fir_init_f32 (data.as_mut_ptr(), num_taps, coeffs.as_ptr(), state.as_mut_ptr(), block_size);
-            Ok(FirFilter(data.assume_init()))
+    /// This is for callers with buffers of varying lengths, such as audio callbacks. If
+    /// block_size is larger than the length of src and dst, only their length is processed.
+    /// This function returns the number of samples processed, or `Error::LengthMismatch` if src
+    /// and dst do not have the same length.
+    pub fn process_checked(&mut self, src: &[f32], dst: &mut [f32], block_size: u32) -> Result<u32> {
+        let length = check_length::<(usize, usize), u32>((src.len(), dst.len()))?;
+        let block_size = block_size.min(length);
+        self.process(src, dst, block_size);
+        Ok(block_size)
+    }
+
+    /// Processes a signal of any length through the filter in chunks of at most chunk
+    /// samples, with one call into CMSIS-DSP per chunk
+    ///
+    /// The filter state carries over between chunks, so the output is the same as processing
+    /// the whole signal in one call.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if src and dst do not have the same length, or if chunk is zero.
+    pub fn process_chunked(&mut self, src: &[f32], dst: &mut [f32], chunk: usize) {
+        check_length_or_panic::<(usize, usize), usize>((src.len(), dst.len()));
+        assert!(chunk > 0, "Chunk size must be greater than zero");
+        for (src, dst) in src.chunks(chunk).zip(dst.chunks_mut(chunk)) {
+            self.process(src, dst, src.len() as u32);
         }
     }
-    /// Process the input data through the filter.
-    /// 
-    /// src: Input data buffer.
-    /// dst: Output data buffer.
-    /// 
-    /// The length of the input and output buffers must be the same.
-    pub fn process(&mut self, src: &[f32], dst: &mut [f32], block_size: u32) {
-        check_length::<(usize, usize), usize>((src.len(), dst.len()));
-        assert!(block_size as usize <= src.len() && block_size as usize <= dst.len());
+
+    /// Processes a single sample through the filter and returns the output sample
+    ///
+    /// This is useful for code that receives one sample at a time, like an interrupt handler
+    /// in a control loop. The filter state is shared with process, so the two methods can be
+    /// mixed and produce the same output as processing all samples in one block.
+    ///
+    /// Each call has the overhead of a call into the CMSIS-DSP library, which sets up the
+    /// coefficient and state pointers for every stage. When samples are available in blocks,
+    /// process is much faster per sample.
+    pub fn process_scalar(&mut self, x: f32) -> f32 {
+        let mut y = 0.0;
         unsafe {
-            cmsis_dsp_sys::arm_fir_f32(&mut self.0, src.as_ptr(), dst.as_mut_ptr(), block_size);
+            cmsis_dsp_sys::arm_biquad_cascade_df2T_f32(&self.0, &x, &mut y, 1);
         }
+        y
+    }
+
+    /// Processes a single sample through the filter and returns the output sample
+    ///
+    /// This is the same as process_scalar, and has the same reduced throughput compared to
+    /// processing a block.
+    pub fn process_sample(&mut self, x: f32) -> f32 {
+        self.process_scalar(x)
+    }
+
+    /// Calculates the frequency response of the filter from its coefficients
+    ///
+    /// This returns num_points (frequency, magnitude) pairs with frequencies evenly spaced from
+    /// 0 to sample_rate / 2 (inclusive), in the units of sample_rate. Each magnitude is the gain
+    /// of the whole cascade at that frequency, in decibels.
+    ///
+    /// This function is available when the `alloc` feature is enabled.
+    #[cfg(feature = "alloc")]
+    pub fn frequency_response(&self, num_points: usize, sample_rate: f32) -> Vec<(f32, f32)> {
+        let coeffs = self.coeffs();
+        let spacing = if num_points > 1 {
+            0.5 * sample_rate / (num_points - 1) as f32
+        } else {
+            0.0
+        };
+        (0..num_points)
+            .map(|i| {
+                let frequency = i as f32 * spacing;
+                let w = 2.0 * PI * frequency / sample_rate;
+                let (cos_w, sin_w) = (cos_f32(w), sin_f32(w));
+                let (cos_2w, sin_2w) = (cos_f32(2.0 * w), sin_f32(2.0 * w));
+                let squared_gain: f32 = coeffs
+                    .chunks_exact(5)
+                    .map(|stage| {
+                        let (b0, b1, b2, a1, a2) = (stage[0], stage[1], stage[2], stage[3], stage[4]);
+                        // CMSIS-DSP uses positive feedback coefficients:
+                        // H(z) = (b0 + b1 z^-1 + b2 z^-2) / (1 - a1 z^-1 - a2 z^-2)
+                        let num_re = b0 + b1 * cos_w + b2 * cos_2w;
+                        let num_im = -(b1 * sin_w + b2 * sin_2w);
+                        let den_re = 1.0 - a1 * cos_w - a2 * cos_2w;
+                        let den_im = a1 * sin_w + a2 * sin_2w;
+                        (num_re * num_re + num_im * num_im) / (den_re * den_re + den_im * den_im)
+                    })
+                    .product();
+                (frequency, 10.0 * log10_f32(squared_gain))
+            })
+            .collect()
     }
 }
 
-pub struct FirFilterDecimate(cmsis_dsp_sys::arm_fir_decimate_instance_f32);
+/// Number of samples of each component that `ComplexBiquad` deinterleaves at a time
+const COMPLEX_BIQUAD_CHUNK: usize = 64;
 
-impl FirFilterDecimate {
-    /// Initializes a Decimating FIR Filter
-    /// 
-    /// num_taps: number of filter coefficients in the filter 
-    /// 
-    /// m: decimation factor 
+/// A biquad cascade filter for complex signals, such as I/Q baseband samples
+///
+/// The filter has real coefficients, so it filters the real and imaginary parts of the signal
+/// independently. Each part is processed by its own `BiquadCascadeDF2TFilter` with the same
+/// coefficients and a separate state buffer.
+///
+/// The filter borrows the coefficients and both state buffers for the lifetime 'a.
+pub struct ComplexBiquad<'a> {
+    real: BiquadCascadeDF2TFilter<'a>,
+    imag: BiquadCascadeDF2TFilter<'a>,
+}
+
+impl<'a> ComplexBiquad<'a> {
+    /// Initializes a complex biquad cascade filter
     ///
-    /// coeffs points to the array of filter coefficients stored in time reversed order: 
-    /// {b[numTaps-1], b[numTaps-2], b[N-2], ..., b[1], b[0]}
-    /// 
-    /// state: State buffer. The array is of length num_taps + block_size - 1.
-    /// 
-    /// block_size: number of samples processed per call 
-    /// 
-    pub fn new(num_taps: u16, m: u8, coeffs: &[f32], state: &mut [f32], block_size: u32) -> Result<Self> {
-        check_length::<(usize, usize), usize>((num_taps as usize, coeffs.len()));
-        check_length::<(usize, usize), usize>((((num_taps as u32)+block_size-1) as usize, state.len()));
-        let mut data = MaybeUninit::<cmsis_dsp_sys::arm_fir_decimate_instance_f32>::uninit();
+    /// num_stages: number of 2nd order stages in the filter.
+    ///
+    /// coeffs: Coefficient array. The array is of length 5 * num_stages, in the same format as
+    /// the coefficients of `BiquadCascadeDF2TFilter`.
+    ///
+    /// real_state, imag_state: State buffers for the real and imaginary parts. Each array is of
+    /// length 2 * num_stages.
+    pub fn new(
+        num_stages: u8,
+        coeffs: &'a [f32],
+        real_state: &'a mut [f32],
+        imag_state: &'a mut [f32],
+    ) -> Result<Self> {
+        Ok(ComplexBiquad {
+            real: BiquadCascadeDF2TFilter::new(num_stages, coeffs, real_state)?,
+            imag: BiquadCascadeDF2TFilter::new(num_stages, coeffs, imag_state)?,
+        })
+    }
+
+    /// Processes complex samples through the filter
+    ///
+    /// The real and imaginary parts are separated into buffers on the stack, 64 samples at a
+    /// time, and each part is filtered with one call into CMSIS-DSP per chunk.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if src and dst do not have the same length.
+    pub fn process(&mut self, src: &[Complex32], dst: &mut [Complex32]) {
+        check_length_or_panic::<(usize, usize), usize>((src.len(), dst.len()));
+        let mut input = [[0.0f32; COMPLEX_BIQUAD_CHUNK]; 2];
+        let mut output = [[0.0f32; COMPLEX_BIQUAD_CHUNK]; 2];
+        for (src, dst) in src.chunks(COMPLEX_BIQUAD_CHUNK).zip(dst.chunks_mut(COMPLEX_BIQUAD_CHUNK)) {
+            let length = src.len();
+            for (i, sample) in src.iter().enumerate() {
+                input[0][i] = sample.re;
+                input[1][i] = sample.im;
+            }
+            let [real_in, imag_in] = &input;
+            let [real_out, imag_out] = &mut output;
+            self.real.process(&real_in[..length], &mut real_out[..length], length as u32);
+            self.imag.process(&imag_in[..length], &mut imag_out[..length], length as u32);
+            for (i, sample) in dst.iter_mut().enumerate() {
+                *sample = Complex32::new(real_out[i], imag_out[i]);
+            }
+        }
+    }
+
+    /// Processes complex samples through the filter, returning an error instead of panicking
+    /// if the lengths are not valid
+    ///
+    /// This function returns `Error::LengthMismatch` if src and dst do not have the same length.
+    pub fn try_process(&mut self, src: &[Complex32], dst: &mut [Complex32]) -> Result<()> {
+        check_length::<(usize, usize), usize>((src.len(), dst.len()))?;
+        self.process(src, dst);
+        Ok(())
+    }
+}
+
+/// A biquad cascade IIR filter for interleaved stereo signals, using a direct form II
+/// transposed structure
+///
+/// Both channels are filtered with the same coefficients and separate state, in one call into
+/// CMSIS-DSP. The filter borrows its coefficients and state buffer for the lifetime 'a.
+pub struct BiquadCascadeStereoDF2TFilter<'a>(
+    cmsis_dsp_sys::arm_biquad_cascade_stereo_df2T_instance_f32,
+    PhantomData<&'a mut [f32]>,
+);
+
+impl<'a> BiquadCascadeStereoDF2TFilter<'a> {
+    /// Initializes a stereo biquad cascade filter
+    ///
+    /// num_stages: number of 2nd order stages in the filter.
+    ///
+    /// coeffs: Coefficient array. The array is of length 5 * num_stages, in the same format as
+    /// the coefficients of `BiquadCascadeDF2TFilter`.
+    ///
+    /// state: State buffer for both channels. The array is of length 4 * num_stages.
+    pub fn new(num_stages: u8, coeffs: &'a [f32], state: &'a mut [f32]) -> Result<Self> {
+        check_length_or_panic::<(usize, usize), usize>((coeffs.len(), 5 * usize::from(num_stages)));
+        check_length_or_panic::<(usize, usize), usize>((state.len(), 4 * usize::from(num_stages)));
+        let mut data = MaybeUninit::<cmsis_dsp_sys::arm_biquad_cascade_stereo_df2T_instance_f32>::uninit();
         unsafe {
-            cmsis_dsp_sys::arm_fir_decimate_init_f32 (data.as_mut_ptr(), num_taps, m,  coeffs.as_ptr(), state.as_mut_ptr(), block_size);
-            Ok(FirFilterDecimate(data.assume_init()))
+            cmsis_dsp_sys::arm_biquad_cascade_stereo_df2T_init_f32(data.as_mut_ptr(), num_stages, coeffs.as_ptr(), state.as_mut_ptr());
+            Ok(BiquadCascadeStereoDF2TFilter(data.assume_init(), PhantomData))
         }
     }
-    /// Process the input data through the filter.
-    /// 
-    /// src: Input data buffer.
-    /// dst: Output data buffer.
-    /// 
-    /// The length of the input and output buffers must be the same.
-    pub fn process(&mut self, src: &[f32], dst: &mut [f32], block_size: u32) {
-        check_length::<(usize, usize), usize>((src.len(), dst.len()));
-        assert!(block_size as usize <= src.len() && block_size as usize <= dst.len());
+
+    /// Processes interleaved stereo samples through the filter
+    ///
+    /// src_interleaved and dst_interleaved contain block_size frames, each of which is a left
+    /// sample followed by a right sample.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if src_interleaved or dst_interleaved does not have a length of
+    /// 2 * block_size.
+    pub fn process(&mut self, src_interleaved: &[f32], dst_interleaved: &mut [f32], block_size: u32) {
+        let samples = 2 * block_size as usize;
+        check_length_or_panic::<(usize, usize, usize), usize>((src_interleaved.len(), dst_interleaved.len(), samples));
         unsafe {
-            cmsis_dsp_sys::arm_fir_decimate_f32(&mut self.0, src.as_ptr(), dst.as_mut_ptr(), block_size);
+            cmsis_dsp_sys::arm_biquad_cascade_stereo_df2T_f32(&self.0, src_interleaved.as_ptr(), dst_interleaved.as_mut_ptr(), block_size);
         }
     }
-}
\ No newline at end of file
+
+    /// Processes interleaved stereo samples through the filter, returning an error instead of
+    /// panicking if the lengths are not valid
+    ///
+    /// This function returns `Error::LengthMismatch` if src_interleaved or dst_interleaved does
+    /// not have a length of 2 * block_size.
+    pub fn try_process(&mut self, src_interleaved: &[f32], dst_interleaved: &mut [f32], block_size: u32) -> Result<()> {
+        let samples = 2 * block_size as usize;
+        check_length::<(usize, usize), usize>((samples, src_interleaved.len()))?;
+        check_length::<(usize, usize), usize>((samples, dst_interleaved.len()))?;
+        self.process(src_interleaved, dst_interleaved, block_size);
+        Ok(())
+    }
+}
+
+/// A biquad cascade IIR filter using a direct form I structure and Q1.31 fixed-point values
+///
+/// CMSIS-DSP does not provide a fixed-point direct form II transposed biquad, so this is the
+/// fixed-point counterpart of `BiquadCascadeDF2TFilter`. The internal accumulator has 64 bits.
+///
+/// The filter borrows its coefficients and state buffer for the lifetime 'a.
+pub struct BiquadCascadeDF1FilterQ31<'a>(
+    cmsis_dsp_sys::arm_biquad_casd_df1_inst_q31,
+    PhantomData<&'a mut [I1F31]>,
+);
+
+impl<'a> BiquadCascadeDF1FilterQ31<'a> {
+    /// Initializes a Q1.31 biquad cascade filter
+    ///
+    /// num_stages: number of 2nd order stages in the filter.
+    ///
+    /// coeffs: Coefficient array. The array is of length 5 * num_stages, in the same order as
+    /// the coefficients of `BiquadCascadeDF2TFilter`: {b10, b11, b12, a11, a12} for stage 1, ...
+    ///
+    /// state: State buffer. The array is of length 4 * num_stages.
+    ///
+    /// post_shift: Coefficients with magnitudes of 1 or more can't be represented in Q1.31, so
+    /// they must be divided by `2^post_shift` before conversion. Each stage multiplies its output
+    /// by `2^post_shift` to compensate.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if coeffs or state does not have the required length.
+    pub fn new(num_stages: u8, coeffs: &'a [I1F31], state: &'a mut [I1F31], post_shift: i8) -> Self {
+        check_length_or_panic::<(usize, usize), usize>((coeffs.len(), 5 * num_stages as usize));
+        check_length_or_panic::<(usize, usize), usize>((state.len(), 4 * num_stages as usize));
+        let mut data = MaybeUninit::<cmsis_dsp_sys::arm_biquad_casd_df1_inst_q31>::uninit();
+        unsafe {
+            cmsis_dsp_sys::arm_biquad_cascade_df1_init_q31(data.as_mut_ptr(), num_stages, as_q31_ptr(coeffs), as_q31_mut_ptr(state), post_shift);
+            BiquadCascadeDF1FilterQ31(data.assume_init(), PhantomData)
+        }
+    }
+
+    /// Processes a block of samples through the filter
+    ///
+    /// # Panics
+    ///
+    /// This function panics if src and dst do not have the same length.
+    pub fn process(&mut self, src: &[I1F31], dst: &mut [I1F31]) {
+        let block_size = check_length_or_panic((src.len(), dst.len()));
+        unsafe {
+            cmsis_dsp_sys::arm_biquad_cascade_df1_q31(&self.0, as_q31_ptr(src), as_q31_mut_ptr(dst), block_size);
+        }
+    }
+
+    /// Processes a block of samples through the filter, returning an error instead of
+    /// panicking if the lengths are not valid
+    ///
+    /// This function returns `Error::LengthMismatch` if src and dst do not have the same length.
+    pub fn try_process(&mut self, src: &[I1F31], dst: &mut [I1F31]) -> Result<()> {
+        check_length::<(usize, usize), u32>((src.len(), dst.len()))?;
+        self.process(src, dst);
+        Ok(())
+    }
+
+    /// Processes a signal of any length through the filter in chunks of at most chunk
+    /// samples, with one call into CMSIS-DSP per chunk
+    ///
+    /// The filter state carries over between chunks, so the output is the same as processing
+    /// the whole signal in one call.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if src and dst do not have the same length, or if chunk is zero.
+    pub fn process_chunked(&mut self, src: &[I1F31], dst: &mut [I1F31], chunk: usize) {
+        check_length_or_panic::<(usize, usize), usize>((src.len(), dst.len()));
+        assert!(chunk > 0, "Chunk size must be greater than zero");
+        for (src, dst) in src.chunks(chunk).zip(dst.chunks_mut(chunk)) {
+            self.process(src, dst);
+        }
+    }
+}
+
+/// A biquad cascade IIR filter using a direct form I structure and Q1.15 fixed-point values
+///
+/// The internal accumulator has 64 bits, and the output of each stage saturates.
+///
+/// The filter borrows its coefficients and state buffer for the lifetime 'a.
+pub struct BiquadCascadeDF1FilterQ15<'a>(
+    cmsis_dsp_sys::arm_biquad_casd_df1_inst_q15,
+    PhantomData<&'a mut [I1F15]>,
+);
+
+impl<'a> BiquadCascadeDF1FilterQ15<'a> {
+    /// Initializes a Q1.15 biquad cascade filter
+    ///
+    /// num_stages: number of 2nd order stages in the filter.
+    ///
+    /// coeffs: Coefficient array. The array is of length 6 * num_stages. Each stage has a zero
+    /// after its first coefficient, which the CMSIS-DSP implementation requires:
+    /// {b10, 0, b11, b12, a11, a12} for stage 1, {b20, 0, b21, b22, a21, a22} for stage 2, ...
+    ///
+    /// state: State buffer. The array is of length 4 * num_stages.
+    ///
+    /// post_shift: Coefficients with magnitudes of 1 or more can't be represented in Q1.15, so
+    /// they must be divided by `2^post_shift` before conversion. Each stage multiplies its output
+    /// by `2^post_shift` to compensate.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if coeffs or state does not have the required length.
+    pub fn new(num_stages: u8, coeffs: &'a [I1F15], state: &'a mut [I1F15], post_shift: i8) -> Self {
+        check_length_or_panic::<(usize, usize), usize>((coeffs.len(), 6 * num_stages as usize));
+        check_length_or_panic::<(usize, usize), usize>((state.len(), 4 * num_stages as usize));
+        let mut data = MaybeUninit::<cmsis_dsp_sys::arm_biquad_casd_df1_inst_q15>::uninit();
+        unsafe {
+            cmsis_dsp_sys::arm_biquad_cascade_df1_init_q15(data.as_mut_ptr(), num_stages, as_q15_ptr(coeffs), as_q15_mut_ptr(state), post_shift);
+            BiquadCascadeDF1FilterQ15(data.assume_init(), PhantomData)
+        }
+    }
+
+    /// Processes a block of samples through the filter
+    ///
+    /// # Panics
+    ///
+    /// This function panics if src and dst do not have the same length.
+    pub fn process(&mut self, src: &[I1F15], dst: &mut [I1F15]) {
+        let block_size = check_length_or_panic((src.len(), dst.len()));
+        unsafe {
+            cmsis_dsp_sys::arm_biquad_cascade_df1_q15(&self.0, as_q15_ptr(src), as_q15_mut_ptr(dst), block_size);
+        }
+    }
+
+    /// Processes a block of samples through the filter, returning an error instead of
+    /// panicking if the lengths are not valid
+    ///
+    /// This function returns `Error::LengthMismatch` if src and dst do not have the same length.
+    pub fn try_process(&mut self, src: &[I1F15], dst: &mut [I1F15]) -> Result<()> {
+        check_length::<(usize, usize), u32>((src.len(), dst.len()))?;
+        self.process(src, dst);
+        Ok(())
+    }
+
+    /// Processes a signal of any length through the filter in chunks of at most chunk
+    /// samples, with one call into CMSIS-DSP per chunk
+    ///
+    /// The filter state carries over between chunks, so the output is the same as processing
+    /// the whole signal in one call.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if src and dst do not have the same length, or if chunk is zero.
+    pub fn process_chunked(&mut self, src: &[I1F15], dst: &mut [I1F15], chunk: usize) {
+        check_length_or_panic::<(usize, usize), usize>((src.len(), dst.len()));
+        assert!(chunk > 0, "Chunk size must be greater than zero");
+        for (src, dst) in src.chunks(chunk).zip(dst.chunks_mut(chunk)) {
+            self.process(src, dst);
+        }
+    }
+}
+
+/// A FIR filter
+///
+/// The filter borrows its coefficients and state buffer for the lifetime 'a.
+pub struct FirFilter<'a> {
+    instance: cmsis_dsp_sys::arm_fir_instance_f32,
+    /// Largest number of samples that one CMSIS-DSP call can process, determined by the state length
+    block_size: u32,
+    _buffers: PhantomData<&'a mut [f32]>,
+}
+
+impl<'a> FirFilter<'a> {
+    /// Initializes a FIR Filter
+    /// 
+    /// num_taps: number of filter coefficients in the filter 
+    ///
+    /// coeffs points to the array of filter coefficients stored in time reversed order: 
+    /// {b[numTaps-1], b[numTaps-2], b[N-2], ..., b[1], b[0]}
+    /// 
+    /// state: State buffer. The array is of length num_taps + block_size - 1.
+    /// 
+    /// block_size: number of samples processed per call 
+    /// 
+    /// This function returns `Error::Argument` if block_size is zero.
+    pub fn new(num_taps: u16, coeffs: &'a [f32], state: &'a mut [f32], block_size: u32) -> Result<Self> {
+        if block_size == 0 {
+            return Err(Error::Argument);
+        }
+        check_length_or_panic::<(usize, usize), usize>((num_taps as usize, coeffs.len()));
+        check_length_or_panic::<(usize, usize), usize>((((num_taps as u32)+block_size-1) as usize, state.len()));
+        let mut data = MaybeUninit::<cmsis_dsp_sys::arm_fir_instance_f32>::uninit();
+        unsafe {
+            cmsis_dsp_sys::arm_fir_init_f32 (data.as_mut_ptr(), num_taps, coeffs.as_ptr(), state.as_mut_ptr(), block_size);
+            Ok(FirFilter {
+                instance: data.assume_init(),
+                block_size,
+                _buffers: PhantomData,
+            })
+        }
+    }
+    /// Process the input data through the filter.
+    /// 
+    /// src: Input data buffer.
+    /// dst: Output data buffer.
+    /// 
+    /// The length of the input and output buffers must be the same.
+    ///
+    /// block_size may differ from the block size passed to new, and may change between calls.
+    /// A block larger than the state buffer can hold is processed in several pieces, so the
+    /// output and the filter state are the same as if the filter had been created with this
+    /// block size.
+    pub fn process(&mut self, src: &[f32], dst: &mut [f32], block_size: u32) {
+        check_length_or_panic::<(usize, usize), usize>((src.len(), dst.len()));
+        assert!(block_size as usize <= src.len() && block_size as usize <= dst.len());
+        let block_size = block_size as usize;
+        if block_size <= self.block_size as usize {
+            unsafe {
+                cmsis_dsp_sys::arm_fir_f32(&self.instance, src.as_ptr(), dst.as_mut_ptr(), block_size as u32);
+            }
+        } else {
+            self.process_streaming(&src[..block_size], &mut dst[..block_size]);
+        }
+    }
+
+    /// Processes a signal of any length through the filter
+    ///
+    /// The signal is split into chunks of the block size passed to new, and each chunk is
+    /// processed in turn. The final chunk may be shorter. The filter state carries over between
+    /// chunks and between calls, so a long signal can be processed in pieces of any size and
+    /// produce the same output as processing it all at once.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if input and output do not have the same length.
+    pub fn process_streaming(&mut self, input: &[f32], output: &mut [f32]) {
+        check_length_or_panic::<(usize, usize), usize>((input.len(), output.len()));
+        let block_size = self.block_size as usize;
+        for (src, dst) in input.chunks(block_size).zip(output.chunks_mut(block_size)) {
+            unsafe {
+                cmsis_dsp_sys::arm_fir_f32(&self.instance, src.as_ptr(), dst.as_mut_ptr(), src.len() as u32);
+            }
+        }
+    }
+
+    /// Processes the input data through the filter, returning an error instead of panicking
+    /// if the lengths are not valid
+    ///
+    /// This function returns `Error::LengthMismatch` if src and dst do not have the same length,
+    /// or `Error::Length` if block_size is larger than their length.
+    pub fn try_process(&mut self, src: &[f32], dst: &mut [f32], block_size: u32) -> Result<()> {
+        check_length::<(usize, usize), usize>((src.len(), dst.len()))?;
+        if block_size as usize > src.len() {
+            return Err(Error::Length);
+        }
+        self.process(src, dst, block_size);
+        Ok(())
+    }
+
+    /// Processes the input data through the filter, limiting block_size to the length of the
+    /// buffers instead of panicking
+    ///
+    /// This is for callers with buffers of varying lengths, such as audio callbacks. If
+    /// block_size is larger than the length of src and dst, only their length is processed.
+    /// This function returns the number of samples processed, or `Error::LengthMismatch` if src
+    /// and dst do not have the same length.
+    pub fn process_checked(&mut self, src: &[f32], dst: &mut [f32], block_size: u32) -> Result<u32> {
+        let length = check_length::<(usize, usize), u32>((src.len(), dst.len()))?;
+        let block_size = block_size.min(length);
+        self.process(src, dst, block_size);
+        Ok(block_size)
+    }
+
+    /// Processes a signal of any length through the filter in chunks of at most chunk
+    /// samples, with one call into CMSIS-DSP per chunk
+    ///
+    /// The filter state carries over between chunks, so the output is the same as processing
+    /// the whole signal in one call.
+    ///
+    /// Chunks larger than the block size passed to new are split further, because the state
+    /// buffer only holds one block.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if src and dst do not have the same length, or if chunk is zero.
+    pub fn process_chunked(&mut self, src: &[f32], dst: &mut [f32], chunk: usize) {
+        check_length_or_panic::<(usize, usize), usize>((src.len(), dst.len()));
+        assert!(chunk > 0, "Chunk size must be greater than zero");
+        for (src, dst) in src.chunks(chunk).zip(dst.chunks_mut(chunk)) {
+            self.process(src, dst, src.len() as u32);
+        }
+    }
+
+    /// Copies the impulse response of the filter into out, in natural order
+    ///
+    /// The coefficients passed to new are stored in time reversed order, so out receives them
+    /// reversed: `out[i]` is b[i], the response i samples after an impulse.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the length of out is not equal to the number of taps.
+    pub fn impulse_response(&self, out: &mut [f32]) {
+        let num_taps = usize::from(self.instance.numTaps);
+        check_length_or_panic::<(usize, usize), usize>((num_taps, out.len()));
+        let coeffs = unsafe { core::slice::from_raw_parts(self.instance.pCoeffs, num_taps) };
+        for (value, coeff) in out.iter_mut().zip(coeffs.iter().rev()) {
+            *value = *coeff;
+        }
+    }
+
+    /// Returns the group delay of the filter in samples, `(num_taps - 1) / 2`
+    ///
+    /// This is the delay of every frequency for a linear-phase filter, which has symmetric or
+    /// antisymmetric coefficients. For other filters, the delay depends on the frequency and
+    /// this value is only an approximation.
+    pub fn group_delay(&self) -> f32 {
+        f32::from(self.instance.numTaps.saturating_sub(1)) / 2.0
+    }
+}
+
+/// Creates FIR filters that own their coefficients and state
+///
+/// With the builder, the caller does not need to calculate the state length or keep the
+/// buffers alive while the filter exists.
+#[cfg(feature = "alloc")]
+pub struct FirFilterBuilder<'a> {
+    coeffs: &'a [f32],
+    block_size: u32,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> FirFilterBuilder<'a> {
+    /// Creates a builder
+    ///
+    /// coeffs: filter coefficients stored in time reversed order, as for `FirFilter::new`.
+    /// The built filter keeps a copy of them.
+    ///
+    /// block_size: number of samples processed per call into CMSIS-DSP. Longer blocks are
+    /// split into blocks of this size.
+    pub fn new(coeffs: &'a [f32], block_size: u32) -> Self {
+        FirFilterBuilder { coeffs, block_size }
+    }
+
+    /// Allocates the coefficients and a state buffer of length `num_taps + block_size - 1`,
+    /// and creates the filter
+    ///
+    /// This function returns an error if there are no coefficients or more than 65535
+    /// coefficients, or if block_size is zero.
+    pub fn build(self) -> Result<OwnedFirFilter> {
+        if self.coeffs.is_empty() || self.coeffs.len() > usize::from(u16::MAX) || self.block_size == 0 {
+            return Err(Error::Argument);
+        }
+        let coeffs = self.coeffs.to_vec();
+        let mut state = vec![0.0; coeffs.len() + self.block_size as usize - 1];
+        // The filter refers to the heap buffers of the vectors, which do not move when the
+        // OwnedFirFilter moves and are only freed after the filter is dropped, so the borrows
+        // can outlive this function
+        let (coeffs_buffer, state_buffer) = unsafe {
+            (
+                core::slice::from_raw_parts(coeffs.as_ptr(), coeffs.len()),
+                core::slice::from_raw_parts_mut(state.as_mut_ptr(), state.len()),
+            )
+        };
+        let filter = FirFilter::new(coeffs.len() as u16, coeffs_buffer, state_buffer, self.block_size)?;
+        Ok(OwnedFirFilter {
+            filter,
+            _coeffs: coeffs,
+            _state: state,
+        })
+    }
+}
+
+/// A FIR filter that owns its coefficients and state
+///
+/// Use a `FirFilterBuilder` to create one.
+#[cfg(feature = "alloc")]
+pub struct OwnedFirFilter {
+    /// Declared before the buffers, so it is dropped first
+    filter: FirFilter<'static>,
+    _coeffs: Vec<f32>,
+    _state: Vec<f32>,
+}
+
+#[cfg(feature = "alloc")]
+impl OwnedFirFilter {
+    /// Returns the number of samples processed per call into CMSIS-DSP
+    pub fn block_size(&self) -> u32 {
+        self.filter.block_size
+    }
+
+    /// Processes a signal of any length through the filter
+    ///
+    /// The filter state carries over between calls.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if src and dst do not have the same length.
+    pub fn process(&mut self, src: &[f32], dst: &mut [f32]) {
+        self.filter.process_streaming(src, dst);
+    }
+
+    /// Processes a signal through the filter, returning an error instead of panicking if the
+    /// lengths are not valid
+    ///
+    /// This function returns `Error::LengthMismatch` if src and dst do not have the same length.
+    pub fn try_process(&mut self, src: &[f32], dst: &mut [f32]) -> Result<()> {
+        check_length::<(usize, usize), usize>((src.len(), dst.len()))?;
+        self.process(src, dst);
+        Ok(())
+    }
+}
+
+/// A FIR filter that stores its coefficients and state in arrays, and can be created in a
+/// constant expression
+///
+/// TAPS is the number of coefficients, and STATE is the length of the state array, which is
+/// `TAPS + block_size - 1` for the largest block that one call into CMSIS-DSP processes.
+/// Because `new` is a `const fn`, a filter can be placed in a `static` and used without any
+/// initialization at run time:
+///
+/// ```
+/// use cmsis_dsp::filter::StaticFirFilter;
+///
+/// static mut LOWPASS: StaticFirFilter<4, 35> = StaticFirFilter::new([0.25; 4]);
+/// ```
+///
+/// Accessing a `static mut` is unsafe: the program must ensure that only one context (for
+/// example, one interrupt handler) uses the filter.
+///
+/// Unlike `FirFilter`, this type contains its buffers instead of referring to them, so it can
+/// be moved freely. The CMSIS-DSP filter instance is created on each call to process, which
+/// only sets three fields.
+#[derive(Debug, Clone)]
+pub struct StaticFirFilter<const TAPS: usize, const STATE: usize> {
+    /// Coefficients, stored in time reversed order
+    coeffs: [f32; TAPS],
+    state: [f32; STATE],
+}
+
+impl<const TAPS: usize, const STATE: usize> StaticFirFilter<TAPS, STATE> {
+    /// Creates a filter
+    ///
+    /// coeffs: filter coefficients stored in time reversed order:
+    /// {b[numTaps-1], b[numTaps-2], b[N-2], ..., b[1], b[0]}
+    ///
+    /// # Panics
+    ///
+    /// This function panics, or fails to compile when used in a constant expression, if TAPS is
+    /// zero or larger than 65535, or if STATE is less than TAPS.
+    pub const fn new(coeffs: [f32; TAPS]) -> Self {
+        assert!(TAPS > 0 && TAPS <= u16::MAX as usize, "TAPS must be in the range [1, 65535]");
+        assert!(STATE >= TAPS, "STATE must be at least TAPS");
+        StaticFirFilter {
+            coeffs,
+            state: [0.0; STATE],
+        }
+    }
+
+    /// Processes a signal of any length through the filter
+    ///
+    /// The signal is split into blocks of `STATE - TAPS + 1` samples. The filter state carries
+    /// over between calls.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if src and dst do not have the same length.
+    pub fn process(&mut self, src: &[f32], dst: &mut [f32]) {
+        check_length_or_panic::<(usize, usize), usize>((src.len(), dst.len()));
+        let instance = cmsis_dsp_sys::arm_fir_instance_f32 {
+            numTaps: TAPS as u16,
+            pState: self.state.as_mut_ptr(),
+            pCoeffs: self.coeffs.as_ptr(),
+        };
+        let block_size = STATE - TAPS + 1;
+        for (src, dst) in src.chunks(block_size).zip(dst.chunks_mut(block_size)) {
+            unsafe {
+                cmsis_dsp_sys::arm_fir_f32(&instance, src.as_ptr(), dst.as_mut_ptr(), src.len() as u32);
+            }
+        }
+    }
+
+    /// Processes a signal through the filter, returning an error instead of panicking if the
+    /// lengths are not valid
+    ///
+    /// This function returns `Error::LengthMismatch` if src and dst do not have the same length.
+    pub fn try_process(&mut self, src: &[f32], dst: &mut [f32]) -> Result<()> {
+        check_length::<(usize, usize), usize>((src.len(), dst.len()))?;
+        self.process(src, dst);
+        Ok(())
+    }
+
+    /// Clears the filter state
+    pub fn reset(&mut self) {
+        self.state = [0.0; STATE];
+    }
+}
+
+pub struct FirFilterDecimate(cmsis_dsp_sys::arm_fir_decimate_instance_f32);
+
+impl FirFilterDecimate {
+    /// Initializes a Decimating FIR Filter
+    /// 
+    /// num_taps: number of filter coefficients in the filter 
+    /// 
+    /// m: decimation factor 
+    ///
+    /// coeffs points to the array of filter coefficients stored in time reversed order: 
+    /// {b[numTaps-1], b[numTaps-2], b[N-2], ..., b[1], b[0]}
+    /// 
+    /// state: State buffer. The array is of length num_taps + block_size - 1.
+    /// 
+    /// block_size: number of samples processed per call 
+    /// 
+    pub fn new(num_taps: u16, m: u8, coeffs: &[f32], state: &mut [f32], block_size: u32) -> Result<Self> {
+        check_length_or_panic::<(usize, usize), usize>((num_taps as usize, coeffs.len()));
+        check_length_or_panic::<(usize, usize), usize>((((num_taps as u32)+block_size-1) as usize, state.len()));
+        let mut data = MaybeUninit::<cmsis_dsp_sys::arm_fir_decimate_instance_f32>::uninit();
+        unsafe {
+            cmsis_dsp_sys::arm_fir_decimate_init_f32 (data.as_mut_ptr(), num_taps, m,  coeffs.as_ptr(), state.as_mut_ptr(), block_size);
+            Ok(FirFilterDecimate(data.assume_init()))
+        }
+    }
+    /// Process the input data through the filter.
+    /// 
+    /// src: Input data buffer.
+    /// dst: Output data buffer.
+    /// 
+    /// The length of the input and output buffers must be the same.
+    pub fn process(&mut self, src: &[f32], dst: &mut [f32], block_size: u32) {
+        check_length_or_panic::<(usize, usize), usize>((src.len(), dst.len()));
+        assert!(block_size as usize <= src.len() && block_size as usize <= dst.len());
+        unsafe {
+            cmsis_dsp_sys::arm_fir_decimate_f32(&self.0, src.as_ptr(), dst.as_mut_ptr(), block_size);
+        }
+    }
+
+    /// Processes the input data through the filter, returning an error instead of panicking
+    /// if the lengths are not valid
+    ///
+    /// This function returns `Error::LengthMismatch` if src and dst do not have the same length,
+    /// or `Error::Length` if block_size is larger than their length or is not a multiple of
+    /// the decimation factor.
+    pub fn try_process(&mut self, src: &[f32], dst: &mut [f32], block_size: u32) -> Result<()> {
+        check_length::<(usize, usize), usize>((src.len(), dst.len()))?;
+        if block_size as usize > src.len() || !block_size.is_multiple_of(u32::from(self.0.M)) {
+            return Err(Error::Length);
+        }
+        self.process(src, dst, block_size);
+        Ok(())
+    }
+
+    /// Processes the input data through the filter, limiting block_size to the length of the
+    /// buffers instead of panicking
+    ///
+    /// If block_size is larger than the length of src and dst, only their length is processed.
+    /// The block size is then rounded down to a multiple of the decimation factor. This function
+    /// returns the number of input samples processed, which produce that number divided by the
+    /// decimation factor of output samples, or `Error::LengthMismatch` if src and dst do not have
+    /// the same length.
+    pub fn process_checked(&mut self, src: &[f32], dst: &mut [f32], block_size: u32) -> Result<u32> {
+        let length = check_length::<(usize, usize), u32>((src.len(), dst.len()))?;
+        let factor = u32::from(self.0.M);
+        let block_size = block_size.min(length) / factor * factor;
+        self.process(src, dst, block_size);
+        Ok(block_size)
+    }
+}
+/// A FIR lattice filter
+///
+/// Lattice filters are specified by reflection coefficients instead of impulse response
+/// coefficients. They are used in speech coding, where linear prediction produces reflection
+/// coefficients directly.
+///
+/// The filter borrows its coefficients and state buffer for the lifetime 'a.
+pub struct FirLatticeFilter<'a>(
+    cmsis_dsp_sys::arm_fir_lattice_instance_f32,
+    PhantomData<&'a mut [f32]>,
+);
+
+impl<'a> FirLatticeFilter<'a> {
+    /// Initializes a FIR lattice filter
+    ///
+    /// num_stages: number of stages in the filter
+    ///
+    /// coeffs: reflection coefficients. The array is of length num_stages, in the order
+    /// {k1, k2, ..., kN}.
+    ///
+    /// state: state buffer. The array is of length num_stages.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if coeffs or state does not have num_stages elements.
+    pub fn new(num_stages: u16, coeffs: &'a [f32], state: &'a mut [f32]) -> Self {
+        check_length_or_panic::<(usize, usize, usize), usize>((num_stages as usize, coeffs.len(), state.len()));
+        let mut data = MaybeUninit::<cmsis_dsp_sys::arm_fir_lattice_instance_f32>::uninit();
+        unsafe {
+            cmsis_dsp_sys::arm_fir_lattice_init_f32(data.as_mut_ptr(), num_stages, coeffs.as_ptr(), state.as_mut_ptr());
+            FirLatticeFilter(data.assume_init(), PhantomData)
+        }
+    }
+
+    /// Processes a block of samples through the filter
+    ///
+    /// # Panics
+    ///
+    /// This function panics if src and dst do not have the same length.
+    pub fn process(&mut self, src: &[f32], dst: &mut [f32]) {
+        let block_size = check_length_or_panic((src.len(), dst.len()));
+        unsafe {
+            cmsis_dsp_sys::arm_fir_lattice_f32(&self.0, src.as_ptr(), dst.as_mut_ptr(), block_size);
+        }
+    }
+
+    /// Processes a block of samples through the filter, returning an error instead of
+    /// panicking if the lengths are not valid
+    ///
+    /// This function returns `Error::LengthMismatch` if src and dst do not have the same length.
+    pub fn try_process(&mut self, src: &[f32], dst: &mut [f32]) -> Result<()> {
+        check_length::<(usize, usize), u32>((src.len(), dst.len()))?;
+        self.process(src, dst);
+        Ok(())
+    }
+
+    /// Processes a signal of any length through the filter in chunks of at most chunk
+    /// samples, with one call into CMSIS-DSP per chunk
+    ///
+    /// The filter state carries over between chunks, so the output is the same as processing
+    /// the whole signal in one call.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if src and dst do not have the same length, or if chunk is zero.
+    pub fn process_chunked(&mut self, src: &[f32], dst: &mut [f32], chunk: usize) {
+        check_length_or_panic::<(usize, usize), usize>((src.len(), dst.len()));
+        assert!(chunk > 0, "Chunk size must be greater than zero");
+        for (src, dst) in src.chunks(chunk).zip(dst.chunks_mut(chunk)) {
+            self.process(src, dst);
+        }
+    }
+}
+
+/// An IIR lattice filter
+///
+/// The filter is specified by reflection coefficients, which determine the poles, and ladder
+/// coefficients, which determine the zeros.
+///
+/// The filter borrows its coefficients and state buffer for the lifetime 'a.
+pub struct IirLatticeFilter<'a> {
+    instance: cmsis_dsp_sys::arm_iir_lattice_instance_f32,
+    /// Maximum number of samples per call to process, determined by the state length
+    block_size: usize,
+    _buffers: PhantomData<&'a mut [f32]>,
+}
+
+impl<'a> IirLatticeFilter<'a> {
+    /// Initializes an IIR lattice filter
+    ///
+    /// num_stages: number of stages in the filter
+    ///
+    /// reflection_coeffs: reflection coefficients. The array is of length num_stages, in
+    /// time-reversed order: {kN, ..., k2, k1}.
+    ///
+    /// ladder_coeffs: ladder coefficients. The array is of length num_stages + 1, in
+    /// time-reversed order: {vN, ..., v1, v0}.
+    ///
+    /// state: state buffer. The array is of length num_stages + block_size.
+    ///
+    /// block_size: maximum number of samples processed per call
+    ///
+    /// # Panics
+    ///
+    /// This function panics if any of the arrays does not have the required length.
+    pub fn new(num_stages: u16, reflection_coeffs: &'a [f32], ladder_coeffs: &'a [f32], state: &'a mut [f32], block_size: u32) -> Self {
+        check_length_or_panic::<(usize, usize), usize>((num_stages as usize, reflection_coeffs.len()));
+        check_length_or_panic::<(usize, usize), usize>((num_stages as usize + 1, ladder_coeffs.len()));
+        check_length_or_panic::<(usize, usize), usize>((num_stages as usize + block_size as usize, state.len()));
+        let mut data = MaybeUninit::<cmsis_dsp_sys::arm_iir_lattice_instance_f32>::uninit();
+        unsafe {
+            // The coefficients are not modified, even though the C function takes mutable pointers
+            cmsis_dsp_sys::arm_iir_lattice_init_f32(
+                data.as_mut_ptr(),
+                num_stages,
+                reflection_coeffs.as_ptr() as *mut _,
+                ladder_coeffs.as_ptr() as *mut _,
+                state.as_mut_ptr(),
+                block_size,
+            );
+            IirLatticeFilter {
+                instance: data.assume_init(),
+                block_size: block_size as usize,
+                _buffers: PhantomData,
+            }
+        }
+    }
+
+    /// Processes a block of samples through the filter
+    ///
+    /// # Panics
+    ///
+    /// This function panics if src and dst do not have the same length, or if they are longer
+    /// than the block size passed to new.
+    pub fn process(&mut self, src: &[f32], dst: &mut [f32]) {
+        let block_size = check_length_or_panic((src.len(), dst.len()));
+        assert!(src.len() <= self.block_size, "Block larger than the filter block size");
+        unsafe {
+            cmsis_dsp_sys::arm_iir_lattice_f32(&self.instance, src.as_ptr(), dst.as_mut_ptr(), block_size);
+        }
+    }
+
+    /// Processes a block of samples through the filter, returning an error instead of
+    /// panicking if the lengths are not valid
+    ///
+    /// This function returns `Error::LengthMismatch` if src and dst do not have the same length,
+    /// or `Error::Length` if they are longer than the block size passed to new.
+    pub fn try_process(&mut self, src: &[f32], dst: &mut [f32]) -> Result<()> {
+        check_length::<(usize, usize), u32>((src.len(), dst.len()))?;
+        if src.len() > self.block_size {
+            return Err(Error::Length);
+        }
+        self.process(src, dst);
+        Ok(())
+    }
+
+    /// Processes a signal of any length through the filter in chunks of at most chunk
+    /// samples, with one call into CMSIS-DSP per chunk
+    ///
+    /// The filter state carries over between chunks, so the output is the same as processing
+    /// the whole signal in one call.
+    ///
+    /// Chunks larger than the block size passed to new are split further, because the state
+    /// buffer only holds one block.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if src and dst do not have the same length, or if chunk is zero.
+    pub fn process_chunked(&mut self, src: &[f32], dst: &mut [f32], chunk: usize) {
+        check_length_or_panic::<(usize, usize), usize>((src.len(), dst.len()));
+        assert!(chunk > 0, "Chunk size must be greater than zero");
+        let chunk = chunk.min(self.block_size);
+        for (src, dst) in src.chunks(chunk).zip(dst.chunks_mut(chunk)) {
+            self.process(src, dst);
+        }
+    }
+}
+
+/// A sparse FIR filter
+///
+/// A sparse filter stores only its non-zero coefficients, each with the delay (in samples) at
+/// which it applies. This is much faster than a direct-form FIR filter for long impulse
+/// responses that are mostly zero, like room reverberation.
+///
+/// The filter borrows its coefficients, tap delays, and state buffer for the lifetime 'a.
+pub struct FirFilterSparse<'a> {
+    instance: cmsis_dsp_sys::arm_fir_sparse_instance_f32,
+    /// Number of samples processed per call
+    block_size: usize,
+    _buffers: PhantomData<&'a mut [f32]>,
+}
+
+impl<'a> FirFilterSparse<'a> {
+    /// Initializes a sparse FIR filter
+    ///
+    /// num_taps: number of non-zero coefficients in the filter
+    ///
+    /// coeffs: non-zero coefficients. The array is of length num_taps.
+    ///
+    /// tap_delay: delay, in samples, of each coefficient. The array is of length num_taps, and
+    /// each delay must be in the range [0, max_delay].
+    ///
+    /// max_delay: largest value in tap_delay
+    ///
+    /// state: state buffer. The array is of length max_delay + block_size.
+    ///
+    /// block_size: number of samples processed per call
+    ///
+    /// # Panics
+    ///
+    /// This function panics if any of the arrays does not have the required length, or if a
+    /// tap delay is outside the range [0, max_delay].
+    pub fn new(num_taps: u16, coeffs: &'a [f32], tap_delay: &'a [i32], max_delay: u16, state: &'a mut [f32], block_size: u32) -> Self {
+        check_length_or_panic::<(usize, usize, usize), usize>((num_taps as usize, coeffs.len(), tap_delay.len()));
+        check_length_or_panic::<(usize, usize), usize>((max_delay as usize + block_size as usize, state.len()));
+        assert!(
+            tap_delay.iter().all(|&delay| delay >= 0 && delay <= i32::from(max_delay)),
+            "Tap delay outside the range [0, max_delay]"
+        );
+        let mut data = MaybeUninit::<cmsis_dsp_sys::arm_fir_sparse_instance_f32>::uninit();
+        unsafe {
+            // The tap delays are not modified, even though the C function takes a mutable pointer
+            cmsis_dsp_sys::arm_fir_sparse_init_f32(
+                data.as_mut_ptr(),
+                num_taps,
+                coeffs.as_ptr(),
+                state.as_mut_ptr(),
+                tap_delay.as_ptr() as *mut _,
+                max_delay,
+                block_size,
+            );
+            FirFilterSparse {
+                instance: data.assume_init(),
+                block_size: block_size as usize,
+                _buffers: PhantomData,
+            }
+        }
+    }
+
+    /// Processes a block of samples through the filter
+    ///
+    /// scratch: temporary buffer used by the CMSIS-DSP function. The array is of length
+    /// block_size.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if src, dst, or scratch does not have the block size passed to
+    /// new.
+    pub fn process(&mut self, src: &[f32], dst: &mut [f32], scratch: &mut [f32]) {
+        check_length_or_panic::<(usize, usize, usize), usize>((src.len(), dst.len(), scratch.len()));
+        let block_size = check_length_or_panic((src.len(), self.block_size));
+        unsafe {
+            cmsis_dsp_sys::arm_fir_sparse_f32(
+                &mut self.instance,
+                src.as_ptr(),
+                dst.as_mut_ptr(),
+                scratch.as_mut_ptr(),
+                block_size,
+            );
+        }
+    }
+
+    /// Processes a block of samples through the filter, returning an error instead of
+    /// panicking if the lengths are not valid
+    ///
+    /// This function returns `Error::LengthMismatch` if src, dst, or scratch does not have the
+    /// block size passed to new.
+    pub fn try_process(&mut self, src: &[f32], dst: &mut [f32], scratch: &mut [f32]) -> Result<()> {
+        check_length::<(usize, usize, usize), u32>((self.block_size, src.len(), dst.len()))?;
+        check_length::<(usize, usize), u32>((self.block_size, scratch.len()))?;
+        self.process(src, dst, scratch);
+        Ok(())
+    }
+}
+
+/// An adaptive notch filter that tracks and removes one narrowband interfering tone
+///
+/// The filter is a constrained second-order section,
+/// `H(z) = (1 + a z^-1 + z^-2) / (1 + r a z^-1 + r^2 z^-2)`, with zeros on the unit circle at the
+/// notch frequency and poles at radius r just inside them. After each sample, an LMS update
+/// moves the coefficient `a = -2 cos(w)` to reduce the output power, so the notch follows a tone
+/// whose frequency drifts. The update is normalized by a running estimate of the signal power,
+/// so the step size does not depend on the signal level.
+pub struct AdaptiveNotch {
+    sample_rate: f32,
+    pole_radius: f32,
+    step_size: f32,
+    /// Notch coefficient, `-2 cos(w)`
+    a: f32,
+    /// Previous internal states `s[n-1]` and `s[n-2]`
+    state: [f32; 2],
+    /// Running estimate of the power of `s[n-1]`
+    power: f32,
+}
+
+impl AdaptiveNotch {
+    /// Creates an adaptive notch filter
+    ///
+    /// sample_rate: sample rate of the signal, in hertz
+    ///
+    /// initial_frequency: notch frequency before adaptation, in hertz
+    ///
+    /// pole_radius: pole radius in the range (0, 1). Values closer to 1 make the notch narrower.
+    /// A typical value is 0.95.
+    ///
+    /// step_size: adaptation step size. Larger values track faster but add more noise to the
+    /// notch frequency. A typical value is 0.01.
+    pub fn new(sample_rate: f32, initial_frequency: f32, pole_radius: f32, step_size: f32) -> Self {
+        AdaptiveNotch {
+            sample_rate,
+            pole_radius,
+            step_size,
+            a: -2.0 * cos_f32(2.0 * PI * initial_frequency / sample_rate),
+            state: [0.0; 2],
+            power: 0.0,
+        }
+    }
+
+    /// Returns the current notch frequency, in hertz
+    pub fn frequency(&self) -> f32 {
+        let w = atan2_f32(sqrt_f32(4.0 - self.a * self.a), -self.a);
+        w * self.sample_rate / (2.0 * PI)
+    }
+
+    /// Processes a block of samples through the filter, adapting the notch frequency after
+    /// each sample
+    ///
+    /// # Panics
+    ///
+    /// This function panics if src and dst do not have the same length.
+    pub fn process(&mut self, src: &[f32], dst: &mut [f32]) {
+        check_length_or_panic::<(usize, usize), usize>((src.len(), dst.len()));
+        let r = self.pole_radius;
+        let [mut s1, mut s2] = self.state;
+        for (x, y) in src.iter().zip(dst.iter_mut()) {
+            let s = x - r * self.a * s1 - r * r * s2;
+            let e = s + self.a * s1 + s2;
+            self.power = 0.99 * self.power + 0.01 * s1 * s1;
+            self.a -= self.step_size * e * s1 / (self.power + 1e-12);
+            self.a = self.a.clamp(-2.0, 2.0);
+            s2 = s1;
+            s1 = s;
+            *y = e;
+        }
+        self.state = [s1, s2];
+    }
+
+    /// Processes a block of samples through the filter, returning an error instead of
+    /// panicking if the lengths are not valid
+    ///
+    /// This function returns `Error::LengthMismatch` if src and dst do not have the same length.
+    pub fn try_process(&mut self, src: &[f32], dst: &mut [f32]) -> Result<()> {
+        check_length::<(usize, usize), u32>((src.len(), dst.len()))?;
+        self.process(src, dst);
+        Ok(())
+    }
+}
+
+/// A two-channel quadrature mirror filter (QMF) bank
+///
+/// Analysis splits a signal into a low band and a high band, each with half the sample rate of
+/// the input. Synthesis combines the two bands into a signal with the original sample rate.
+///
+/// All four filters are derived from one prototype lowpass filter `h0`:
+///
+/// * Low band analysis: `h0[k]`
+/// * High band analysis: `(-1)^k * h0[k]`
+/// * Low band synthesis: `2 * h0[k]`
+/// * High band synthesis: `-2 * (-1)^k * h0[k]`
+///
+/// With these filters, the aliasing caused by decimation cancels in synthesis. The prototype
+/// should be a symmetric lowpass filter with an even number of taps and a cutoff at one quarter
+/// of the input sample rate, like the Johnston QMF designs. Analysis followed by synthesis then
+/// reconstructs the signal nearly perfectly, delayed by `h0.len() - 1` samples.
+pub struct QmfBank<'a> {
+    /// The prototype lowpass filter coefficients
+    prototype: &'a [f32],
+    /// Input history for analysis, followed by the upsampled low and high band histories for
+    /// synthesis. The most recent sample is first in each history.
+    state: &'a mut [f32],
+}
+
+impl<'a> QmfBank<'a> {
+    /// Initializes a QMF bank
+    ///
+    /// prototype: coefficients of the prototype lowpass filter. The number of coefficients must
+    /// be even and greater than zero.
+    ///
+    /// state: state buffer. The array is of length 3 * prototype.len().
+    ///
+    /// This function returns an error if the prototype does not have an even, non-zero number
+    /// of coefficients.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if state does not have the required length.
+    pub fn new(prototype: &'a [f32], state: &'a mut [f32]) -> Result<Self> {
+        check_length_or_panic::<(usize, usize), usize>((3 * prototype.len(), state.len()));
+        if prototype.is_empty() || !prototype.len().is_multiple_of(2) {
+            return Err(Error::Argument);
+        }
+        for value in state.iter_mut() {
+            *value = 0.0;
+        }
+        Ok(QmfBank { prototype, state })
+    }
+
+    /// Splits a block of samples into low and high bands
+    ///
+    /// # Panics
+    ///
+    /// This function panics if low and high do not have the same length, or if src is not
+    /// twice as long as low and high.
+    pub fn analyze(&mut self, src: &[f32], low: &mut [f32], high: &mut [f32]) {
+        check_length_or_panic::<(usize, usize, usize), usize>((src.len(), 2 * low.len(), 2 * high.len()));
+        let taps = self.prototype.len();
+        let history = &mut self.state[..taps];
+        for (pair, (low, high)) in src.chunks_exact(2).zip(low.iter_mut().zip(high.iter_mut())) {
+            push_sample(history, pair[0]);
+            push_sample(history, pair[1]);
+            let mut even = 0.0;
+            let mut odd = 0.0;
+            for (coeffs, samples) in self.prototype.chunks_exact(2).zip(history.chunks_exact(2)) {
+                even += coeffs[0] * samples[0];
+                odd += coeffs[1] * samples[1];
+            }
+            *low = even + odd;
+            *high = even - odd;
+        }
+    }
+
+    /// Combines blocks of low and high band samples into one signal
+    ///
+    /// # Panics
+    ///
+    /// This function panics if low and high do not have the same length, or if dst is not
+    /// twice as long as low and high.
+    pub fn synthesize(&mut self, low: &[f32], high: &[f32], dst: &mut [f32]) {
+        check_length_or_panic::<(usize, usize, usize), usize>((dst.len(), 2 * low.len(), 2 * high.len()));
+        let taps = self.prototype.len();
+        let (low_history, high_history) = self.state[taps..].split_at_mut(taps);
+        for (pair, (low, high)) in dst.chunks_exact_mut(2).zip(low.iter().zip(high.iter())) {
+            for (output, value) in pair.iter_mut().zip([0.0, 1.0].iter()) {
+                push_sample(low_history, value * low);
+                push_sample(high_history, value * high);
+                let mut sum = 0.0;
+                for (k, coeff) in self.prototype.iter().enumerate() {
+                    let high_coeff = if k % 2 == 0 { -coeff } else { *coeff };
+                    sum += coeff * low_history[k] + high_coeff * high_history[k];
+                }
+                *output = 2.0 * sum;
+            }
+        }
+    }
+
+    /// Splits a block of samples into low and high bands, returning an error instead of
+    /// panicking if the lengths are not valid
+    ///
+    /// This function returns `Error::LengthMismatch` if src is not twice as long as low and
+    /// high.
+    pub fn try_analyze(&mut self, src: &[f32], low: &mut [f32], high: &mut [f32]) -> Result<()> {
+        check_length::<(usize, usize, usize), usize>((src.len(), 2 * low.len(), 2 * high.len()))?;
+        self.analyze(src, low, high);
+        Ok(())
+    }
+
+    /// Combines blocks of low and high band samples into one signal, returning an error instead
+    /// of panicking if the lengths are not valid
+    ///
+    /// This function returns `Error::LengthMismatch` if dst is not twice as long as low and
+    /// high.
+    pub fn try_synthesize(&mut self, low: &[f32], high: &[f32], dst: &mut [f32]) -> Result<()> {
+        check_length::<(usize, usize, usize), usize>((dst.len(), 2 * low.len(), 2 * high.len()))?;
+        self.synthesize(low, high, dst);
+        Ok(())
+    }
+}
+
+/// Runs a per-sample nonlinear process at a multiple of the sample rate
+///
+/// Nonlinear processes such as clipping and distortion create harmonics above the Nyquist
+/// frequency, which alias to lower frequencies. The oversampler reduces this aliasing by
+/// upsampling the signal by an integer factor, running the process on the upsampled signal,
+/// filtering out everything above the original Nyquist frequency, and downsampling the result.
+///
+/// The same lowpass filter `h` is used for upsampling, as a polyphase interpolator, and for
+/// downsampling. It should have a gain of 1 at DC and a cutoff at `1 / (2 * factor)` of the
+/// upsampled rate, or slightly below. The output is delayed by `h.len() - 1` samples of the
+/// upsampled signal.
+pub struct Oversampler<'a, P> {
+    /// Oversampling factor
+    factor: usize,
+    /// Lowpass filter coefficients
+    coeffs: &'a [f32],
+    /// Input history for upsampling, followed by the processed history for downsampling.
+    /// The most recent sample is first in each history.
+    state: &'a mut [f32],
+    /// The nonlinear process
+    processor: P,
+}
+
+impl<'a, P> Oversampler<'a, P>
+where
+    P: FnMut(f32) -> f32,
+{
+    /// Creates an oversampler
+    ///
+    /// factor: oversampling factor. This must be at least 1.
+    ///
+    /// coeffs: lowpass filter coefficients, in normal order (`h[0]` first). The number of
+    /// coefficients must be a non-zero multiple of factor.
+    ///
+    /// state: state buffer. The array is of length `coeffs.len() / factor + coeffs.len()`.
+    ///
+    /// processor: the process to run on each upsampled sample
+    ///
+    /// This function returns an error if factor is zero, or if the number of coefficients is
+    /// not a non-zero multiple of factor.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if state does not have the required length.
+    pub fn new(factor: usize, coeffs: &'a [f32], state: &'a mut [f32], processor: P) -> Result<Self> {
+        if factor == 0 || coeffs.is_empty() || !coeffs.len().is_multiple_of(factor) {
+            return Err(Error::Argument);
+        }
+        check_length_or_panic::<(usize, usize), usize>((coeffs.len() / factor + coeffs.len(), state.len()));
+        for value in state.iter_mut() {
+            *value = 0.0;
+        }
+        Ok(Oversampler {
+            factor,
+            coeffs,
+            state,
+            processor,
+        })
+    }
+
+    /// Returns a reference to the process
+    pub fn processor(&self) -> &P {
+        &self.processor
+    }
+
+    /// Returns a mutable reference to the process, which can be used to change its parameters
+    pub fn processor_mut(&mut self) -> &mut P {
+        &mut self.processor
+    }
+
+    /// Processes a block of samples
+    ///
+    /// # Panics
+    ///
+    /// This function panics if src and dst do not have the same length.
+    pub fn process(&mut self, src: &[f32], dst: &mut [f32]) {
+        check_length_or_panic::<(usize, usize), usize>((src.len(), dst.len()));
+        let factor = self.factor;
+        let (input_history, processed_history) =
+            self.state.split_at_mut(self.coeffs.len() / factor);
+        for (x, y) in src.iter().zip(dst.iter_mut()) {
+            push_sample(input_history, *x);
+            for phase in 0..factor {
+                // Only every factor-th coefficient meets a non-zero sample of the upsampled signal
+                let mut upsampled = 0.0;
+                for (coeff, sample) in self.coeffs[phase..]
+                    .iter()
+                    .step_by(factor)
+                    .zip(input_history.iter())
+                {
+                    upsampled += coeff * sample;
+                }
+                push_sample(processed_history, (self.processor)(factor as f32 * upsampled));
+            }
+            let mut sum = 0.0;
+            for (coeff, sample) in self.coeffs.iter().zip(processed_history.iter()) {
+                sum += coeff * sample;
+            }
+            *y = sum;
+        }
+    }
+
+    /// Processes a signal through the filter, returning an error instead of panicking if the
+    /// lengths are not valid
+    ///
+    /// This function returns `Error::LengthMismatch` if src and dst do not have the same length.
+    pub fn try_process(&mut self, src: &[f32], dst: &mut [f32]) -> Result<()> {
+        check_length::<(usize, usize), usize>((src.len(), dst.len()))?;
+        self.process(src, dst);
+        Ok(())
+    }
+}
+
+/// Number of input samples that each output sample of an `ArbitraryResampler` depends on
+#[cfg(feature = "alloc")]
+const RESAMPLER_TAPS: usize = 16;
+/// Number of fractional delays at which the interpolation filter of an `ArbitraryResampler`
+/// is tabulated
+#[cfg(feature = "alloc")]
+const RESAMPLER_PHASES: usize = 64;
+
+/// Changes the sample rate of a signal by any ratio
+///
+/// Each output sample is interpolated from the 16 nearest input samples with a windowed-sinc
+/// filter. The filter is tabulated at 64 fractional delays, and linear interpolation between
+/// adjacent delays gives the coefficients for any delay. When the ratio is less than 1, the
+/// cutoff of the filter is lowered to the output Nyquist frequency to prevent aliasing.
+///
+/// Output sample m corresponds to time `m / ratio` in input samples, so the output is not
+/// delayed. Because each output depends on the 8 input samples after it, the outputs near the
+/// end of a block are returned by a later call, once those input samples arrive.
+#[cfg(feature = "alloc")]
+pub struct ArbitraryResampler {
+    ratio: f32,
+    /// Interpolation filter coefficients for each tabulated fractional delay, followed by the
+    /// coefficients for a delay of one sample
+    table: Vec<f32>,
+    /// Input samples that outputs still depend on
+    buffer: Vec<f32>,
+    /// Time of the next output sample, as an index into buffer. Double precision keeps the
+    /// rounding of the accumulated steps small compared to the spacing of the tabulated delays.
+    time: f64,
+}
+
+#[cfg(feature = "alloc")]
+impl ArbitraryResampler {
+    /// Creates a resampler
+    ///
+    /// ratio: output sample rate divided by input sample rate. For example, 1.1 converts a
+    /// signal at 40 kHz to 44 kHz.
+    ///
+    /// This function returns an error if ratio is not a positive finite number.
+    pub fn new(ratio: f32) -> Result<Self> {
+        if !(ratio > 0.0 && ratio.is_finite()) {
+            return Err(Error::Argument);
+        }
+        let cutoff = if ratio < 1.0 { ratio } else { 1.0 };
+        let half = (RESAMPLER_TAPS / 2) as f32;
+        let mut table = Vec::with_capacity((RESAMPLER_PHASES + 1) * RESAMPLER_TAPS);
+        for phase in 0..=RESAMPLER_PHASES {
+            let start = table.len();
+            for k in 0..RESAMPLER_TAPS {
+                // Distance from the output time to the input sample that this coefficient
+                // multiplies
+                let distance = phase as f32 / RESAMPLER_PHASES as f32 + half - 1.0 - k as f32;
+                let x = PI * cutoff * distance;
+                let sinc = if x == 0.0 { 1.0 } else { sin_f32(x) / x };
+                // Blackman window
+                let w = 0.42 + 0.5 * cos_f32(PI * distance / half) + 0.08 * cos_f32(2.0 * PI * distance / half);
+                table.push(sinc * w);
+            }
+            // Normalize each delay to unity gain at DC
+            let sum: f32 = table[start..].iter().sum();
+            for coeff in table[start..].iter_mut() {
+                *coeff /= sum;
+            }
+        }
+        let mut resampler = ArbitraryResampler {
+            ratio,
+            table,
+            buffer: Vec::new(),
+            time: 0.0,
+        };
+        resampler.reset();
+        Ok(resampler)
+    }
+
+    /// Returns the ratio of the output sample rate to the input sample rate
+    pub fn ratio(&self) -> f32 {
+        self.ratio
+    }
+
+    /// Resamples a block of samples and returns the output samples that are complete
+    ///
+    /// Over many calls, the number of output samples is about ratio times the number of input
+    /// samples.
+    pub fn process(&mut self, src: &[f32]) -> Vec<f32> {
+        let half = RESAMPLER_TAPS / 2;
+        let step = 1.0 / f64::from(self.ratio);
+        self.buffer.extend_from_slice(src);
+        let mut output = Vec::with_capacity((src.len() as f32 * self.ratio) as usize + 1);
+        while (self.time as usize) + half < self.buffer.len() {
+            let index = self.time as usize;
+            let position = (self.time - index as f64) * RESAMPLER_PHASES as f64;
+            let phase = position as usize;
+            let fraction = (position - phase as f64) as f32;
+            let lower = &self.table[phase * RESAMPLER_TAPS..(phase + 1) * RESAMPLER_TAPS];
+            let upper = &self.table[(phase + 1) * RESAMPLER_TAPS..(phase + 2) * RESAMPLER_TAPS];
+            let samples = &self.buffer[index + 1 - half..=index + half];
+            let mut sum = 0.0;
+            for ((a, b), x) in lower.iter().zip(upper.iter()).zip(samples.iter()) {
+                sum += (a + fraction * (b - a)) * x;
+            }
+            output.push(sum);
+            self.time += step;
+        }
+        // Discard the samples that no later output depends on
+        let first_needed = (self.time as usize + 1).saturating_sub(half);
+        if first_needed > 0 {
+            self.buffer.drain(..first_needed);
+            self.time -= first_needed as f64;
+        }
+        output
+    }
+
+    /// Clears all buffered input samples
+    pub fn reset(&mut self) {
+        let half = RESAMPLER_TAPS / 2;
+        self.buffer.clear();
+        self.buffer.resize(half - 1, 0.0);
+        self.time = (half - 1) as f64;
+    }
+}
+
+/// Changes the sample rate of a signal by a rational factor `l / m`
+///
+/// The signal is upsampled by l with a polyphase interpolating FIR filter
+/// (`arm_fir_interpolate_f32`), and the result is downsampled by m with a decimating FIR filter
+/// (`arm_fir_decimate_f32`). Both filters use the same windowed-sinc lowpass prototype, designed
+/// at the upsampled rate with a cutoff at 90% of the lower of the input and output Nyquist
+/// frequencies, so it removes both the images created by upsampling and the frequencies that
+/// downsampling would alias.
+///
+/// For example, l = 160 and m = 147 converts 44.1 kHz to 48 kHz. The output is delayed by
+/// `num_taps - 1` samples of the upsampled signal.
+///
+/// This type is available when the `alloc` feature is enabled.
+#[cfg(feature = "alloc")]
+pub struct Resampler {
+    l: usize,
+    m: usize,
+    block_size: usize,
+    interpolator: cmsis_dsp_sys::arm_fir_interpolate_instance_f32,
+    decimator: cmsis_dsp_sys::arm_fir_decimate_instance_f32,
+    /// Upsampled samples of one block
+    buffer: Vec<f32>,
+    // The instances refer to the heap buffers of these vectors, which do not move when the
+    // Resampler moves
+    _interpolator_coeffs: Vec<f32>,
+    _interpolator_state: Vec<f32>,
+    _decimator_coeffs: Vec<f32>,
+    _decimator_state: Vec<f32>,
+}
+
+#[cfg(feature = "alloc")]
+impl Resampler {
+    /// Creates a resampler
+    ///
+    /// l: upsampling factor. m: downsampling factor. The factors are divided by their greatest
+    /// common divisor, so 2 / 4 gives the same resampler as 1 / 2.
+    ///
+    /// num_taps: number of coefficients in the lowpass prototype, which must be a multiple of l
+    /// after the factors are reduced. More taps give a narrower transition band.
+    ///
+    /// block_size: maximum number of input samples processed per call into CMSIS-DSP, which
+    /// must be a non-zero multiple of m after the factors are reduced
+    ///
+    /// This function returns `Error::Argument` if l or m is zero or num_taps is not a non-zero
+    /// multiple of l, or `Error::Length` if block_size is not valid.
+    pub fn new(l: u8, m: u8, num_taps: u16, block_size: u32) -> Result<Self> {
+        if l == 0 || m == 0 {
+            return Err(Error::Argument);
+        }
+        let divisor = gcd(l, m);
+        let (l, m) = (l / divisor, m / divisor);
+        let taps = usize::from(num_taps);
+        if taps == 0 || !taps.is_multiple_of(usize::from(l)) {
+            return Err(Error::Argument);
+        }
+        if block_size == 0 || !block_size.is_multiple_of(u32::from(m)) {
+            return Err(Error::Length);
+        }
+        let upsampled_block = block_size * u32::from(l);
+
+        // The prototype is symmetric, so it is the same in time reversed order
+        let mut decimator_coeffs = vec![0.0; taps];
+        let cutoff = 0.45 / f32::from(l.max(m));
+        fir_design::lowpass(cutoff, 1.0, Window::Hamming, &mut decimator_coeffs)?;
+        // Upsampling inserts l - 1 zeros after each sample, so the interpolator needs a gain of l
+        let interpolator_coeffs: Vec<f32> =
+            decimator_coeffs.iter().map(|coeff| coeff * f32::from(l)).collect();
+        let mut interpolator_state = vec![0.0; taps / usize::from(l) + block_size as usize - 1];
+        let mut decimator_state = vec![0.0; taps + upsampled_block as usize - 1];
+
+        let mut interpolator = MaybeUninit::<cmsis_dsp_sys::arm_fir_interpolate_instance_f32>::uninit();
+        let mut decimator = MaybeUninit::<cmsis_dsp_sys::arm_fir_decimate_instance_f32>::uninit();
+        unsafe {
+            cmsis_dsp_sys::arm_fir_interpolate_init_f32(
+                interpolator.as_mut_ptr(),
+                l,
+                num_taps,
+                interpolator_coeffs.as_ptr(),
+                interpolator_state.as_mut_ptr(),
+                block_size,
+            )
+            .check_status()?;
+            cmsis_dsp_sys::arm_fir_decimate_init_f32(
+                decimator.as_mut_ptr(),
+                num_taps,
+                m,
+                decimator_coeffs.as_ptr(),
+                decimator_state.as_mut_ptr(),
+                upsampled_block,
+            )
+            .check_status()?;
+            Ok(Resampler {
+                l: usize::from(l),
+                m: usize::from(m),
+                block_size: block_size as usize,
+                interpolator: interpolator.assume_init(),
+                decimator: decimator.assume_init(),
+                buffer: vec![0.0; upsampled_block as usize],
+                _interpolator_coeffs: interpolator_coeffs,
+                _interpolator_state: interpolator_state,
+                _decimator_coeffs: decimator_coeffs,
+                _decimator_state: decimator_state,
+            })
+        }
+    }
+
+    /// Returns the reduced upsampling and downsampling factors, (l, m)
+    pub fn factors(&self) -> (usize, usize) {
+        (self.l, self.m)
+    }
+
+    /// Returns the number of output samples for an input of input_length samples,
+    /// `input_length * l / m`, or None if input_length is not a multiple of m
+    pub fn output_length(&self, input_length: usize) -> Option<usize> {
+        if input_length.is_multiple_of(self.m) {
+            Some(input_length / self.m * self.l)
+        } else {
+            None
+        }
+    }
+
+    /// Resamples a signal
+    ///
+    /// The signal is processed in blocks of at most block_size samples. The filter state carries
+    /// over between calls.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the length of src is not a multiple of m, or if the length of dst
+    /// is not `src.len() * l / m`.
+    pub fn process(&mut self, src: &[f32], dst: &mut [f32]) {
+        let output_length = self
+            .output_length(src.len())
+            .expect("Input length must be a multiple of the downsampling factor");
+        check_length_or_panic::<(usize, usize), usize>((output_length, dst.len()));
+        let output_block = self.block_size / self.m * self.l;
+        for (src, dst) in src.chunks(self.block_size).zip(dst.chunks_mut(output_block)) {
+            let upsampled = &mut self.buffer[..src.len() * self.l];
+            unsafe {
+                cmsis_dsp_sys::arm_fir_interpolate_f32(
+                    &self.interpolator,
+                    src.as_ptr(),
+                    upsampled.as_mut_ptr(),
+                    src.len() as u32,
+                );
+                cmsis_dsp_sys::arm_fir_decimate_f32(
+                    &self.decimator,
+                    upsampled.as_ptr(),
+                    dst.as_mut_ptr(),
+                    upsampled.len() as u32,
+                );
+            }
+        }
+    }
+
+    /// Resamples a signal, returning an error instead of panicking if the lengths are not valid
+    ///
+    /// This function returns `Error::Length` if the length of src is not a multiple of m, or
+    /// `Error::LengthMismatch` if the length of dst is not `src.len() * l / m`.
+    pub fn try_process(&mut self, src: &[f32], dst: &mut [f32]) -> Result<()> {
+        let output_length = self.output_length(src.len()).ok_or(Error::Length)?;
+        check_length::<(usize, usize), usize>((output_length, dst.len()))?;
+        self.process(src, dst);
+        Ok(())
+    }
+}
+
+/// Returns the greatest common divisor of a and b
+#[cfg(feature = "alloc")]
+fn gcd(mut a: u8, mut b: u8) -> u8 {
+    while b != 0 {
+        let remainder = a % b;
+        a = b;
+        b = remainder;
+    }
+    a
+}
+
+/// Coefficients of one second-order section
+///
+/// The section has the transfer function `(b0 + b1 z^-1 + b2 z^-2) / (1 + a1 z^-1 + a2 z^-2)`,
+/// where b is `[b0, b1, b2]` and a is `[a1, a2]`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Biquad {
+    pub b: [f32; 3],
+    pub a: [f32; 2],
+}
+
+impl Biquad {
+    /// Returns the coefficients of this section in the format that `BiquadCascadeDF2TFilter::new`
+    /// uses, `[b0, b1, b2, -a1, -a2]`
+    pub fn coeffs(&self) -> [f32; 5] {
+        [self.b[0], self.b[1], self.b[2], -self.a[0], -self.a[1]]
+    }
+}
+
+/// Approximates an FIR filter with a cascade of biquad sections
+///
+/// The impulse response of the FIR filter is first converted into its minimum-phase equivalent,
+/// which has the same magnitude response but concentrates its energy at the start. A
+/// denominator of order `2 * num_stages` is then fitted to it with Prony's method, which
+/// minimizes the squared error of the denominator equation over the impulse response in the
+/// least-squares sense, and the numerator is chosen to match the first samples exactly. Finally,
+/// both polynomials are factored into second-order sections. Any fitted pole outside the unit
+/// circle is reflected inside it, which keeps the magnitude response and makes the cascade
+/// stable.
+///
+/// The result approximates the magnitude response of the FIR filter, but not its phase. A linear
+/// phase FIR filter becomes a minimum phase IIR filter with less delay.
+///
+/// fir_coeffs: coefficients of the FIR filter, with at most 4096 elements
+///
+/// num_stages: number of sections in the result
+///
+/// The overall gain is placed in the numerator of the first section. The coefficients of each
+/// section can be passed to `BiquadCascadeDF2TFilter::new` with `Biquad::coeffs`.
+///
+/// This function returns `Error::Argument` if num_stages is zero, if fir_coeffs has no more than
+/// `2 * num_stages` elements, or if fir_coeffs has more than 4096 elements, and `Error::Singular`
+/// if the least-squares problem has no unique solution (for example, if the filter is all zeros).
+///
+/// This function is available when the `alloc` feature is enabled.
+#[cfg(feature = "alloc")]
+pub fn approximate_as_biquads(fir_coeffs: &[f32], num_stages: usize) -> Result<Vec<Biquad>> {
+    let length = fir_coeffs.len();
+    let order = 2 * num_stages;
+    if num_stages == 0 || length <= order || length > 4096 {
+        return Err(Error::Argument);
+    }
+    if fir_coeffs.iter().all(|&x| x == 0.0) {
+        return Err(Error::Singular);
+    }
+
+    // Minimum-phase equivalent, calculated with an FFT of 8 times the filter length to reduce
+    // time aliasing in the cepstrum
+    let mut size = 256;
+    while size < 8 * length && size < 4096 {
+        size *= 2;
+    }
+    let fft = FloatRealFft::new(size as u16)?;
+    let mut padded = vec![0.0; size];
+    padded[..length].copy_from_slice(fir_coeffs);
+    let mut spectrum = vec![0.0; size];
+    fft.run(&padded, &mut spectrum);
+    let mut magnitude = vec![0.0; size / 2 + 1];
+    magnitude[0] = spectrum[0].abs();
+    magnitude[size / 2] = spectrum[1].abs();
+    for (value, bin) in magnitude[1..size / 2].iter_mut().zip(spectrum[2..].chunks_exact(2)) {
+        *value = sqrt_f32(bin[0] * bin[0] + bin[1] * bin[1]);
+    }
+    let mut min_phase = vec![0.0; size];
+    crate::spectral::minimum_phase_f32(&magnitude, &mut min_phase)?;
+    let h: Vec<f64> = min_phase[..length].iter().map(|&x| f64::from(x)).collect();
+    // Impulse response, which is zero outside the filter
+    let sample = |n: isize| -> f64 {
+        if n >= 0 && (n as usize) < length {
+            h[n as usize]
+        } else {
+            0.0
+        }
+    };
+
+    // Prony's method: choose a[1..=order] to minimize the sum over n of
+    // (h[n] + a[1] h[n - 1] + ... + a[order] h[n - order])^2, for the samples after the
+    // numerator (n > order) up to the end of the filter
+    let mut normal = vec![0.0f64; order * order];
+    let mut rhs = vec![0.0f64; order];
+    for n in order + 1..length + order {
+        let n = n as isize;
+        for i in 0..order {
+            let hi = sample(n - 1 - i as isize);
+            rhs[i] -= hi * sample(n);
+            for j in 0..order {
+                normal[i * order + j] += hi * sample(n - 1 - j as isize);
+            }
+        }
+    }
+    solve_linear(&mut normal, &mut rhs)?;
+    let mut a = Vec::with_capacity(order + 1);
+    a.push(1.0);
+    a.extend_from_slice(&rhs);
+    let b: Vec<f64> = (0..=order)
+        .map(|n| (0..=n).map(|k| a[k] * sample((n - k) as isize)).sum())
+        .collect();
+
+    let (numerator_gain, numerator) = second_order_factors(&b, num_stages, false);
+    let (denominator_gain, denominator) = second_order_factors(&a, num_stages, true);
+    let gain = numerator_gain / denominator_gain;
+    Ok(numerator
+        .iter()
+        .zip(denominator.iter())
+        .enumerate()
+        .map(|(i, (num, den))| {
+            let scale = if i == 0 { gain } else { 1.0 };
+            Biquad {
+                b: [
+                    (num[0] * scale) as f32,
+                    (num[1] * scale) as f32,
+                    (num[2] * scale) as f32,
+                ],
+                a: [den[1] as f32, den[2] as f32],
+            }
+        })
+        .collect())
+}
+
+/// Solves the square linear system `matrix * x = rhs` with Gaussian elimination and partial
+/// pivoting, placing x in rhs
+///
+/// matrix is stored in row-major order and is modified.
+#[cfg(feature = "alloc")]
+fn solve_linear(matrix: &mut [f64], rhs: &mut [f64]) -> Result<()> {
+    let n = rhs.len();
+    let scale = matrix.iter().fold(0.0f64, |max, x| max.max(x.abs()));
+    if scale == 0.0 {
+        return Err(Error::Singular);
+    }
+    for col in 0..n {
+        let pivot = (col..n)
+            .max_by(|&i, &j| {
+                matrix[i * n + col]
+                    .abs()
+                    .partial_cmp(&matrix[j * n + col].abs())
+                    .unwrap_or(core::cmp::Ordering::Equal)
+            })
+            .unwrap_or(col);
+        let magnitude = matrix[pivot * n + col].abs();
+        if magnitude.is_nan() || magnitude <= 1e-12 * scale {
+            return Err(Error::Singular);
+        }
+        if pivot != col {
+            for k in 0..n {
+                matrix.swap(pivot * n + k, col * n + k);
+            }
+            rhs.swap(pivot, col);
+        }
+        for row in col + 1..n {
+            let factor = matrix[row * n + col] / matrix[col * n + col];
+            for k in col..n {
+                matrix[row * n + k] -= factor * matrix[col * n + k];
+            }
+            rhs[row] -= factor * rhs[col];
+        }
+    }
+    for row in (0..n).rev() {
+        let mut sum = rhs[row];
+        for k in row + 1..n {
+            sum -= matrix[row * n + k] * rhs[k];
+        }
+        rhs[row] = sum / matrix[row * n + row];
+    }
+    Ok(())
+}
+
+/// Factors the polynomial `c[0] + c[1] z^-1 + ... + c[2 * sections] z^(-2 * sections)` into a
+/// gain and second-order factors `[d0, d1, d2]`
+///
+/// Leading coefficients that are nearly zero become factors of z^-1, and d0 is 1 for all other
+/// factors. When stable is true, roots outside the unit circle are reflected inside it and the
+/// gain is adjusted so that the magnitude of the polynomial on the unit circle does not change.
+#[cfg(feature = "alloc")]
+fn second_order_factors(c: &[f64], sections: usize, stable: bool) -> (f64, Vec<[f64; 3]>) {
+    let peak = c.iter().fold(0.0f64, |max, x| max.max(x.abs()));
+    let delays = c.iter().take_while(|x| x.abs() <= 1e-9 * peak).count();
+    if delays == c.len() {
+        return (0.0, vec![[1.0, 0.0, 0.0]; sections]);
+    }
+    let mut gain = c[delays];
+    let mut roots = polynomial_roots(&c[delays..]);
+    if stable {
+        for root in roots.iter_mut() {
+            let norm_sqr = root.norm_sqr();
+            if norm_sqr > 1.0 {
+                gain *= sqrt_f64(norm_sqr);
+                *root = root.conj() / norm_sqr;
+            }
+        }
+    }
+
+    // Pair each root in the upper half plane with the nearest root to its conjugate, and collect
+    // the delays and the remaining roots, which are real, as first-order factors [0, 1] and
+    // [1, -root]
+    let mut factors = vec![[1.0, 0.0, 0.0]; sections];
+    let mut next = 0;
+    let mut first_order: Vec<[f64; 2]> = vec![[0.0, 1.0]; delays];
+    while let Some(index) = roots.iter().position(|root| root.im > 1e-6) {
+        let root = roots.swap_remove(index);
+        let conjugate = roots
+            .iter()
+            .enumerate()
+            .min_by(|(_, x), (_, y)| {
+                (**x - root.conj())
+                    .norm_sqr()
+                    .partial_cmp(&(**y - root.conj()).norm_sqr())
+                    .unwrap_or(core::cmp::Ordering::Equal)
+            })
+            .map(|(i, _)| i);
+        if let Some(conjugate) = conjugate {
+            roots.swap_remove(conjugate);
+        }
+        factors[next] = [1.0, -2.0 * root.re, root.norm_sqr()];
+        next += 1;
+    }
+    first_order.extend(roots.iter().map(|root| [1.0, -root.re]));
+    for pair in first_order.chunks(2) {
+        let (p, q) = (pair[0], pair.get(1).copied().unwrap_or([1.0, 0.0]));
+        factors[next] = [p[0] * q[0], p[0] * q[1] + p[1] * q[0], p[1] * q[1]];
+        next += 1;
+    }
+    (gain, factors)
+}
+
+/// Calculates the square root of a positive x in double precision
+///
+/// The single-precision square root is refined with two Newton iterations, which only needs
+/// `sqrtf` from the C standard library.
+#[cfg(feature = "alloc")]
+fn sqrt_f64(x: f64) -> f64 {
+    let mut root = f64::from(sqrt_f32(x as f32));
+    for _ in 0..2 {
+        root = 0.5 * (root + x / root);
+    }
+    root
+}
+
+/// Finds all roots of the polynomial `c[0] x^n + c[1] x^(n-1) + ... + c[n]` with the
+/// Durand-Kerner method
+#[cfg(feature = "alloc")]
+fn polynomial_roots(c: &[f64]) -> Vec<Complex<f64>> {
+    let degree = c.len() - 1;
+    let monic: Vec<f64> = c.iter().map(|x| x / c[0]).collect();
+    let evaluate = |x: Complex<f64>| monic[1..].iter().fold(Complex::new(1.0, 0.0), |sum, &k| sum * x + k);
+    let seed = Complex::new(0.4, 0.9);
+    let mut roots: Vec<Complex<f64>> = (0..degree)
+        .scan(Complex::new(1.0, 0.0), |power, _| {
+            let root = *power;
+            *power *= seed;
+            Some(root)
+        })
+        .collect();
+    for _ in 0..500 {
+        let mut change: f64 = 0.0;
+        for i in 0..degree {
+            let mut denominator = Complex::new(1.0, 0.0);
+            for j in 0..degree {
+                if i != j {
+                    denominator *= roots[i] - roots[j];
+                }
+            }
+            let step = evaluate(roots[i]) / denominator;
+            roots[i] -= step;
+            change = change.max(step.norm_sqr());
+        }
+        if change < 1e-24 {
+            break;
+        }
+    }
+    roots
+}
+
+/// Shifts a history buffer by one sample and places a new sample at the beginning
+fn push_sample(history: &mut [f32], sample: f32) {
+    history.copy_within(..history.len() - 1, 1);
+    history[0] = sample;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A lowpass and a highpass stage, in the CMSIS-DSP coefficient format
+    const LOWPASS: [f32; 5] = [0.2, 0.4, 0.2, 0.5, -0.3];
+    const HIGHPASS: [f32; 5] = [0.6, -1.2, 0.6, 0.5, -0.3];
+
+    #[test]
+    fn update_coeffs_keeps_state() {
+        let input = [1.0, 0.5, -0.25, 0.75, -1.0, 0.25, 0.5, -0.5];
+        let (first, second) = input.split_at(4);
+
+        // Lowpass for the whole signal
+        let mut unchanged = [0.0; 4];
+        let mut state = [0.0; 2];
+        let mut filter = BiquadCascadeDF2TFilter::new(1, &LOWPASS, &mut state).unwrap();
+        let mut output = [0.0; 4];
+        filter.process(first, &mut output, 4);
+        filter.process(second, &mut unchanged, 4);
+
+        // Lowpass for the first half, then highpass
+        let mut updated = [0.0; 4];
+        let mut state = [0.0; 2];
+        let mut filter = BiquadCascadeDF2TFilter::new(1, &LOWPASS, &mut state).unwrap();
+        filter.process(first, &mut output, 4);
+        filter.update_coeffs(&HIGHPASS);
+        filter.process(second, &mut updated, 4);
+
+        // Highpass for the second half only, starting from zero state
+        let mut fresh = [0.0; 4];
+        let mut state = [0.0; 2];
+        let mut filter = BiquadCascadeDF2TFilter::new(1, &HIGHPASS, &mut state).unwrap();
+        filter.process(second, &mut fresh, 4);
+
+        assert_ne!(updated, unchanged, "the new coefficients were not used");
+        assert_ne!(updated, fresh, "the state was reset");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn to_ba_inverts_from_sos() {
+        let sos = [
+            [0.5, 1.0, 0.5, 2.0, -1.0, 0.5],
+            [1.0, -2.0, 1.0, 1.0, 0.25, -0.125],
+        ];
+        let mut coeffs = [0.0; 10];
+        let mut state = [0.0; 4];
+        let filter = BiquadCascadeDF2TFilter::from_sos(&sos, &mut coeffs, &mut state).unwrap();
+        let ba = filter.to_ba();
+        assert_eq!(
+            ba,
+            [
+                ([0.25, 0.5, 0.25], [1.0, -0.5, 0.25]),
+                ([1.0, -2.0, 1.0], [1.0, 0.25, -0.125]),
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn to_ba_uses_updated_coeffs() {
+        let mut state = [0.0; 2];
+        let mut filter = BiquadCascadeDF2TFilter::new(1, &LOWPASS, &mut state).unwrap();
+        filter.update_coeffs(&HIGHPASS);
+        assert_eq!(filter.to_ba(), [([0.6, -1.2, 0.6], [1.0, -0.5, 0.3])]);
+    }
+
+    #[test]
+    fn fir_oversized_block_matches_base_blocks() {
+        // Coefficients in time reversed order
+        let coeffs = [0.1, -0.2, 0.3, 0.5];
+        let input: [f32; 16] = [
+            1.0, 0.5, -0.25, 0.75, -1.0, 0.25, 0.5, -0.5, 0.0, 2.0, -1.5, 0.125, 0.25, -0.75, 1.0,
+            0.5,
+        ];
+
+        // One call with four times the block size passed to new
+        let mut oversized = [0.0; 16];
+        let mut state = [0.0; 7];
+        let mut filter = FirFilter::new(4, &coeffs, &mut state, 4).unwrap();
+        filter.process(&input, &mut oversized, 16);
+
+        // Four calls with the block size passed to new
+        let mut blocks = [0.0; 16];
+        let mut state = [0.0; 7];
+        let mut filter = FirFilter::new(4, &coeffs, &mut state, 4).unwrap();
+        for (src, dst) in input.chunks(4).zip(blocks.chunks_mut(4)) {
+            filter.process(src, dst, 4);
+        }
+
+        assert_eq!(oversized, blocks);
+        // The first outputs are the start of the impulse response scaled by the first input
+        assert_eq!(oversized[0], 0.5);
+        assert_eq!(oversized[1], 0.5 * 0.5 + 0.3);
+    }
+
+    #[test]
+    fn fir_impulse_response_is_in_forward_order() {
+        // b = [0.5, 0.3, -0.2, 0.1], stored in time reversed order
+        let coeffs = [0.1, -0.2, 0.3, 0.5];
+        let mut state = [0.0; 7];
+        let mut filter = FirFilter::new(4, &coeffs, &mut state, 4).unwrap();
+        let mut response = [0.0; 4];
+        filter.impulse_response(&mut response);
+        assert_eq!(response, [0.5, 0.3, -0.2, 0.1]);
+        assert_eq!(filter.group_delay(), 1.5);
+
+        // The response matches the output of the filter for an impulse
+        let mut output = [0.0; 4];
+        filter.process(&[1.0, 0.0, 0.0, 0.0], &mut output, 4);
+        assert_eq!(output, response);
+    }
+
+    #[test]
+    fn fir_group_delay_of_odd_length_filter() {
+        let coeffs = [0.25, 0.5, 0.25];
+        let mut state = [0.0; 6];
+        let filter = FirFilter::new(3, &coeffs, &mut state, 4).unwrap();
+        assert_eq!(filter.group_delay(), 1.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn fir_impulse_response_checks_length() {
+        let coeffs = [0.25, 0.5, 0.25];
+        let mut state = [0.0; 6];
+        let filter = FirFilter::new(3, &coeffs, &mut state, 4).unwrap();
+        filter.impulse_response(&mut [0.0; 4]);
+    }
+
+    /// Checks the response of a filter with one stage against hand-calculated gains in decibels
+    #[cfg(feature = "alloc")]
+    fn check_response(coeffs: &[f32; 5], expected: &[(f32, f32)]) {
+        let mut state = [0.0; 2];
+        let filter = BiquadCascadeDF2TFilter::new(1, coeffs, &mut state).unwrap();
+        let response = filter.frequency_response(expected.len(), 1000.0);
+        for (&(frequency, gain), &(expected_frequency, expected_gain)) in
+            response.iter().zip(expected)
+        {
+            assert!(
+                (frequency - expected_frequency).abs() < 1e-3,
+                "{} Hz",
+                frequency
+            );
+            assert!(
+                (gain - expected_gain).abs() < 1e-4,
+                "{} dB at {} Hz",
+                gain,
+                frequency
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn frequency_response_of_passthrough() {
+        let expected = [
+            (0.0, 0.0),
+            (125.0, 0.0),
+            (250.0, 0.0),
+            (375.0, 0.0),
+            (500.0, 0.0),
+        ];
+        check_response(&[1.0, 0.0, 0.0, 0.0, 0.0], &expected);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn frequency_response_of_single_biquads() {
+        // FIR: |H| = |0.75 + 0.25 e^-jw|, which is 1, sqrt(0.625), and 0.5 at w = 0, pi/2, and pi
+        check_response(
+            &[0.75, 0.25, 0.0, 0.0, 0.0],
+            &[(0.0, 0.0), (250.0, -2.0412), (500.0, -6.0206)],
+        );
+        // One pole, y[n] = x[n] + 0.5 y[n-1]: |H| = 2 at DC and 2/3 at the Nyquist frequency
+        check_response(
+            &[1.0, 0.0, 0.0, 0.5, 0.0],
+            &[(0.0, 6.0206), (500.0, -3.5218)],
+        );
+        // The pole at z = -0.5 and the zero at z = 0.5 swap the gains at DC and at fs / 2
+        check_response(
+            &[1.0, -0.5, 0.0, -0.5, 0.0],
+            &[(0.0, -9.5424), (500.0, 9.5424)],
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn update_coeffs_checks_length() {
+        let mut state = [0.0; 2];
+        let mut filter = BiquadCascadeDF2TFilter::new(1, &LOWPASS, &mut state).unwrap();
+        filter.update_coeffs(&[1.0, 0.0, 0.0]);
+    }
+
+    /// The lowpass stage followed by the highpass stage
+    fn biquad_cascade() -> [f32; 10] {
+        let mut coeffs = [0.0; 10];
+        coeffs[..5].copy_from_slice(&LOWPASS);
+        coeffs[5..].copy_from_slice(&HIGHPASS);
+        coeffs
+    }
+
+    #[test]
+    fn fixed_point_biquads_match_f32() {
+        let mut input = [0.0; 32];
+        for (i, x) in input.iter_mut().enumerate() {
+            *x = 0.5 * ((i * 7 % 11) as f32 / 5.0 - 1.0);
+        }
+        let coeffs = biquad_cascade();
+        let mut state = [0.0; 4];
+        let mut filter = BiquadCascadeDF2TFilter::new(2, &coeffs, &mut state).unwrap();
+        let mut expected = [0.0; 32];
+        filter.process(&input, &mut expected, 32);
+
+        // The highpass coefficients are larger than 1, so all coefficients are halved and each
+        // stage shifts its output left by one bit
+        let halved = coeffs.map(|c| c / 2.0);
+
+        let coeffs = halved.map(I1F31::from_num);
+        let mut state = [I1F31::ZERO; 8];
+        let mut filter = BiquadCascadeDF1FilterQ31::new(2, &coeffs, &mut state, 1);
+        let mut dst = [I1F31::ZERO; 32];
+        filter.process(&input.map(I1F31::from_num), &mut dst);
+        for (i, (y, expected)) in dst.iter().zip(expected.iter()).enumerate() {
+            let y = y.to_num::<f32>();
+            assert!(
+                (y - expected).abs() < 1e-5,
+                "Q31 sample {}: {} != {}",
+                i,
+                y,
+                expected
+            );
+        }
+
+        // The Q15 format has a zero after the first coefficient of each stage
+        let mut coeffs = [I1F15::ZERO; 12];
+        for (dst, src) in coeffs.chunks_exact_mut(6).zip(halved.chunks_exact(5)) {
+            dst[0] = I1F15::from_num(src[0]);
+            for (dst, &src) in dst[2..].iter_mut().zip(&src[1..]) {
+                *dst = I1F15::from_num(src);
+            }
+        }
+        let mut state = [I1F15::ZERO; 8];
+        let mut filter = BiquadCascadeDF1FilterQ15::new(2, &coeffs, &mut state, 1);
+        let mut dst = [I1F15::ZERO; 32];
+        filter.process(&input.map(I1F15::from_num), &mut dst);
+        for (i, (y, expected)) in dst.iter().zip(expected.iter()).enumerate() {
+            let y = y.to_num::<f32>();
+            assert!(
+                (y - expected).abs() < 2e-3,
+                "Q15 sample {}: {} != {}",
+                i,
+                y,
+                expected
+            );
+        }
+    }
+
+    fn assert_close(actual: &[f32], expected: &[f32]) {
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-6, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn fir_lattice_impulse_response() {
+        // A(z) = 1 + k1 (1 + k2) z^-1 + k2 z^-2
+        let coeffs = [0.5, 0.25];
+        let mut state = [0.0; 2];
+        let mut filter = FirLatticeFilter::new(2, &coeffs, &mut state);
+        let mut response = [0.0; 5];
+        filter.process(&[1.0, 0.0, 0.0, 0.0, 0.0], &mut response);
+        assert_close(&response, &[1.0, 0.625, 0.25, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn iir_lattice_impulse_responses() {
+        // All-pole: 1 / A(z) with the A(z) of the FIR lattice test above
+        let reflection = [0.25, 0.5];
+        let ladder = [0.0, 0.0, 1.0];
+        let mut state = [0.0; 6];
+        let mut filter = IirLatticeFilter::new(2, &reflection, &ladder, &mut state, 4);
+        let mut response = [0.0; 6];
+        // Two calls, so the state carries over between blocks
+        filter.process(&[1.0, 0.0, 0.0, 0.0], &mut response[..4]);
+        filter.process(&[0.0, 0.0], &mut response[4..]);
+        let mut expected = [1.0, -0.625, 0.0, 0.0, 0.0, 0.0];
+        for n in 2..6 {
+            expected[n] = -0.625 * expected[n - 1] - 0.25 * expected[n - 2];
+        }
+        assert_close(&response, &expected);
+
+        // One stage with a zero from the ladder coefficients:
+        // H(z) = (k1 v1 + v0 + v1 z^-1) / (1 + k1 z^-1) = (1 + z^-1) / (1 + 0.5 z^-1)
+        let reflection = [0.5];
+        let ladder = [1.0, 0.5];
+        let mut state = [0.0; 5];
+        let mut filter = IirLatticeFilter::new(1, &reflection, &ladder, &mut state, 4);
+        let mut response = [0.0; 4];
+        filter.process(&[1.0, 0.0, 0.0, 0.0], &mut response);
+        assert_close(&response, &[1.0, 0.5, -0.25, 0.125]);
+    }
+
+    #[test]
+    fn iir_lattice_inverts_fir_lattice() {
+        let input = [1.0, -0.5, 0.25, 0.75, -1.0, 0.5, 0.0, 0.125];
+        let coeffs = [0.5, -0.3, 0.2];
+        let mut state = [0.0; 3];
+        let mut fir = FirLatticeFilter::new(3, &coeffs, &mut state);
+        let mut filtered = [0.0; 8];
+        fir.process(&input, &mut filtered);
+
+        let reversed = [0.2, -0.3, 0.5];
+        let ladder = [0.0, 0.0, 0.0, 1.0];
+        let mut state = [0.0; 11];
+        let mut iir = IirLatticeFilter::new(3, &reversed, &ladder, &mut state, 8);
+        let mut restored = [0.0; 8];
+        iir.process(&filtered, &mut restored);
+        assert_close(&restored, &input);
+    }
+
+    #[test]
+    fn sparse_fir_matches_dense_fir() {
+        let input: [f32; 24] = [
+            1.0, 0.5, -0.25, 0.75, -1.0, 0.25, 0.5, -0.5, 0.0, 2.0, -1.5, 0.125, 0.25, -0.75, 1.0,
+            0.5, -0.125, 0.0, 0.375, -0.5, 1.5, -1.0, 0.25, 0.0,
+        ];
+        // h[0] = 0.5, h[3] = -0.25, h[9] = 0.125, and zero otherwise
+        let coeffs = [0.5, -0.25, 0.125];
+        let tap_delay = [0, 3, 9];
+        let mut state = [0.0; 15];
+        let mut sparse = FirFilterSparse::new(3, &coeffs, &tap_delay, 9, &mut state, 6);
+        let mut sparse_output = [0.0; 24];
+        let mut scratch = [0.0; 6];
+        for (src, dst) in input.chunks(6).zip(sparse_output.chunks_mut(6)) {
+            sparse.process(src, dst, &mut scratch);
+        }
+
+        // The same filter with all ten taps, in time reversed order
+        let mut dense_coeffs = [0.0; 10];
+        dense_coeffs[9] = 0.5;
+        dense_coeffs[6] = -0.25;
+        dense_coeffs[0] = 0.125;
+        let mut state = [0.0; 33];
+        let mut dense = FirFilter::new(10, &dense_coeffs, &mut state, 24).unwrap();
+        let mut dense_output = [0.0; 24];
+        dense.process(&input, &mut dense_output, 24);
+
+        assert_close(&sparse_output, &dense_output);
+        // The longest delay spans more than one block
+        assert_eq!(sparse_output[9], 0.5 * 2.0 - 0.25 * 0.5 + 0.125 * 1.0);
+    }
+
+    /// Processes a signal through a filter with the given block size, in pieces of the given
+    /// length
+    fn fir_streaming(input: &[f32; 1000], block_size: u32, piece: usize) -> [f32; 1000] {
+        let coeffs = [0.1, -0.2, 0.3, 0.5, 0.25, -0.125, 0.0625];
+        let mut state = [0.0; 106];
+        let state = &mut state[..coeffs.len() + block_size as usize - 1];
+        let mut filter = FirFilter::new(7, &coeffs, state, block_size).unwrap();
+        let mut output = [0.0; 1000];
+        for (src, dst) in input.chunks(piece).zip(output.chunks_mut(piece)) {
+            filter.process_streaming(src, dst);
+        }
+        output
+    }
+
+    #[test]
+    fn fir_streaming_does_not_depend_on_block_size() {
+        let mut input = [0.0; 1000];
+        for (i, x) in input.iter_mut().enumerate() {
+            *x = ((i * 37 % 101) as f32 - 50.0) / 50.0;
+        }
+        let blocks_of_64 = fir_streaming(&input, 64, 1000);
+        let blocks_of_100 = fir_streaming(&input, 100, 1000);
+        assert_eq!(blocks_of_64, blocks_of_100);
+        // Pieces that are not multiples of the block size leave short chunks
+        assert_eq!(fir_streaming(&input, 64, 100), blocks_of_64);
+        assert_eq!(fir_streaming(&input, 100, 64), blocks_of_64);
+    }
+
+    #[test]
+    fn fir_rejects_zero_block_size() {
+        let coeffs = [0.25, 0.5, 0.25];
+        let mut state = [0.0; 2];
+        assert!(matches!(
+            FirFilter::new(3, &coeffs, &mut state, 0),
+            Err(Error::Argument)
+        ));
+    }
+
+    #[test]
+    fn match_template_finds_embedded_offset() {
+        let template = [0.5, -1.0, 0.75, 0.25, -0.5];
+        let mut signal = [0.0; 64];
+        for (n, value) in signal.iter_mut().enumerate() {
+            *value = 0.1 * (((n * 37) % 17) as f32 / 17.0 - 0.5);
+        }
+        // A negated copy correlates negatively, and a scaled copy matches exactly
+        for (i, t) in template.iter().enumerate() {
+            signal[5 + i] = -t;
+            signal[23 + i] = 3.0 * t;
+        }
+        let (offset, confidence) = match_template_f32(&signal, &template);
+        assert_eq!(offset, 23);
+        assert!((confidence - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn match_template_without_energy() {
+        assert_eq!(match_template_f32(&[0.0; 8], &[1.0, 2.0]), (0, 0.0));
+        assert_eq!(match_template_f32(&[1.0; 8], &[0.0, 0.0]), (0, 0.0));
+    }
+
+    /// Returns the gain of a cascade of biquads at the normalized frequency f, with a sample
+    /// rate of 1
+    #[cfg(feature = "alloc")]
+    fn cascade_gain(stages: &[Biquad], f: f64) -> f64 {
+        let w = 2.0 * core::f64::consts::PI * f;
+        let evaluate = |c: [f64; 3]| {
+            let re = c[0] + c[1] * w.cos() + c[2] * (2.0 * w).cos();
+            let im = -c[1] * w.sin() - c[2] * (2.0 * w).sin();
+            (re * re + im * im).sqrt()
+        };
+        stages
+            .iter()
+            .map(|stage| {
+                let b = stage.b.map(f64::from);
+                let a = stage.a.map(f64::from);
+                evaluate(b) / evaluate([1.0, a[0], a[1]])
+            })
+            .product()
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn approximate_as_biquads_recovers_a_cascade() {
+        // A minimum-phase cascade: zeros at radius 0.5 and 0.7, and poles at radius 0.9 and 0.6
+        let conjugate_pair = |radius: f64, angle: f64| {
+            [
+                (-2.0 * radius * (angle * core::f64::consts::PI).cos()) as f32,
+                (radius * radius) as f32,
+            ]
+        };
+        let (z1, z2) = (conjugate_pair(0.5, 0.3), conjugate_pair(0.7, 0.7));
+        let (p1, p2) = (conjugate_pair(0.9, 0.2), conjugate_pair(0.6, 0.6));
+        let original = [
+            Biquad {
+                b: [0.5, 0.5 * z1[0], 0.5 * z1[1]],
+                a: p1,
+            },
+            Biquad {
+                b: [1.0, z2[0], z2[1]],
+                a: p2,
+            },
+        ];
+
+        // The impulse response, which has decayed to almost zero after 256 samples
+        let coeffs = [original[0].coeffs(), original[1].coeffs()].concat();
+        let mut state = [0.0; 4];
+        let mut filter = BiquadCascadeDF2TFilter::new(2, &coeffs, &mut state).unwrap();
+        let mut impulse = vec![0.0; 256];
+        impulse[0] = 1.0;
+        let mut response = vec![0.0; 256];
+        filter.process(&impulse, &mut response, 256);
+
+        let stages = approximate_as_biquads(&response, 2).unwrap();
+        assert_eq!(stages.len(), 2);
+        // The poles are recovered, in either order
+        let matches = |stage: &Biquad, poles: [f32; 2]| {
+            (stage.a[0] - poles[0]).abs() < 1e-3 && (stage.a[1] - poles[1]).abs() < 1e-3
+        };
+        assert!(
+            (matches(&stages[0], p1) && matches(&stages[1], p2))
+                || (matches(&stages[0], p2) && matches(&stages[1], p1)),
+            "{:?}",
+            stages
+        );
+        // The gain is the same at all frequencies
+        for i in 0..=20 {
+            let f = 0.025 * f64::from(i);
+            let (expected, actual) = (cascade_gain(&original, f), cascade_gain(&stages, f));
+            assert!(
+                (actual - expected).abs() < 1e-3 * expected,
+                "{}: {} != {}",
+                f,
+                actual,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn approximate_as_biquads_checks_arguments() {
+        assert!(matches!(
+            approximate_as_biquads(&[1.0; 8], 0),
+            Err(Error::Argument)
+        ));
+        assert!(matches!(
+            approximate_as_biquads(&[1.0; 4], 2),
+            Err(Error::Argument)
+        ));
+        assert!(matches!(
+            approximate_as_biquads(&[0.0; 16], 2),
+            Err(Error::Singular)
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn sqrt_f64_has_double_precision() {
+        for &x in [2.0, 1.0 + 1e-9, 0.81, 1e6].iter() {
+            let root = sqrt_f64(x);
+            assert!((root * root - x).abs() <= 4.0 * f64::EPSILON * x, "{}", x);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn frequency_response_is_3_db_down_at_the_cutoff() {
+        // |H(fc)| = 1 / sqrt(2) for a Butterworth design, which is -3.0103 dB
+        let half_power = 20.0 * core::f32::consts::FRAC_1_SQRT_2.log10();
+        let q = core::f32::consts::FRAC_1_SQRT_2;
+        // 25 points from 0 to 24 kHz are 1 kHz apart, so the cutoffs are at points 1 and 6
+        for &(fc, index) in [(1000.0, 1), (6000.0, 6)].iter() {
+            let designs = [
+                (biquad::lowpass(fc, 48000.0, q), 0),
+                (biquad::highpass(fc, 48000.0, q), 24),
+            ];
+            for (coeffs, pass_index) in designs.iter() {
+                let mut state = [0.0; 2];
+                let filter = BiquadCascadeDF2TFilter::new(1, coeffs, &mut state).unwrap();
+                let response = filter.frequency_response(25, 48000.0);
+                assert_eq!(response[index].0, fc);
+                assert!(
+                    (response[index].1 - half_power).abs() < 0.01,
+                    "{:?}",
+                    response[index]
+                );
+                // The passband has unity gain
+                assert!(response[*pass_index].1.abs() < 0.01);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn arbitrary_resampler_converts_a_sinusoid() {
+        // 1.1 is not a ratio of small integers, and the tone is 0.05 cycles per input sample
+        let mut resampler = ArbitraryResampler::new(1.1).unwrap();
+        assert_eq!(resampler.ratio(), 1.1);
+        let input: Vec<f32> = (0..400)
+            .map(|n| sin_f32(2.0 * PI * 0.05 * n as f32))
+            .collect();
+        let output = resampler.process(&input);
+        // Output m is at time m / 1.1 and is not delayed. The first outputs depend on the zeros
+        // before the signal, and the last 8 inputs are still needed for later outputs.
+        assert_eq!(output.len(), ((400 - 8) as f32 * 1.1).ceil() as usize);
+        for (m, value) in output.iter().enumerate().skip(10) {
+            let expected = sin_f32(2.0 * PI * 0.05 * m as f32 / 1.1);
+            assert!(
+                (value - expected).abs() < 2e-3,
+                "{}: {} != {}",
+                m,
+                value,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn arbitrary_resampler_is_continuous_across_calls() {
+        let input: Vec<f32> = (0..300)
+            .map(|n| ((n * 37) % 17) as f32 / 17.0 - 0.5)
+            .collect();
+        for &ratio in [0.7, 1.1, 2.5].iter() {
+            let mut whole = ArbitraryResampler::new(ratio).unwrap();
+            let expected = whole.process(&input);
+
+            let mut blocks = ArbitraryResampler::new(ratio).unwrap();
+            let mut output = Vec::new();
+            let mut start = 0;
+            for length in [1, 13, 0, 64, 5, 100].iter().cycle() {
+                let end = (start + length).min(input.len());
+                output.extend(blocks.process(&input[start..end]));
+                start = end;
+                if start == input.len() {
+                    break;
+                }
+            }
+            assert_eq!(output.len(), expected.len());
+            for (value, expected) in output.iter().zip(expected.iter()) {
+                assert!((value - expected).abs() < 1e-5, "ratio {}", ratio);
+            }
+
+            // After a reset, the resampler starts again from silence
+            blocks.reset();
+            assert_eq!(blocks.process(&input), expected);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn arbitrary_resampler_rejects_invalid_ratios() {
+        for &ratio in [0.0, -1.0, f32::NAN, f32::INFINITY].iter() {
+            assert!(matches!(
+                ArbitraryResampler::new(ratio),
+                Err(Error::Argument)
+            ));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn resampler_converts_a_sinusoid() {
+        // Raising the rate by 3 / 2, like 32 kHz to 48 kHz
+        let mut resampler = Resampler::new(3, 2, 48, 32).unwrap();
+        assert_eq!(resampler.factors(), (3, 2));
+        let input: Vec<f32> = (0..300)
+            .map(|n| sin_f32(2.0 * PI * 0.05 * n as f32))
+            .collect();
+        assert_eq!(resampler.output_length(input.len()), Some(450));
+        let mut output = vec![0.0; 450];
+        resampler.process(&input, &mut output);
+
+        // Output n is at time 2n / 3 in input samples, minus the delay of 47 upsampled samples
+        for (n, value) in output.iter().enumerate().skip(60) {
+            let time = (2.0 * n as f32 - 47.0) / 3.0;
+            let expected = sin_f32(2.0 * PI * 0.05 * time);
+            assert!(
+                (value - expected).abs() < 1e-2,
+                "{}: {} != {}",
+                n,
+                value,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn resampler_is_continuous_across_calls() {
+        let input: Vec<f32> = (0..120)
+            .map(|n| ((n * 37) % 17) as f32 / 17.0 - 0.5)
+            .collect();
+        let mut whole = Resampler::new(2, 3, 30, 24).unwrap();
+        let mut expected = vec![0.0; 80];
+        whole.process(&input, &mut expected);
+
+        // Any blocks with lengths that are multiples of m give the same output
+        let mut blocks = Resampler::new(2, 3, 30, 24).unwrap();
+        let mut output = vec![0.0; 80];
+        let (mut start, mut out_start) = (0, 0);
+        for &length in [3, 27, 0, 6, 48, 36].iter() {
+            let out_length = blocks.output_length(length).unwrap();
+            blocks.process(
+                &input[start..start + length],
+                &mut output[out_start..out_start + out_length],
+            );
+            start += length;
+            out_start += out_length;
+        }
+        assert_eq!(start, input.len());
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn resampler_checks_arguments_and_lengths() {
+        assert_eq!(Resampler::new(4, 6, 30, 24).unwrap().factors(), (2, 3));
+        assert!(matches!(Resampler::new(0, 3, 30, 24), Err(Error::Argument)));
+        assert!(matches!(Resampler::new(2, 3, 31, 24), Err(Error::Argument)));
+        assert!(matches!(Resampler::new(2, 3, 30, 25), Err(Error::Length)));
+
+        let mut resampler = Resampler::new(2, 3, 30, 24).unwrap();
+        assert_eq!(resampler.output_length(10), None);
+        assert!(matches!(
+            resampler.try_process(&[0.0; 10], &mut [0.0; 6]),
+            Err(Error::Length)
+        ));
+        assert!(matches!(
+            resampler.try_process(&[0.0; 9], &mut [0.0; 5]),
+            Err(Error::LengthMismatch {
+                expected: 6,
+                got: 5
+            })
+        ));
+        assert!(resampler.try_process(&[0.0; 9], &mut [0.0; 6]).is_ok());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn owned_fir_filter_matches_fir_filter() {
+        let mut input = [0.0; 1000];
+        for (i, x) in input.iter_mut().enumerate() {
+            *x = ((i * 37 % 101) as f32 - 50.0) / 50.0;
+        }
+        let expected = fir_streaming(&input, 64, 1000);
+
+        let coeffs = vec![0.1, -0.2, 0.3, 0.5, 0.25, -0.125, 0.0625];
+        let filter = FirFilterBuilder::new(&coeffs, 64).build().unwrap();
+        // The builder copied the coefficients, and the filter can be moved
+        drop(coeffs);
+        let mut filter = alloc::boxed::Box::new(filter);
+        assert_eq!(filter.block_size(), 64);
+        let mut output = [0.0; 1000];
+        for (src, dst) in input.chunks(300).zip(output.chunks_mut(300)) {
+            filter.process(src, dst);
+        }
+        assert_eq!(output, expected);
+        assert!(matches!(
+            filter.try_process(&input[..3], &mut output[..2]),
+            Err(Error::LengthMismatch { .. })
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn fir_filter_builder_checks_arguments() {
+        assert!(matches!(
+            FirFilterBuilder::new(&[], 64).build(),
+            Err(Error::Argument)
+        ));
+        assert!(matches!(
+            FirFilterBuilder::new(&[1.0], 0).build(),
+            Err(Error::Argument)
+        ));
+        assert!(matches!(
+            FirFilterBuilder::new(&vec![0.0; 65536], 1).build(),
+            Err(Error::Argument)
+        ));
+    }
+
+    #[test]
+    fn try_process_checks_lengths() {
+        let input: [f32; 8] = core::array::from_fn(|i| i as f32 - 3.5);
+        let coeffs = [0.25, 0.5, 0.25, 0.1];
+
+        let mut state = [0.0; 2];
+        let mut filter = BiquadCascadeDF2TFilter::new(1, &LOWPASS, &mut state).unwrap();
+        let mut dst = [0.0; 7];
+        assert!(matches!(
+            filter.try_process(&input, &mut dst, 7),
+            Err(Error::LengthMismatch { .. })
+        ));
+        let mut dst = [0.0; 8];
+        assert!(matches!(
+            filter.try_process(&input, &mut dst, 9),
+            Err(Error::Length)
+        ));
+        assert!(filter.try_process(&input, &mut dst, 8).is_ok());
+        let mut state = [0.0; 2];
+        let mut expected = [0.0; 8];
+        BiquadCascadeDF2TFilter::new(1, &LOWPASS, &mut state)
+            .unwrap()
+            .process(&input, &mut expected, 8);
+        assert_eq!(dst, expected);
+
+        let mut state = [0.0; 11];
+        let mut filter = FirFilter::new(4, &coeffs, &mut state, 8).unwrap();
+        let mut dst = [0.0; 7];
+        assert!(matches!(
+            filter.try_process(&input, &mut dst, 7),
+            Err(Error::LengthMismatch { .. })
+        ));
+        let mut dst = [0.0; 8];
+        assert!(matches!(
+            filter.try_process(&input, &mut dst, 9),
+            Err(Error::Length)
+        ));
+        assert!(filter.try_process(&input, &mut dst, 8).is_ok());
+        let mut state = [0.0; 11];
+        let mut expected = [0.0; 8];
+        FirFilter::new(4, &coeffs, &mut state, 8)
+            .unwrap()
+            .process(&input, &mut expected, 8);
+        assert_eq!(dst, expected);
+
+        let mut state = [0.0; 11];
+        let mut filter = FirFilterDecimate::new(4, 2, &coeffs, &mut state, 8).unwrap();
+        let mut dst = [0.0; 7];
+        assert!(matches!(
+            filter.try_process(&input, &mut dst, 6),
+            Err(Error::LengthMismatch { .. })
+        ));
+        let mut dst = [0.0; 8];
+        assert!(matches!(
+            filter.try_process(&input, &mut dst, 10),
+            Err(Error::Length)
+        ));
+        assert!(matches!(
+            filter.try_process(&input, &mut dst, 7),
+            Err(Error::Length)
+        ));
+        assert!(filter.try_process(&input, &mut dst, 8).is_ok());
+        let mut state = [0.0; 11];
+        let mut expected = [0.0; 8];
+        FirFilterDecimate::new(4, 2, &coeffs, &mut state, 8)
+            .unwrap()
+            .process(&input, &mut expected, 8);
+        assert_eq!(dst[..4], expected[..4]);
+
+        let reflection = [0.5, -0.25];
+        let mut state = [0.0; 2];
+        let mut filter = FirLatticeFilter::new(2, &reflection, &mut state);
+        let mut dst = [0.0; 9];
+        assert!(matches!(
+            filter.try_process(&input, &mut dst),
+            Err(Error::LengthMismatch { .. })
+        ));
+        let mut dst = [0.0; 8];
+        assert!(filter.try_process(&input, &mut dst).is_ok());
+        let mut state = [0.0; 2];
+        let mut expected = [0.0; 8];
+        FirLatticeFilter::new(2, &reflection, &mut state).process(&input, &mut expected);
+        assert_eq!(dst, expected);
+
+        let coeffs = [
+            I1F31::from_num(0.25),
+            I1F31::ZERO,
+            I1F31::ZERO,
+            I1F31::ZERO,
+            I1F31::ZERO,
+        ];
+        let mut state = [I1F31::ZERO; 4];
+        let mut filter = BiquadCascadeDF1FilterQ31::new(1, &coeffs, &mut state, 1);
+        let src = [I1F31::from_num(0.5); 4];
+        let mut dst = [I1F31::ZERO; 3];
+        assert!(matches!(
+            filter.try_process(&src, &mut dst),
+            Err(Error::LengthMismatch { .. })
+        ));
+        let mut dst = [I1F31::ZERO; 4];
+        assert!(filter.try_process(&src, &mut dst).is_ok());
+        assert!(dst.iter().all(|&y| (y.to_num::<f32>() - 0.25).abs() < 1e-6));
+    }
+
+    #[test]
+    fn process_checked_limits_the_block_size() {
+        let input: [f32; 8] = core::array::from_fn(|i| i as f32 - 3.5);
+        let coeffs = [0.25, 0.5, 0.25, 0.1];
+
+        let mut state = [0.0; 2];
+        let mut expected = [0.0; 8];
+        BiquadCascadeDF2TFilter::new(1, &LOWPASS, &mut state)
+            .unwrap()
+            .process(&input, &mut expected, 8);
+        let mut state = [0.0; 2];
+        let mut filter = BiquadCascadeDF2TFilter::new(1, &LOWPASS, &mut state).unwrap();
+        let mut dst = [0.0; 7];
+        assert!(matches!(
+            filter.process_checked(&input, &mut dst, 8),
+            Err(Error::LengthMismatch { .. })
+        ));
+        let mut dst = [0.0; 8];
+        assert_eq!(filter.process_checked(&input, &mut dst, 100).unwrap(), 8);
+        assert_eq!(dst, expected);
+        let mut state = [0.0; 2];
+        let mut filter = BiquadCascadeDF2TFilter::new(1, &LOWPASS, &mut state).unwrap();
+        let mut dst = [0.0; 8];
+        assert_eq!(filter.process_checked(&input, &mut dst, 5).unwrap(), 5);
+        assert_eq!(dst[..5], expected[..5]);
+        assert_eq!(dst[5..], [0.0; 3]);
+
+        let mut state = [0.0; 11];
+        let mut expected = [0.0; 8];
+        FirFilter::new(4, &coeffs, &mut state, 8)
+            .unwrap()
+            .process(&input, &mut expected, 8);
+        let mut state = [0.0; 11];
+        let mut filter = FirFilter::new(4, &coeffs, &mut state, 8).unwrap();
+        let mut dst = [0.0; 9];
+        assert!(matches!(
+            filter.process_checked(&input, &mut dst, 8),
+            Err(Error::LengthMismatch { .. })
+        ));
+        let mut dst = [0.0; 8];
+        assert_eq!(filter.process_checked(&input, &mut dst, 9).unwrap(), 8);
+        assert_eq!(dst, expected);
+
+        let mut state = [0.0; 11];
+        let mut expected = [0.0; 8];
+        FirFilterDecimate::new(4, 2, &coeffs, &mut state, 8)
+            .unwrap()
+            .process(&input, &mut expected, 8);
+        let mut state = [0.0; 11];
+        let mut filter = FirFilterDecimate::new(4, 2, &coeffs, &mut state, 8).unwrap();
+        let mut dst = [0.0; 7];
+        assert!(matches!(
+            filter.process_checked(&input, &mut dst, 8),
+            Err(Error::LengthMismatch { .. })
+        ));
+        let mut dst = [0.0; 8];
+        assert_eq!(filter.process_checked(&input, &mut dst, 7).unwrap(), 6);
+        assert_eq!(dst[..3], expected[..3]);
+        assert_eq!(dst[3], 0.0);
+        let mut state = [0.0; 11];
+        let mut filter = FirFilterDecimate::new(4, 2, &coeffs, &mut state, 8).unwrap();
+        let mut dst = [0.0; 8];
+        assert_eq!(filter.process_checked(&input, &mut dst, 20).unwrap(), 8);
+        assert_eq!(dst[..4], expected[..4]);
+    }
+
+    fn chunk_signal() -> [f32; 32] {
+        core::array::from_fn(|i| 0.5 * ((i * 7 % 11) as f32 / 11.0 - 0.5))
+    }
+
+    #[test]
+    fn process_chunked_matches_single_process() {
+        let input = chunk_signal();
+        let coeffs = [0.25, 0.5, 0.25, 0.1];
+        for &chunk in &[1, 3, 5, 13, 32, 40] {
+            let mut state = [0.0; 4];
+            let mut expected = [0.0; 32];
+            BiquadCascadeDF2TFilter::new(2, &biquad_cascade(), &mut state)
+                .unwrap()
+                .process(&input, &mut expected, 32);
+            let mut state = [0.0; 4];
+            let mut dst = [0.0; 32];
+            BiquadCascadeDF2TFilter::new(2, &biquad_cascade(), &mut state)
+                .unwrap()
+                .process_chunked(&input, &mut dst, chunk);
+            assert_eq!(dst, expected, "biquad, chunk {}", chunk);
+
+            let mut state = [0.0; 35];
+            let mut expected = [0.0; 32];
+            FirFilter::new(4, &coeffs, &mut state, 32)
+                .unwrap()
+                .process(&input, &mut expected, 32);
+            let mut state = [0.0; 11];
+            let mut dst = [0.0; 32];
+            FirFilter::new(4, &coeffs, &mut state, 8)
+                .unwrap()
+                .process_chunked(&input, &mut dst, chunk);
+            assert_eq!(dst, expected, "FIR, chunk {}", chunk);
+
+            let mut state = [0.0; 3];
+            let mut expected = [0.0; 32];
+            FirLatticeFilter::new(3, &[0.5, -0.3, 0.2], &mut state).process(&input, &mut expected);
+            let mut state = [0.0; 3];
+            let mut dst = [0.0; 32];
+            FirLatticeFilter::new(3, &[0.5, -0.3, 0.2], &mut state)
+                .process_chunked(&input, &mut dst, chunk);
+            assert_eq!(dst, expected, "FIR lattice, chunk {}", chunk);
+
+            let (reflection, ladder) = ([0.25, 0.5], [0.1, 0.2, 1.0]);
+            let mut state = [0.0; 34];
+            let mut expected = [0.0; 32];
+            IirLatticeFilter::new(2, &reflection, &ladder, &mut state, 32)
+                .process(&input, &mut expected);
+            let mut state = [0.0; 6];
+            let mut dst = [0.0; 32];
+            IirLatticeFilter::new(2, &reflection, &ladder, &mut state, 4)
+                .process_chunked(&input, &mut dst, chunk);
+            assert_eq!(dst, expected, "IIR lattice, chunk {}", chunk);
+
+            let coeffs = [0.1, 0.2, 0.1, 0.25, -0.15].map(I1F31::from_num);
+            let src = input.map(I1F31::from_num);
+            let mut state = [I1F31::ZERO; 4];
+            let mut expected = [I1F31::ZERO; 32];
+            BiquadCascadeDF1FilterQ31::new(1, &coeffs, &mut state, 1).process(&src, &mut expected);
+            let mut state = [I1F31::ZERO; 4];
+            let mut dst = [I1F31::ZERO; 32];
+            BiquadCascadeDF1FilterQ31::new(1, &coeffs, &mut state, 1)
+                .process_chunked(&src, &mut dst, chunk);
+            assert_eq!(dst, expected, "Q31 biquad, chunk {}", chunk);
+
+            let coeffs = [0.1, 0.0, 0.2, 0.1, 0.25, -0.15].map(I1F15::from_num);
+            let src = input.map(I1F15::from_num);
+            let mut state = [I1F15::ZERO; 4];
+            let mut expected = [I1F15::ZERO; 32];
+            BiquadCascadeDF1FilterQ15::new(1, &coeffs, &mut state, 1).process(&src, &mut expected);
+            let mut state = [I1F15::ZERO; 4];
+            let mut dst = [I1F15::ZERO; 32];
+            BiquadCascadeDF1FilterQ15::new(1, &coeffs, &mut state, 1)
+                .process_chunked(&src, &mut dst, chunk);
+            assert_eq!(dst, expected, "Q15 biquad, chunk {}", chunk);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn process_chunked_rejects_zero_chunk() {
+        let mut state = [0.0; 2];
+        let mut filter = BiquadCascadeDF2TFilter::new(1, &LOWPASS, &mut state).unwrap();
+        filter.process_chunked(&[0.0; 4], &mut [0.0; 4], 0);
+    }
+
+    #[test]
+    fn process_sample_matches_block_processing() {
+        let input = chunk_signal();
+        let coeffs = biquad_cascade();
+        let mut state = [0.0; 4];
+        let mut expected = [0.0; 32];
+        BiquadCascadeDF2TFilter::new(2, &coeffs, &mut state)
+            .unwrap()
+            .process(&input, &mut expected, 32);
+
+        let mut state = [0.0; 4];
+        let mut filter = BiquadCascadeDF2TFilter::new(2, &coeffs, &mut state).unwrap();
+        let output: [f32; 32] = core::array::from_fn(|i| filter.process_sample(input[i]));
+        assert_eq!(output, expected);
+
+        // Blocks and single samples share the filter state
+        let mut state = [0.0; 4];
+        let mut filter = BiquadCascadeDF2TFilter::new(2, &coeffs, &mut state).unwrap();
+        let mut output = [0.0; 32];
+        filter.process(&input[..10], &mut output[..10], 10);
+        for i in 10..20 {
+            output[i] = filter.process_sample(input[i]);
+        }
+        filter.process(&input[20..], &mut output[20..], 12);
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn process_scalar_matches_block_processing() {
+        let input = chunk_signal();
+        for coeffs in &[LOWPASS, HIGHPASS] {
+            let mut state = [0.0; 2];
+            let mut expected = [0.0; 32];
+            BiquadCascadeDF2TFilter::new(1, coeffs, &mut state)
+                .unwrap()
+                .process(&input, &mut expected, 32);
+
+            let mut state = [0.0; 2];
+            let mut filter = BiquadCascadeDF2TFilter::new(1, coeffs, &mut state).unwrap();
+            let output: [f32; 32] = core::array::from_fn(|i| filter.process_scalar(input[i]));
+            assert_eq!(output, expected);
+        }
+    }
+
+    #[test]
+    fn try_conv_checks_output_length() {
+        let a = [1.0, 2.0, 3.0];
+        let b = [1.0, -1.0];
+        let mut dst = [0.0; 4];
+        assert!(try_conv_f32(&a, &b, &mut dst).is_ok());
+        assert_eq!(dst, [1.0, 1.0, 1.0, -3.0]);
+        assert!(matches!(
+            try_conv_f32(&a, &b, &mut [0.0; 3]),
+            Err(Error::LengthMismatch { .. })
+        ));
+        assert!(matches!(
+            try_conv_f32(&a, &b, &mut [0.0; 5]),
+            Err(Error::LengthMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn conv_of_empty_and_single_sample_inputs() {
+        let mut dst: [f32; 0] = [];
+        conv_f32(&[], &[1.0, 2.0], &mut dst);
+        conv_f32(&[1.0, 2.0], &[], &mut dst);
+        conv_f32(&[], &[], &mut dst);
+        assert!(try_conv_f32(&[], &[1.0], &mut dst).is_ok());
+        assert!(matches!(
+            try_conv_f32(&[], &[1.0, 2.0], &mut [0.0; 1]),
+            Err(Error::LengthMismatch { .. })
+        ));
+
+        let mut dst = [0.0; 1];
+        conv_f32(&[2.0], &[-1.5], &mut dst);
+        assert_eq!(dst, [-3.0]);
+
+        // Convolution with a single sample scales the other sequence
+        let mut dst = [0.0; 3];
+        conv_f32(&[0.5], &[1.0, 2.0, -4.0], &mut dst);
+        assert_eq!(dst, [0.5, 1.0, -2.0]);
+        conv_f32(&[1.0, 2.0, -4.0], &[0.5], &mut dst);
+        assert_eq!(dst, [0.5, 1.0, -2.0]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn estimate_lag_of_a_delayed_signal() {
+        let reference = [0.0, 1.0, -0.5, 0.25, 2.0, -1.0, 0.5, 0.0];
+        for delay in 0..6 {
+            let mut signal = [0.0; 12];
+            for (dst, &x) in signal[delay..].iter_mut().zip(&reference) {
+                *dst = x;
+            }
+            let (lag, peak) = estimate_lag_f32(&reference, &signal);
+            assert_eq!(lag, delay);
+            assert!((peak - 6.5625).abs() < 1e-5, "{}", peak);
+        }
+
+        // A scaled copy has the same lag, and a leading signal gives a lag of 0
+        let signal = [0.0, 0.0, 0.0, 1.5, -0.75, 0.375, 3.0, -1.5, 0.75, 0.0];
+        assert_eq!(estimate_lag_f32(&reference, &signal).0, 2);
+        assert_eq!(estimate_lag_f32(&reference, &reference[3..]).0, 0);
+    }
+
+    #[test]
+    fn correlate_lags_are_centered() {
+        let mut dst = [0.0; 5];
+        correlate_f32(&[1.0, 2.0, 3.0], &[1.0, -1.0], &mut dst);
+        // dst[k] = sum(a[n + k - 2] * b[n])
+        assert_eq!(dst, [0.0, -1.0, -1.0, -1.0, 3.0]);
+    }
+
+    #[test]
+    fn complex_biquad_filters_both_parts() {
+        // Longer than the 64-sample chunks of ComplexBiquad, so the state carries over
+        let input: [Complex32; 100] = core::array::from_fn(|i| {
+            Complex32::new((i % 7) as f32 - 3.0, 0.5 * ((i * 5 % 9) as f32 - 4.0))
+        });
+        let coeffs = biquad_cascade();
+        let mut real_state = [0.0; 4];
+        let mut imag_state = [0.0; 4];
+        let mut filter = ComplexBiquad::new(2, &coeffs, &mut real_state, &mut imag_state).unwrap();
+        let mut output = [Complex32::new(0.0, 0.0); 100];
+        filter.process(&input, &mut output);
+
+        for (part, get) in [|z: &Complex32| z.re, |z: &Complex32| z.im]
+            .iter()
+            .enumerate()
+        {
+            let src: [f32; 100] = core::array::from_fn(|i| get(&input[i]));
+            let mut state = [0.0; 4];
+            let mut expected = [0.0; 100];
+            BiquadCascadeDF2TFilter::new(2, &coeffs, &mut state)
+                .unwrap()
+                .process(&src, &mut expected, 100);
+            for (i, (y, expected)) in output.iter().map(get).zip(&expected).enumerate() {
+                assert!(
+                    (y - expected).abs() < 1e-6,
+                    "part {}, sample {}: {} != {}",
+                    part,
+                    i,
+                    y,
+                    expected
+                );
+            }
+        }
+
+        assert!(matches!(
+            filter.try_process(&input, &mut output[..99]),
+            Err(Error::LengthMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn dc_blocker_step_response_decays() {
+        let mut blocker = DcBlocker::new(0.9).unwrap();
+        let mut output = [0.0; 64];
+        blocker.process(&[1.0; 64], &mut output);
+        // y[n] = x[n] - x[n-1] + r y[n-1] gives r^n for a unit step
+        let mut expected = 1.0f32;
+        for (n, &y) in output.iter().enumerate() {
+            assert!(
+                (y - expected).abs() < 1e-5,
+                "sample {}: {} != {}",
+                n,
+                y,
+                expected
+            );
+            expected *= 0.9;
+        }
+
+        // After reset, the step response starts again
+        blocker.reset();
+        let mut output = [0.0; 2];
+        blocker.process(&[1.0; 2], &mut output);
+        assert!((output[0] - 1.0).abs() < 1e-6 && (output[1] - 0.9).abs() < 1e-6);
+    }
+
+    #[test]
+    fn dc_blocker_removes_offset_and_keeps_tone() {
+        let mut blocker = DcBlocker::from_cutoff(10.0, 8000.0).unwrap();
+        assert!((blocker.pole() - (1.0 - 2.0 * PI * 10.0 / 8000.0)).abs() < 1e-6);
+        let input: [f32; 8000] =
+            core::array::from_fn(|n| 0.75 + (2.0 * PI * 1000.0 * n as f32 / 8000.0).sin());
+        let mut output = [0.0; 8000];
+        blocker.process(&input, &mut output);
+        let tail = &output[4000..];
+        let mean = tail.iter().sum::<f32>() / tail.len() as f32;
+        let power = tail.iter().map(|y| y * y).sum::<f32>() / tail.len() as f32;
+        assert!(mean.abs() < 1e-3, "{}", mean);
+        assert!((power - 0.5).abs() < 0.01, "{}", power);
+
+        assert!(matches!(
+            blocker.try_process(&input, &mut output[1..]),
+            Err(Error::LengthMismatch { .. })
+        ));
+        assert!(matches!(DcBlocker::new(1.0), Err(Error::Argument)));
+        assert!(matches!(DcBlocker::new(-0.1), Err(Error::Argument)));
+        assert!(matches!(
+            DcBlocker::from_cutoff(0.0, 8000.0),
+            Err(Error::Argument)
+        ));
+        assert!(matches!(
+            DcBlocker::from_cutoff(2000.0, 8000.0),
+            Err(Error::Argument)
+        ));
+    }
+
+    #[test]
+    fn remove_dc_step_response() {
+        let mut state = DcState::new(0.25);
+        let mut output = [0.0; 16];
+        remove_dc_f32(&[2.0; 16], &mut output, &mut state);
+        // The offset estimate is 2 (1 - 0.75^(n + 1)), so the output is 2 * 0.75^(n + 1)
+        let mut expected = 2.0f32;
+        for (n, &y) in output.iter().enumerate() {
+            expected *= 0.75;
+            assert!(
+                (y - expected).abs() < 1e-6,
+                "sample {}: {} != {}",
+                n,
+                y,
+                expected
+            );
+        }
+        assert!((state.offset - (2.0 - expected)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn remove_dc_removes_offset_across_blocks() {
+        let input: [f32; 4000] =
+            core::array::from_fn(|n| -0.5 + 0.25 * (2.0 * PI * n as f32 / 16.0).sin());
+        let mut whole_state = DcState::new(0.01);
+        let mut whole = [0.0; 4000];
+        remove_dc_f32(&input, &mut whole, &mut whole_state);
+        let tail = &whole[2000..];
+        let mean = tail.iter().sum::<f32>() / tail.len() as f32;
+        assert!(mean.abs() < 1e-3, "{}", mean);
+        assert!(
+            (whole_state.offset + 0.5).abs() < 0.01,
+            "{}",
+            whole_state.offset
+        );
+
+        // A copied state resumes from the same checkpoint
+        let mut state = DcState::new(0.01);
+        let mut blocks = [0.0; 4000];
+        remove_dc_f32(&input[..1000], &mut blocks[..1000], &mut state);
+        let checkpoint = state;
+        remove_dc_f32(&input[1000..], &mut blocks[1000..], &mut state);
+        assert_eq!(blocks, whole);
+        assert_eq!(state, whole_state);
+        let mut state = checkpoint;
+        remove_dc_f32(&input[1000..], &mut blocks[1000..], &mut state);
+        assert_eq!(state, whole_state);
+    }
+
+    #[test]
+    fn exponential_moving_average_step_response() {
+        let mut average = ExponentialMovingAverage::new(0.1).unwrap();
+        assert_eq!(average.alpha(), 0.1);
+        let mut output = [0.0; 50];
+        average.process(&[1.0; 50], &mut output);
+        let mut expected = 0.0f32;
+        for (n, &y) in output.iter().enumerate() {
+            expected = 1.0 - 0.9 * (1.0 - expected);
+            assert!(
+                (y - expected).abs() < 1e-6,
+                "sample {}: {} != {}",
+                n,
+                y,
+                expected
+            );
+        }
+        // The output covers 63% of the step in about 1 / alpha samples
+        assert!(output[9] > 0.63 && output[8] < 0.63);
+        assert_eq!(average.value(), output[49]);
+
+        // Samples and blocks share the average
+        average.reset(0.5);
+        assert_eq!(average.process_sample(1.5), 0.6);
+        assert!(matches!(
+            average.try_process(&[0.0; 2], &mut [0.0; 3]),
+            Err(Error::LengthMismatch { .. })
+        ));
+
+        let mut passthrough = ExponentialMovingAverage::new(1.0).unwrap();
+        assert_eq!(passthrough.process_sample(-2.5), -2.5);
+        for &alpha in &[0.0, -0.5, 1.5, f32::NAN] {
+            assert!(matches!(
+                ExponentialMovingAverage::new(alpha),
+                Err(Error::Argument)
+            ));
+        }
+    }
+
+    fn power(signal: &[f32]) -> f32 {
+        signal.iter().map(|x| x * x).sum::<f32>() / signal.len() as f32
+    }
+
+    #[test]
+    fn adaptive_notch_attenuates_a_tone() {
+        let input: [f32; 8000] =
+            core::array::from_fn(|n| (2.0 * PI * 1000.0 * n as f32 / 8000.0).sin());
+
+        // Without adaptation, a notch at the tone frequency removes it
+        let mut notch = AdaptiveNotch::new(8000.0, 1000.0, 0.95, 0.0);
+        assert!((notch.frequency() - 1000.0).abs() < 0.1);
+        let mut output = [0.0; 8000];
+        notch.process(&input, &mut output);
+        assert!(
+            power(&output[200..]) < 1e-6 * power(&input),
+            "{}",
+            power(&output[200..])
+        );
+
+        // Starting away from the tone, the notch moves to it
+        let mut notch = AdaptiveNotch::new(8000.0, 900.0, 0.95, 0.01);
+        notch.process(&input, &mut output);
+        assert!(
+            (notch.frequency() - 1000.0).abs() < 5.0,
+            "{}",
+            notch.frequency()
+        );
+        assert!(
+            power(&output[6000..]) < 1e-3 * power(&input),
+            "{}",
+            power(&output[6000..])
+        );
+
+        assert!(matches!(
+            notch.try_process(&input, &mut output[1..]),
+            Err(Error::LengthMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn oversampler_passes_low_frequencies() {
+        let mut coeffs = [0.0; 32];
+        fir_design::lowpass(0.2, 1.0, crate::window::Window::Hamming, &mut coeffs).unwrap();
+        let calls = core::cell::Cell::new(0);
+        let mut state = [0.0; 48];
+        let mut oversampler = Oversampler::new(2, &coeffs, &mut state, |x| {
+            calls.set(calls.get() + 1);
+            x
+        })
+        .unwrap();
+
+        // A constant passes with a gain of 1 once the filters are full
+        let mut output = [0.0; 64];
+        oversampler.process(&[1.0; 64], &mut output);
+        assert!(
+            output[32..].iter().all(|y| (y - 1.0).abs() < 1e-3),
+            "{:?}",
+            &output[32..]
+        );
+        assert_eq!(calls.get(), 128);
+
+        // A tone well below the cutoff keeps its power
+        let input: [f32; 512] = core::array::from_fn(|n| (2.0 * PI * n as f32 / 32.0).sin());
+        let mut output = [0.0; 512];
+        oversampler.process(&input, &mut output);
+        assert!(
+            (power(&output[64..]) - 0.5).abs() < 0.01,
+            "{}",
+            power(&output[64..])
+        );
+
+        assert!(matches!(
+            oversampler.try_process(&input, &mut output[1..]),
+            Err(Error::LengthMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn oversampler_runs_the_process_on_upsampled_samples() {
+        let mut coeffs = [0.0; 32];
+        fir_design::lowpass(0.2, 1.0, crate::window::Window::Hamming, &mut coeffs).unwrap();
+        let mut state = [0.0; 48];
+        let mut oversampler = Oversampler::new(2, &coeffs, &mut state, |x: f32| 0.5 * x).unwrap();
+        let mut output = [0.0; 64];
+        oversampler.process(&[1.0; 64], &mut output);
+        assert!((output[63] - 0.5).abs() < 1e-3, "{}", output[63]);
+
+        assert!(matches!(
+            Oversampler::new(0, &coeffs, &mut [0.0; 32], |x| x),
+            Err(Error::Argument)
+        ));
+        assert!(matches!(
+            Oversampler::new(3, &coeffs, &mut [0.0; 42], |x| x),
+            Err(Error::Argument)
+        ));
+        assert!(matches!(
+            Oversampler::new(2, &[], &mut [], |x| x),
+            Err(Error::Argument)
+        ));
+    }
+
+    /// Reconstructs input through a QMF bank in blocks of 16 samples
+    fn qmf_round_trip(prototype: &[f32], input: &[f32; 256]) -> [f32; 256] {
+        let mut state = [0.0; 48];
+        let mut bank = QmfBank::new(prototype, &mut state[..3 * prototype.len()]).unwrap();
+        let mut output = [0.0; 256];
+        for (src, dst) in input.chunks(16).zip(output.chunks_mut(16)) {
+            let mut low = [0.0; 8];
+            let mut high = [0.0; 8];
+            bank.analyze(src, &mut low, &mut high);
+            bank.synthesize(&low, &high, dst);
+        }
+        output
+    }
+
+    #[test]
+    fn qmf_bank_reconstructs_the_input() {
+        let input: [f32; 256] = core::array::from_fn(|n| ((n * 37 % 23) as f32 - 11.0) / 11.0);
+
+        // The Haar prototype reconstructs exactly, delayed by one sample
+        let output = qmf_round_trip(&[0.5, 0.5], &input);
+        assert_eq!(output[0], 0.0);
+        assert_close(&output[1..], &input[..255]);
+
+        // The Johnston 16A prototype reconstructs nearly perfectly, delayed by 15 samples
+        let half = [
+            0.002898163,
+            -0.009972252,
+            -0.001920936,
+            0.03596853,
+            -0.01611869,
+            -0.09530234,
+            0.1067987,
+            0.4773469,
+        ];
+        let mut prototype = [0.0; 16];
+        for (i, &coeff) in half.iter().enumerate() {
+            prototype[i] = coeff;
+            prototype[15 - i] = coeff;
+        }
+        let output = qmf_round_trip(&prototype, &input);
+        let error: [f32; 241] = core::array::from_fn(|n| output[n + 15] - input[n]);
+        assert!(power(&error) < 1e-4 * power(&input), "{}", power(&error));
+    }
+
+    #[test]
+    fn qmf_bank_splits_low_and_high_frequencies() {
+        let mut state = [0.0; 6];
+        let mut bank = QmfBank::new(&[0.5, 0.5], &mut state).unwrap();
+        let mut low = [0.0; 4];
+        let mut high = [0.0; 4];
+        bank.analyze(&[1.0; 8], &mut low, &mut high);
+        assert_eq!(low, [1.0; 4]);
+        assert_eq!(high, [0.0; 4]);
+        bank.analyze(
+            &[1.0, -1.0, 1.0, -1.0, 1.0, -1.0, 1.0, -1.0],
+            &mut low,
+            &mut high,
+        );
+        // The high band filter is (0.5, -0.5), applied to x[2n + 1] and x[2n]
+        assert_eq!(low, [0.0; 4]);
+        assert_eq!(high, [-1.0; 4]);
+
+        assert!(matches!(
+            bank.try_analyze(&[0.0; 8], &mut low, &mut high[..3]),
+            Err(Error::LengthMismatch { .. })
+        ));
+        assert!(matches!(
+            bank.try_synthesize(&low, &high, &mut [0.0; 6]),
+            Err(Error::LengthMismatch { .. })
+        ));
+        assert!(matches!(
+            QmfBank::new(&[0.5, 0.25, 0.5], &mut [0.0; 9]),
+            Err(Error::Argument)
+        ));
+        assert!(matches!(QmfBank::new(&[], &mut []), Err(Error::Argument)));
+    }
+
+    #[test]
+    fn de_emphasis_inverts_pre_emphasis() {
+        let input: [f32; 64] = core::array::from_fn(|n| ((n * 13 % 17) as f32 - 8.0) / 8.0);
+        let mut emphasized = [0.0; 64];
+        let mut restored = [0.0; 64];
+        let mut pre_state = 0.0;
+        let mut de_state = 0.0;
+        // Blocks of different sizes, so the states carry over
+        for (start, end) in [(0, 10), (10, 11), (11, 40), (40, 64)].iter().copied() {
+            pre_emphasis_f32(
+                &input[start..end],
+                &mut emphasized[start..end],
+                0.95,
+                &mut pre_state,
+            );
+            de_emphasis_f32(
+                &emphasized[start..end],
+                &mut restored[start..end],
+                0.95,
+                &mut de_state,
+            );
+        }
+        assert_eq!(pre_state, input[63]);
+        assert_eq!(de_state, restored[63]);
+        for (n, (y, x)) in restored.iter().zip(&input).enumerate() {
+            assert!((y - x).abs() < 1e-5, "sample {}: {} != {}", n, y, x);
+        }
+    }
+
+    #[test]
+    fn pre_emphasis_known_values() {
+        let mut state = 0.5;
+        let mut dst = [0.0; 3];
+        pre_emphasis_f32(&[1.0, 1.0, -2.0], &mut dst, 0.5, &mut state);
+        assert_eq!(dst, [0.75, 0.5, -2.5]);
+        let mut state = 0.0;
+        de_emphasis_f32(&[1.0, 0.0, 0.0], &mut dst, 0.5, &mut state);
+        assert_eq!(dst, [1.0, 0.5, 0.25]);
+    }
+
+    #[test]
+    fn static_fir_filter_matches_fir_filter() {
+        const FILTER: StaticFirFilter<4, 11> = StaticFirFilter::new([0.1, 0.25, 0.5, 0.15]);
+        let input = chunk_signal();
+        let coeffs = [0.1, 0.25, 0.5, 0.15];
+        let mut state = [0.0; 35];
+        let mut expected = [0.0; 32];
+        FirFilter::new(4, &coeffs, &mut state, 32)
+            .unwrap()
+            .process(&input, &mut expected, 32);
+
+        // The filter processes blocks of 8 samples, and the state carries over between calls
+        let mut filter = FILTER;
+        let mut output = [0.0; 32];
+        filter.process(&input[..13], &mut output[..13]);
+        filter.process(&input[13..], &mut output[13..]);
+        assert_eq!(output, expected);
+
+        filter.reset();
+        let mut output = [0.0; 32];
+        filter.try_process(&input, &mut output).unwrap();
+        assert_eq!(output, expected);
+        assert!(matches!(
+            filter.try_process(&input, &mut output[1..]),
+            Err(Error::LengthMismatch { .. })
+        ));
+    }
+
+    #[test]
+    #[should_panic]
+    fn static_fir_filter_rejects_short_state() {
+        StaticFirFilter::<4, 3>::new([0.25; 4]);
+    }
+}
@@ -0,0 +1,156 @@
+//! Coefficient design for biquad filters
+//!
+//! These functions implement the formulas from Robert Bristow-Johnson's Audio EQ Cookbook, and
+//! return the coefficients of one stage of a `BiquadCascadeDF2TFilter`.
+//!
+//! # Sign convention
+//!
+//! The cookbook, like scipy and most textbooks, writes the transfer function of a stage as
+//! `(b0 + b1 z^-1 + b2 z^-2) / (a0 + a1 z^-1 + a2 z^-2)`, so the difference equation subtracts
+//! the feedback terms: `a0 y[n] = b0 x[n] + b1 x[n-1] + b2 x[n-2] - a1 y[n-1] - a2 y[n-2]`.
+//! CMSIS-DSP adds the feedback terms instead, so its coefficients `{b0, b1, b2, a1, a2}` contain
+//! the negated feedback coefficients. These functions normalize by a0 and return
+//! `[b0 / a0, b1 / a0, b2 / a0, -a1 / a0, -a2 / a0]`, which can be passed to
+//! `BiquadCascadeDF2TFilter::new` directly. Coefficients from other sources must be negated in
+//! the same way.
+//!
+//! All frequencies are in the same units as the sample rate fs, and must be between 0 and fs / 2.
+//! q is the quality factor. A q of `1 / sqrt(2)` gives a maximally flat (Butterworth) lowpass
+//! or highpass response.
+
+use core::f32::consts::{LN_10, PI};
+
+use crate::fast_math::{cos_f32, exp_f32, sin_f32};
+
+/// Designs a second-order lowpass filter with cutoff frequency fc
+pub fn lowpass(fc: f32, fs: f32, q: f32) -> [f32; 5] {
+    let (cos_w0, alpha) = intermediates(fc, fs, q);
+    normalize(
+        [(1.0 - cos_w0) / 2.0, 1.0 - cos_w0, (1.0 - cos_w0) / 2.0],
+        [1.0 + alpha, -2.0 * cos_w0, 1.0 - alpha],
+    )
+}
+
+/// Designs a second-order highpass filter with cutoff frequency fc
+pub fn highpass(fc: f32, fs: f32, q: f32) -> [f32; 5] {
+    let (cos_w0, alpha) = intermediates(fc, fs, q);
+    normalize(
+        [(1.0 + cos_w0) / 2.0, -(1.0 + cos_w0), (1.0 + cos_w0) / 2.0],
+        [1.0 + alpha, -2.0 * cos_w0, 1.0 - alpha],
+    )
+}
+
+/// Designs a second-order bandpass filter with center frequency fc and a peak gain of 0 dB
+pub fn bandpass(fc: f32, fs: f32, q: f32) -> [f32; 5] {
+    let (cos_w0, alpha) = intermediates(fc, fs, q);
+    normalize(
+        [alpha, 0.0, -alpha],
+        [1.0 + alpha, -2.0 * cos_w0, 1.0 - alpha],
+    )
+}
+
+/// Designs a notch filter that removes frequency fc
+pub fn notch(fc: f32, fs: f32, q: f32) -> [f32; 5] {
+    let (cos_w0, alpha) = intermediates(fc, fs, q);
+    normalize(
+        [1.0, -2.0 * cos_w0, 1.0],
+        [1.0 + alpha, -2.0 * cos_w0, 1.0 - alpha],
+    )
+}
+
+/// Designs a peaking equalizer filter that changes the gain around frequency fc by gain_db
+/// decibels, and leaves the gain at other frequencies at 0 dB
+pub fn peaking(fc: f32, fs: f32, q: f32, gain_db: f32) -> [f32; 5] {
+    let (cos_w0, alpha) = intermediates(fc, fs, q);
+    // Square root of the linear gain, 10^(gain_db / 40)
+    let a = exp_f32(gain_db / 40.0 * LN_10);
+    normalize(
+        [1.0 + alpha * a, -2.0 * cos_w0, 1.0 - alpha * a],
+        [1.0 + alpha / a, -2.0 * cos_w0, 1.0 - alpha / a],
+    )
+}
+
+/// Returns cos(w0) and alpha for a center or cutoff frequency
+fn intermediates(fc: f32, fs: f32, q: f32) -> (f32, f32) {
+    let w0 = 2.0 * PI * fc / fs;
+    (cos_f32(w0), sin_f32(w0) / (2.0 * q))
+}
+
+/// Converts cookbook coefficients into the CMSIS-DSP format
+fn normalize(b: [f32; 3], a: [f32; 3]) -> [f32; 5] {
+    let a0 = a[0];
+    [b[0] / a0, b[1] / a0, b[2] / a0, -a[1] / a0, -a[2] / a0]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the gain of a stage with CMSIS-DSP coefficients at frequency f
+    fn gain(coeffs: [f32; 5], f: f32, fs: f32) -> f32 {
+        let w = 2.0 * core::f64::consts::PI * f64::from(f) / f64::from(fs);
+        // The numerator and denominator at z = e^(jw), as (re, im)
+        let evaluate = |c0: f64, c1: f64, c2: f64| {
+            (
+                c0 + c1 * w.cos() + c2 * (2.0 * w).cos(),
+                -c1 * w.sin() - c2 * (2.0 * w).sin(),
+            )
+        };
+        let c = coeffs.map(f64::from);
+        let (num_re, num_im) = evaluate(c[0], c[1], c[2]);
+        let (den_re, den_im) = evaluate(1.0, -c[3], -c[4]);
+        ((num_re * num_re + num_im * num_im) / (den_re * den_re + den_im * den_im)).sqrt() as f32
+    }
+
+    const FS: f32 = 48000.0;
+    const BUTTERWORTH_Q: f32 = core::f32::consts::FRAC_1_SQRT_2;
+
+    #[test]
+    fn lowpass_and_highpass() {
+        let lowpass = lowpass(1000.0, FS, BUTTERWORTH_Q);
+        assert!((gain(lowpass, 0.0, FS) - 1.0).abs() < 1e-4);
+        assert!((gain(lowpass, 1000.0, FS) - BUTTERWORTH_Q).abs() < 1e-4);
+        assert!(gain(lowpass, FS / 2.0, FS) < 1e-4);
+
+        let highpass = highpass(1000.0, FS, BUTTERWORTH_Q);
+        assert!(gain(highpass, 0.0, FS) < 1e-4);
+        assert!((gain(highpass, 1000.0, FS) - BUTTERWORTH_Q).abs() < 1e-4);
+        assert!((gain(highpass, FS / 2.0, FS) - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn bandpass_and_notch() {
+        let bandpass = bandpass(2000.0, FS, 2.0);
+        assert!((gain(bandpass, 2000.0, FS) - 1.0).abs() < 1e-4);
+        assert!(gain(bandpass, 0.0, FS) < 1e-4);
+        assert!(gain(bandpass, FS / 2.0, FS) < 1e-4);
+
+        let notch = notch(2000.0, FS, 2.0);
+        assert!(gain(notch, 2000.0, FS) < 1e-3);
+        assert!((gain(notch, 0.0, FS) - 1.0).abs() < 1e-4);
+        assert!((gain(notch, FS / 2.0, FS) - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn peaking_gain() {
+        for &gain_db in &[6.0, -12.0] {
+            let peaking = peaking(3000.0, FS, 1.0, gain_db);
+            let expected = 10.0f32.powf(gain_db / 20.0);
+            assert!((gain(peaking, 3000.0, FS) / expected - 1.0).abs() < 1e-3);
+            assert!((gain(peaking, 0.0, FS) - 1.0).abs() < 1e-4);
+            assert!((gain(peaking, FS / 2.0, FS) - 1.0).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn feedback_coefficients_are_negated() {
+        // With w0 = pi / 2, cos(w0) = 0 and alpha = 1 / (2 q)
+        let [_, _, _, a1, a2] = lowpass(1.0, 4.0, 0.5);
+        assert!(a1.abs() < 1e-6);
+        assert!(a2.abs() < 1e-6);
+        // a0 = 1.5, a2 = 0.5
+        let [_, _, _, a1, a2] = lowpass(1.0, 4.0, 1.0);
+        assert!(a1.abs() < 1e-6);
+        assert!((a2 + 1.0 / 3.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,226 @@
+//! Coefficient design for FIR filters with the windowed-sinc method
+//!
+//! Each function fills a slice with the coefficients of a filter, where the number of taps is
+//! the length of the slice. The ideal (infinitely long) impulse response is truncated to the
+//! number of taps and multiplied by a window, which trades the width of the transition band for
+//! stopband attenuation. For example, `Window::Hamming` gives about 53 dB of attenuation and
+//! `Window::BlackmanHarris92dB` about 90 dB, with a wider transition band.
+//!
+//! The windowed-sinc coefficients are symmetric, so the filters have linear phase and are the
+//! same in time-reversed order: the slices can be passed to `FirFilter::new` and the other FIR
+//! filter types directly. The group delay is `(num_taps - 1) / 2` samples. The number of taps
+//! should be odd, so that the delay is a whole number of samples. A highpass filter requires an
+//! odd number of taps, because an even-length symmetric filter always has a zero at fs / 2.
+//!
+//! Frequencies are in the same units as the sample rate fs, and must be between 0 and fs / 2.
+//!
+//! Each function normalizes the gain of the filter at one frequency, and returns
+//! `Error::Argument` if the gain at that frequency is zero. This happens when the window is zero
+//! at every tap, for example with one or two taps and `Window::Hanning`, or when the passband is
+//! empty.
+
+use core::f32::consts::PI;
+
+use crate::fast_math::{cos_f32, sin_f32};
+use crate::window::Window;
+use crate::{Error, Result};
+
+/// Designs a lowpass filter with cutoff frequency fc and a gain of exactly 1 at DC
+///
+/// This function returns `Error::Length` if coeffs is empty, or `Error::Argument` if the
+/// filter has no gain at DC.
+pub fn lowpass(fc: f32, fs: f32, window: Window, coeffs: &mut [f32]) -> Result<()> {
+    if coeffs.is_empty() {
+        return Err(Error::Length);
+    }
+    windowed_sinc(coeffs, window, |t| ideal_lowpass(fc / fs, t));
+    let gain: f32 = coeffs.iter().sum();
+    normalize(coeffs, gain)
+}
+
+/// Designs a highpass filter with cutoff frequency fc and a gain of exactly 1 at fs / 2
+///
+/// This function returns `Error::Length` if the number of taps is even, or `Error::Argument` if
+/// the filter has no gain at fs / 2.
+pub fn highpass(fc: f32, fs: f32, window: Window, coeffs: &mut [f32]) -> Result<()> {
+    if coeffs.len().is_multiple_of(2) {
+        return Err(Error::Length);
+    }
+    // Spectral inversion of a lowpass filter: delta[n - center] - lowpass[n]. Both have a gain
+    // of 1 at DC, so the highpass filter has a gain of 0 at DC.
+    lowpass(fc, fs, window, coeffs)?;
+    for coeff in coeffs.iter_mut() {
+        *coeff = -*coeff;
+    }
+    coeffs[coeffs.len() / 2] += 1.0;
+    // Normalize the gain at fs / 2, where the frequency response is sum((-1)^n h[n])
+    let gain: f32 = coeffs
+        .iter()
+        .enumerate()
+        .map(|(i, coeff)| if i.is_multiple_of(2) { *coeff } else { -*coeff })
+        .sum();
+    normalize(coeffs, gain.abs())
+}
+
+/// Designs a bandpass filter that passes frequencies between f_low and f_high, with a gain of
+/// exactly 1 at the center frequency `(f_low + f_high) / 2`
+///
+/// This function returns `Error::Length` if coeffs is empty, or `Error::Argument` if the
+/// filter has no gain at the center frequency.
+pub fn bandpass(
+    f_low: f32,
+    f_high: f32,
+    fs: f32,
+    window: Window,
+    coeffs: &mut [f32],
+) -> Result<()> {
+    if coeffs.is_empty() {
+        return Err(Error::Length);
+    }
+    windowed_sinc(coeffs, window, |t| {
+        ideal_lowpass(f_high / fs, t) - ideal_lowpass(f_low / fs, t)
+    });
+    let center = (f_low + f_high) / (2.0 * fs);
+    let offset = (coeffs.len() as f32 - 1.0) / 2.0;
+    let gain: f32 = coeffs
+        .iter()
+        .enumerate()
+        .map(|(i, coeff)| coeff * cos_f32(2.0 * PI * center * (i as f32 - offset)))
+        .sum();
+    normalize(coeffs, gain)
+}
+
+/// Fills coeffs with a symmetric window multiplied by an impulse response, which is evaluated
+/// at times relative to the center of the filter
+fn windowed_sinc<F>(coeffs: &mut [f32], window: Window, impulse: F)
+where
+    F: Fn(f32) -> f32,
+{
+    let length = coeffs.len();
+    if length == 0 {
+        return;
+    }
+    // The windows are periodic, so an (n - 1)-point periodic window with its first point
+    // repeated at the end is an n-point symmetric window
+    window.fill(&mut coeffs[..length - 1]);
+    coeffs[length - 1] = if length == 1 { 1.0 } else { coeffs[0] };
+    let offset = (length as f32 - 1.0) / 2.0;
+    for (i, coeff) in coeffs.iter_mut().enumerate() {
+        *coeff *= impulse(i as f32 - offset);
+    }
+}
+
+/// Returns the impulse response of an ideal lowpass filter at time t (in samples), where fc is
+/// the cutoff frequency divided by the sample rate
+fn ideal_lowpass(fc: f32, t: f32) -> f32 {
+    let x = 2.0 * fc * t;
+    if x == 0.0 {
+        2.0 * fc
+    } else {
+        sin_f32(PI * x) / (PI * t)
+    }
+}
+
+/// Divides each coefficient by gain, or returns `Error::Argument` if gain is zero or not finite
+fn normalize(coeffs: &mut [f32], gain: f32) -> Result<()> {
+    if gain == 0.0 || !gain.is_finite() {
+        return Err(Error::Argument);
+    }
+    for coeff in coeffs.iter_mut() {
+        *coeff /= gain;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the gain of a filter with real, symmetric coefficients at frequency f / fs
+    fn gain_at(coeffs: &[f32], f: f32) -> f32 {
+        let (re, im) = coeffs
+            .iter()
+            .enumerate()
+            .fold((0.0, 0.0), |(re, im), (i, coeff)| {
+                let phase = 2.0 * PI * f * i as f32;
+                (re + coeff * phase.cos(), im - coeff * phase.sin())
+            });
+        (re * re + im * im).sqrt()
+    }
+
+    fn assert_symmetric(coeffs: &[f32]) {
+        for (a, b) in coeffs.iter().zip(coeffs.iter().rev()) {
+            assert!((a - b).abs() < 1e-6, "{:?}", coeffs);
+        }
+    }
+
+    #[test]
+    fn lowpass_gains() {
+        let mut coeffs = [0.0; 31];
+        lowpass(1000.0, 8000.0, Window::Hamming, &mut coeffs).unwrap();
+        assert_symmetric(&coeffs);
+        assert!((coeffs.iter().sum::<f32>() - 1.0).abs() < 1e-6);
+        assert!((gain_at(&coeffs, 0.0625) - 1.0).abs() < 0.01);
+        assert!(gain_at(&coeffs, 0.375) < 0.01);
+    }
+
+    #[test]
+    fn highpass_gains() {
+        let mut coeffs = [0.0; 31];
+        highpass(1000.0, 8000.0, Window::Hamming, &mut coeffs).unwrap();
+        assert_symmetric(&coeffs);
+        assert!((gain_at(&coeffs, 0.5) - 1.0).abs() < 1e-5);
+        assert!(coeffs.iter().sum::<f32>().abs() < 1e-6);
+        assert!(gain_at(&coeffs, 0.0625) < 0.01);
+    }
+
+    #[test]
+    fn bandpass_gains() {
+        let mut coeffs = [0.0; 63];
+        bandpass(1000.0, 2000.0, 8000.0, Window::Hamming, &mut coeffs).unwrap();
+        assert_symmetric(&coeffs);
+        assert!((gain_at(&coeffs, 0.1875) - 1.0).abs() < 1e-5);
+        assert!(gain_at(&coeffs, 0.0) < 0.01);
+        assert!(gain_at(&coeffs, 0.4) < 0.01);
+    }
+
+    #[test]
+    fn rejects_zero_gain() {
+        let mut coeffs = [0.0; 2];
+        assert!(matches!(
+            lowpass(1000.0, 8000.0, Window::Hanning, &mut coeffs),
+            Err(Error::Argument)
+        ));
+        let mut coeffs = [0.0; 1];
+        assert!(matches!(
+            highpass(1000.0, 8000.0, Window::Hanning, &mut coeffs),
+            Err(Error::Argument)
+        ));
+        let mut coeffs = [0.0; 31];
+        assert!(matches!(
+            lowpass(0.0, 8000.0, Window::Hamming, &mut coeffs),
+            Err(Error::Argument)
+        ));
+        assert!(matches!(
+            bandpass(1000.0, 1000.0, 8000.0, Window::Hamming, &mut coeffs),
+            Err(Error::Argument)
+        ));
+    }
+
+    #[test]
+    fn rejects_invalid_lengths() {
+        assert!(matches!(
+            lowpass(1000.0, 8000.0, Window::Hamming, &mut []),
+            Err(Error::Length)
+        ));
+        assert!(matches!(
+            bandpass(1000.0, 2000.0, 8000.0, Window::Hamming, &mut []),
+            Err(Error::Length)
+        ));
+        let mut coeffs = [0.0; 30];
+        assert!(matches!(
+            highpass(1000.0, 8000.0, Window::Hamming, &mut coeffs),
+            Err(Error::Length)
+        ));
+    }
+}
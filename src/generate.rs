@@ -0,0 +1,134 @@
+//! Test signal generators
+//!
+//! These functions fill a slice with a reference signal, for example to test a filter or check
+//! which FFT bin a tone falls into. The phase is accumulated one sample at a time and kept in
+//! the range [0, 2 * pi), so long signals do not lose precision.
+
+use core::f32::consts::PI;
+
+use crate::fast_math::sin_f32;
+
+/// Fills dst with a sine wave
+///
+/// `dst[n] = amplitude * sin(2 * pi * freq * n / sample_rate + phase)`, with the sine
+/// calculated by `arm_sin_f32`.
+///
+/// phase: phase of the first sample, in radians
+pub fn sine_f32(dst: &mut [f32], freq: f32, sample_rate: f32, amplitude: f32, phase: f32) {
+    let step = wrap_phase(2.0 * PI * freq / sample_rate);
+    let mut angle = wrap_phase(phase);
+    for value in dst.iter_mut() {
+        *value = amplitude * sin_f32(angle);
+        angle = wrap_phase(angle + step);
+    }
+}
+
+/// Fills dst with a linear chirp, a sine wave with an amplitude of 1 whose frequency changes
+/// linearly from f_start at the first sample to f_end at the end of the slice
+///
+/// The instantaneous frequency at sample n is `f_start + (f_end - f_start) * n / dst.len()`, and
+/// the phase of the first sample is zero.
+pub fn chirp_f32(dst: &mut [f32], f_start: f32, f_end: f32, sample_rate: f32) {
+    let sweep = (f_end - f_start) / dst.len() as f32;
+    let mut angle = 0.0;
+    for (n, value) in dst.iter_mut().enumerate() {
+        *value = sin_f32(angle);
+        let freq = f_start + sweep * n as f32;
+        angle = wrap_phase(angle + wrap_phase(2.0 * PI * freq / sample_rate));
+    }
+}
+
+/// Brings an angle in radians into the range [0, 2 * pi)
+fn wrap_phase(angle: f32) -> f32 {
+    let turns = angle / (2.0 * PI);
+    // Truncation toward zero, adjusted to round toward negative infinity
+    let mut whole = turns as i32 as f32;
+    if whole > turns {
+        whole -= 1.0;
+    }
+    let wrapped = angle - whole * 2.0 * PI;
+    if wrapped >= 2.0 * PI {
+        wrapped - 2.0 * PI
+    } else {
+        wrapped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transform::FloatRealFft;
+
+    /// Returns the bin with the largest magnitude in the FFT of signal, excluding the Nyquist bin
+    fn peak_bin(signal: &[f32]) -> usize {
+        let fft = FloatRealFft::new(signal.len() as u16).unwrap();
+        let mut packed = [0.0; 64];
+        fft.run(signal, &mut packed);
+        packed[1] = 0.0;
+        let magnitudes = packed
+            .chunks_exact(2)
+            .map(|bin| (bin[0] * bin[0] + bin[1] * bin[1]).sqrt());
+        let (bin, _) = magnitudes
+            .enumerate()
+            .fold((0, 0.0), |peak, (bin, magnitude)| {
+                if magnitude > peak.1 {
+                    (bin, magnitude)
+                } else {
+                    peak
+                }
+            });
+        bin
+    }
+
+    #[test]
+    fn sine_peak_bin() {
+        // 8 kHz / 64 = 125 Hz per bin
+        let mut signal = [0.0; 64];
+        sine_f32(&mut signal, 1000.0, 8000.0, 1.0, 0.0);
+        assert_eq!(peak_bin(&signal), 8);
+    }
+
+    #[test]
+    fn sine_amplitude_and_phase() {
+        let mut signal = [0.0; 32];
+        sine_f32(&mut signal, 1000.0, 8000.0, 0.5, -PI / 2.0);
+        for (n, value) in signal.iter().enumerate() {
+            let expected = 0.5 * (2.0 * PI * 1000.0 * n as f32 / 8000.0 - PI / 2.0).sin();
+            assert!((value - expected).abs() < 1e-5, "{} at {}", value, n);
+        }
+    }
+
+    #[test]
+    fn chirp_sweeps_frequency() {
+        let mut signal = [0.0; 64];
+        chirp_f32(&mut signal, 500.0, 500.0, 8000.0);
+        let mut sine = [0.0; 64];
+        sine_f32(&mut sine, 500.0, 8000.0, 1.0, 0.0);
+        for (chirp, sine) in signal.iter().zip(sine.iter()) {
+            assert!((chirp - sine).abs() < 1e-4);
+        }
+
+        // The frequency rises from 0 to 2 kHz. It averages 250 Hz (bin 2) over the first
+        // quarter of the signal and 1750 Hz (bin 14) over the last quarter.
+        let mut signal = [0.0; 256];
+        chirp_f32(&mut signal, 0.0, 2000.0, 8000.0);
+        assert_eq!(signal[0], 0.0);
+        assert!((1..=3).contains(&peak_bin(&signal[..64])));
+        assert!((13..=15).contains(&peak_bin(&signal[192..])));
+    }
+
+    #[test]
+    fn wrap_phase_range() {
+        for &angle in &[0.0, 1.0, 2.0 * PI, 7.0, -0.5, -7.0, -100.0] {
+            let wrapped = wrap_phase(angle);
+            assert!(
+                (0.0..2.0 * PI).contains(&wrapped),
+                "{} -> {}",
+                angle,
+                wrapped
+            );
+            assert!((wrapped.sin() - angle.sin()).abs() < 1e-4);
+            assert!((wrapped.cos() - angle.cos()).abs() < 1e-4);
+        }
+    }
+}
@@ -0,0 +1,222 @@
+//! Interpolation functions
+//!
+//! The CMSIS-DSP interpolation functions are defined inline in the header files and are missing
+//! from the compiled libraries, so the functions in this module are Rust implementations of the
+//! same algorithms.
+
+use crate::check_length_or_panic;
+
+/// Interpolates linearly between values in a table with evenly spaced x values
+///
+/// This is equivalent to `arm_linear_interp_f32`.
+pub struct LinearInterpolator<'a> {
+    /// The x value that corresponds to the first value in the table
+    x_start: f32,
+    /// The difference between x values that correspond to adjacent table values
+    x_spacing: f32,
+    /// The y values
+    table: &'a [f32],
+}
+
+impl<'a> LinearInterpolator<'a> {
+    /// Creates an interpolator
+    ///
+    /// n_values: number of values in the table
+    ///
+    /// x_start: x value that corresponds to `table[0]`
+    ///
+    /// x_spacing: difference between the x values that correspond to adjacent table values
+    ///
+    /// table: y values for `x_start`, `x_start + x_spacing`, `x_start + 2 * x_spacing`, ...
+    ///
+    /// # Panics
+    ///
+    /// This function panics if table does not have n_values elements, or if n_values is zero.
+    pub fn new(n_values: u32, x_start: f32, x_spacing: f32, table: &'a [f32]) -> Self {
+        check_length_or_panic::<(usize, usize), u32>((n_values as usize, table.len()));
+        assert!(n_values != 0, "Table must not be empty");
+        LinearInterpolator {
+            x_start,
+            x_spacing,
+            table,
+        }
+    }
+
+    /// Calculates the y value that corresponds to an x value
+    ///
+    /// If x is below the range of the table, this function returns the first value in the table.
+    /// If x is above the range of the table, this function returns the last value in the table.
+    pub fn interpolate(&self, x: f32) -> f32 {
+        let index = ((x - self.x_start) / self.x_spacing) as i32;
+        if index < 0 {
+            self.table[0]
+        } else if index as usize >= self.table.len() - 1 {
+            self.table[self.table.len() - 1]
+        } else {
+            let index = index as usize;
+            let x0 = self.x_start + index as f32 * self.x_spacing;
+            let x1 = self.x_start + (index + 1) as f32 * self.x_spacing;
+            let y0 = self.table[index];
+            let y1 = self.table[index + 1];
+            y0 + (x - x0) * ((y1 - y0) / (x1 - x0))
+        }
+    }
+}
+
+/// Interpolates between values in a two-dimensional table
+///
+/// The table has one value for each pair of integer coordinates (x, y), where x is the column
+/// and y is the row. This is equivalent to `arm_bilinear_interp_f32`.
+pub struct BilinearInterpolator<'a> {
+    /// Number of rows in the table
+    num_rows: u16,
+    /// Number of columns in the table
+    num_cols: u16,
+    /// Table values, in row-major order
+    table: &'a [f32],
+}
+
+impl<'a> BilinearInterpolator<'a> {
+    /// Creates an interpolator
+    ///
+    /// num_rows: number of rows (y values) in the table
+    ///
+    /// num_cols: number of columns (x values) in the table
+    ///
+    /// table: values in row-major order. The value for coordinates (x, y) is at
+    /// `table[x + y * num_cols]`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if table does not have `num_rows * num_cols` elements.
+    pub fn new(num_rows: u16, num_cols: u16, table: &'a [f32]) -> Self {
+        check_length_or_panic::<(usize, usize), u32>((
+            num_rows as usize * num_cols as usize,
+            table.len(),
+        ));
+        BilinearInterpolator {
+            num_rows,
+            num_cols,
+            table,
+        }
+    }
+
+    /// Calculates the value that corresponds to the coordinates (x, y)
+    ///
+    /// If the coordinates are outside the range of the table, this function returns zero. As
+    /// in `arm_bilinear_interp_f32`, the range is `0 <= x < num_cols - 1` and
+    /// `0 <= y < num_rows - 1`, because each value is interpolated from the next row and column.
+    pub fn interpolate(&self, x: f32, y: f32) -> f32 {
+        // Conversion to i32 truncates toward zero, so values between -1 and 0 (and NaN) are
+        // checked separately
+        if !(x >= 0.0 && y >= 0.0) {
+            return 0.0;
+        }
+        let x_index = x as i32;
+        let y_index = y as i32;
+        if x_index < 0
+            || x_index > i32::from(self.num_cols) - 2
+            || y_index < 0
+            || y_index > i32::from(self.num_rows) - 2
+        {
+            return 0.0;
+        }
+        let num_cols = usize::from(self.num_cols);
+        let index = x_index as usize + y_index as usize * num_cols;
+        let f00 = self.table[index];
+        let f01 = self.table[index + 1];
+        let f10 = self.table[index + num_cols];
+        let f11 = self.table[index + num_cols + 1];
+
+        let x_diff = x - x_index as f32;
+        let y_diff = y - y_index as f32;
+        f00 + (f01 - f00) * x_diff
+            + (f10 - f00) * y_diff
+            + (f00 - f01 - f10 + f11) * x_diff * y_diff
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn linear_interpolation_of_line() {
+        // y = 2x for x = 1.0, 1.5, ..., 5.0
+        let mut table = [0.0; 9];
+        for (i, value) in table.iter_mut().enumerate() {
+            *value = 2.0 * (1.0 + 0.5 * i as f32);
+        }
+        let interpolator = LinearInterpolator::new(9, 1.0, 0.5, &table);
+        for &x in &[1.0, 1.25, 2.0, 3.1, 4.3, 4.99] {
+            let y = interpolator.interpolate(x);
+            assert!((y - 2.0 * x).abs() < 1e-5, "{} at {}", y, x);
+        }
+        // Outside the table, the nearest value is returned
+        assert_eq!(interpolator.interpolate(0.0), 2.0);
+        assert_eq!(interpolator.interpolate(5.0), 10.0);
+        assert_eq!(interpolator.interpolate(100.0), 10.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn linear_interpolator_checks_length() {
+        LinearInterpolator::new(4, 0.0, 1.0, &[0.0; 3]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn linear_interpolator_rejects_empty_table() {
+        LinearInterpolator::new(0, 0.0, 1.0, &[]);
+    }
+
+    #[test]
+    fn bilinear_interpolation_of_plane() {
+        // z = x + y on a table with 4 rows and 3 columns
+        let mut table = [0.0; 12];
+        for (i, value) in table.iter_mut().enumerate() {
+            *value = (i % 3 + i / 3) as f32;
+        }
+        let interpolator = BilinearInterpolator::new(4, 3, &table);
+        for &(x, y) in &[
+            (0.0, 0.0),
+            (0.5, 0.25),
+            (1.75, 2.5),
+            (1.1, 0.9),
+            (0.3, 2.99),
+        ] {
+            let z = interpolator.interpolate(x, y);
+            assert!((z - (x + y)).abs() < 1e-5, "{} at ({}, {})", z, x, y);
+        }
+    }
+
+    #[test]
+    fn bilinear_interpolation_of_product() {
+        // z = x * y is bilinear, so it is interpolated exactly
+        let table = [0.0, 0.0, 0.0, 1.0];
+        let interpolator = BilinearInterpolator::new(2, 2, &table);
+        assert!((interpolator.interpolate(0.5, 0.5) - 0.25).abs() < 1e-6);
+        assert!((interpolator.interpolate(0.2, 0.7) - 0.14).abs() < 1e-6);
+    }
+
+    #[test]
+    fn bilinear_interpolation_outside_table() {
+        let table = [1.0; 12];
+        let interpolator = BilinearInterpolator::new(4, 3, &table);
+        assert_eq!(interpolator.interpolate(-0.5, 1.0), 0.0);
+        assert_eq!(interpolator.interpolate(1.0, -1.0), 0.0);
+        assert_eq!(interpolator.interpolate(2.5, 1.0), 0.0);
+        assert_eq!(interpolator.interpolate(1.0, 3.5), 0.0);
+        assert_eq!(interpolator.interpolate(f32::NAN, 1.0), 0.0);
+        // The last column and row are outside the range
+        assert_eq!(interpolator.interpolate(2.0, 1.0), 0.0);
+        assert_eq!(interpolator.interpolate(1.0, 3.0), 0.0);
+        assert_eq!(interpolator.interpolate(1.99, 2.99), 1.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn bilinear_interpolator_checks_length() {
+        BilinearInterpolator::new(4, 3, &[0.0; 11]);
+    }
+}
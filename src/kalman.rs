@@ -0,0 +1,380 @@
+//! Kalman filters for linear systems
+//!
+//! A [`KalmanFilter`] estimates the state of a system with n state variables from m measurements.
+//! The system is described by four matrices:
+//!
+//!  * the state transition matrix F (n x n), which advances the state by one time step
+//!  * the measurement matrix H (m x n), which maps a state to the measurements it would produce
+//!  * the process noise covariance Q (n x n)
+//!  * the measurement noise covariance R (m x m)
+//!
+//! All calculations use the operations of `MatrixF32`, with vectors stored as matrices with one
+//! column.
+//!
+//! This module is available when the `alloc` feature is enabled.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::check_length_or_panic;
+use crate::matrix::MatrixF32;
+use crate::{Error, Result};
+
+/// A linear Kalman filter that owns its matrices, state, and scratch buffers
+pub struct KalmanFilter {
+    /// Number of state variables, n
+    states: u16,
+    /// Number of measurements, m
+    measurements: u16,
+    /// Number of control inputs, or zero if there is no control matrix
+    controls: u16,
+    /// State estimate (n x 1)
+    state: Vec<f32>,
+    /// State covariance P (n x n)
+    covariance: Vec<f32>,
+    transition: Vec<f32>,
+    measurement: Vec<f32>,
+    process_noise: Vec<f32>,
+    measurement_noise: Vec<f32>,
+    /// Control matrix B (n x controls)
+    control: Vec<f32>,
+    /// Kalman gain K from the most recent update (n x m)
+    gain: Vec<f32>,
+    // Scratch buffers, named by their dimensions
+    scratch_n: [Vec<f32>; 2],
+    scratch_m: [Vec<f32>; 3],
+    scratch_controls: Vec<f32>,
+    scratch_nn: [Vec<f32>; 2],
+    scratch_mn: Vec<f32>,
+    scratch_nm: Vec<f32>,
+    scratch_mm: [Vec<f32>; 2],
+}
+
+impl KalmanFilter {
+    /// Creates a filter with a state of zero and a state covariance equal to the identity matrix
+    ///
+    /// transition: state transition matrix F, with n rows and n columns
+    ///
+    /// measurement: measurement matrix H, with m rows and n columns
+    ///
+    /// process_noise: process noise covariance Q, with n rows and n columns
+    ///
+    /// measurement_noise: measurement noise covariance R, with m rows and m columns
+    ///
+    /// This function returns `Error::SizeMismatch` if the matrices do not have these dimensions
+    /// or if n or m is zero.
+    pub fn new(
+        transition: &MatrixF32,
+        measurement: &MatrixF32,
+        process_noise: &MatrixF32,
+        measurement_noise: &MatrixF32,
+    ) -> Result<Self> {
+        let (n, m) = (transition.rows(), measurement.rows());
+        let square = |matrix: &MatrixF32, size: u16| matrix.rows() == size && matrix.cols() == size;
+        if n == 0
+            || m == 0
+            || !square(transition, n)
+            || measurement.cols() != n
+            || !square(process_noise, n)
+            || !square(measurement_noise, m)
+        {
+            return Err(Error::SizeMismatch);
+        }
+        let (n_size, m_size) = (usize::from(n), usize::from(m));
+        let mut covariance = vec![0.0; n_size * n_size];
+        for value in covariance.iter_mut().step_by(n_size + 1) {
+            *value = 1.0;
+        }
+        Ok(KalmanFilter {
+            states: n,
+            measurements: m,
+            controls: 0,
+            state: vec![0.0; n_size],
+            covariance,
+            transition: transition.data().to_vec(),
+            measurement: measurement.data().to_vec(),
+            process_noise: process_noise.data().to_vec(),
+            measurement_noise: measurement_noise.data().to_vec(),
+            control: Vec::new(),
+            gain: vec![0.0; n_size * m_size],
+            scratch_n: [vec![0.0; n_size], vec![0.0; n_size]],
+            scratch_m: [vec![0.0; m_size], vec![0.0; m_size], vec![0.0; m_size]],
+            scratch_controls: Vec::new(),
+            scratch_nn: [vec![0.0; n_size * n_size], vec![0.0; n_size * n_size]],
+            scratch_mn: vec![0.0; m_size * n_size],
+            scratch_nm: vec![0.0; n_size * m_size],
+            scratch_mm: [vec![0.0; m_size * m_size], vec![0.0; m_size * m_size]],
+        })
+    }
+
+    /// Adds a control matrix B, with n rows and one column for each control input, which maps
+    /// the control inputs passed to predict into changes of the state
+    ///
+    /// This function returns `Error::SizeMismatch` if control does not have n rows or has no
+    /// columns.
+    pub fn with_control(mut self, control: &MatrixF32) -> Result<Self> {
+        if control.rows() != self.states || control.cols() == 0 {
+            return Err(Error::SizeMismatch);
+        }
+        self.controls = control.cols();
+        self.control = control.data().to_vec();
+        self.scratch_controls = vec![0.0; usize::from(self.controls)];
+        Ok(self)
+    }
+
+    /// Returns the state estimate
+    pub fn state(&self) -> &[f32] {
+        &self.state
+    }
+
+    /// Returns the state covariance, in row-major order
+    pub fn covariance(&self) -> &[f32] {
+        &self.covariance
+    }
+
+    /// Returns the Kalman gain calculated by the most recent update, in row-major order
+    pub fn gain(&self) -> &[f32] {
+        &self.gain
+    }
+
+    /// Replaces the state estimate and its covariance
+    ///
+    /// # Panics
+    ///
+    /// This function panics if state does not have n elements or covariance does not have n * n
+    /// elements.
+    pub fn set_state(&mut self, state: &[f32], covariance: &[f32]) {
+        check_length_or_panic::<(usize, usize), usize>((self.state.len(), state.len()));
+        check_length_or_panic::<(usize, usize), usize>((self.covariance.len(), covariance.len()));
+        self.state.copy_from_slice(state);
+        self.covariance.copy_from_slice(covariance);
+    }
+
+    /// Advances the state estimate by one time step
+    ///
+    /// The state becomes `F * x + B * u`, where u is the control input, and the covariance
+    /// becomes `F * P * F^T + Q`. If control is None, the `B * u` term is omitted.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if control is provided and the filter has no control matrix, or if
+    /// the length of control is not equal to the number of columns of the control matrix.
+    pub fn predict(&mut self, control: Option<&[f32]>) {
+        if let Some(control) = control {
+            assert!(self.controls != 0, "The filter has no control matrix");
+            check_length_or_panic::<(usize, usize), usize>((
+                self.scratch_controls.len(),
+                control.len(),
+            ));
+            self.scratch_controls.copy_from_slice(control);
+        }
+        self.predict_inner(control.is_some())
+            .expect("Matrix dimensions were checked when the filter was created");
+    }
+
+    /// Corrects the state estimate with a set of measurements
+    ///
+    /// This calculates the innovation covariance `S = H * P * H^T + R`, the gain
+    /// `K = P * H^T * S^-1`, and then updates the state with `x + K * (z - H * x)` and the
+    /// covariance with `P - K * H * P`, where z is the measurement.
+    ///
+    /// This function returns `Error::Singular` if S cannot be inverted. In that case, the state
+    /// and its covariance are not changed.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if measurement does not have m elements.
+    pub fn update(&mut self, measurement: &[f32]) -> Result<()> {
+        let (n, m) = (self.states, self.measurements);
+        check_length_or_panic::<(usize, usize), usize>((usize::from(m), measurement.len()));
+        let [z, predicted, innovation] = &mut self.scratch_m;
+        z.copy_from_slice(measurement);
+        let [nn0, nn1] = &mut self.scratch_nn;
+        let [mm0, mm1] = &mut self.scratch_mm;
+        let [n0, n1] = &mut self.scratch_n;
+
+        let h = matrix(m, n, &mut self.measurement);
+        let r = matrix(m, m, &mut self.measurement_noise);
+        let mut x = matrix(n, 1, &mut self.state);
+        let mut p = matrix(n, n, &mut self.covariance);
+
+        // Innovation y = z - H * x
+        let z = matrix(m, 1, z);
+        let mut predicted = matrix(m, 1, predicted);
+        let mut innovation = matrix(m, 1, innovation);
+        h.mult(&x, &mut predicted)?;
+        z.sub(&predicted, &mut innovation)?;
+
+        // S = H * P * H^T + R, and its inverse
+        let mut hp = matrix(m, n, &mut self.scratch_mn);
+        let mut hph = matrix(m, m, mm0);
+        let mut s = matrix(m, m, mm1);
+        h.mult(&p, &mut hp)?;
+        hp.mult_transpose(&h, &mut hph)?;
+        hph.add(&r, &mut s)?;
+        let mut s_inverse = matrix(m, m, mm0);
+        s.inverse(&mut s_inverse)?;
+
+        // K = P * H^T * S^-1
+        let mut pht = matrix(n, m, &mut self.scratch_nm);
+        let mut gain = matrix(n, m, &mut self.gain);
+        p.mult_transpose(&h, &mut pht)?;
+        pht.mult(&s_inverse, &mut gain)?;
+
+        // x = x + K * y
+        let mut correction = matrix(n, 1, n0);
+        let mut corrected = matrix(n, 1, n1);
+        gain.mult(&innovation, &mut correction)?;
+        x.add(&correction, &mut corrected)?;
+        x.data_mut().copy_from_slice(corrected.data());
+
+        // P = P - K * H * P
+        let mut kh = matrix(n, n, nn0);
+        let mut khp = matrix(n, n, nn1);
+        gain.mult(&h, &mut kh)?;
+        kh.mult(&p, &mut khp)?;
+        let mut corrected = matrix(n, n, nn0);
+        p.sub(&khp, &mut corrected)?;
+        p.data_mut().copy_from_slice(corrected.data());
+        Ok(())
+    }
+
+    /// Runs the predict step, with the control input in scratch_controls if has_control is true
+    fn predict_inner(&mut self, has_control: bool) -> Result<()> {
+        let (n, k) = (self.states, self.controls);
+        let [n0, n1] = &mut self.scratch_n;
+        let [nn0, nn1] = &mut self.scratch_nn;
+
+        let f = matrix(n, n, &mut self.transition);
+        let mut x = matrix(n, 1, &mut self.state);
+
+        // x = F * x + B * u
+        let mut predicted = matrix(n, 1, n0);
+        f.mult(&x, &mut predicted)?;
+        if has_control {
+            let b = matrix(n, k, &mut self.control);
+            let u = matrix(k, 1, &mut self.scratch_controls);
+            let mut effect = matrix(n, 1, n1);
+            b.mult(&u, &mut effect)?;
+            predicted.add(&effect, &mut x)?;
+        } else {
+            x.data_mut().copy_from_slice(predicted.data());
+        }
+
+        // P = F * P * F^T + Q
+        let mut p = matrix(n, n, &mut self.covariance);
+        let q = matrix(n, n, &mut self.process_noise);
+        let mut fp = matrix(n, n, nn0);
+        let mut fpf = matrix(n, n, nn1);
+        f.mult(&p, &mut fp)?;
+        fp.mult_transpose(&f, &mut fpf)?;
+        fpf.add(&q, &mut p)
+    }
+}
+
+/// Creates a matrix that refers to a buffer owned by a filter
+fn matrix(rows: u16, cols: u16, data: &mut [f32]) -> MatrixF32<'_> {
+    MatrixF32::new(rows, cols, data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Creates a filter from matrix data, with n states and m measurements
+    fn filter(n: u16, m: u16, f: &[f32], h: &[f32], q: &[f32], r: &[f32]) -> Result<KalmanFilter> {
+        let (mut f, mut h, mut q, mut r) = (f.to_vec(), h.to_vec(), q.to_vec(), r.to_vec());
+        KalmanFilter::new(
+            &MatrixF32::new(n, n, &mut f),
+            &MatrixF32::new(m, n, &mut h),
+            &MatrixF32::new(n, n, &mut q),
+            &MatrixF32::new(m, m, &mut r),
+        )
+    }
+
+    #[test]
+    fn scalar_filter_averages_measurements() {
+        // With no process noise and unit measurement noise, the estimate is the average of the
+        // measurements and the initial state of zero
+        let mut kalman = filter(1, 1, &[1.0], &[1.0], &[0.0], &[1.0]).unwrap();
+        kalman.predict(None);
+        kalman.update(&[2.0]).unwrap();
+        assert!((kalman.state()[0] - 1.0).abs() < 1e-6);
+        assert!((kalman.gain()[0] - 0.5).abs() < 1e-6);
+        assert!((kalman.covariance()[0] - 0.5).abs() < 1e-6);
+        kalman.predict(None);
+        kalman.update(&[4.0]).unwrap();
+        assert!((kalman.state()[0] - 2.0).abs() < 1e-6);
+        assert!((kalman.covariance()[0] - 1.0 / 3.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn tracks_constant_velocity() {
+        // State (position, velocity), measuring the position
+        let transition = [1.0, 1.0, 0.0, 1.0];
+        let process_noise = [1e-4, 0.0, 0.0, 1e-4];
+        let mut kalman = filter(2, 1, &transition, &[1.0, 0.0], &process_noise, &[1.0]).unwrap();
+        for t in 0..50 {
+            kalman.predict(None);
+            kalman.update(&[1.0 + 2.0 * t as f32]).unwrap();
+        }
+        assert!((kalman.state()[0] - 99.0).abs() < 0.05);
+        assert!((kalman.state()[1] - 2.0).abs() < 0.05);
+    }
+
+    #[test]
+    fn predict_with_control() {
+        let mut b = [0.5, 1.0];
+        let kalman = filter(2, 1, &[1.0, 0.0, 0.0, 1.0], &[1.0, 0.0], &[0.0; 4], &[1.0]).unwrap();
+        let mut kalman = kalman.with_control(&MatrixF32::new(2, 1, &mut b)).unwrap();
+        kalman.set_state(&[1.0, 0.0], &[1.0, 0.0, 0.0, 1.0]);
+        kalman.predict(Some(&[4.0]));
+        assert_eq!(kalman.state(), [3.0, 4.0]);
+        // Without process noise, the covariance is unchanged
+        assert_eq!(kalman.covariance(), [1.0, 0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn rejects_mismatched_matrices() {
+        assert!(filter(2, 1, &[1.0; 4], &[1.0; 2], &[0.0; 4], &[1.0]).is_ok());
+        let (mut f, mut q) = ([1.0; 4], [0.0; 4]);
+        let (f, q) = (MatrixF32::new(2, 2, &mut f), MatrixF32::new(2, 2, &mut q));
+        // H has the wrong number of columns
+        let (mut h, mut r) = ([1.0; 3], [1.0]);
+        let (h, r) = (MatrixF32::new(1, 3, &mut h), MatrixF32::new(1, 1, &mut r));
+        assert!(matches!(
+            KalmanFilter::new(&f, &h, &q, &r),
+            Err(Error::SizeMismatch)
+        ));
+        // R does not have one row and column for each measurement
+        let (mut h, mut r) = ([1.0; 4], [1.0]);
+        let (h, r) = (MatrixF32::new(2, 2, &mut h), MatrixF32::new(1, 1, &mut r));
+        assert!(matches!(
+            KalmanFilter::new(&f, &h, &q, &r),
+            Err(Error::SizeMismatch)
+        ));
+        let kalman = filter(2, 1, &[1.0; 4], &[1.0; 2], &[0.0; 4], &[1.0]).unwrap();
+        let mut b = [1.0; 3];
+        assert!(matches!(
+            kalman.with_control(&MatrixF32::new(3, 1, &mut b)),
+            Err(Error::SizeMismatch)
+        ));
+    }
+
+    #[test]
+    fn singular_innovation_covariance() {
+        // Without measurement noise, a measurement matrix of zero makes S zero
+        let mut kalman = filter(1, 1, &[1.0], &[0.0], &[0.0], &[0.0]).unwrap();
+        kalman.set_state(&[3.0], &[2.0]);
+        assert!(matches!(kalman.update(&[1.0]), Err(Error::Singular)));
+        assert_eq!(kalman.state(), [3.0]);
+        assert_eq!(kalman.covariance(), [2.0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn predict_rejects_control_without_control_matrix() {
+        let mut kalman = filter(1, 1, &[1.0], &[1.0], &[0.0], &[1.0]).unwrap();
+        kalman.predict(Some(&[1.0]));
+    }
+}
@@ -1,4 +1,4 @@
-#![no_std]
+#![cfg_attr(not(feature = "std"), no_std)]
 
 //!
 //! This library provides Rust-friendly interfaces to the CMSIS DSP library, which implements
@@ -20,32 +20,113 @@
 //! All other targets will be configured automatically based on the target passed to cargo. If you forget to enable a
 //! feature, everything should still work but it may be slower.
 //!
+//! ## Memory allocation and the standard library
+//!
+//! This library does not depend on the Rust standard library, and by default it does not allocate memory. These
+//! features enable more functionality:
+//!
+//!  * `alloc`: Enables items that allocate memory using the `alloc` crate. This requires a global allocator.
+//!    These items are:
+//!     * The `kalman`, `pipeline`, and `spectral` modules
+//!     * `filter::ArbitraryResampler`, `filter::Resampler`, `filter::approximate_as_biquads`, and
+//!       `filter::estimate_lag_f32`
+//!     * `filter::FirFilterBuilder` and `filter::OwnedFirFilter`
+//!     * `filter::BiquadCascadeDF2TFilter::frequency_response` and `filter::BiquadCascadeDF2TFilter::to_ba`
+//!     * `statistics::median_f32` and `statistics::percentile_f32`
+//!     * `transform::FftConvolver`, `transform::SpectralSubtractor`, and `transform::Welch`
+//!  * `std`: Links the standard library. This also enables `alloc`.
+//!  * `f64`: Enables `matrix::MatrixF64` and `basic::dot_product_f64`, for calculations that need
+//!    more precision than f32 provides.
+//!
+//! ## Functions implemented in Rust
+//!
+//! This crate links the CMSIS-DSP library from CMSIS 5.7.0. Some functions are missing from it,
+//! or are defined inline in the C headers so that the bindings do not contain them. This crate
+//! provides Rust implementations of these, which follow the CMSIS-DSP definitions:
+//!
+//!  * The `controller`, `quaternion`, and `window` modules
+//!  * `basic::clip_f32`, `basic::clip_q31`, `basic::clip_q15`, and `basic::dot_product_f64`
+//!  * `fast_math::sqrt_f32`, `fast_math::atan2_f32`, and `fast_math::vatan2_f32`
+//!  * `matrix::MatrixF32::vector_mult`, `matrix::MatrixF32::qr`, and the `matrix::MatrixF64`
+//!    operations other than `inverse`
+//!  * `statistics::mse_f32`, `statistics::mse_q31`, and `statistics::mse_q15`
+//!  * `transform::Mfcc`
 //!
 
 extern crate cmsis_dsp_sys_pregenerated as cmsis_dsp_sys;
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
 pub mod basic;
+pub mod bayes;
 pub mod transform;
 pub mod complex;
+pub mod controller;
+pub mod distance;
+pub mod fast_math;
 pub mod filter;
+pub mod generate;
+pub mod interpolation;
+#[cfg(feature = "alloc")]
+pub mod kalman;
+pub mod matrix;
+#[cfg(feature = "alloc")]
+pub mod pipeline;
+pub mod quaternion;
+#[cfg(feature = "alloc")]
+pub mod spectral;
 pub mod statistics;
+pub mod support;
+pub mod svm;
+pub mod window;
 #[cfg(feature = "libm")]
 mod libm_c;
 #[cfg(all(feature = "micromath", not(feature = "libm")))]
 mod micromath_c;
+#[cfg(all(test, not(feature = "std")))]
+extern crate std;
+#[cfg(all(test, not(target_arch = "arm")))]
+mod test_cmsis;
 
 use core::convert::TryInto;
 use core::fmt::Debug;
 
 /// DSP library errors
+///
+/// Most variants correspond to a CMSIS-DSP status code.
 #[derive(Debug)]
 pub enum Error {
+    /// One or more arguments are incorrect (`ARM_MATH_ARGUMENT_ERROR`)
     Argument,
+    /// The length of a data buffer is incorrect (`ARM_MATH_LENGTH_ERROR`)
     Length,
+    /// Two slices that must have related lengths do not
+    LengthMismatch {
+        /// The length that was required
+        expected: usize,
+        /// The actual length
+        got: usize,
+    },
+    /// The sizes of the matrices are not compatible with the operation (`ARM_MATH_SIZE_MISMATCH`)
     SizeMismatch,
+    /// A matrix operand does not have the dimensions that a matrix operation requires
+    DimensionMismatch {
+        /// The dimensions that were required, as (rows, columns)
+        expected: (usize, usize),
+        /// The actual dimensions, as (rows, columns)
+        got: (usize, usize),
+        /// The name of the operation, such as "add" or "mult"
+        op: &'static str,
+    },
+    /// Not-a-number or infinity was generated (`ARM_MATH_NANINF`)
     NanInf,
+    /// The input matrix is singular and cannot be inverted (`ARM_MATH_SINGULAR`)
     Singular,
+    /// A test failed (`ARM_MATH_TEST_FAILURE`)
     TestFailure,
+    /// The operation or configuration is not supported by the linked version of CMSIS-DSP
+    Unsupported,
+    /// CMSIS-DSP returned an unknown status code
     Unknown,
 }
 
@@ -78,7 +159,10 @@ pub type Result<T> = ::core::result::Result<T, Error>;
 
 /// Checks that all elements of the provided lengths value/tuple are equal, and that the length
 /// value fits into the returned integer type. This function panics if any condition does not hold.
-fn check_length<L, N>(lengths: L) -> N
+///
+/// This is for functions that document a panic on invalid lengths. Functions that return errors
+/// use `check_length`.
+fn check_length_or_panic<L, N>(lengths: L) -> N
     where
         L: Lengths,
         usize: TryInto<N>,
@@ -91,9 +175,27 @@ fn check_length<L, N>(lengths: L) -> N
         .expect("Length too large for size type")
 }
 
+/// Checks that all elements of the provided lengths value/tuple are equal, and that the length
+/// value fits into the returned integer type. This function returns an error if any condition
+/// does not hold.
+///
+/// A mismatch is reported with the first length as the expected length.
+fn check_length<L, N>(lengths: L) -> Result<N>
+    where
+        L: Lengths,
+        usize: TryInto<N>,
+{
+    if let Some((expected, got)) = lengths.mismatch() {
+        return Err(Error::LengthMismatch { expected, got });
+    }
+    lengths.length().try_into().map_err(|_| Error::Length)
+}
+
 trait Lengths {
     fn assert_lengths_equal(&self);
     fn length(&self) -> usize;
+    /// Returns the first length and the first length that is not equal to it, if any
+    fn mismatch(&self) -> Option<(usize, usize)>;
 }
 
 impl Lengths for usize {
@@ -104,6 +206,10 @@ impl Lengths for usize {
     fn length(&self) -> usize {
         *self
     }
+
+    fn mismatch(&self) -> Option<(usize, usize)> {
+        None
+    }
 }
 
 impl Lengths for (usize, usize) {
@@ -114,6 +220,14 @@ impl Lengths for (usize, usize) {
     fn length(&self) -> usize {
         self.0
     }
+
+    fn mismatch(&self) -> Option<(usize, usize)> {
+        if self.0 != self.1 {
+            Some((self.0, self.1))
+        } else {
+            None
+        }
+    }
 }
 
 impl Lengths for (usize, usize, usize) {
@@ -124,4 +238,156 @@ impl Lengths for (usize, usize, usize) {
     fn length(&self) -> usize {
         self.0
     }
+
+    fn mismatch(&self) -> Option<(usize, usize)> {
+        if self.0 != self.1 {
+            Some((self.0, self.1))
+        } else if self.0 != self.2 {
+            Some((self.0, self.2))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filter::{FirFilter, IirLatticeFilter};
+    use crate::matrix::MatrixF32;
+    use crate::transform::{DynamicComplexFft, FloatRealFft};
+
+    #[test]
+    fn status_codes() {
+        use cmsis_dsp_sys::arm_status::*;
+        assert!(ARM_MATH_SUCCESS.check_status().is_ok());
+        assert!(matches!(
+            ARM_MATH_ARGUMENT_ERROR.check_status(),
+            Err(Error::Argument)
+        ));
+        assert!(matches!(
+            ARM_MATH_LENGTH_ERROR.check_status(),
+            Err(Error::Length)
+        ));
+        assert!(matches!(
+            ARM_MATH_SIZE_MISMATCH.check_status(),
+            Err(Error::SizeMismatch)
+        ));
+        assert!(matches!(ARM_MATH_NANINF.check_status(), Err(Error::NanInf)));
+        assert!(matches!(
+            ARM_MATH_SINGULAR.check_status(),
+            Err(Error::Singular)
+        ));
+        assert!(matches!(
+            ARM_MATH_TEST_FAILURE.check_status(),
+            Err(Error::TestFailure)
+        ));
+        assert!(matches!((-100).check_status(), Err(Error::Unknown)));
+    }
+
+    #[test]
+    fn length_mismatch_error() {
+        let coeffs = [0.25, 0.5, 0.25];
+        let mut state = [0.0; 6];
+        let mut filter = FirFilter::new(3, &coeffs, &mut state, 4).unwrap();
+        let result = filter.try_process(&[1.0; 4], &mut [0.0; 3], 3);
+        assert!(matches!(
+            result,
+            Err(Error::LengthMismatch {
+                expected: 4,
+                got: 3
+            })
+        ));
+    }
+
+    #[test]
+    fn length_error() {
+        let (reflection, ladder) = ([0.5], [1.0, 0.0]);
+        let mut state = [0.0; 5];
+        let mut filter = IirLatticeFilter::new(1, &reflection, &ladder, &mut state, 4);
+        let result = filter.try_process(&[1.0; 5], &mut [0.0; 5]);
+        assert!(matches!(result, Err(Error::Length)));
+    }
+
+    #[test]
+    fn argument_error() {
+        // From the status code of arm_rfft_fast_init_f32
+        assert!(matches!(FloatRealFft::new(100), Err(Error::Argument)));
+    }
+
+    #[test]
+    fn singular_error() {
+        let mut data = [1.0, 2.0, 2.0, 4.0];
+        let mut matrix = MatrixF32::new(2, 2, &mut data);
+        let mut inverse_data = [0.0; 4];
+        let mut inverse = MatrixF32::new(2, 2, &mut inverse_data);
+        assert!(matches!(matrix.inverse(&mut inverse), Err(Error::Singular)));
+    }
+
+    #[test]
+    fn unsupported_error() {
+        assert!(matches!(
+            DynamicComplexFft::new(17),
+            Err(Error::Unsupported)
+        ));
+        assert!(DynamicComplexFft::new(16).is_ok());
+    }
+
+    #[test]
+    fn check_length_returns_the_length() {
+        assert_eq!(check_length::<usize, u32>(7).unwrap(), 7);
+        assert_eq!(check_length::<(usize, usize), u16>((5, 5)).unwrap(), 5);
+        assert_eq!(
+            check_length::<(usize, usize, usize), usize>((0, 0, 0)).unwrap(),
+            0
+        );
+        assert_eq!(
+            check_length_or_panic::<(usize, usize, usize), u8>((9, 9, 9)),
+            9
+        );
+    }
+
+    #[test]
+    fn check_length_reports_errors() {
+        assert!(matches!(
+            check_length::<(usize, usize), u32>((4, 6)),
+            Err(Error::LengthMismatch {
+                expected: 4,
+                got: 6
+            })
+        ));
+        // The first length that differs from the first element is reported
+        assert!(matches!(
+            check_length::<(usize, usize, usize), u32>((4, 4, 2)),
+            Err(Error::LengthMismatch {
+                expected: 4,
+                got: 2
+            })
+        ));
+        assert!(matches!(
+            check_length::<(usize, usize, usize), u32>((4, 3, 2)),
+            Err(Error::LengthMismatch {
+                expected: 4,
+                got: 3
+            })
+        ));
+        // A length that does not fit into the size type
+        assert!(matches!(check_length::<usize, u8>(256), Err(Error::Length)));
+        assert!(matches!(
+            check_length::<(usize, usize), u16>((70_000, 70_000)),
+            Err(Error::Length)
+        ));
+    }
+
+    #[test]
+    #[should_panic]
+    fn check_length_or_panic_rejects_mismatch() {
+        check_length_or_panic::<(usize, usize, usize), u32>((3, 3, 4));
+    }
+
+    #[test]
+    #[should_panic(expected = "Length too large for size type")]
+    fn check_length_or_panic_rejects_large_lengths() {
+        check_length_or_panic::<usize, u8>(1000);
+    }
 }
@@ -0,0 +1,865 @@
+//! Matrix operations
+//!
+//! Matrices are stored in row-major order: the element in row i and column j of a matrix with
+//! n columns is at index `i * n + j` of its data.
+//!
+//! The `MatrixF32` operations call the CMSIS-DSP matrix functions, except for these, which are
+//! implemented in Rust: `vector_mult` and `mult_transpose` use `arm_dot_prod_f32` for each row,
+//! and `qr` is written entirely in Rust. `MatrixF64::inverse` calls `arm_mat_inverse_f64`, and
+//! the other `MatrixF64` operations are implemented in Rust.
+
+use core::marker::PhantomData;
+
+use crate::fast_math::sqrt_f32;
+use crate::{check_length, check_length_or_panic, Error, Result, StatusCode};
+
+/// A matrix of f32 values that refers to a slice of data
+pub struct MatrixF32<'a> {
+    instance: cmsis_dsp_sys::arm_matrix_instance_f32,
+    _data: PhantomData<&'a mut [f32]>,
+}
+
+impl<'a> MatrixF32<'a> {
+    /// Creates a matrix with the provided number of rows and columns, which uses data to store
+    /// its elements
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the length of data is not equal to rows * cols.
+    pub fn new(rows: u16, cols: u16, data: &'a mut [f32]) -> Self {
+        check_length_or_panic::<(usize, usize), usize>((
+            usize::from(rows) * usize::from(cols),
+            data.len(),
+        ));
+        let mut instance = cmsis_dsp_sys::arm_matrix_instance_f32 {
+            numRows: 0,
+            numCols: 0,
+            pData: core::ptr::null_mut(),
+        };
+        unsafe {
+            cmsis_dsp_sys::arm_mat_init_f32(&mut instance, rows, cols, data.as_mut_ptr());
+        }
+        MatrixF32 {
+            instance,
+            _data: PhantomData,
+        }
+    }
+
+    /// Creates a matrix like new, but returns an error instead of panicking if the length of data
+    /// is not valid
+    ///
+    /// This function returns `Error::LengthMismatch` if the length of data is not equal to
+    /// rows * cols.
+    pub fn from_slice(rows: u16, cols: u16, data: &'a mut [f32]) -> Result<Self> {
+        check_length::<(usize, usize), usize>((usize::from(rows) * usize::from(cols), data.len()))?;
+        Ok(Self::new(rows, cols, data))
+    }
+
+    /// Creates a matrix like new, and sets all its elements to zero
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the length of data is not equal to rows * cols.
+    pub fn zeros(rows: u16, cols: u16, data: &'a mut [f32]) -> Self {
+        let mut matrix = Self::new(rows, cols, data);
+        matrix.fill(0.0);
+        matrix
+    }
+
+    /// Sets dst to an identity matrix, with ones on the diagonal and zeros everywhere else
+    ///
+    /// If dst is not square, the diagonal consists of the elements with equal row and column
+    /// indices.
+    pub fn identity(dst: &mut MatrixF32) {
+        dst.fill(0.0);
+        let cols = usize::from(dst.cols());
+        let diagonal = usize::from(dst.rows().min(dst.cols()));
+        let data = dst.data_mut();
+        for i in 0..diagonal {
+            data[i * cols + i] = 1.0;
+        }
+    }
+
+    /// Sets all elements of this matrix to value
+    pub fn fill(&mut self, value: f32) {
+        let length = self.len() as u32;
+        unsafe {
+            cmsis_dsp_sys::arm_fill_f32(value, self.instance.pData, length);
+        }
+    }
+
+    /// Returns the number of rows in this matrix
+    pub fn rows(&self) -> u16 {
+        self.instance.numRows
+    }
+
+    /// Returns the number of columns in this matrix
+    pub fn cols(&self) -> u16 {
+        self.instance.numCols
+    }
+
+    /// Returns the elements of this matrix
+    pub fn data(&self) -> &[f32] {
+        unsafe { core::slice::from_raw_parts(self.instance.pData, self.len()) }
+    }
+
+    /// Returns the elements of this matrix, which can be modified
+    pub fn data_mut(&mut self) -> &mut [f32] {
+        unsafe { core::slice::from_raw_parts_mut(self.instance.pData, self.len()) }
+    }
+
+    /// Adds this matrix and other, placing the result in dst
+    ///
+    /// This function returns `Error::DimensionMismatch` if other or dst does not have the same
+    /// dimensions as this matrix.
+    pub fn add(&self, other: &MatrixF32, dst: &mut MatrixF32) -> Result<()> {
+        check_dimensions("add", self.shape(), other.shape())?;
+        check_dimensions("add", self.shape(), dst.shape())?;
+        unsafe {
+            cmsis_dsp_sys::arm_mat_add_f32(&self.instance, &other.instance, &mut dst.instance)
+        }
+        .check_status()
+    }
+
+    /// Subtracts other from this matrix, placing the result in dst
+    ///
+    /// This function returns `Error::DimensionMismatch` if other or dst does not have the same
+    /// dimensions as this matrix.
+    pub fn sub(&self, other: &MatrixF32, dst: &mut MatrixF32) -> Result<()> {
+        check_dimensions("sub", self.shape(), other.shape())?;
+        check_dimensions("sub", self.shape(), dst.shape())?;
+        unsafe {
+            cmsis_dsp_sys::arm_mat_sub_f32(&self.instance, &other.instance, &mut dst.instance)
+        }
+        .check_status()
+    }
+
+    /// Multiplies this matrix by other, placing the result in dst
+    ///
+    /// This function returns `Error::DimensionMismatch` if the number of columns in this matrix
+    /// is not equal to the number of rows in other, or if dst does not have the same number of
+    /// rows as this matrix and the same number of columns as other.
+    pub fn mult(&self, other: &MatrixF32, dst: &mut MatrixF32) -> Result<()> {
+        let (rows, cols) = (usize::from(self.rows()), usize::from(other.cols()));
+        check_dimensions("mult", (usize::from(self.cols()), cols), other.shape())?;
+        check_dimensions("mult", (rows, cols), dst.shape())?;
+        unsafe {
+            cmsis_dsp_sys::arm_mat_mult_f32(&self.instance, &other.instance, &mut dst.instance)
+        }
+        .check_status()
+    }
+
+    /// Multiplies this matrix by the transpose of other, placing the result in dst
+    ///
+    /// Element (i, j) of the product is the dot product of row i of this matrix and row j of
+    /// other, so this is calculated with one `arm_dot_prod_f32` call per element, without
+    /// transposing other into a separate buffer. With other equal to this matrix, the result is
+    /// the Gram matrix `A * A^T`.
+    ///
+    /// This function returns an error if this matrix and other do not have the same number of
+    /// columns, or if dst does not have the same number of rows as this matrix and as many
+    /// columns as other has rows.
+    pub fn mult_transpose(&self, other: &MatrixF32, dst: &mut MatrixF32) -> Result<()> {
+        if self.cols() != other.cols() || dst.rows() != self.rows() || dst.cols() != other.rows() {
+            return Err(Error::SizeMismatch);
+        }
+        let cols = usize::from(self.cols());
+        let dst_data = dst.data_mut();
+        if cols == 0 || other.rows() == 0 {
+            for value in dst_data.iter_mut() {
+                *value = 0.0;
+            }
+            return Ok(());
+        }
+        let other_data = other.data();
+        for (row, dst_row) in self
+            .data()
+            .chunks_exact(cols)
+            .zip(dst_data.chunks_exact_mut(usize::from(other.rows())))
+        {
+            for (other_row, value) in other_data.chunks_exact(cols).zip(dst_row.iter_mut()) {
+                unsafe {
+                    cmsis_dsp_sys::arm_dot_prod_f32(
+                        row.as_ptr(),
+                        other_row.as_ptr(),
+                        cols as u32,
+                        value,
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Transposes this matrix, placing the result in dst
+    ///
+    /// This function returns an error if dst does not have as many rows as this matrix has
+    /// columns and as many columns as this matrix has rows.
+    pub fn transpose(&self, dst: &mut MatrixF32) -> Result<()> {
+        if dst.rows() != self.cols() || dst.cols() != self.rows() {
+            return Err(Error::SizeMismatch);
+        }
+        unsafe { cmsis_dsp_sys::arm_mat_trans_f32(&self.instance, &mut dst.instance) }
+            .check_status()
+    }
+
+    /// Calculates the inverse of this matrix, placing the result in dst
+    ///
+    /// `arm_mat_inverse_f32` uses Gauss-Jordan elimination on the elements of this matrix, so
+    /// they are modified.
+    ///
+    /// This function returns `Error::SizeMismatch` if this matrix is not square or dst does not
+    /// have the same dimensions, and `Error::Singular` if this matrix does not have an inverse.
+    pub fn inverse(&mut self, dst: &mut MatrixF32) -> Result<()> {
+        if self.rows() != self.cols() || !self.same_size(dst) {
+            return Err(Error::SizeMismatch);
+        }
+        unsafe { cmsis_dsp_sys::arm_mat_inverse_f32(&self.instance, &mut dst.instance) }
+            .check_status()
+    }
+
+    /// Multiplies each element of this matrix by factor, placing the result in dst
+    ///
+    /// # Panics
+    ///
+    /// This function panics if dst does not have the same dimensions as this matrix.
+    pub fn scale(&self, factor: f32, dst: &mut MatrixF32) {
+        assert!(
+            self.same_size(dst),
+            "dst must have the same dimensions as the matrix"
+        );
+        unsafe {
+            cmsis_dsp_sys::arm_mat_scale_f32(&self.instance, factor, &mut dst.instance);
+        }
+    }
+
+    /// Multiplies this matrix by a column vector, placing the result in out
+    ///
+    /// This is equivalent to `arm_mat_vec_mult_f32`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the length of vec is not equal to the number of columns in this
+    /// matrix, or if the length of out is not equal to the number of rows.
+    pub fn vector_mult(&self, vec: &[f32], out: &mut [f32]) {
+        let cols =
+            check_length_or_panic::<(usize, usize), u32>((usize::from(self.cols()), vec.len()));
+        check_length_or_panic::<(usize, usize), usize>((usize::from(self.rows()), out.len()));
+        if cols == 0 {
+            for value in out.iter_mut() {
+                *value = 0.0;
+            }
+            return;
+        }
+        let data = self.data();
+        for (row, value) in data.chunks_exact(cols as usize).zip(out.iter_mut()) {
+            unsafe {
+                cmsis_dsp_sys::arm_dot_prod_f32(row.as_ptr(), vec.as_ptr(), cols, value);
+            }
+        }
+    }
+
+    /// Calculates the QR decomposition of this matrix with Householder reflections
+    ///
+    /// This matrix A, with m rows and n columns, must have at least as many rows as columns.
+    /// The decomposition is `A = Q * R`, where Q is an m x m orthogonal matrix and R is an m x n
+    /// upper triangular matrix. Column j of the decomposition uses the reflection
+    /// `I - tau[j] * v * v^T`, where v is zero above row j and 1 in row j.
+    ///
+    /// This is equivalent to `arm_mat_qr_f32`.
+    ///
+    /// threshold: smallest column norm that does not make the matrix singular. When a column of
+    /// the partly reduced matrix, from the diagonal down, has a norm below this value, the
+    /// columns of A are not linearly independent.
+    ///
+    /// r: matrix with m rows and n columns that receives R
+    ///
+    /// q: matrix with m rows and m columns that receives Q
+    ///
+    /// tau: receives the n reflection coefficients
+    ///
+    /// scratch: scratch buffer with m elements
+    ///
+    /// This function returns `Error::SizeMismatch` if this matrix has fewer rows than columns or
+    /// if r or q has incorrect dimensions, and `Error::Singular` if the matrix does not have full
+    /// column rank.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if tau or scratch does not have the required length.
+    pub fn qr(
+        &self,
+        threshold: f32,
+        r: &mut MatrixF32,
+        q: &mut MatrixF32,
+        tau: &mut [f32],
+        scratch: &mut [f32],
+    ) -> Result<()> {
+        let (m, n) = (usize::from(self.rows()), usize::from(self.cols()));
+        if m < n || !self.same_size(r) || q.rows() != self.rows() || q.cols() != self.rows() {
+            return Err(Error::SizeMismatch);
+        }
+        check_length_or_panic::<(usize, usize), usize>((n, tau.len()));
+        check_length_or_panic::<(usize, usize), usize>((m, scratch.len()));
+
+        let r_data = r.data_mut();
+        r_data.copy_from_slice(self.data());
+        let q_data = q.data_mut();
+        for (i, value) in q_data.iter_mut().enumerate() {
+            *value = if i % (m + 1) == 0 { 1.0 } else { 0.0 };
+        }
+
+        for j in 0..n {
+            let v = &mut scratch[..m - j];
+            for (i, value) in v.iter_mut().enumerate() {
+                *value = r_data[(j + i) * n + j];
+            }
+            let norm = sqrt_f32(v.iter().map(|x| x * x).sum());
+            if norm < threshold || norm == 0.0 {
+                return Err(Error::Singular);
+            }
+            let x0 = v[0];
+            let beta = if x0 < 0.0 { norm } else { -norm };
+            let v0 = x0 - beta;
+            for value in v[1..].iter_mut() {
+                *value /= v0;
+            }
+            v[0] = 1.0;
+            tau[j] = (beta - x0) / beta;
+
+            // Apply the reflection to the remaining columns of R
+            for k in j + 1..n {
+                let mut sum = 0.0;
+                for (i, value) in v.iter().enumerate() {
+                    sum += value * r_data[(j + i) * n + k];
+                }
+                for (i, value) in v.iter().enumerate() {
+                    r_data[(j + i) * n + k] -= tau[j] * sum * value;
+                }
+            }
+            r_data[j * n + j] = beta;
+            for i in j + 1..m {
+                r_data[i * n + j] = 0.0;
+            }
+
+            // Apply the reflection to the right side of Q
+            for row in q_data.chunks_exact_mut(m) {
+                let mut sum = 0.0;
+                for (value, x) in v.iter().zip(row[j..].iter()) {
+                    sum += value * x;
+                }
+                for (value, x) in v.iter().zip(row[j..].iter_mut()) {
+                    *x -= tau[j] * sum * value;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the number of elements in this matrix
+    fn len(&self) -> usize {
+        usize::from(self.rows()) * usize::from(self.cols())
+    }
+
+    /// Returns the dimensions of this matrix, as (rows, columns)
+    fn shape(&self) -> (usize, usize) {
+        (usize::from(self.rows()), usize::from(self.cols()))
+    }
+
+    /// Returns true if this matrix and other have the same dimensions
+    fn same_size(&self, other: &MatrixF32) -> bool {
+        self.rows() == other.rows() && self.cols() == other.cols()
+    }
+}
+
+/// Returns `Error::DimensionMismatch` for the operation op if a matrix with dimensions got was
+/// passed where dimensions expected were required
+fn check_dimensions(op: &'static str, expected: (usize, usize), got: (usize, usize)) -> Result<()> {
+    if expected == got {
+        Ok(())
+    } else {
+        Err(Error::DimensionMismatch { expected, got, op })
+    }
+}
+
+/// A matrix of f64 values that refers to a slice of data
+///
+/// This has the same interface as `MatrixF32`. Only inversion calls CMSIS-DSP.
+///
+/// This type is available when the `f64` feature is enabled.
+#[cfg(feature = "f64")]
+pub struct MatrixF64<'a> {
+    instance: cmsis_dsp_sys::arm_matrix_instance_f64,
+    _data: PhantomData<&'a mut [f64]>,
+}
+
+#[cfg(feature = "f64")]
+impl<'a> MatrixF64<'a> {
+    /// Creates a matrix with the provided number of rows and columns, which uses data to store
+    /// its elements
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the length of data is not equal to rows * cols.
+    pub fn new(rows: u16, cols: u16, data: &'a mut [f64]) -> Self {
+        check_length_or_panic::<(usize, usize), usize>((
+            usize::from(rows) * usize::from(cols),
+            data.len(),
+        ));
+        MatrixF64 {
+            instance: cmsis_dsp_sys::arm_matrix_instance_f64 {
+                numRows: rows,
+                numCols: cols,
+                pData: data.as_mut_ptr(),
+            },
+            _data: PhantomData,
+        }
+    }
+
+    /// Creates a matrix like new, but returns an error instead of panicking if the length of data
+    /// is not valid
+    ///
+    /// This function returns `Error::LengthMismatch` if the length of data is not equal to
+    /// rows * cols.
+    pub fn from_slice(rows: u16, cols: u16, data: &'a mut [f64]) -> Result<Self> {
+        check_length::<(usize, usize), usize>((usize::from(rows) * usize::from(cols), data.len()))?;
+        Ok(Self::new(rows, cols, data))
+    }
+
+    /// Sets dst to an identity matrix, with ones on the diagonal and zeros everywhere else
+    pub fn identity(dst: &mut MatrixF64) {
+        let cols = usize::from(dst.cols());
+        for (i, value) in dst.data_mut().iter_mut().enumerate() {
+            *value = if i / cols == i % cols { 1.0 } else { 0.0 };
+        }
+    }
+
+    /// Returns the number of rows in this matrix
+    pub fn rows(&self) -> u16 {
+        self.instance.numRows
+    }
+
+    /// Returns the number of columns in this matrix
+    pub fn cols(&self) -> u16 {
+        self.instance.numCols
+    }
+
+    /// Returns the elements of this matrix
+    pub fn data(&self) -> &[f64] {
+        unsafe { core::slice::from_raw_parts(self.instance.pData, self.len()) }
+    }
+
+    /// Returns the elements of this matrix, which can be modified
+    pub fn data_mut(&mut self) -> &mut [f64] {
+        unsafe { core::slice::from_raw_parts_mut(self.instance.pData, self.len()) }
+    }
+
+    /// Adds this matrix and other, placing the result in dst
+    ///
+    /// This function returns `Error::DimensionMismatch` if other or dst does not have the same
+    /// dimensions as this matrix.
+    pub fn add(&self, other: &MatrixF64, dst: &mut MatrixF64) -> Result<()> {
+        check_dimensions("add", self.shape(), other.shape())?;
+        check_dimensions("add", self.shape(), dst.shape())?;
+        self.element_wise(other, dst, |a, b| a + b)
+    }
+
+    /// Subtracts other from this matrix, placing the result in dst
+    ///
+    /// This function returns `Error::DimensionMismatch` if other or dst does not have the same
+    /// dimensions as this matrix.
+    pub fn sub(&self, other: &MatrixF64, dst: &mut MatrixF64) -> Result<()> {
+        check_dimensions("sub", self.shape(), other.shape())?;
+        check_dimensions("sub", self.shape(), dst.shape())?;
+        self.element_wise(other, dst, |a, b| a - b)
+    }
+
+    /// Multiplies this matrix by other, placing the result in dst
+    ///
+    /// This function returns `Error::DimensionMismatch` if the number of columns in this matrix
+    /// is not equal to the number of rows in other, or if dst does not have the same number of
+    /// rows as this matrix and the same number of columns as other.
+    pub fn mult(&self, other: &MatrixF64, dst: &mut MatrixF64) -> Result<()> {
+        let (rows, cols) = (usize::from(self.rows()), usize::from(other.cols()));
+        check_dimensions("mult", (usize::from(self.cols()), cols), other.shape())?;
+        check_dimensions("mult", (rows, cols), dst.shape())?;
+        let (inner, cols) = (usize::from(self.cols()), usize::from(other.cols()));
+        let (a, b) = (self.data(), other.data());
+        for (i, value) in dst.data_mut().iter_mut().enumerate() {
+            let (row, col) = (i / cols, i % cols);
+            *value = (0..inner)
+                .map(|k| a[row * inner + k] * b[k * cols + col])
+                .sum();
+        }
+        Ok(())
+    }
+
+    /// Transposes this matrix, placing the result in dst
+    ///
+    /// This function returns an error if dst does not have as many rows as this matrix has
+    /// columns and as many columns as this matrix has rows.
+    pub fn transpose(&self, dst: &mut MatrixF64) -> Result<()> {
+        if dst.rows() != self.cols() || dst.cols() != self.rows() {
+            return Err(Error::SizeMismatch);
+        }
+        let (rows, cols) = (usize::from(self.rows()), usize::from(self.cols()));
+        let src = self.data();
+        for (i, value) in dst.data_mut().iter_mut().enumerate() {
+            *value = src[(i % rows) * cols + i / rows];
+        }
+        Ok(())
+    }
+
+    /// Multiplies each element of this matrix by factor, placing the result in dst
+    ///
+    /// # Panics
+    ///
+    /// This function panics if dst does not have the same dimensions as this matrix.
+    pub fn scale(&self, factor: f64, dst: &mut MatrixF64) {
+        assert!(
+            self.same_size(dst),
+            "dst must have the same dimensions as the matrix"
+        );
+        for (value, out) in self.data().iter().zip(dst.data_mut()) {
+            *out = value * factor;
+        }
+    }
+
+    /// Calculates the inverse of this matrix, placing the result in dst
+    ///
+    /// `arm_mat_inverse_f64` uses Gauss-Jordan elimination on the elements of this matrix, so
+    /// they are modified.
+    ///
+    /// This function returns `Error::SizeMismatch` if this matrix is not square or dst does not
+    /// have the same dimensions, and `Error::Singular` if this matrix does not have an inverse.
+    pub fn inverse(&mut self, dst: &mut MatrixF64) -> Result<()> {
+        if self.rows() != self.cols() || !self.same_size(dst) {
+            return Err(Error::SizeMismatch);
+        }
+        unsafe { cmsis_dsp_sys::arm_mat_inverse_f64(&self.instance, &mut dst.instance) }
+            .check_status()
+    }
+
+    /// Calculates each element of dst from the corresponding elements of this matrix and other,
+    /// which must have the same dimensions
+    fn element_wise<F>(&self, other: &MatrixF64, dst: &mut MatrixF64, operation: F) -> Result<()>
+    where
+        F: Fn(f64, f64) -> f64,
+    {
+        for ((a, b), out) in self.data().iter().zip(other.data()).zip(dst.data_mut()) {
+            *out = operation(*a, *b);
+        }
+        Ok(())
+    }
+
+    /// Returns the number of elements in this matrix
+    fn len(&self) -> usize {
+        usize::from(self.rows()) * usize::from(self.cols())
+    }
+
+    /// Returns the dimensions of this matrix, as (rows, columns)
+    fn shape(&self) -> (usize, usize) {
+        (usize::from(self.rows()), usize::from(self.cols()))
+    }
+
+    /// Returns true if this matrix and other have the same dimensions
+    fn same_size(&self, other: &MatrixF64) -> bool {
+        self.rows() == other.rows() && self.cols() == other.cols()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns element (i, j) of the n x n Hilbert matrix, `1 / (i + j + 1)`, which is
+    /// ill-conditioned: the 4 x 4 matrix has a condition number of about 15500
+    fn hilbert(i: usize, j: usize) -> f64 {
+        1.0 / (i + j + 1) as f64
+    }
+
+    /// Returns the largest absolute difference between a 4 x 4 matrix and the identity matrix
+    fn identity_error<T: Into<f64> + Copy>(data: &[T]) -> f64 {
+        data.iter()
+            .enumerate()
+            .map(|(i, value)| {
+                let expected = if i % 5 == 0 { 1.0 } else { 0.0 };
+                ((*value).into() - expected).abs()
+            })
+            .fold(0.0, f64::max)
+    }
+
+    fn hilbert_inverse_error_f32() -> f64 {
+        let mut a = [0.0f32; 16];
+        for (i, value) in a.iter_mut().enumerate() {
+            *value = hilbert(i / 4, i % 4) as f32;
+        }
+        let mut a_copy = a;
+        let (mut inverse, mut product) = ([0.0; 16], [0.0; 16]);
+        let mut inverse = MatrixF32::new(4, 4, &mut inverse);
+        MatrixF32::new(4, 4, &mut a_copy)
+            .inverse(&mut inverse)
+            .unwrap();
+        let mut product_matrix = MatrixF32::new(4, 4, &mut product);
+        MatrixF32::new(4, 4, &mut a)
+            .mult(&inverse, &mut product_matrix)
+            .unwrap();
+        identity_error(&product)
+    }
+
+    #[test]
+    fn inverse_of_ill_conditioned_matrix_f32() {
+        // f32 has a precision of about 6e-8, which the condition number amplifies
+        let error = hilbert_inverse_error_f32();
+        assert!(error < 1e-3, "error {}", error);
+    }
+
+    #[test]
+    #[cfg(feature = "f64")]
+    fn inverse_of_ill_conditioned_matrix_f64() {
+        let mut a = [0.0f64; 16];
+        for (i, value) in a.iter_mut().enumerate() {
+            *value = hilbert(i / 4, i % 4);
+        }
+        let mut a_copy = a;
+        let (mut inverse, mut product) = ([0.0; 16], [0.0; 16]);
+        let mut inverse = MatrixF64::new(4, 4, &mut inverse);
+        MatrixF64::new(4, 4, &mut a_copy)
+            .inverse(&mut inverse)
+            .unwrap();
+        let mut product_matrix = MatrixF64::new(4, 4, &mut product);
+        MatrixF64::new(4, 4, &mut a)
+            .mult(&inverse, &mut product_matrix)
+            .unwrap();
+        let error = identity_error(&product);
+        assert!(error < 1e-10, "error {}", error);
+        // The f64 inverse is accurate to many more digits than the f32 inverse
+        let error_f32 = hilbert_inverse_error_f32();
+        assert!(error * 1e4 < error_f32, "f64 {} f32 {}", error, error_f32);
+    }
+
+    /// Returns the contents of a `DimensionMismatch` error
+    fn dimension_mismatch(result: Result<()>) -> ((usize, usize), (usize, usize), &'static str) {
+        match result {
+            Err(Error::DimensionMismatch { expected, got, op }) => (expected, got, op),
+            other => panic!("Expected DimensionMismatch, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn mult_dimension_mismatch() {
+        let (mut a, mut b, mut c) = ([0.0f32; 6], [0.0f32; 6], [0.0f32; 9]);
+        let a = MatrixF32::new(2, 3, &mut a);
+        // The inner dimensions do not match
+        let mut c_matrix = MatrixF32::new(2, 2, &mut c[..4]);
+        assert_eq!(
+            dimension_mismatch(a.mult(&MatrixF32::new(2, 2, &mut b[..4]), &mut c_matrix)),
+            ((3, 2), (2, 2), "mult")
+        );
+        // The product is 2 x 2, not 3 x 3
+        let b = MatrixF32::new(3, 2, &mut b);
+        assert_eq!(
+            dimension_mismatch(a.mult(&b, &mut MatrixF32::new(3, 3, &mut c))),
+            ((2, 2), (3, 3), "mult")
+        );
+    }
+
+    #[test]
+    fn add_and_sub() {
+        let (mut a, mut b, mut c) = ([1.0, 2.0, 3.0, 4.0], [0.5, -1.0, 2.0, 0.0], [0.0; 4]);
+        let (a, b) = (MatrixF32::new(2, 2, &mut a), MatrixF32::new(2, 2, &mut b));
+        let mut c_matrix = MatrixF32::new(2, 2, &mut c);
+        a.add(&b, &mut c_matrix).unwrap();
+        assert_eq!(c_matrix.data(), [1.5, 1.0, 5.0, 4.0]);
+        a.sub(&b, &mut c_matrix).unwrap();
+        assert_eq!(c_matrix.data(), [0.5, 3.0, 1.0, 4.0]);
+    }
+
+    #[test]
+    fn add_dimension_mismatch() {
+        let (mut a, mut b, mut c) = ([0.0f32; 4], [0.0f32; 6], [0.0f32; 6]);
+        let a = MatrixF32::new(2, 2, &mut a);
+        assert_eq!(
+            dimension_mismatch(a.add(
+                &MatrixF32::new(2, 3, &mut b),
+                &mut MatrixF32::new(2, 2, &mut c[..4])
+            )),
+            ((2, 2), (2, 3), "add")
+        );
+        assert_eq!(
+            dimension_mismatch(a.sub(
+                &MatrixF32::new(2, 2, &mut b[..4]),
+                &mut MatrixF32::new(3, 2, &mut c)
+            )),
+            ((2, 2), (3, 2), "sub")
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "f64")]
+    fn f64_dimension_mismatch() {
+        let (mut a, mut b, mut c) = ([0.0f64; 6], [0.0f64; 6], [0.0f64; 4]);
+        let a = MatrixF64::new(2, 3, &mut a);
+        let mut c = MatrixF64::new(2, 2, &mut c);
+        assert_eq!(
+            dimension_mismatch(a.mult(&MatrixF64::new(2, 3, &mut b), &mut c)),
+            ((3, 3), (2, 3), "mult")
+        );
+        assert_eq!(
+            dimension_mismatch(a.add(&MatrixF64::new(2, 3, &mut b), &mut c)),
+            ((2, 3), (2, 2), "add")
+        );
+    }
+
+    /// Data of a 4 x 3 matrix with linearly independent columns
+    const TALL: [f32; 12] = [
+        2.0, -1.0, 0.5, //
+        1.0, 3.0, -2.0, //
+        -0.5, 1.0, 4.0, //
+        1.5, 0.0, 1.0,
+    ];
+
+    #[test]
+    fn qr_reconstructs_tall_matrix() {
+        let mut a_data = TALL;
+        let a = MatrixF32::new(4, 3, &mut a_data);
+        let (mut r_data, mut q_data) = ([0.0; 12], [0.0; 16]);
+        let mut r = MatrixF32::new(4, 3, &mut r_data);
+        let mut q = MatrixF32::new(4, 4, &mut q_data);
+        let (mut tau, mut scratch) = ([0.0; 3], [0.0; 4]);
+        a.qr(1e-6, &mut r, &mut q, &mut tau, &mut scratch).unwrap();
+
+        // R is upper triangular
+        for i in 0..4 {
+            for j in 0..i.min(3) {
+                assert_eq!(r.data()[i * 3 + j], 0.0, "R[{}][{}]", i, j);
+            }
+        }
+        // Q * R = A
+        let mut product_data = [0.0; 12];
+        let mut product = MatrixF32::new(4, 3, &mut product_data);
+        q.mult(&r, &mut product).unwrap();
+        for (value, expected) in product.data().iter().zip(TALL.iter()) {
+            assert!((value - expected).abs() < 1e-5, "{:?}", product.data());
+        }
+        // Q is orthogonal, so Q * Q^T = I
+        let mut gram_data = [0.0; 16];
+        let mut gram = MatrixF32::new(4, 4, &mut gram_data);
+        q.mult_transpose(&q, &mut gram).unwrap();
+        assert!(identity_error(gram.data()) < 1e-5, "{:?}", gram.data());
+    }
+
+    #[test]
+    fn qr_of_rank_deficient_matrix_is_singular() {
+        // The third column is the sum of the first two
+        let mut a_data = TALL;
+        for row in a_data.chunks_exact_mut(3) {
+            row[2] = row[0] + row[1];
+        }
+        let a = MatrixF32::new(4, 3, &mut a_data);
+        let (mut r_data, mut q_data) = ([0.0; 12], [0.0; 16]);
+        let mut r = MatrixF32::new(4, 3, &mut r_data);
+        let mut q = MatrixF32::new(4, 4, &mut q_data);
+        let (mut tau, mut scratch) = ([0.0; 3], [0.0; 4]);
+        let result = a.qr(1e-4, &mut r, &mut q, &mut tau, &mut scratch);
+        assert!(matches!(result, Err(Error::Singular)));
+    }
+
+    #[test]
+    fn gram_matrix_is_symmetric() {
+        let mut a_data = TALL;
+        let a = MatrixF32::new(4, 3, &mut a_data);
+        let mut gram_data = [0.0; 16];
+        let mut gram = MatrixF32::new(4, 4, &mut gram_data);
+        a.mult_transpose(&a, &mut gram).unwrap();
+        let gram = gram.data();
+        for i in 0..4 {
+            for j in 0..4 {
+                assert_eq!(gram[i * 4 + j], gram[j * 4 + i], "({}, {})", i, j);
+            }
+            // The diagonal holds the squared norms of the rows
+            let row = &TALL[i * 3..i * 3 + 3];
+            assert_eq!(gram[i * 5], row.iter().map(|x| x * x).sum::<f32>());
+        }
+        // Rows 0 and 1: 2 * 1 - 1 * 3 - 0.5 * 2
+        assert_eq!(gram[1], -2.0);
+    }
+
+    #[test]
+    fn mult_transpose_checks_dimensions() {
+        let (mut a_data, mut b_data, mut dst_data) = ([0.0; 6], [0.0; 6], [0.0; 4]);
+        let a = MatrixF32::new(2, 3, &mut a_data);
+        let b = MatrixF32::new(3, 2, &mut b_data);
+        let mut dst = MatrixF32::new(2, 2, &mut dst_data);
+        assert!(matches!(
+            a.mult_transpose(&b, &mut dst),
+            Err(Error::SizeMismatch)
+        ));
+    }
+
+    #[test]
+    fn scale() {
+        let mut a_data = [1.0, -2.0, 0.5, 4.0, 0.0, -0.25];
+        let a = MatrixF32::new(2, 3, &mut a_data);
+        let mut dst_data = [0.0; 6];
+        let mut dst = MatrixF32::new(2, 3, &mut dst_data);
+        a.scale(-2.0, &mut dst);
+        assert_eq!(dst.data(), [-2.0, 4.0, -1.0, -8.0, 0.0, 0.5]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn scale_checks_dimensions() {
+        let (mut a_data, mut dst_data) = ([0.0; 6], [0.0; 6]);
+        let a = MatrixF32::new(2, 3, &mut a_data);
+        let mut dst = MatrixF32::new(3, 2, &mut dst_data);
+        a.scale(1.0, &mut dst);
+    }
+
+    #[test]
+    fn vector_mult() {
+        let mut a_data = [1.0, -2.0, 0.5, 4.0, 0.0, -0.25];
+        let a = MatrixF32::new(2, 3, &mut a_data);
+        let mut out = [0.0; 2];
+        a.vector_mult(&[2.0, 1.0, -4.0], &mut out);
+        // 2 - 2 - 2 and 8 + 0 + 1
+        assert_eq!(out, [-2.0, 9.0]);
+    }
+
+    #[test]
+    fn vector_mult_with_zero_columns() {
+        let a = MatrixF32::new(3, 0, &mut []);
+        let mut out = [1.0; 3];
+        a.vector_mult(&[], &mut out);
+        assert_eq!(out, [0.0; 3]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn vector_mult_checks_length() {
+        let mut a_data = [0.0; 6];
+        let a = MatrixF32::new(2, 3, &mut a_data);
+        a.vector_mult(&[0.0; 2], &mut [0.0; 2]);
+    }
+
+    #[test]
+    fn identity_square() {
+        let mut data = [5.0; 9];
+        let mut matrix = MatrixF32::new(3, 3, &mut data);
+        MatrixF32::identity(&mut matrix);
+        assert_eq!(matrix.data(), [1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn identity_non_square() {
+        let mut wide_data = [5.0; 6];
+        let mut wide = MatrixF32::new(2, 3, &mut wide_data);
+        MatrixF32::identity(&mut wide);
+        assert_eq!(wide.data(), [1.0, 0.0, 0.0, 0.0, 1.0, 0.0]);
+
+        let mut tall_data = [5.0; 6];
+        let mut tall = MatrixF32::new(3, 2, &mut tall_data);
+        MatrixF32::identity(&mut tall);
+        assert_eq!(tall.data(), [1.0, 0.0, 0.0, 1.0, 0.0, 0.0]);
+    }
+}
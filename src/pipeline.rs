@@ -0,0 +1,319 @@
+//! Processing pipelines that combine conversions, filters, and analysis
+//!
+//! A [`Pipeline`] converts a block of input samples to f32, passes it through a sequence of
+//! stages, and converts the result to the output type. It owns the intermediate f32 buffers, so
+//! a complete signal path can run with a single call:
+//!
+//! ```ignore
+//! let mut pipeline = Pipeline::new(support::q15_to_f32, support::f32_to_q15)
+//!     .stage(|src, dst| filter.process(src, dst, block_size))
+//!     .inspect(|block| level = statistics::rms_f32(block));
+//! pipeline.run(&input, &mut output);
+//! ```
+//!
+//! A [`BlockFramer`] collects samples that arrive in chunks of any size into blocks of a fixed
+//! size, for filters that process a fixed number of samples per call.
+//!
+//! This module is available when the `alloc` feature is enabled.
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use crate::check_length_or_panic;
+
+/// A function that converts or processes a block of samples into a block of the same length
+type BlockFn<'a, S, D> = Box<dyn FnMut(&[S], &mut [D]) + 'a>;
+/// A function that reads a block of samples
+type InspectFn<'a> = Box<dyn FnMut(&[f32]) + 'a>;
+
+/// One step of a pipeline
+enum Step<'a> {
+    /// Transforms a block into a block of the same length
+    Process(BlockFn<'a, f32, f32>),
+    /// Reads a block without changing it
+    Inspect(InspectFn<'a>),
+}
+
+/// A sequence of operations that converts input samples of type I to f32, processes them, and
+/// converts the result to output samples of type O
+///
+/// Stages and inspectors run in the order in which they were added. The pipeline keeps two f32
+/// buffers with the length of the most recent block, and each stage reads from one and writes to
+/// the other, so no stage needs to work in place.
+///
+/// The lifetime 'a allows stages to borrow filters and other state from the caller.
+pub struct Pipeline<'a, I, O> {
+    input: BlockFn<'a, I, f32>,
+    steps: Vec<Step<'a>>,
+    output: BlockFn<'a, f32, O>,
+    buffers: [Vec<f32>; 2],
+}
+
+impl<'a, I, O> Pipeline<'a, I, O> {
+    /// Creates a pipeline with no stages
+    ///
+    /// input: converts input samples to f32, for example `support::q15_to_f32`
+    ///
+    /// output: converts f32 samples to output samples, for example `support::f32_to_q15`
+    ///
+    /// Each conversion is called with two slices of the same length.
+    pub fn new<In, Out>(input: In, output: Out) -> Self
+    where
+        In: FnMut(&[I], &mut [f32]) + 'a,
+        Out: FnMut(&[f32], &mut [O]) + 'a,
+    {
+        Pipeline {
+            input: Box::new(input),
+            steps: Vec::new(),
+            output: Box::new(output),
+            buffers: [Vec::new(), Vec::new()],
+        }
+    }
+
+    /// Adds a stage that processes each block, such as a filter
+    ///
+    /// The stage is called with a source and a destination slice of the same length, and must
+    /// write every element of the destination.
+    pub fn stage<F>(mut self, stage: F) -> Self
+    where
+        F: FnMut(&[f32], &mut [f32]) + 'a,
+    {
+        self.steps.push(Step::Process(Box::new(stage)));
+        self
+    }
+
+    /// Adds an inspector that reads each block at this point of the pipeline, for example to
+    /// calculate statistics
+    pub fn inspect<F>(mut self, inspector: F) -> Self
+    where
+        F: FnMut(&[f32]) + 'a,
+    {
+        self.steps.push(Step::Inspect(Box::new(inspector)));
+        self
+    }
+
+    /// Runs a block of samples through the pipeline
+    ///
+    /// # Panics
+    ///
+    /// This function panics if input and output do not have the same length.
+    pub fn run(&mut self, input: &[I], output: &mut [O]) {
+        let length = check_length_or_panic::<(usize, usize), usize>((input.len(), output.len()));
+        for buffer in self.buffers.iter_mut() {
+            buffer.resize(length, 0.0);
+        }
+        let [first, second] = &mut self.buffers;
+        let (mut current, mut next) = (first, second);
+        (self.input)(input, current);
+        for step in self.steps.iter_mut() {
+            match step {
+                Step::Process(stage) => {
+                    stage(current, next);
+                    core::mem::swap(&mut current, &mut next);
+                }
+                Step::Inspect(inspector) => inspector(current),
+            }
+        }
+        (self.output)(current, output);
+    }
+}
+
+/// A buffer that accepts samples in chunks of any length and returns them in frames of a fixed
+/// length
+///
+/// Samples are returned in the order in which they were pushed, and each sample is returned
+/// exactly once. Samples that do not yet fill a frame stay in the buffer until more samples
+/// arrive.
+///
+/// ```
+/// use cmsis_dsp::pipeline::BlockFramer;
+///
+/// let mut framer = BlockFramer::new(64);
+/// framer.push(&[0.5; 100]);
+/// while let Some(frame) = framer.next_frame() {
+///     // Process the frame, for example with FirFilter::process
+///     assert_eq!(frame.len(), 64);
+/// }
+/// assert_eq!(framer.buffered(), 36);
+/// ```
+pub struct BlockFramer {
+    block_size: usize,
+    /// Buffered samples. The samples before start have already been returned.
+    buffer: Vec<f32>,
+    start: usize,
+}
+
+impl BlockFramer {
+    /// Creates an empty framer that returns frames of block_size samples
+    ///
+    /// # Panics
+    ///
+    /// This function panics if block_size is zero.
+    pub fn new(block_size: usize) -> Self {
+        assert!(block_size != 0, "Block size must not be zero");
+        BlockFramer {
+            block_size,
+            buffer: Vec::with_capacity(2 * block_size),
+            start: 0,
+        }
+    }
+
+    /// Returns the number of samples in each frame
+    pub fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    /// Returns the number of samples that have been pushed and not yet returned in a frame
+    pub fn buffered(&self) -> usize {
+        self.buffer.len() - self.start
+    }
+
+    /// Adds samples to the end of the buffer
+    pub fn push(&mut self, samples: &[f32]) {
+        // Discard the samples that have been returned, so the buffer only grows to hold the
+        // samples that have not
+        if self.start != 0 {
+            self.buffer.drain(..self.start);
+            self.start = 0;
+        }
+        self.buffer.extend_from_slice(samples);
+    }
+
+    /// Returns the next complete frame of block_size samples, or None if fewer than block_size
+    /// samples are buffered
+    pub fn next_frame(&mut self) -> Option<&[f32]> {
+        if self.buffered() < self.block_size {
+            return None;
+        }
+        let start = self.start;
+        self.start += self.block_size;
+        Some(&self.buffer[start..self.start])
+    }
+
+    /// Discards all buffered samples
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+        self.start = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filter::FirFilter;
+    use crate::{statistics, support};
+    use alloc::vec;
+    use fixed::types::{I1F15, I1F31};
+
+    #[test]
+    fn block_framer_returns_samples_in_order() {
+        let mut framer = BlockFramer::new(64);
+        let mut returned = Vec::new();
+        let mut next_sample = 0.0;
+        for _ in 0..20 {
+            let chunk: Vec<f32> = (0..50).map(|i| next_sample + i as f32).collect();
+            next_sample += 50.0;
+            framer.push(&chunk);
+            while let Some(frame) = framer.next_frame() {
+                assert_eq!(frame.len(), 64);
+                returned.extend_from_slice(frame);
+            }
+            assert!(framer.buffered() < 64);
+        }
+        // 1000 samples fill 15 frames, and 40 samples remain
+        assert_eq!(returned.len(), 15 * 64);
+        assert_eq!(framer.buffered(), 1000 - 15 * 64);
+        for (i, &sample) in returned.iter().enumerate() {
+            assert_eq!(sample, i as f32);
+        }
+    }
+
+    #[test]
+    fn block_framer_returns_several_frames_from_one_push() {
+        let mut framer = BlockFramer::new(4);
+        let samples: Vec<f32> = (0..10).map(|i| i as f32).collect();
+        framer.push(&samples);
+        assert_eq!(framer.next_frame(), Some(&samples[0..4]));
+        assert_eq!(framer.next_frame(), Some(&samples[4..8]));
+        assert_eq!(framer.next_frame(), None);
+        assert_eq!(framer.buffered(), 2);
+        framer.push(&[10.0, 11.0]);
+        assert_eq!(framer.next_frame(), Some(&[8.0, 9.0, 10.0, 11.0][..]));
+        assert_eq!(framer.buffered(), 0);
+    }
+
+    #[test]
+    fn block_framer_clear() {
+        let mut framer = BlockFramer::new(4);
+        framer.push(&[1.0, 2.0, 3.0]);
+        framer.clear();
+        assert_eq!(framer.buffered(), 0);
+        framer.push(&[4.0, 5.0, 6.0, 7.0]);
+        assert_eq!(framer.next_frame(), Some(&[4.0, 5.0, 6.0, 7.0][..]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn block_framer_rejects_zero_block_size() {
+        BlockFramer::new(0);
+    }
+
+    #[test]
+    fn pipeline_converts_filters_and_inspects() {
+        let coeffs = [0.25, 0.5, 0.25];
+        let mut state = [0.0; 34];
+        let mut filter = FirFilter::new(3, &coeffs, &mut state, 32).unwrap();
+        let mut levels = Vec::new();
+        let mut inputs = Vec::new();
+        let mut pipeline = Pipeline::new(support::q15_to_f32, support::f32_to_q15)
+            .inspect(|block| inputs.push(block[0]))
+            .stage(|src, dst| filter.process(src, dst, src.len() as u32))
+            .stage(|src, dst| {
+                for (x, y) in src.iter().zip(dst.iter_mut()) {
+                    *y = 2.0 * x;
+                }
+            })
+            .inspect(|block| levels.push(statistics::rms(block)));
+
+        let input: Vec<I1F15> = (0..32)
+            .map(|i| I1F15::from_num((i % 8) as f32 / 32.0))
+            .collect();
+        let mut output = vec![I1F15::ZERO; 32];
+        pipeline.run(&input[..20], &mut output[..20]);
+        // A shorter block resizes the buffers, and the filter state carries over
+        pipeline.run(&input[20..], &mut output[20..]);
+        drop(pipeline);
+
+        let mut state = [0.0; 34];
+        let mut expected = [0.0; 32];
+        let samples: Vec<f32> = input.iter().map(|x| x.to_num()).collect();
+        FirFilter::new(3, &coeffs, &mut state, 32)
+            .unwrap()
+            .process(&samples, &mut expected, 32);
+        let expected: Vec<I1F15> = expected.iter().map(|y| I1F15::from_num(2.0 * y)).collect();
+        assert_eq!(output, expected);
+
+        assert_eq!(inputs, [0.0, 0.125]);
+        let doubled: Vec<f32> = expected.iter().map(|y| y.to_num()).collect();
+        assert_eq!(levels.len(), 2);
+        assert!((levels[0] - statistics::rms(&doubled[..20])).abs() < 1e-4);
+        assert!((levels[1] - statistics::rms(&doubled[20..])).abs() < 1e-4);
+    }
+
+    #[test]
+    fn pipeline_without_stages_converts() {
+        let mut pipeline = Pipeline::new(support::q15_to_f32, support::f32_to_q31);
+        let input = [I1F15::from_num(0.5), I1F15::MIN, I1F15::from_num(-0.25)];
+        let mut output = [I1F31::ZERO; 3];
+        pipeline.run(&input, &mut output);
+        assert_eq!(output, [0.5, -1.0, -0.25].map(I1F31::from_num));
+        pipeline.run(&[], &mut []);
+    }
+
+    #[test]
+    #[should_panic]
+    fn pipeline_checks_length() {
+        let mut pipeline = Pipeline::new(support::q15_to_f32, support::f32_to_q15);
+        pipeline.run(&[I1F15::ZERO; 4], &mut [I1F15::ZERO; 3]);
+    }
+}
@@ -0,0 +1,324 @@
+//! Quaternion operations
+//!
+//! These functions follow the conventions of the CMSIS-DSP quaternion functions
+//! (`arm_quaternion_normalize_f32` and others). Quaternions are stored as `[w, x, y, z]`, where
+//! w is the real part, and rotation matrices are 3x3 matrices stored in row-major order.
+
+use crate::check_length_or_panic;
+use crate::fast_math::sqrt_f32;
+
+/// A quaternion `w + x i + y j + z k`, stored as `[w, x, y, z]`
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[repr(transparent)]
+pub struct Quaternion(pub [f32; 4]);
+
+impl Quaternion {
+    /// The identity quaternion, which represents no rotation
+    pub const IDENTITY: Quaternion = Quaternion([1.0, 0.0, 0.0, 0.0]);
+
+    /// Creates a quaternion from its real part w and imaginary parts x, y, and z
+    pub fn new(w: f32, x: f32, y: f32, z: f32) -> Self {
+        Quaternion([w, x, y, z])
+    }
+
+    /// Returns the norm (length) of this quaternion
+    pub fn norm(&self) -> f32 {
+        let [w, x, y, z] = self.0;
+        sqrt_f32(w * w + x * x + y * y + z * z)
+    }
+
+    /// Returns this quaternion scaled to have a norm of 1
+    ///
+    /// This is equivalent to `arm_quaternion_normalize_f32`.
+    pub fn normalize(&self) -> Quaternion {
+        let inverse_norm = 1.0 / self.norm();
+        let [w, x, y, z] = self.0;
+        Quaternion([
+            w * inverse_norm,
+            x * inverse_norm,
+            y * inverse_norm,
+            z * inverse_norm,
+        ])
+    }
+
+    /// Returns the conjugate of this quaternion, `w - x i - y j - z k`
+    ///
+    /// This is equivalent to `arm_quaternion_conjugate_f32`.
+    pub fn conjugate(&self) -> Quaternion {
+        let [w, x, y, z] = self.0;
+        Quaternion([w, -x, -y, -z])
+    }
+
+    /// Returns the Hamilton product `self * other`
+    ///
+    /// This is equivalent to `arm_quaternion_product_single_f32`.
+    pub fn product(&self, other: &Quaternion) -> Quaternion {
+        let [aw, ax, ay, az] = self.0;
+        let [bw, bx, by, bz] = other.0;
+        Quaternion([
+            aw * bw - ax * bx - ay * by - az * bz,
+            aw * bx + ax * bw + ay * bz - az * by,
+            aw * by - ax * bz + ay * bw + az * bx,
+            aw * bz + ax * by - ay * bx + az * bw,
+        ])
+    }
+
+    /// Converts this quaternion, which must have a norm of 1, into a rotation matrix
+    ///
+    /// This is equivalent to `arm_quaternion2rotation_f32`.
+    pub fn to_rotation(&self) -> [f32; 9] {
+        let [w, x, y, z] = self.0;
+        [
+            1.0 - 2.0 * (y * y + z * z),
+            2.0 * (x * y - w * z),
+            2.0 * (x * z + w * y),
+            2.0 * (x * y + w * z),
+            1.0 - 2.0 * (x * x + z * z),
+            2.0 * (y * z - w * x),
+            2.0 * (x * z - w * y),
+            2.0 * (y * z + w * x),
+            1.0 - 2.0 * (x * x + y * y),
+        ]
+    }
+
+    /// Converts a rotation matrix into a quaternion with a norm of 1
+    ///
+    /// This is equivalent to `arm_rotation2quaternion_f32`.
+    pub fn from_rotation(m: &[f32; 9]) -> Quaternion {
+        let trace = m[0] + m[4] + m[8];
+        let q = if trace > 0.0 {
+            let doubler = sqrt_f32(trace + 1.0) * 2.0;
+            let s = 1.0 / doubler;
+            [
+                0.25 * doubler,
+                (m[7] - m[5]) * s,
+                (m[2] - m[6]) * s,
+                (m[3] - m[1]) * s,
+            ]
+        } else if m[0] > m[4] && m[0] > m[8] {
+            let doubler = sqrt_f32(1.0 + m[0] - m[4] - m[8]) * 2.0;
+            let s = 1.0 / doubler;
+            [
+                (m[7] - m[5]) * s,
+                0.25 * doubler,
+                (m[1] + m[3]) * s,
+                (m[2] + m[6]) * s,
+            ]
+        } else if m[4] > m[8] {
+            let doubler = sqrt_f32(1.0 + m[4] - m[0] - m[8]) * 2.0;
+            let s = 1.0 / doubler;
+            [
+                (m[2] - m[6]) * s,
+                (m[1] + m[3]) * s,
+                0.25 * doubler,
+                (m[5] + m[7]) * s,
+            ]
+        } else {
+            let doubler = sqrt_f32(1.0 + m[8] - m[0] - m[4]) * 2.0;
+            let s = 1.0 / doubler;
+            [
+                (m[3] - m[1]) * s,
+                (m[2] + m[6]) * s,
+                (m[5] + m[7]) * s,
+                0.25 * doubler,
+            ]
+        };
+        Quaternion(q)
+    }
+}
+
+/// Normalizes multiple quaternions
+///
+/// # Panics
+///
+/// This function panics if src and dst do not have the same length.
+pub fn normalize_f32(src: &[Quaternion], dst: &mut [Quaternion]) {
+    check_length_or_panic::<(usize, usize), usize>((src.len(), dst.len()));
+    for (q, out) in src.iter().zip(dst.iter_mut()) {
+        *out = q.normalize();
+    }
+}
+
+/// Calculates the conjugates of multiple quaternions
+///
+/// # Panics
+///
+/// This function panics if src and dst do not have the same length.
+pub fn conjugate_f32(src: &[Quaternion], dst: &mut [Quaternion]) {
+    check_length_or_panic::<(usize, usize), usize>((src.len(), dst.len()));
+    for (q, out) in src.iter().zip(dst.iter_mut()) {
+        *out = q.conjugate();
+    }
+}
+
+/// Calculates the Hamilton products of multiple pairs of quaternions
+///
+/// This is functionally equivalent to performing `dst[i] = src_a[i] * src_b[i]` for all values
+/// of i in range.
+///
+/// # Panics
+///
+/// This function panics if src_a, src_b, and dst do not have the same length.
+pub fn product_f32(src_a: &[Quaternion], src_b: &[Quaternion], dst: &mut [Quaternion]) {
+    check_length_or_panic::<(usize, usize, usize), usize>((src_a.len(), src_b.len(), dst.len()));
+    for ((a, b), out) in src_a.iter().zip(src_b.iter()).zip(dst.iter_mut()) {
+        *out = a.product(b);
+    }
+}
+
+/// Converts multiple quaternions, which must have norms of 1, into rotation matrices
+///
+/// # Panics
+///
+/// This function panics if src and dst do not have the same length.
+pub fn quaternion_to_rotation_f32(src: &[Quaternion], dst: &mut [[f32; 9]]) {
+    check_length_or_panic::<(usize, usize), usize>((src.len(), dst.len()));
+    for (q, out) in src.iter().zip(dst.iter_mut()) {
+        *out = q.to_rotation();
+    }
+}
+
+/// Converts multiple rotation matrices into quaternions
+///
+/// # Panics
+///
+/// This function panics if src and dst do not have the same length.
+pub fn rotation_to_quaternion_f32(src: &[[f32; 9]], dst: &mut [Quaternion]) {
+    check_length_or_panic::<(usize, usize), usize>((src.len(), dst.len()));
+    for (m, out) in src.iter().zip(dst.iter_mut()) {
+        *out = Quaternion::from_rotation(m);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOLERANCE: f32 = 1e-5;
+
+    /// Quaternions that are not normalized, including rotations of 180 degrees about each axis
+    const QUATERNIONS: [Quaternion; 6] = [
+        Quaternion([1.0, 2.0, 3.0, 4.0]),
+        Quaternion([-0.5, 0.1, -2.0, 0.7]),
+        Quaternion([3.0, 0.0, 0.0, 0.2]),
+        Quaternion([0.0, 2.0, 0.0, 0.0]),
+        Quaternion([0.0, 0.0, 0.5, 0.0]),
+        Quaternion([0.0, 0.0, 0.0, 3.0]),
+    ];
+
+    fn assert_close(a: &[f32], b: &[f32]) {
+        for (x, y) in a.iter().zip(b.iter()) {
+            assert!((x - y).abs() < TOLERANCE, "{:?} != {:?}", a, b);
+        }
+    }
+
+    /// Returns the product of two 3x3 matrices in row-major order
+    fn mat_mult(a: &[f32; 9], b: &[f32; 9]) -> [f32; 9] {
+        let mut out = [0.0; 9];
+        for row in 0..3 {
+            for col in 0..3 {
+                out[3 * row + col] = (0..3).map(|k| a[3 * row + k] * b[3 * k + col]).sum();
+            }
+        }
+        out
+    }
+
+    fn transpose(m: &[f32; 9]) -> [f32; 9] {
+        [m[0], m[3], m[6], m[1], m[4], m[7], m[2], m[5], m[8]]
+    }
+
+    #[test]
+    fn normalized_rotation_is_orthonormal() {
+        let identity = [1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0];
+        for q in QUATERNIONS.iter() {
+            let unit = q.normalize();
+            assert!((unit.norm() - 1.0).abs() < TOLERANCE);
+            let m = unit.to_rotation();
+            assert_close(&mat_mult(&m, &transpose(&m)), &identity);
+            let determinant = m[0] * (m[4] * m[8] - m[5] * m[7])
+                - m[1] * (m[3] * m[8] - m[5] * m[6])
+                + m[2] * (m[3] * m[7] - m[4] * m[6]);
+            assert!((determinant - 1.0).abs() < TOLERANCE);
+        }
+        assert_eq!(Quaternion::IDENTITY.to_rotation(), identity);
+    }
+
+    #[test]
+    fn rotation_round_trip() {
+        for q in QUATERNIONS.iter() {
+            let unit = q.normalize();
+            let round_trip = Quaternion::from_rotation(&unit.to_rotation());
+            // q and -q represent the same rotation
+            let dot: f32 = round_trip
+                .0
+                .iter()
+                .zip(unit.0.iter())
+                .map(|(a, b)| a * b)
+                .sum();
+            let sign = if dot < 0.0 { -1.0 } else { 1.0 };
+            let mut round_trip = round_trip.0;
+            for value in round_trip.iter_mut() {
+                *value *= sign;
+            }
+            assert_close(&round_trip, &unit.0);
+        }
+    }
+
+    #[test]
+    fn hamilton_product() {
+        let i = Quaternion::new(0.0, 1.0, 0.0, 0.0);
+        let j = Quaternion::new(0.0, 0.0, 1.0, 0.0);
+        let k = Quaternion::new(0.0, 0.0, 0.0, 1.0);
+        assert_eq!(i.product(&j), k);
+        assert_eq!(j.product(&i), Quaternion::new(0.0, 0.0, 0.0, -1.0));
+        assert_eq!(k.product(&k), Quaternion::new(-1.0, 0.0, 0.0, 0.0));
+
+        // q * conj(q) is the squared norm
+        let q = QUATERNIONS[0];
+        assert_eq!(q.conjugate(), Quaternion::new(1.0, -2.0, -3.0, -4.0));
+        assert_eq!(
+            q.product(&q.conjugate()),
+            Quaternion::new(30.0, 0.0, 0.0, 0.0)
+        );
+
+        // Rotating a vector with q v conj(q) matches the rotation matrix
+        let vector = [1.0f32, -2.0, 0.5];
+        let unit = QUATERNIONS[1].normalize();
+        let rotated = unit
+            .product(&Quaternion::new(0.0, vector[0], vector[1], vector[2]))
+            .product(&unit.conjugate());
+        let m = unit.to_rotation();
+        let mut expected = [0.0; 3];
+        for (row, value) in expected.iter_mut().enumerate() {
+            *value = (0..3).map(|col| m[3 * row + col] * vector[col]).sum();
+        }
+        assert_close(&rotated.0[1..], &expected);
+    }
+
+    #[test]
+    fn batch_functions() {
+        let mut unit = [Quaternion::IDENTITY; 6];
+        normalize_f32(&QUATERNIONS, &mut unit);
+        let mut conjugates = [Quaternion::IDENTITY; 6];
+        conjugate_f32(&unit, &mut conjugates);
+        let mut products = [Quaternion::IDENTITY; 6];
+        product_f32(&unit, &conjugates, &mut products);
+        for product in products.iter() {
+            assert_close(&product.0, &Quaternion::IDENTITY.0);
+        }
+        let mut matrices = [[0.0; 9]; 6];
+        quaternion_to_rotation_f32(&unit, &mut matrices);
+        let mut round_trip = [Quaternion::IDENTITY; 6];
+        rotation_to_quaternion_f32(&matrices, &mut round_trip);
+        for ((q, m), round_trip) in unit.iter().zip(matrices.iter()).zip(round_trip.iter()) {
+            assert_eq!(q.to_rotation(), *m);
+            assert_close(&round_trip.to_rotation(), m);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn batch_functions_check_length() {
+        normalize_f32(&QUATERNIONS, &mut [Quaternion::IDENTITY; 5]);
+    }
+}
@@ -0,0 +1,1231 @@
+//! Short-time Fourier transform processing
+//!
+//! The types in this module divide a signal into overlapping frames, multiply each frame by a
+//! Hanning window (or, for types that process a stream of samples, a square-root Hanning window
+//! before and after modifying the spectrum), and transform it with a
+//! [`FloatRealFft`](crate::transform::FloatRealFft).
+//! Spectra use the packed format of the real FFT: elements 0 and 1 contain the real parts of
+//! the DC and Nyquist bins, and the remaining elements contain the real and imaginary parts of
+//! the other bins.
+//!
+//! This module is available when the `alloc` feature is enabled.
+
+use core::convert::TryInto;
+use core::f32::consts::{LN_2, PI};
+
+use alloc::vec;
+use alloc::vec::Vec;
+use num_complex::Complex32;
+
+use crate::fast_math::{atan2_f32, cos_f32, exp_f32, ln_f32, sin_f32, sqrt_f32};
+use crate::transform::FloatRealFft;
+use crate::window::{hanning_f32, Window};
+use crate::{check_length_or_panic, Error, Result};
+
+/// Reconstructs a signal from a magnitude spectrogram using the Griffin-Lim algorithm
+///
+/// Each iteration transforms the current spectrogram estimate back into a signal, transforms
+/// that signal into a new spectrogram, and replaces the magnitudes of the new spectrogram with
+/// the target magnitudes. The phases converge toward a set that is consistent with the
+/// magnitudes. The first estimate uses a phase of zero for every bin.
+///
+/// A magnitude spectrogram contains `fft_size / 2 + 1` magnitudes (from DC to the Nyquist
+/// frequency) for each frame, with the frames stored one after another.
+pub struct GriffinLim {
+    fft: FloatRealFft,
+    window: Vec<f32>,
+    hop_size: usize,
+    iterations: usize,
+}
+
+impl GriffinLim {
+    /// Creates a Griffin-Lim reconstructor
+    ///
+    /// fft_size: number of samples in each frame. Valid values are 32, 64, 128, 256, 512, 1024,
+    /// 2048, and 4096.
+    ///
+    /// hop_size: number of samples between the starts of adjacent frames. This must be greater
+    /// than zero and no greater than fft_size. For a good reconstruction, it should be at most
+    /// fft_size / 2.
+    ///
+    /// iterations: number of iterations that reconstruct runs
+    ///
+    /// This function returns an error if fft_size or hop_size is not valid.
+    pub fn new(fft_size: u16, hop_size: usize, iterations: usize) -> Result<Self> {
+        if hop_size == 0 || hop_size > usize::from(fft_size) {
+            return Err(Error::Argument);
+        }
+        let fft = FloatRealFft::new(fft_size)?;
+        let mut window = vec![0.0; usize::from(fft_size)];
+        hanning_f32(&mut window);
+        Ok(GriffinLim {
+            fft,
+            window,
+            hop_size,
+            iterations,
+        })
+    }
+
+    /// Returns the number of magnitudes in each frame of a spectrogram
+    pub fn bins(&self) -> usize {
+        self.window.len() / 2 + 1
+    }
+
+    /// Returns the length of the signal that corresponds to a number of frames
+    pub fn signal_length(&self, frames: usize) -> usize {
+        if frames == 0 {
+            0
+        } else {
+            (frames - 1) * self.hop_size + self.window.len()
+        }
+    }
+
+    /// Calculates the magnitude spectrogram of a signal
+    ///
+    /// The spectrogram uses the same frames, window, and hop size as reconstruct. The number of
+    /// frames is `(signal.len() - fft_size) / hop_size + 1`, and any samples after the last
+    /// complete frame are ignored.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if signal is shorter than fft_size, or if magnitudes does not have
+    /// `bins()` elements for each frame.
+    pub fn magnitudes(&self, signal: &[f32], magnitudes: &mut [f32]) {
+        let size = self.window.len();
+        assert!(signal.len() >= size, "Signal shorter than one frame");
+        let frames = (signal.len() - size) / self.hop_size + 1;
+        assert_eq!(magnitudes.len(), frames * self.bins());
+
+        let mut frame = vec![0.0; size];
+        let mut spectrum = vec![0.0; size];
+        for (index, frame_magnitudes) in magnitudes.chunks_exact_mut(self.bins()).enumerate() {
+            self.analyze(signal, index, &mut frame, &mut spectrum);
+            packed_magnitudes(&spectrum, frame_magnitudes);
+        }
+    }
+
+    /// Reconstructs a signal from a magnitude spectrogram, placing the result in output
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the length of magnitudes is not a non-zero multiple of `bins()`,
+    /// or if the length of output is not `signal_length(magnitudes.len() / bins())`.
+    pub fn reconstruct(&self, magnitudes: &[f32], output: &mut [f32]) {
+        let size = self.window.len();
+        let bins = self.bins();
+        assert!(
+            !magnitudes.is_empty() && magnitudes.len().is_multiple_of(bins),
+            "Magnitude length must be a non-zero multiple of the number of bins"
+        );
+        let frames = magnitudes.len() / bins;
+        assert_eq!(output.len(), self.signal_length(frames));
+
+        // Squared window sums, used to normalize the overlap-added frames
+        let mut normalization = vec![0.0; output.len()];
+        for index in 0..frames {
+            let start = index * self.hop_size;
+            for (sum, w) in normalization[start..start + size]
+                .iter_mut()
+                .zip(self.window.iter())
+            {
+                *sum += w * w;
+            }
+        }
+
+        let mut spectra = vec![0.0; frames * size];
+        for (spectrum, frame_magnitudes) in spectra
+            .chunks_exact_mut(size)
+            .zip(magnitudes.chunks_exact(bins))
+        {
+            apply_magnitudes(spectrum, frame_magnitudes);
+        }
+
+        let mut frame = vec![0.0; size];
+        let mut scratch = vec![0.0; size];
+        for _ in 0..self.iterations {
+            self.synthesize(&spectra, &normalization, &mut frame, &mut scratch, output);
+            for ((index, spectrum), frame_magnitudes) in spectra
+                .chunks_exact_mut(size)
+                .enumerate()
+                .zip(magnitudes.chunks_exact(bins))
+            {
+                self.analyze(output, index, &mut frame, spectrum);
+                apply_magnitudes(spectrum, frame_magnitudes);
+            }
+        }
+        self.synthesize(&spectra, &normalization, &mut frame, &mut scratch, output);
+    }
+
+    /// Windows one frame of a signal and calculates its spectrum
+    fn analyze(&self, signal: &[f32], index: usize, frame: &mut [f32], spectrum: &mut [f32]) {
+        let start = index * self.hop_size;
+        for ((value, sample), w) in frame
+            .iter_mut()
+            .zip(signal[start..].iter())
+            .zip(self.window.iter())
+        {
+            *value = sample * w;
+        }
+        self.fft.run(frame, spectrum);
+    }
+
+    /// Converts a set of spectra into a signal with windowed overlap-add
+    fn synthesize(
+        &self,
+        spectra: &[f32],
+        normalization: &[f32],
+        frame: &mut [f32],
+        scratch: &mut [f32],
+        output: &mut [f32],
+    ) {
+        let size = self.window.len();
+        for value in output.iter_mut() {
+            *value = 0.0;
+        }
+        for (index, spectrum) in spectra.chunks_exact(size).enumerate() {
+            // The inverse FFT modifies its input, so run it on a copy
+            scratch.copy_from_slice(spectrum);
+            self.fft.run_inverse(scratch, frame);
+            let start = index * self.hop_size;
+            for ((value, sample), w) in output[start..start + size]
+                .iter_mut()
+                .zip(frame.iter())
+                .zip(self.window.iter())
+            {
+                *value += sample * w;
+            }
+        }
+        for (value, sum) in output.iter_mut().zip(normalization.iter()) {
+            if *sum > 1e-6 {
+                *value /= sum;
+            }
+        }
+    }
+}
+
+/// Calculates the magnitude of each bin of a packed real FFT spectrum
+fn packed_magnitudes(spectrum: &[f32], magnitudes: &mut [f32]) {
+    let last = magnitudes.len() - 1;
+    magnitudes[0] = spectrum[0].abs();
+    magnitudes[last] = spectrum[1].abs();
+    for (magnitude, bin) in magnitudes[1..last]
+        .iter_mut()
+        .zip(spectrum[2..].chunks_exact(2))
+    {
+        *magnitude = sqrt_f32(bin[0] * bin[0] + bin[1] * bin[1]);
+    }
+}
+
+/// Replaces the magnitude of each bin of a packed real FFT spectrum, keeping its phase
+///
+/// Bins with a magnitude of zero are given a phase of zero.
+fn apply_magnitudes(spectrum: &mut [f32], magnitudes: &[f32]) {
+    let last = magnitudes.len() - 1;
+    spectrum[0] = if spectrum[0] < 0.0 {
+        -magnitudes[0]
+    } else {
+        magnitudes[0]
+    };
+    spectrum[1] = if spectrum[1] < 0.0 {
+        -magnitudes[last]
+    } else {
+        magnitudes[last]
+    };
+    for (bin, magnitude) in spectrum[2..]
+        .chunks_exact_mut(2)
+        .zip(magnitudes[1..last].iter())
+    {
+        let current = sqrt_f32(bin[0] * bin[0] + bin[1] * bin[1]);
+        if current > 0.0 {
+            let scale = magnitude / current;
+            bin[0] *= scale;
+            bin[1] *= scale;
+        } else {
+            bin[0] = *magnitude;
+            bin[1] = 0.0;
+        }
+    }
+}
+
+/// Splits a stream of samples into overlapping frames, transforms each frame, passes the
+/// spectrum to a function that can modify it, and reconstructs the stream with overlap-add
+///
+/// Frames are multiplied by a square-root Hanning window before the forward FFT and after the
+/// inverse FFT. If the function does not modify the spectra, the output is the input delayed
+/// by fft_size samples.
+struct StreamingStft {
+    fft: FloatRealFft,
+    window: Vec<f32>,
+    hop_size: usize,
+    /// Gain that compensates for the sum of the overlapping squared windows
+    scale: f32,
+    /// The most recent fft_size input samples. The last hop_size samples are filled in as
+    /// they arrive.
+    input: Vec<f32>,
+    /// Overlap-add accumulator
+    output: Vec<f32>,
+    /// Completed output samples, which are returned as new input samples arrive
+    ready: Vec<f32>,
+    /// Number of samples received since the last frame
+    count: usize,
+    frame: Vec<f32>,
+    spectrum: Vec<f32>,
+}
+
+impl StreamingStft {
+    /// Creates a streaming STFT
+    ///
+    /// This function returns an error if fft_size is not a valid real FFT size, or if
+    /// hop_size is not fft_size divided by an integer of at least 2.
+    fn new(fft_size: u16, hop_size: usize) -> Result<Self> {
+        let size = usize::from(fft_size);
+        if hop_size == 0 || !size.is_multiple_of(hop_size) || size / hop_size < 2 {
+            return Err(Error::Argument);
+        }
+        let fft = FloatRealFft::new(fft_size)?;
+        let mut window = vec![0.0; size];
+        hanning_f32(&mut window);
+        for value in window.iter_mut() {
+            *value = sqrt_f32(*value);
+        }
+        Ok(StreamingStft {
+            fft,
+            window,
+            hop_size,
+            // The squared windows (periodic Hanning windows) sum to size / (2 * hop_size)
+            scale: 2.0 * hop_size as f32 / size as f32,
+            input: vec![0.0; size],
+            output: vec![0.0; size],
+            ready: vec![0.0; hop_size],
+            count: 0,
+            frame: vec![0.0; size],
+            spectrum: vec![0.0; size],
+        })
+    }
+
+    /// Returns the number of samples in each frame
+    fn size(&self) -> usize {
+        self.window.len()
+    }
+
+    /// Processes a block of samples, calling modify with the packed spectrum of each frame
+    /// that is completed
+    ///
+    /// # Panics
+    ///
+    /// This function panics if src and dst do not have the same length.
+    fn process<F>(&mut self, src: &[f32], dst: &mut [f32], mut modify: F)
+    where
+        F: FnMut(&mut [f32]),
+    {
+        check_length_or_panic::<(usize, usize), usize>((src.len(), dst.len()));
+        let first_new = self.size() - self.hop_size;
+        for (x, y) in src.iter().zip(dst.iter_mut()) {
+            *y = self.ready[self.count];
+            self.input[first_new + self.count] = *x;
+            self.count += 1;
+            if self.count == self.hop_size {
+                self.count = 0;
+                self.run_frame(&mut modify);
+            }
+        }
+    }
+
+    fn run_frame<F>(&mut self, modify: &mut F)
+    where
+        F: FnMut(&mut [f32]),
+    {
+        let hop_size = self.hop_size;
+        for ((value, sample), w) in self
+            .frame
+            .iter_mut()
+            .zip(self.input.iter())
+            .zip(self.window.iter())
+        {
+            *value = sample * w;
+        }
+        self.fft.run(&self.frame, &mut self.spectrum);
+        modify(&mut self.spectrum);
+        self.fft.run_inverse(&self.spectrum, &mut self.frame);
+        for ((sum, sample), w) in self
+            .output
+            .iter_mut()
+            .zip(self.frame.iter())
+            .zip(self.window.iter())
+        {
+            *sum += sample * w * self.scale;
+        }
+
+        self.ready.copy_from_slice(&self.output[..hop_size]);
+        self.output.copy_within(hop_size.., 0);
+        let size = self.size();
+        for value in self.output[size - hop_size..].iter_mut() {
+            *value = 0.0;
+        }
+        self.input.copy_within(hop_size.., 0);
+    }
+
+    /// Clears all buffered input and output samples
+    fn reset(&mut self) {
+        let buffers = self
+            .input
+            .iter_mut()
+            .chain(self.output.iter_mut())
+            .chain(self.ready.iter_mut());
+        for value in buffers {
+            *value = 0.0;
+        }
+        self.count = 0;
+    }
+}
+
+/// Runs a spectral-domain process on a stream of samples
+///
+/// The processor splits the stream into overlapping frames, multiplies each frame by a
+/// square-root Hanning window, and transforms it with an FFT. It passes the spectrum of each
+/// frame to a function that can modify it, and then transforms the spectrum back, multiplies
+/// it by the window again, and reconstructs the stream with overlap-add. The windows and the
+/// hop size are chosen so that a function that does not modify the spectra reproduces the
+/// input exactly, delayed by fft_size samples.
+///
+/// Each spectrum contains `fft_size / 2 + 1` bins, from DC to the Nyquist frequency. The
+/// imaginary parts of the DC and Nyquist bins are always zero on input and are ignored on
+/// output, because the signal is real.
+pub struct FrameProcessor {
+    stft: StreamingStft,
+    bins: Vec<Complex32>,
+}
+
+impl FrameProcessor {
+    /// Creates a frame processor
+    ///
+    /// fft_size: number of samples in each frame. Valid values are 32, 64, 128, 256, 512, 1024,
+    /// 2048, and 4096.
+    ///
+    /// hop_size: number of samples between the starts of adjacent frames. This must be
+    /// fft_size divided by an integer of at least 2, like fft_size / 2 or fft_size / 4.
+    ///
+    /// This function returns an error if fft_size or hop_size is not valid.
+    pub fn new(fft_size: u16, hop_size: usize) -> Result<Self> {
+        let stft = StreamingStft::new(fft_size, hop_size)?;
+        let bins = vec![Complex32::new(0.0, 0.0); usize::from(fft_size) / 2 + 1];
+        Ok(FrameProcessor { stft, bins })
+    }
+
+    /// Returns the delay between the input and the output, in samples
+    pub fn latency(&self) -> usize {
+        self.stft.size()
+    }
+
+    /// Processes a block of samples, calling modify with the spectrum of each frame that is
+    /// completed
+    ///
+    /// # Panics
+    ///
+    /// This function panics if src and dst do not have the same length.
+    pub fn process<F>(&mut self, src: &[f32], dst: &mut [f32], mut modify: F)
+    where
+        F: FnMut(&mut [Complex32]),
+    {
+        let FrameProcessor { stft, bins } = self;
+        stft.process(src, dst, |spectrum| {
+            let nyquist = bins.len() - 1;
+            bins[0] = Complex32::new(spectrum[0], 0.0);
+            bins[nyquist] = Complex32::new(spectrum[1], 0.0);
+            for (bin, pair) in bins[1..nyquist]
+                .iter_mut()
+                .zip(spectrum[2..].chunks_exact(2))
+            {
+                *bin = Complex32::new(pair[0], pair[1]);
+            }
+            modify(bins);
+            spectrum[0] = bins[0].re;
+            spectrum[1] = bins[nyquist].re;
+            for (bin, pair) in bins[1..nyquist]
+                .iter()
+                .zip(spectrum[2..].chunks_exact_mut(2))
+            {
+                pair[0] = bin.re;
+                pair[1] = bin.im;
+            }
+        });
+    }
+
+    /// Clears all buffered input and output samples
+    pub fn reset(&mut self) {
+        self.stft.reset();
+    }
+}
+
+/// Weight of the previous value when smoothing the power of each bin between frames
+const GATE_POWER_SMOOTHING: f32 = 0.7;
+/// Factor by which the noise floor estimate of each bin can rise in each frame
+const GATE_NOISE_RISE: f32 = 1.01;
+
+/// Reduces stationary background noise by attenuating frequency bins that are not
+/// significantly above the noise floor
+///
+/// The gate tracks a noise floor for each frequency bin: the noise floor follows the smoothed
+/// power of the bin down immediately, and rises slowly (by 1% per frame) when the bin is
+/// louder. Bins with smoothed power above `margin` times the noise floor pass unchanged,
+/// and other bins are multiplied by `attenuation`. Each bin's gain moves gradually toward
+/// its target, which avoids the "musical noise" produced by gains that change abruptly.
+///
+/// The noise floor estimates start from the first frame that contains only input samples, so
+/// the silence before the input does not lower them. The output is delayed by fft_size samples.
+pub struct SpectralGate {
+    stft: StreamingStft,
+    margin: f32,
+    attenuation: f32,
+    smoothing: f32,
+    /// Smoothed power of each bin
+    power: Vec<f32>,
+    /// Noise floor estimate of each bin
+    noise: Vec<f32>,
+    /// Current gain of each bin
+    gains: Vec<f32>,
+    /// Number of frames, counting from when this gate was created or reset, that do not yet
+    /// contain only received samples. The estimates are reset on each of these frames, so
+    /// they start from the first complete frame instead of a frame that is mostly silence.
+    warmup_frames: usize,
+}
+
+impl SpectralGate {
+    /// Creates a spectral gate
+    ///
+    /// fft_size: number of samples in each frame. Valid values are 32, 64, 128, 256, 512, 1024,
+    /// 2048, and 4096.
+    ///
+    /// hop_size: number of samples between the starts of adjacent frames. This must be
+    /// fft_size divided by an integer of at least 2, like fft_size / 2 or fft_size / 4.
+    ///
+    /// margin: ratio of bin power to noise floor power above which a bin passes. For example,
+    /// 4.0 passes bins that are more than 6 dB above the noise floor.
+    ///
+    /// attenuation: gain applied to bins that do not pass, like 0.1 (-20 dB)
+    ///
+    /// smoothing: how much of each bin's gain carries over from one frame to the next, in the
+    /// range [0, 1). Larger values reduce musical noise but respond more slowly.
+    ///
+    /// This function returns an error if fft_size or hop_size is not valid.
+    pub fn new(
+        fft_size: u16,
+        hop_size: usize,
+        margin: f32,
+        attenuation: f32,
+        smoothing: f32,
+    ) -> Result<Self> {
+        let stft = StreamingStft::new(fft_size, hop_size)?;
+        let bins = usize::from(fft_size) / 2 + 1;
+        Ok(SpectralGate {
+            stft,
+            margin,
+            attenuation,
+            smoothing,
+            power: vec![0.0; bins],
+            noise: vec![0.0; bins],
+            gains: vec![1.0; bins],
+            warmup_frames: usize::from(fft_size) / hop_size,
+        })
+    }
+
+    /// Processes a block of samples
+    ///
+    /// # Panics
+    ///
+    /// This function panics if src and dst do not have the same length.
+    pub fn process(&mut self, src: &[f32], dst: &mut [f32]) {
+        let SpectralGate {
+            stft,
+            margin,
+            attenuation,
+            smoothing,
+            power,
+            noise,
+            gains,
+            warmup_frames,
+        } = self;
+        stft.process(src, dst, |spectrum| {
+            for bin in 0..power.len() {
+                let bin_power = packed_bin_power(spectrum, bin);
+                if *warmup_frames != 0 {
+                    power[bin] = bin_power;
+                    noise[bin] = bin_power;
+                } else {
+                    power[bin] = GATE_POWER_SMOOTHING * power[bin]
+                        + (1.0 - GATE_POWER_SMOOTHING) * bin_power;
+                    noise[bin] = if power[bin] < noise[bin] {
+                        power[bin]
+                    } else {
+                        noise[bin] * GATE_NOISE_RISE
+                    };
+                }
+                let target = if power[bin] > *margin * noise[bin] {
+                    1.0
+                } else {
+                    *attenuation
+                };
+                gains[bin] = *smoothing * gains[bin] + (1.0 - *smoothing) * target;
+                scale_packed_bin(spectrum, bin, gains[bin]);
+            }
+            *warmup_frames = warmup_frames.saturating_sub(1);
+        });
+    }
+
+    /// Clears the buffered samples and the noise floor estimates
+    pub fn reset(&mut self) {
+        self.stft.reset();
+        for gain in self.gains.iter_mut() {
+            *gain = 1.0;
+        }
+        self.warmup_frames = self.stft.size() / self.stft.hop_size;
+    }
+}
+
+/// Returns the squared magnitude of one bin of a packed real FFT spectrum
+fn packed_bin_power(spectrum: &[f32], bin: usize) -> f32 {
+    let nyquist = spectrum.len() / 2;
+    if bin == 0 {
+        spectrum[0] * spectrum[0]
+    } else if bin == nyquist {
+        spectrum[1] * spectrum[1]
+    } else {
+        spectrum[2 * bin] * spectrum[2 * bin] + spectrum[2 * bin + 1] * spectrum[2 * bin + 1]
+    }
+}
+
+/// Multiplies one bin of a packed real FFT spectrum by a gain
+fn scale_packed_bin(spectrum: &mut [f32], bin: usize, gain: f32) {
+    let nyquist = spectrum.len() / 2;
+    if bin == 0 {
+        spectrum[0] *= gain;
+    } else if bin == nyquist {
+        spectrum[1] *= gain;
+    } else {
+        spectrum[2 * bin] *= gain;
+        spectrum[2 * bin + 1] *= gain;
+    }
+}
+
+/// Changes the duration of a signal without changing its pitch, using a phase vocoder
+///
+/// The vocoder reads overlapping frames from the input that are `analysis_hop` samples apart,
+/// and writes them to the output `stretch * analysis_hop` samples apart. For each frequency
+/// bin, it estimates the true frequency from the phase difference between adjacent input
+/// frames, and advances the output phase at that frequency, so sinusoids stay continuous
+/// across output frames.
+pub struct PhaseVocoder {
+    fft: FloatRealFft,
+    window: Vec<f32>,
+    analysis_hop: usize,
+    synthesis_hop: usize,
+}
+
+impl PhaseVocoder {
+    /// Creates a phase vocoder
+    ///
+    /// fft_size: number of samples in each frame. Valid values are 32, 64, 128, 256, 512, 1024,
+    /// 2048, and 4096.
+    ///
+    /// analysis_hop: number of input samples between the starts of adjacent frames. This must
+    /// be greater than zero, and should be at most fft_size / 4.
+    ///
+    /// stretch: ratio of output duration to input duration. Values greater than 1 make the
+    /// signal longer (slower).
+    ///
+    /// The output hop, `stretch * analysis_hop` rounded to the nearest integer, must be in the
+    /// range [1, fft_size]. This function returns an error if it is not, or if fft_size or
+    /// analysis_hop is not valid.
+    pub fn new(fft_size: u16, analysis_hop: usize, stretch: f32) -> Result<Self> {
+        let synthesis_hop = (analysis_hop as f32 * stretch + 0.5) as usize;
+        let size = usize::from(fft_size);
+        if analysis_hop == 0 || analysis_hop > size || synthesis_hop == 0 || synthesis_hop > size {
+            return Err(Error::Argument);
+        }
+        let fft = FloatRealFft::new(fft_size)?;
+        let mut window = vec![0.0; size];
+        hanning_f32(&mut window);
+        Ok(PhaseVocoder {
+            fft,
+            window,
+            analysis_hop,
+            synthesis_hop,
+        })
+    }
+
+    /// Returns the number of output samples produced from an input signal with the provided
+    /// length
+    ///
+    /// The input is divided into `(input_length - fft_size) / analysis_hop + 1` frames, and
+    /// the output has `(frames - 1) * synthesis_hop + fft_size` samples. If the input is
+    /// shorter than one frame, the output is empty.
+    pub fn output_length(&self, input_length: usize) -> usize {
+        let size = self.window.len();
+        if input_length < size {
+            0
+        } else {
+            let frames = (input_length - size) / self.analysis_hop + 1;
+            (frames - 1) * self.synthesis_hop + size
+        }
+    }
+
+    /// Time-stretches a signal and returns the result
+    ///
+    /// The returned signal has `output_length(src.len())` samples.
+    pub fn process(&self, src: &[f32]) -> Vec<f32> {
+        let size = self.window.len();
+        let mut output = vec![0.0; self.output_length(src.len())];
+        if output.is_empty() {
+            return output;
+        }
+        let frames = (src.len() - size) / self.analysis_hop + 1;
+        let bins = size / 2 + 1;
+
+        let mut frame = vec![0.0; size];
+        let mut spectrum = vec![0.0; size];
+        let mut previous_phase = vec![0.0; bins];
+        let mut output_phase = vec![0.0; bins];
+        let mut normalization = vec![0.0; output.len()];
+
+        for index in 0..frames {
+            let input_start = index * self.analysis_hop;
+            for ((value, sample), w) in frame
+                .iter_mut()
+                .zip(src[input_start..].iter())
+                .zip(self.window.iter())
+            {
+                *value = sample * w;
+            }
+            self.fft.run(&frame, &mut spectrum);
+
+            // The DC and Nyquist bins are real, so they pass through unchanged
+            for bin in 1..bins - 1 {
+                let (re, im) = (spectrum[2 * bin], spectrum[2 * bin + 1]);
+                let magnitude = sqrt_f32(re * re + im * im);
+                let phase = atan2_f32(im, re);
+                if index == 0 {
+                    output_phase[bin] = phase;
+                } else {
+                    let bin_frequency = 2.0 * PI * bin as f32 / size as f32;
+                    let expected = bin_frequency * self.analysis_hop as f32;
+                    let deviation = wrap_phase(phase - previous_phase[bin] - expected);
+                    let frequency = bin_frequency + deviation / self.analysis_hop as f32;
+                    output_phase[bin] =
+                        wrap_phase(output_phase[bin] + frequency * self.synthesis_hop as f32);
+                }
+                previous_phase[bin] = phase;
+                spectrum[2 * bin] = magnitude * cos_f32(output_phase[bin]);
+                spectrum[2 * bin + 1] = magnitude * sin_f32(output_phase[bin]);
+            }
+
+            self.fft.run_inverse(&spectrum, &mut frame);
+            let output_start = index * self.synthesis_hop;
+            for (((value, sum), sample), w) in output[output_start..output_start + size]
+                .iter_mut()
+                .zip(normalization[output_start..output_start + size].iter_mut())
+                .zip(frame.iter())
+                .zip(self.window.iter())
+            {
+                *value += sample * w;
+                *sum += w * w;
+            }
+        }
+        for (value, sum) in output.iter_mut().zip(normalization.iter()) {
+            if *sum > 1e-6 {
+                *value /= sum;
+            }
+        }
+        output
+    }
+}
+
+/// Shifts the pitch of a signal without changing its duration
+///
+/// The pitch shifter stretches the signal with a [`PhaseVocoder`] by the pitch ratio, and then
+/// resamples the stretched signal with linear interpolation so that it has the original
+/// duration. Resampling multiplies every frequency by the ratio.
+pub struct PitchShifter {
+    vocoder: PhaseVocoder,
+    ratio: f32,
+}
+
+impl PitchShifter {
+    /// Creates a pitch shifter
+    ///
+    /// fft_size: number of samples in each frame. Valid values are 32, 64, 128, 256, 512, 1024,
+    /// 2048, and 4096.
+    ///
+    /// hop_size: number of input samples between the starts of adjacent frames. This should be
+    /// at most fft_size / 4.
+    ///
+    /// semitones: amount to shift the pitch by. Positive values raise the pitch, and negative
+    /// values lower it. A shift of 12 semitones doubles every frequency.
+    ///
+    /// The vocoder's output hop is rounded to an integer, so the actual ratio may differ
+    /// slightly from `2 ^ (semitones / 12)`. A larger hop_size makes the ratio more accurate.
+    ///
+    /// This function returns an error if fft_size or hop_size is not valid, or if the shift is
+    /// too large for the hop size.
+    pub fn new(fft_size: u16, hop_size: usize, semitones: f32) -> Result<Self> {
+        let target_ratio = exp_f32(semitones / 12.0 * LN_2);
+        let vocoder = PhaseVocoder::new(fft_size, hop_size, target_ratio)?;
+        let ratio = vocoder.synthesis_hop as f32 / vocoder.analysis_hop as f32;
+        Ok(PitchShifter { vocoder, ratio })
+    }
+
+    /// Returns the ratio of output frequencies to input frequencies
+    pub fn ratio(&self) -> f32 {
+        self.ratio
+    }
+
+    /// Shifts the pitch of a signal and returns the result
+    ///
+    /// The returned signal has the same length as src. Samples at the end of src that do not
+    /// fill a complete frame are not processed, so the corresponding output samples are zero.
+    pub fn process(&self, src: &[f32]) -> Vec<f32> {
+        let stretched = self.vocoder.process(src);
+        let mut output = vec![0.0; src.len()];
+        for (i, value) in output.iter_mut().enumerate() {
+            let position = i as f32 * self.ratio;
+            let index = position as usize;
+            if index + 1 >= stretched.len() {
+                break;
+            }
+            let fraction = position - index as f32;
+            *value = stretched[index] + fraction * (stretched[index + 1] - stretched[index]);
+        }
+        output
+    }
+}
+
+/// Estimates the power spectral density of a signal with a windowed periodogram
+///
+/// The signal is multiplied by the window and transformed with one FFT of length
+/// `src.len()`. Each bin of the result is `|X[k]|^2 / sum(w^2)`, where X is the FFT of the
+/// windowed signal and w is the window, and every bin except DC and Nyquist is doubled so that
+/// dst is a one-sided spectrum. This is the density per unit of normalized frequency (cycles
+/// per sample): to get the density per hertz, divide each value by the sample rate.
+///
+/// With this normalization, the power (mean square value) of the signal is about
+/// `sum(dst) / src.len()`, independent of the window.
+///
+/// The length of src must be one of the valid sizes of a [`FloatRealFft`], and dst must have
+/// `src.len() / 2 + 1` elements. This function returns an error if the length of src is not
+/// valid.
+///
+/// # Panics
+///
+/// This function panics if dst does not have the required length.
+pub fn periodogram_f32(src: &[f32], window: Window, dst: &mut [f32]) -> Result<()> {
+    let size = src.len();
+    let fft = FloatRealFft::new(size.try_into().map_err(|_| Error::Argument)?)?;
+    check_length_or_panic::<(usize, usize), usize>((size / 2 + 1, dst.len()));
+
+    let mut weights = vec![0.0; size];
+    window.fill(&mut weights);
+    let window_power: f32 = weights.iter().map(|w| w * w).sum();
+    let frame: Vec<f32> = src.iter().zip(weights.iter()).map(|(x, w)| x * w).collect();
+    let mut spectrum = vec![0.0; size];
+    fft.run(&frame, &mut spectrum);
+
+    let nyquist = size / 2;
+    for (bin, value) in dst.iter_mut().enumerate() {
+        let scale = if bin == 0 || bin == nyquist { 1.0 } else { 2.0 };
+        *value = scale * packed_bin_power(&spectrum, bin) / window_power;
+    }
+    Ok(())
+}
+
+/// Smallest magnitude, relative to the largest magnitude, that `minimum_phase_f32` uses
+/// (-100 dB). Smaller magnitudes, including zeros, are raised to this level because their
+/// logarithms are not finite.
+const MINIMUM_PHASE_FLOOR: f32 = 1e-5;
+
+/// Calculates the impulse response of the minimum-phase filter with the provided magnitude
+/// response
+///
+/// Of all causal filters with the same magnitude response, the minimum-phase filter has the
+/// least delay: its energy is concentrated at the start of the impulse response. This function
+/// uses the cepstral method. It transforms the logarithm of the magnitude into the real
+/// cepstrum, folds the anti-causal part of the cepstrum onto the causal part, and transforms
+/// the result back into a spectrum, which has the same magnitude and a minimum phase.
+///
+/// magnitude: magnitude response at `dst.len() / 2 + 1` evenly spaced frequencies, from DC to
+/// the Nyquist frequency. Magnitudes below 1e-5 times the largest magnitude (-100 dB) are
+/// raised to that level.
+///
+/// dst: receives the impulse response. Its length is the FFT size, which must be one of the
+/// valid sizes of a [`FloatRealFft`]. Because the cepstrum is calculated with an FFT of this
+/// size, a larger size reduces time aliasing for magnitude responses with deep notches or
+/// sharp transitions.
+///
+/// This function returns an error if the length of dst is not valid.
+///
+/// # Panics
+///
+/// This function panics if magnitude does not have the required length.
+pub fn minimum_phase_f32(magnitude: &[f32], dst: &mut [f32]) -> Result<()> {
+    let size = dst.len();
+    let fft = FloatRealFft::new(size.try_into().map_err(|_| Error::Argument)?)?;
+    let nyquist = size / 2;
+    check_length_or_panic::<(usize, usize), usize>((nyquist + 1, magnitude.len()));
+
+    let peak = magnitude
+        .iter()
+        .fold(0.0, |peak: f32, value| peak.max(*value));
+    let floor = if peak > 0.0 {
+        peak * MINIMUM_PHASE_FLOOR
+    } else {
+        1.0
+    };
+    let log_magnitude = |bin: usize| ln_f32(magnitude[bin].max(floor));
+
+    // The logarithm of the magnitude is real and even, so its inverse FFT is the real cepstrum
+    let mut spectrum = vec![0.0; size];
+    spectrum[0] = log_magnitude(0);
+    spectrum[1] = log_magnitude(nyquist);
+    for bin in 1..nyquist {
+        spectrum[2 * bin] = log_magnitude(bin);
+    }
+    let mut cepstrum = vec![0.0; size];
+    fft.run_inverse(&spectrum, &mut cepstrum);
+
+    // Fold the negative quefrencies onto the positive ones
+    for value in cepstrum[1..nyquist].iter_mut() {
+        *value *= 2.0;
+    }
+    for value in cepstrum[nyquist + 1..].iter_mut() {
+        *value = 0.0;
+    }
+    fft.run(&cepstrum, &mut spectrum);
+
+    // The result is the complex logarithm of the minimum-phase spectrum
+    spectrum[0] = exp_f32(spectrum[0]);
+    spectrum[1] = exp_f32(spectrum[1]);
+    for bin in 1..nyquist {
+        let gain = exp_f32(spectrum[2 * bin]);
+        let phase = spectrum[2 * bin + 1];
+        spectrum[2 * bin] = gain * cos_f32(phase);
+        spectrum[2 * bin + 1] = gain * sin_f32(phase);
+    }
+    fft.run_inverse(&spectrum, dst);
+    Ok(())
+}
+
+/// Wraps a phase into the range [-pi, pi]
+fn wrap_phase(phase: f32) -> f32 {
+    let turns = (phase / (2.0 * PI)) as i32;
+    let wrapped = phase - turns as f32 * 2.0 * PI;
+    if wrapped > PI {
+        wrapped - 2.0 * PI
+    } else if wrapped < -PI {
+        wrapped + 2.0 * PI
+    } else {
+        wrapped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::f32::consts::FRAC_1_SQRT_2;
+
+    /// Returns a sine with the provided frequency in cycles per sample
+    fn sine(length: usize, frequency: f32) -> Vec<f32> {
+        (0..length)
+            .map(|n| (2.0 * PI * frequency * n as f32).sin())
+            .collect()
+    }
+
+    /// Returns the norm of the difference of two spectrograms relative to the norm of the
+    /// first
+    fn spectral_error(expected: &[f32], actual: &[f32]) -> f32 {
+        let difference: f32 = expected
+            .iter()
+            .zip(actual.iter())
+            .map(|(a, b)| (a - b) * (a - b))
+            .sum();
+        let norm: f32 = expected.iter().map(|a| a * a).sum();
+        (difference / norm).sqrt()
+    }
+
+    #[test]
+    fn griffin_lim_rejects_invalid_arguments() {
+        assert!(matches!(GriffinLim::new(64, 0, 10), Err(Error::Argument)));
+        assert!(matches!(GriffinLim::new(64, 65, 10), Err(Error::Argument)));
+        assert!(GriffinLim::new(100, 25, 10).is_err());
+        let griffin_lim = GriffinLim::new(64, 16, 10).unwrap();
+        assert_eq!(griffin_lim.bins(), 33);
+        assert_eq!(griffin_lim.signal_length(0), 0);
+        assert_eq!(griffin_lim.signal_length(13), 256);
+    }
+
+    #[test]
+    fn griffin_lim_reconstructs_magnitudes() {
+        let signal: Vec<f32> = sine(256, 0.1)
+            .iter()
+            .zip(sine(256, 0.23).iter())
+            .map(|(a, b)| a + 0.5 * b)
+            .collect();
+        let mut errors = [0.0; 2];
+        for (error, &iterations) in errors.iter_mut().zip(&[0, 30]) {
+            let griffin_lim = GriffinLim::new(64, 16, iterations).unwrap();
+            let mut target = vec![0.0; 13 * griffin_lim.bins()];
+            griffin_lim.magnitudes(&signal, &mut target);
+            let mut output = vec![0.0; signal.len()];
+            griffin_lim.reconstruct(&target, &mut output);
+            let mut magnitudes = vec![0.0; target.len()];
+            griffin_lim.magnitudes(&output, &mut magnitudes);
+            *error = spectral_error(&target, &magnitudes);
+        }
+        // The error with zero phases is about 0.9, and the iterations reduce it to below 0.2
+        assert!(errors[0] > 0.5);
+        assert!(errors[1] < 0.25);
+    }
+
+    /// Returns uniformly distributed pseudo-random noise in the range [-amplitude, amplitude)
+    fn noise(length: usize, amplitude: f32) -> Vec<f32> {
+        let mut state = 12345u32;
+        (0..length)
+            .map(|_| {
+                state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                amplitude * ((state >> 8) as f32 / (1 << 23) as f32 - 1.0)
+            })
+            .collect()
+    }
+
+    fn rms(values: &[f32]) -> f32 {
+        (values.iter().map(|x| x * x).sum::<f32>() / values.len() as f32).sqrt()
+    }
+
+    #[test]
+    fn spectral_gate_attenuates_stationary_noise() {
+        let input = noise(4096, 0.01);
+        let mut output = vec![0.0; input.len()];
+        let mut gate = SpectralGate::new(64, 16, 8.0, 0.1, 0.5).unwrap();
+        gate.process(&input, &mut output);
+        assert!(rms(&output[2048..]) < 0.3 * rms(&input[2048..]));
+        // After a reset, the estimates start again from the first complete frame
+        gate.reset();
+        gate.process(&input, &mut output);
+        assert!(rms(&output[2048..]) < 0.3 * rms(&input[2048..]));
+    }
+
+    #[test]
+    fn spectral_gate_passes_tone_above_noise() {
+        let mut input = noise(4096, 0.01);
+        // A tone at the center of bin 8 starts after 2048 samples of noise
+        for (value, tone) in input[2048..].iter_mut().zip(sine(2048, 0.125).iter()) {
+            *value += tone;
+        }
+        let mut output = vec![0.0; input.len()];
+        let mut gate = SpectralGate::new(64, 16, 4.0, 0.1, 0.5).unwrap();
+        gate.process(&input, &mut output);
+        // The output is delayed by 64 samples. Measure after the gain has settled.
+        let tone_output = rms(&output[2048 + 64 + 256..2048 + 64 + 1024]);
+        assert!(
+            (tone_output - FRAC_1_SQRT_2).abs() < 0.05,
+            "{}",
+            tone_output
+        );
+    }
+
+    #[test]
+    fn spectral_gate_rejects_invalid_hop_size() {
+        assert!(matches!(
+            SpectralGate::new(64, 48, 4.0, 0.1, 0.5),
+            Err(Error::Argument)
+        ));
+        assert!(matches!(
+            SpectralGate::new(64, 64, 4.0, 0.1, 0.5),
+            Err(Error::Argument)
+        ));
+    }
+
+    /// Returns the frequency of a sine, in cycles per sample, from the number of upward zero
+    /// crossings
+    fn crossing_frequency(values: &[f32]) -> f32 {
+        let crossings = values
+            .windows(2)
+            .filter(|pair| pair[0] < 0.0 && pair[1] >= 0.0)
+            .count();
+        crossings as f32 / values.len() as f32
+    }
+
+    #[test]
+    fn phase_vocoder_output_length() {
+        let vocoder = PhaseVocoder::new(256, 64, 1.5).unwrap();
+        assert_eq!(vocoder.output_length(255), 0);
+        assert_eq!(vocoder.output_length(256), 256);
+        // 13 frames, 96 samples apart
+        assert_eq!(vocoder.output_length(1024), 12 * 96 + 256);
+        assert!(vocoder.process(&[0.0; 100]).is_empty());
+        assert!(matches!(
+            PhaseVocoder::new(256, 0, 1.0),
+            Err(Error::Argument)
+        ));
+        assert!(matches!(
+            PhaseVocoder::new(256, 64, 0.0),
+            Err(Error::Argument)
+        ));
+        assert!(matches!(
+            PhaseVocoder::new(256, 64, 4.1),
+            Err(Error::Argument)
+        ));
+    }
+
+    #[test]
+    fn phase_vocoder_keeps_frequency() {
+        let input = sine(2048, 0.05);
+        for &stretch in &[0.5, 2.0] {
+            let vocoder = PhaseVocoder::new(256, 64, stretch).unwrap();
+            let output = vocoder.process(&input);
+            assert_eq!(output.len(), vocoder.output_length(input.len()));
+            // Away from the ends, the output is a sine with the same frequency and amplitude
+            let middle = &output[256..output.len() - 256];
+            assert!(
+                (crossing_frequency(middle) - 0.05).abs() < 0.002,
+                "{}",
+                stretch
+            );
+            assert!((rms(middle) - FRAC_1_SQRT_2).abs() < 0.05, "{}", stretch);
+        }
+    }
+
+    #[test]
+    fn pitch_shifter_ratio() {
+        assert_eq!(PitchShifter::new(256, 64, 12.0).unwrap().ratio(), 2.0);
+        assert_eq!(PitchShifter::new(256, 64, -12.0).unwrap().ratio(), 0.5);
+        // 2^(7 / 12) is about 1.498, and the hop of 96 samples gives exactly 1.5
+        assert_eq!(PitchShifter::new(256, 64, 7.0).unwrap().ratio(), 1.5);
+    }
+
+    #[test]
+    fn pitch_shifter_changes_frequency() {
+        let input = sine(2048, 0.04);
+        for &semitones in &[7.0, -12.0] {
+            let shifter = PitchShifter::new(256, 64, semitones).unwrap();
+            let output = shifter.process(&input);
+            assert_eq!(output.len(), input.len());
+            let middle = &output[256..1536];
+            let expected = 0.04 * shifter.ratio();
+            assert!(
+                (crossing_frequency(middle) - expected).abs() < 0.002,
+                "{}",
+                semitones
+            );
+        }
+    }
+
+    #[test]
+    fn periodogram_preserves_power() {
+        // A sine at the center of bin 32 has a power of 0.5
+        let input = sine(256, 0.125);
+        let mut psd = [0.0; 129];
+        for &window in &[Window::Rectangular, Window::Hanning, Window::Hft95] {
+            periodogram_f32(&input, window, &mut psd).unwrap();
+            let power = psd.iter().sum::<f32>() / 256.0;
+            assert!((power - 0.5).abs() < 1e-3, "{:?}", window);
+            let peak = (0..psd.len())
+                .max_by(|&a, &b| psd[a].partial_cmp(&psd[b]).unwrap())
+                .unwrap();
+            assert_eq!(peak, 32);
+        }
+        // For noise, the window changes the estimate slightly
+        let input = noise(256, 1.0);
+        periodogram_f32(&input, Window::Hanning, &mut psd).unwrap();
+        let power = psd.iter().sum::<f32>() / 256.0;
+        assert!((power - rms(&input).powi(2)).abs() < 0.05);
+    }
+
+    #[test]
+    fn periodogram_rejects_invalid_length() {
+        assert!(periodogram_f32(&[0.0; 100], Window::Hanning, &mut [0.0; 51]).is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn periodogram_checks_output_length() {
+        let _ = periodogram_f32(&[0.0; 64], Window::Hanning, &mut [0.0; 32]);
+    }
+
+    #[test]
+    fn minimum_phase_of_flat_response_is_impulse() {
+        let mut impulse = [1.0; 64];
+        minimum_phase_f32(&[2.0; 33], &mut impulse).unwrap();
+        assert!((impulse[0] - 2.0).abs() < 1e-4);
+        assert!(impulse[1..].iter().all(|value| value.abs() < 1e-4));
+    }
+
+    #[test]
+    fn minimum_phase_of_two_tap_filter() {
+        // [0.5, 1] and [1, 0.5] have the same magnitude response, and the second has the zero
+        // inside the unit circle
+        let magnitude: Vec<f32> = (0..33)
+            .map(|bin| {
+                let w = 2.0 * PI * bin as f32 / 64.0;
+                (1.25 + w.cos()).sqrt()
+            })
+            .collect();
+        let mut impulse = [0.0; 64];
+        minimum_phase_f32(&magnitude, &mut impulse).unwrap();
+        assert!((impulse[0] - 1.0).abs() < 1e-3);
+        assert!((impulse[1] - 0.5).abs() < 1e-3);
+        assert!(impulse[2..].iter().all(|value| value.abs() < 1e-3));
+    }
+
+    #[test]
+    fn minimum_phase_rejects_invalid_length() {
+        assert!(minimum_phase_f32(&[1.0; 51], &mut [0.0; 100]).is_err());
+    }
+
+    #[test]
+    fn frame_processor_identity_delays_input() {
+        let input = noise(1024, 1.0);
+        for &hop_size in &[32, 16] {
+            let mut processor = FrameProcessor::new(64, hop_size).unwrap();
+            assert_eq!(processor.latency(), 64);
+            let mut output = vec![0.0; input.len()];
+            // Blocks that are not multiples of the hop size
+            for (src, dst) in input.chunks(37).zip(output.chunks_mut(37)) {
+                processor.process(src, dst, |_| {});
+            }
+            for (delayed, original) in output[64..].iter().zip(input.iter()) {
+                assert!((delayed - original).abs() < 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn frame_processor_modifies_spectra() {
+        let input = noise(512, 1.0);
+        let mut processor = FrameProcessor::new(64, 16).unwrap();
+        let mut output = vec![0.0; input.len()];
+        processor.process(&input, &mut output, |bins| {
+            assert_eq!(bins.len(), 33);
+            assert_eq!(bins[0].im, 0.0);
+            assert_eq!(bins[32].im, 0.0);
+            for bin in bins.iter_mut() {
+                *bin *= 0.5;
+            }
+        });
+        for (scaled, original) in output[64..].iter().zip(input.iter()) {
+            assert!((scaled - 0.5 * original).abs() < 1e-4);
+        }
+
+        processor.reset();
+        processor.process(&input, &mut output, |bins| {
+            for bin in bins.iter_mut() {
+                *bin = Complex32::new(0.0, 0.0);
+            }
+        });
+        assert!(output.iter().all(|value| value.abs() < 1e-6));
+    }
+
+    #[test]
+    fn frame_processor_rejects_invalid_hop_size() {
+        assert!(matches!(FrameProcessor::new(64, 0), Err(Error::Argument)));
+        assert!(matches!(FrameProcessor::new(64, 24), Err(Error::Argument)));
+        assert!(FrameProcessor::new(100, 25).is_err());
+    }
+}
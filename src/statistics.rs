@@ -1,14 +1,25 @@
-use crate::check_length;
+use core::f32::consts::PI;
+
+#[cfg(feature = "alloc")]
+use alloc::vec;
+use fixed::types::{I1F15, I1F31};
+
+use crate::fast_math::{exp_f32, log10_f32, sqrt_f32, tan_f32, DB_FLOOR};
+#[cfg(feature = "alloc")]
+use crate::support::{SortAlgorithm, SortOrder, Sorter};
+use crate::{check_length_or_panic, Error, Result};
 
 /// Calculates the rms of a sequence of f32 values.
 ///
+/// For the absolute value of each element, see `basic::abs_f32`.
+///
 /// # Panics
 ///
 /// This function panics if src does not have 'size' elements.
 pub fn rms_f32(src: &[f32], size: usize, dst: &mut f32) {
-    let length = check_length((src.len(), size));
+    let length = check_length_or_panic((src.len(), size));
     unsafe {
-        cmsis_dsp_sys::arm_abs_f32(src.as_ptr(), dst, length);
+        cmsis_dsp_sys::arm_rms_f32(src.as_ptr(), length, dst);
     }
 }
 
@@ -18,7 +29,7 @@ pub fn rms_f32(src: &[f32], size: usize, dst: &mut f32) {
 ///
 /// This function panics if src does not have 'size' elements.
 pub fn mean_f32(src: &[f32], size: usize, dst: &mut f32) {
-    let length = check_length((src.len(), size));
+    let length = check_length_or_panic((src.len(), size));
     unsafe {
         cmsis_dsp_sys::arm_mean_f32(src.as_ptr(), length, dst);
     }
@@ -30,7 +41,7 @@ pub fn mean_f32(src: &[f32], size: usize, dst: &mut f32) {
 ///
 /// This function panics if src does not have 'size' elements.
 pub fn var_f32(src: &[f32], size: usize, dst: &mut f32) {
-    let length = check_length((src.len(), size));
+    let length = check_length_or_panic((src.len(), size));
     unsafe {
         cmsis_dsp_sys::arm_var_f32(src.as_ptr(), length, dst);
     }
@@ -42,7 +53,7 @@ pub fn var_f32(src: &[f32], size: usize, dst: &mut f32) {
 ///
 /// This function panics if src does not have 'size' elements.
 pub fn std_f32(src: &[f32], size: usize, dst: &mut f32) {
-    let length = check_length((src.len(), size));
+    let length = check_length_or_panic((src.len(), size));
     unsafe {
         cmsis_dsp_sys::arm_std_f32(src.as_ptr(), length, dst);
     }
@@ -54,7 +65,7 @@ pub fn std_f32(src: &[f32], size: usize, dst: &mut f32) {
 ///
 /// This function panics if src does not have 'size' elements.
 pub fn max_f32(src: &[f32], size: usize, dst_value: &mut f32, dst_index: &mut u32) {
-    let length = check_length((src.len(), size));
+    let length = check_length_or_panic((src.len(), size));
     unsafe {
         cmsis_dsp_sys::arm_max_f32(src.as_ptr(), length, dst_value, dst_index);
     }
@@ -66,10 +77,1343 @@ pub fn max_f32(src: &[f32], size: usize, dst_value: &mut f32, dst_index: &mut u3
 ///
 /// This function panics if src does not have 'size' elements.
 pub fn min_f32(src: &[f32], size: usize, dst_value: &mut f32, dst_index: &mut u32) {
-    let length = check_length((src.len(), size));
+    let length = check_length_or_panic((src.len(), size));
     unsafe {
         cmsis_dsp_sys::arm_min_f32(src.as_ptr(), length, dst_value, dst_index);
     }
 }
 
+/// Calculates the mean of a sequence of f32 values and returns it.
+///
+/// This is equivalent to `mean_f32(src, src.len(), &mut result)`.
+pub fn mean(src: &[f32]) -> f32 {
+    let mut result = 0.0;
+    mean_f32(src, src.len(), &mut result);
+    result
+}
+
+/// Calculates the variance of a sequence of f32 values and returns it.
+///
+/// This is equivalent to `var_f32(src, src.len(), &mut result)`.
+pub fn var(src: &[f32]) -> f32 {
+    let mut result = 0.0;
+    var_f32(src, src.len(), &mut result);
+    result
+}
+
+/// Calculates the standard deviation of a sequence of f32 values and returns it.
+///
+/// This is equivalent to `std_f32(src, src.len(), &mut result)`.
+pub fn std(src: &[f32]) -> f32 {
+    let mut result = 0.0;
+    std_f32(src, src.len(), &mut result);
+    result
+}
+
+/// Calculates the rms of a sequence of f32 values and returns it.
+pub fn rms(src: &[f32]) -> f32 {
+    let length = check_length_or_panic(src.len());
+    let mut result = 0.0;
+    unsafe {
+        cmsis_dsp_sys::arm_rms_f32(src.as_ptr(), length, &mut result);
+    }
+    result
+}
+
+/// Calculates the power (sum of squares) of a sequence of f32 values and returns it.
+pub fn power(src: &[f32]) -> f32 {
+    let length = check_length_or_panic(src.len());
+    let mut result = 0.0;
+    unsafe {
+        cmsis_dsp_sys::arm_power_f32(src.as_ptr(), length, &mut result);
+    }
+    result
+}
+
+/// Calculates the short-time energy of a frame of f32 values, the mean of their squares
+///
+/// This is the result of `power(src)` divided by the number of values. The result for an empty
+/// frame is zero.
+pub fn short_time_energy_f32(src: &[f32]) -> f32 {
+    if src.is_empty() {
+        return 0.0;
+    }
+    power(src) / src.len() as f32
+}
+
+/// Calculates the zero-crossing rate of a frame of f32 values, the number of sign changes
+/// between adjacent values divided by the number of values
+///
+/// Zero counts as positive. A sinusoid with frequency f sampled at rate fs crosses zero about
+/// `2 * f / fs` times per sample. The result for an empty frame is zero.
+pub fn zero_crossing_rate_f32(src: &[f32]) -> f32 {
+    if src.is_empty() {
+        return 0.0;
+    }
+    let crossings = src
+        .windows(2)
+        .filter(|pair| (pair[0] >= 0.0) != (pair[1] >= 0.0))
+        .count();
+    crossings as f32 / src.len() as f32
+}
+
+/// Subtracts the mean of a sequence of f32 values from each value, producing a sequence with a
+/// mean of zero.
+///
+/// This is commonly done before an FFT or an RMS measurement, so that a DC offset does not
+/// dominate the result.
+///
+/// # Panics
+///
+/// This function panics if src and dst do not have the same length.
+pub fn remove_dc_f32(src: &[f32], dst: &mut [f32]) {
+    let length = check_length_or_panic((src.len(), dst.len()));
+    if length == 0 {
+        return;
+    }
+    let mut mean = 0.0;
+    unsafe {
+        cmsis_dsp_sys::arm_mean_f32(src.as_ptr(), length, &mut mean);
+        cmsis_dsp_sys::arm_offset_f32(src.as_ptr(), -mean, dst.as_mut_ptr(), length);
+    }
+}
+
+/// Calculates the mean of each channel of interleaved stereo samples and returns them as
+/// (left, right)
+///
+/// The samples are in the order left, right, left, right, and so on.
+///
+/// # Panics
+///
+/// This function panics if the number of samples is odd.
+pub fn mean_stereo_f32(interleaved: &[f32]) -> (f32, f32) {
+    let frames = check_stereo(interleaved);
+    let (left, right) = stereo_sums(interleaved, |x| x);
+    (left / frames, right / frames)
+}
+
+/// Calculates the rms of each channel of interleaved stereo samples and returns them as
+/// (left, right)
+///
+/// The samples are in the order left, right, left, right, and so on.
+///
+/// # Panics
+///
+/// This function panics if the number of samples is odd.
+pub fn rms_stereo_f32(interleaved: &[f32]) -> (f32, f32) {
+    let frames = check_stereo(interleaved);
+    let (left, right) = stereo_sums(interleaved, |x| x * x);
+    (sqrt_f32(left / frames), sqrt_f32(right / frames))
+}
+
+/// Calculates the largest absolute value in each channel of interleaved stereo samples and
+/// returns them as (left, right)
+///
+/// The samples are in the order left, right, left, right, and so on.
+///
+/// # Panics
+///
+/// This function panics if the number of samples is odd.
+pub fn peak_stereo_f32(interleaved: &[f32]) -> (f32, f32) {
+    check_stereo(interleaved);
+    interleaved
+        .chunks_exact(2)
+        .fold((0.0, 0.0), |(left, right): (f32, f32), frame| {
+            (left.max(frame[0].abs()), right.max(frame[1].abs()))
+        })
+}
+
+/// Checks that interleaved stereo samples contain a whole number of frames, and returns the
+/// number of frames
+fn check_stereo(interleaved: &[f32]) -> f32 {
+    assert!(
+        interleaved.len().is_multiple_of(2),
+        "Interleaved stereo samples must have an even length"
+    );
+    (interleaved.len() / 2) as f32
+}
+
+/// Returns the sums of a function of the left and right samples
+fn stereo_sums<F>(interleaved: &[f32], f: F) -> (f32, f32)
+where
+    F: Fn(f32) -> f32,
+{
+    interleaved
+        .chunks_exact(2)
+        .fold((0.0, 0.0), |(left, right), frame| {
+            (left + f(frame[0]), right + f(frame[1]))
+        })
+}
+
+/// Calculates the median of a set of values
+///
+/// The values are sorted in a copy of src. For an odd number of values, the median is the middle
+/// value, and for an even number it is the average of the two middle values. This is equivalent
+/// to `percentile_f32(src, 50.0)`.
+///
+/// This function is available when the `alloc` feature is enabled.
+///
+/// # Panics
+///
+/// This function panics if src is empty.
+#[cfg(feature = "alloc")]
+pub fn median_f32(src: &[f32]) -> f32 {
+    percentile_f32(src, 50.0)
+}
+
+/// Calculates a percentile of a set of values
+///
+/// The values are sorted in a copy of src, and percentile p is found at the fractional rank
+/// `p / 100 * (src.len() - 1)`, interpolating linearly between the two values at the nearest
+/// ranks. A p of 0 gives the smallest value and a p of 100 gives the largest.
+///
+/// This function is available when the `alloc` feature is enabled.
+///
+/// # Panics
+///
+/// This function panics if src is empty or if p is not in the range [0, 100].
+#[cfg(feature = "alloc")]
+pub fn percentile_f32(src: &[f32], p: f32) -> f32 {
+    assert!(!src.is_empty(), "src must not be empty");
+    assert!(
+        (0.0..=100.0).contains(&p),
+        "p must be in the range [0, 100]"
+    );
+    let mut sorted = vec![0.0; src.len()];
+    Sorter::new(SortAlgorithm::Heap, SortOrder::Ascending).sort(src, &mut sorted);
+
+    let rank = p / 100.0 * (sorted.len() - 1) as f32;
+    let lower = rank as usize;
+    let upper = (lower + 1).min(sorted.len() - 1);
+    let fraction = rank - lower as f32;
+    sorted[lower] + fraction * (sorted[upper] - sorted[lower])
+}
+
+/// Counts the values in src that fall into each of a number of equal-width bins
+///
+/// The range from min to max is divided into `bins.len()` bins of equal width, and the count of
+/// each bin is incremented for every value in it. Bin i contains the values in
+/// `[min + i * width, min + (i + 1) * width)`. Counts are added to the existing contents of bins,
+/// so a histogram can be accumulated over several blocks; set them to zero before the first
+/// call to start a new histogram.
+///
+/// Values outside the range `[min, max)`, and NaN values, are ignored.
+///
+/// If min is equal to max, the range is instead taken from the smallest and largest values in
+/// src, and includes the largest value, which is counted in the last bin. If all values in src
+/// are equal, they are all counted in the first bin.
+///
+/// # Panics
+///
+/// This function panics if min is greater than max.
+pub fn histogram_f32(src: &[f32], min: f32, max: f32, bins: &mut [u32]) {
+    assert!(min <= max, "min must not be greater than max");
+    if src.is_empty() || bins.is_empty() {
+        return;
+    }
+    let (low, high, include_high) = if min == max {
+        let length = check_length_or_panic(src.len());
+        let (mut low, mut high) = (0.0, 0.0);
+        let mut index = 0;
+        unsafe {
+            cmsis_dsp_sys::arm_min_f32(src.as_ptr(), length, &mut low, &mut index);
+            cmsis_dsp_sys::arm_max_f32(src.as_ptr(), length, &mut high, &mut index);
+        }
+        (low, high, true)
+    } else {
+        (min, max, false)
+    };
+
+    let last = bins.len() - 1;
+    let scale = if high > low {
+        bins.len() as f32 / (high - low)
+    } else {
+        0.0
+    };
+    for &value in src {
+        if value >= low && (value < high || (include_high && value == high)) {
+            // Rounding can place values just below high in the bin after the last one
+            let bin = (((value - low) * scale) as usize).min(last);
+            bins[bin] += 1;
+        }
+    }
+}
+
+/// Finds the local maxima of src that are above a threshold, such as the peaks of a spectrum
+///
+/// A local maximum is a value that is greater than the value before it and not less than the
+/// value after it, so a flat peak is reported at its first index. The first and last values of
+/// src are never peaks. NaN values are never peaks.
+///
+/// min_distance: smallest allowed difference between the indices of two peaks. When two maxima
+/// are closer than this, only the larger one is kept. A value of 0 or 1 keeps all maxima.
+///
+/// peaks: receives `(index, value)` for each peak, in increasing order of index. If there are
+/// more peaks than elements of peaks, the peaks with the lowest indices are written and the rest
+/// are ignored.
+///
+/// This function returns the number of peaks written.
+pub fn find_peaks_f32(
+    src: &[f32],
+    threshold: f32,
+    min_distance: usize,
+    peaks: &mut [(usize, f32)],
+) -> usize {
+    let mut count = 0;
+    for (offset, window) in src.windows(3).enumerate() {
+        let (index, value) = (offset + 1, window[1]);
+        if !(value > threshold && value > window[0] && value >= window[2]) {
+            continue;
+        }
+        if count != 0 && index - peaks[count - 1].0 < min_distance {
+            // Too close to the previous peak, so keep the larger of the two
+            if value > peaks[count - 1].1 {
+                peaks[count - 1] = (index, value);
+            }
+        } else if count < peaks.len() {
+            peaks[count] = (index, value);
+            count += 1;
+        } else {
+            break;
+        }
+    }
+    count
+}
+
+/// Calculates the mean square error between two sequences of f32 values.
+///
+/// The result, `sum((src_a[i] - src_b[i])^2) / n`, is written to dst. This is equivalent to
+/// `arm_mse_f32`.
+///
+/// # Panics
+///
+/// This function panics if src_a and src_b do not have the same length, or if they are empty.
+pub fn mse_f32(src_a: &[f32], src_b: &[f32], dst: &mut f32) {
+    check_mse_lengths(src_a.len(), src_b.len());
+    let sum: f32 = src_a
+        .iter()
+        .zip(src_b.iter())
+        .map(|(a, b)| (a - b) * (a - b))
+        .sum();
+    *dst = sum / src_a.len() as f32;
+}
+
+/// Calculates the mean square error between two sequences of Q1.31 values.
+///
+/// The result, `sum((src_a[i] - src_b[i])^2) / n`, is written to dst. This follows
+/// `arm_mse_q31`: each value is halved before the difference is calculated, so the difference
+/// cannot overflow, and the squares are accumulated in 64 bits. Unlike `arm_mse_q31`, the result
+/// saturates to the range of Q1.31 instead of wrapping around.
+///
+/// # Panics
+///
+/// This function panics if src_a and src_b do not have the same length, or if they are empty.
+pub fn mse_q31(src_a: &[I1F31], src_b: &[I1F31], dst: &mut I1F31) {
+    check_mse_lengths(src_a.len(), src_b.len());
+    let sum: i64 = src_a
+        .iter()
+        .zip(src_b.iter())
+        .map(|(a, b)| {
+            let difference = i64::from(a.to_bits() >> 1) - i64::from(b.to_bits() >> 1);
+            (difference * difference) >> 14
+        })
+        .sum();
+    let mean = (sum / src_a.len() as i64) >> 15;
+    *dst = I1F31::from_bits(mean.min(i64::from(i32::MAX)) as i32);
+}
+
+/// Calculates the mean square error between two sequences of Q1.15 values.
+///
+/// The result, `sum((src_a[i] - src_b[i])^2) / n`, is written to dst. This follows
+/// `arm_mse_q15`: each value is halved before the difference is calculated, and the result
+/// saturates to the range of Q1.15.
+///
+/// # Panics
+///
+/// This function panics if src_a and src_b do not have the same length, or if they are empty.
+pub fn mse_q15(src_a: &[I1F15], src_b: &[I1F15], dst: &mut I1F15) {
+    check_mse_lengths(src_a.len(), src_b.len());
+    let sum: i64 = src_a
+        .iter()
+        .zip(src_b.iter())
+        .map(|(a, b)| {
+            let difference = i64::from(a.to_bits() >> 1) - i64::from(b.to_bits() >> 1);
+            difference * difference
+        })
+        .sum();
+    let mean = (sum / src_a.len() as i64) >> 13;
+    *dst = I1F15::from_bits(mean.min(i64::from(i16::MAX)) as i16);
+}
+
+fn check_mse_lengths(a: usize, b: usize) {
+    check_length_or_panic::<(usize, usize), usize>((a, b));
+    assert!(a > 0, "Sources must not be empty");
+}
+
+/// Calculates the mean and variance of a stream of values without storing them
+///
+/// Single values update the statistics with Welford's algorithm. Blocks are summarized with
+/// `arm_mean_f32` and `arm_var_f32` and merged into the running statistics, which is faster
+/// and as accurate. Like `var_f32`, the variance is the sample variance, which divides the sum
+/// of squared deviations by one less than the number of values.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct RunningStats {
+    count: usize,
+    mean: f32,
+    /// Sum of the squared deviations from the mean
+    m2: f32,
+}
+
+impl RunningStats {
+    /// Creates an accumulator that has not received any values
+    pub const fn new() -> Self {
+        RunningStats {
+            count: 0,
+            mean: 0.0,
+            m2: 0.0,
+        }
+    }
+
+    /// Adds one value
+    pub fn push(&mut self, x: f32) {
+        self.count += 1;
+        let delta = x - self.mean;
+        self.mean += delta / self.count as f32;
+        self.m2 += delta * (x - self.mean);
+    }
+
+    /// Adds a block of values
+    pub fn push_block(&mut self, src: &[f32]) {
+        if src.len() < 2 {
+            if let Some(&x) = src.first() {
+                self.push(x);
+            }
+            return;
+        }
+        let length = check_length_or_panic(src.len());
+        let (mut block_mean, mut block_var) = (0.0, 0.0);
+        unsafe {
+            cmsis_dsp_sys::arm_mean_f32(src.as_ptr(), length, &mut block_mean);
+            cmsis_dsp_sys::arm_var_f32(src.as_ptr(), length, &mut block_var);
+        }
+        let block_count = src.len() as f32;
+        let count = self.count as f32;
+        let total = count + block_count;
+        let delta = block_mean - self.mean;
+        self.mean += delta * block_count / total;
+        self.m2 += block_var * (block_count - 1.0) + delta * delta * count * block_count / total;
+        self.count += src.len();
+    }
+
+    /// Returns the number of values that have been added
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the mean of the values, or 0 if no values have been added
+    pub fn mean(&self) -> f32 {
+        self.mean
+    }
+
+    /// Returns the sample variance of the values, or 0 if fewer than 2 values have been added
+    pub fn variance(&self) -> f32 {
+        if self.count < 2 {
+            0.0
+        } else {
+            self.m2 / (self.count - 1) as f32
+        }
+    }
+
+    /// Returns the sample standard deviation of the values, or 0 if fewer than 2 values have
+    /// been added
+    pub fn std(&self) -> f32 {
+        sqrt_f32(self.variance())
+    }
+
+    /// Removes all values
+    pub fn reset(&mut self) {
+        *self = RunningStats::new();
+    }
+}
+
+/// Number of bin frequencies that the spectral feature functions calculate at a time
+const SPECTRAL_CHUNK: usize = 64;
+
+/// Calculates the spectral centroid of a magnitude spectrum, in the units of sample_rate
+///
+/// The centroid is the average of the bin frequencies weighted by their magnitudes:
+/// `sum(f[k] * magnitudes[k]) / sum(magnitudes[k])`. It describes the "brightness" of a sound.
+///
+/// magnitudes: the magnitudes of the bins of a real FFT of size N, from DC to the Nyquist
+/// frequency (inclusive), so it has `N / 2 + 1` elements. Bin k has the frequency
+/// `f[k] = k * sample_rate / N`, where `N = 2 * (magnitudes.len() - 1)`.
+///
+/// This function returns 0 if all magnitudes are zero.
+///
+/// # Panics
+///
+/// This function panics if magnitudes has fewer than 2 elements.
+pub fn spectral_centroid_f32(magnitudes: &[f32], sample_rate: f32) -> f32 {
+    let spacing = bin_spacing(magnitudes, sample_rate);
+    let total = magnitude_sum(magnitudes);
+    if total == 0.0 {
+        return 0.0;
+    }
+    weighted_bin_sum(magnitudes, |k| k as f32 * spacing) / total
+}
+
+/// Calculates the spectral bandwidth of a magnitude spectrum, in the units of sample_rate
+///
+/// The bandwidth is the standard deviation of the bin frequencies around the spectral centroid
+/// c, weighted by their magnitudes: `sqrt(sum((f[k] - c)^2 * magnitudes[k]) / sum(magnitudes[k]))`.
+///
+/// magnitudes has the same format as for `spectral_centroid_f32`.
+///
+/// This function returns 0 if all magnitudes are zero.
+///
+/// # Panics
+///
+/// This function panics if magnitudes has fewer than 2 elements.
+pub fn spectral_bandwidth_f32(magnitudes: &[f32], sample_rate: f32) -> f32 {
+    let spacing = bin_spacing(magnitudes, sample_rate);
+    let total = magnitude_sum(magnitudes);
+    if total == 0.0 {
+        return 0.0;
+    }
+    let centroid = weighted_bin_sum(magnitudes, |k| k as f32 * spacing) / total;
+    let variance = weighted_bin_sum(magnitudes, |k| {
+        let deviation = k as f32 * spacing - centroid;
+        deviation * deviation
+    }) / total;
+    sqrt_f32(variance)
+}
+
+/// Returns the frequency spacing of the bins of a magnitude spectrum from DC to Nyquist
+fn bin_spacing(magnitudes: &[f32], sample_rate: f32) -> f32 {
+    assert!(
+        magnitudes.len() >= 2,
+        "magnitudes must have at least 2 elements"
+    );
+    0.5 * sample_rate / (magnitudes.len() - 1) as f32
+}
+
+/// Returns the sum of the magnitudes, which are not negative
+fn magnitude_sum(magnitudes: &[f32]) -> f32 {
+    let length = check_length_or_panic(magnitudes.len());
+    let mut mean = 0.0;
+    unsafe {
+        cmsis_dsp_sys::arm_mean_f32(magnitudes.as_ptr(), length, &mut mean);
+    }
+    mean * magnitudes.len() as f32
+}
+
+/// Calculates `sum(weight(k) * magnitudes[k])` with `arm_dot_prod_f32`, calculating the weights
+/// in chunks on the stack
+fn weighted_bin_sum<F: Fn(usize) -> f32>(magnitudes: &[f32], weight: F) -> f32 {
+    let mut weights = [0.0f32; SPECTRAL_CHUNK];
+    let mut sum = 0.0;
+    for (chunk_index, chunk) in magnitudes.chunks(SPECTRAL_CHUNK).enumerate() {
+        let start = chunk_index * SPECTRAL_CHUNK;
+        for (i, value) in weights[..chunk.len()].iter_mut().enumerate() {
+            *value = weight(start + i);
+        }
+        let mut dot = 0.0;
+        unsafe {
+            cmsis_dsp_sys::arm_dot_prod_f32(
+                weights.as_ptr(),
+                chunk.as_ptr(),
+                chunk.len() as u32,
+                &mut dot,
+            );
+        }
+        sum += dot;
+    }
+    sum
+}
+
 pub fn xa() {}
+
+/// Averages repeated, time-aligned frames of a periodic signal
+///
+/// When each frame contains the same signal plus uncorrelated noise, averaging N frames improves
+/// the signal-to-noise ratio by a factor of about sqrt(N).
+pub struct CoherentAverager<'a> {
+    /// Sum of all frames added so far
+    sum: &'a mut [f32],
+    /// Number of frames added
+    count: u32,
+}
+
+impl<'a> CoherentAverager<'a> {
+    /// Creates an averager that accumulates frames in the provided buffer
+    ///
+    /// The length of sum is the frame length. The buffer is cleared.
+    pub fn new(sum: &'a mut [f32]) -> Self {
+        for value in sum.iter_mut() {
+            *value = 0.0;
+        }
+        CoherentAverager { sum, count: 0 }
+    }
+
+    /// Adds a frame to the average
+    ///
+    /// # Panics
+    ///
+    /// This function panics if frame does not have the same length as this averager.
+    pub fn add_frame(&mut self, frame: &[f32]) {
+        let length = check_length_or_panic((frame.len(), self.sum.len()));
+        unsafe {
+            let ptr = self.sum.as_mut_ptr();
+            cmsis_dsp_sys::arm_add_f32(ptr, frame.as_ptr(), ptr, length);
+        }
+        self.count += 1;
+    }
+
+    /// Calculates the average of all frames added so far and stores it in dst
+    ///
+    /// If no frames have been added, dst is filled with zeros.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if dst does not have the same length as this averager.
+    pub fn average(&self, dst: &mut [f32]) {
+        let length = check_length_or_panic((self.sum.len(), dst.len()));
+        let scale = if self.count == 0 {
+            0.0
+        } else {
+            1.0 / self.count as f32
+        };
+        unsafe {
+            cmsis_dsp_sys::arm_scale_f32(self.sum.as_ptr(), scale, dst.as_mut_ptr(), length);
+        }
+    }
+
+    /// Returns the number of frames that have been added
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Removes all frames from the average
+    pub fn reset(&mut self) {
+        for value in self.sum.iter_mut() {
+            *value = 0.0;
+        }
+        self.count = 0;
+    }
+}
+
+/// Number of loudness histogram bins per loudness unit
+const LOUDNESS_BINS_PER_LU: f32 = 10.0;
+/// The absolute gate (LKFS), which is also the lowest loudness in the histogram
+const LOUDNESS_ABSOLUTE_GATE: f32 = -70.0;
+/// The relative gate, in LU below the loudness of the blocks that pass the absolute gate
+const LOUDNESS_RELATIVE_GATE: f32 = -10.0;
+/// Number of loudness histogram bins, covering -70 to +10 LKFS
+const LOUDNESS_BINS: usize = 800;
+
+/// Measures the integrated loudness of a mono signal according to ITU-R BS.1770
+///
+/// The signal is passed through the K-weighting filter, and its mean square is calculated over
+/// 400 ms blocks that overlap by 75%. The integrated loudness is the loudness of all blocks that
+/// pass the absolute gate (-70 LKFS) and the relative gate (10 LU below the loudness of the blocks
+/// that pass the absolute gate).
+///
+/// To avoid storing every block, block energies are collected in a histogram with a resolution
+/// of 0.1 LU. The histogram makes this type about 6.5 kB large.
+pub struct LoudnessMeter {
+    /// K-weighting filter coefficients (high shelf and high-pass stages)
+    coeffs: [f32; 10],
+    /// K-weighting filter state
+    state: [f32; 4],
+    /// Number of samples in each 100 ms step
+    step_length: usize,
+    /// Sum of squares of the filtered samples in the current step
+    current_energy: f32,
+    /// Number of samples in the current step
+    current_count: usize,
+    /// Sums of squares of the most recent complete steps
+    previous_energy: [f32; 3],
+    /// Number of valid values in previous_energy
+    previous_count: usize,
+    /// Number of blocks in each histogram bin
+    bin_counts: [u32; LOUDNESS_BINS],
+    /// Sum of the mean squares of the blocks in each histogram bin
+    bin_energy: [f32; LOUDNESS_BINS],
+}
+
+impl LoudnessMeter {
+    /// Creates a loudness meter for a signal with the provided sample rate in hertz
+    ///
+    /// This function returns `Error::Argument` if sample_rate is not finite or is too low to
+    /// divide the signal into 100 ms steps of at least one sample.
+    pub fn new(sample_rate: f32) -> Result<Self> {
+        if !sample_rate.is_finite() {
+            return Err(Error::Argument);
+        }
+        let step_length = (sample_rate / 10.0 + 0.5) as usize;
+        if step_length == 0 {
+            return Err(Error::Argument);
+        }
+        // Filter designs from ITU-R BS.1770, adapted to any sample rate
+        // Stage 1: high shelf
+        let k = tan_f32(PI * 1681.9745 / sample_rate);
+        let q = 0.707_175_24;
+        // Gain at high frequencies (3.9998 dB) and at the center of the shelf
+        let vh = 1.584_864_7;
+        let vb = 1.258_720_9;
+        let a0 = 1.0 + k / q + k * k;
+        let shelf = [
+            (vh + vb * k / q + k * k) / a0,
+            2.0 * (k * k - vh) / a0,
+            (vh - vb * k / q + k * k) / a0,
+            -2.0 * (k * k - 1.0) / a0,
+            -(1.0 - k / q + k * k) / a0,
+        ];
+        // Stage 2: high-pass
+        let k = tan_f32(PI * 38.135_47 / sample_rate);
+        let q = 0.500_327_04;
+        let a0 = 1.0 + k / q + k * k;
+        let high_pass = [
+            1.0,
+            -2.0,
+            1.0,
+            -2.0 * (k * k - 1.0) / a0,
+            -(1.0 - k / q + k * k) / a0,
+        ];
+
+        let mut coeffs = [0.0; 10];
+        coeffs[..5].copy_from_slice(&shelf);
+        coeffs[5..].copy_from_slice(&high_pass);
+        Ok(LoudnessMeter {
+            coeffs,
+            state: [0.0; 4],
+            step_length,
+            current_energy: 0.0,
+            current_count: 0,
+            previous_energy: [0.0; 3],
+            previous_count: 0,
+            bin_counts: [0; LOUDNESS_BINS],
+            bin_energy: [0.0; LOUDNESS_BINS],
+        })
+    }
+
+    /// Adds samples to the measurement
+    ///
+    /// The samples may be provided in blocks of any length.
+    pub fn process(&mut self, src: &[f32]) {
+        let mut filtered = [0.0f32; 64];
+        let mut remaining = src;
+        while !remaining.is_empty() {
+            let chunk_length = remaining
+                .len()
+                .min(filtered.len())
+                .min(self.step_length - self.current_count);
+            let (chunk, rest) = remaining.split_at(chunk_length);
+            let instance = cmsis_dsp_sys::arm_biquad_cascade_df2T_instance_f32 {
+                numStages: 2,
+                pState: self.state.as_mut_ptr(),
+                pCoeffs: self.coeffs.as_ptr(),
+            };
+            let mut energy = 0.0;
+            unsafe {
+                cmsis_dsp_sys::arm_biquad_cascade_df2T_f32(
+                    &instance,
+                    chunk.as_ptr(),
+                    filtered.as_mut_ptr(),
+                    chunk_length as u32,
+                );
+                cmsis_dsp_sys::arm_power_f32(filtered.as_ptr(), chunk_length as u32, &mut energy);
+            }
+            self.current_energy += energy;
+            self.current_count += chunk_length;
+            if self.current_count == self.step_length {
+                self.finish_step();
+            }
+            remaining = rest;
+        }
+    }
+
+    /// Returns the integrated loudness of all samples processed so far, in LKFS (LUFS)
+    ///
+    /// If no 400 ms block has passed the gates, this function returns negative infinity.
+    pub fn integrated_lufs(&self) -> f32 {
+        let (energy, count) = self.gated_energy(0);
+        if count == 0 {
+            return f32::NEG_INFINITY;
+        }
+        let relative_gate = energy_to_loudness(energy / count as f32) + LOUDNESS_RELATIVE_GATE;
+        let first_bin = if relative_gate > LOUDNESS_ABSOLUTE_GATE {
+            ((relative_gate - LOUDNESS_ABSOLUTE_GATE) * LOUDNESS_BINS_PER_LU) as usize
+        } else {
+            0
+        };
+        let (energy, count) = self.gated_energy(first_bin);
+        if count == 0 {
+            f32::NEG_INFINITY
+        } else {
+            energy_to_loudness(energy / count as f32)
+        }
+    }
+
+    /// Clears the measurement and the filter state
+    pub fn reset(&mut self) {
+        self.state = [0.0; 4];
+        self.current_energy = 0.0;
+        self.current_count = 0;
+        self.previous_count = 0;
+        self.bin_counts = [0; LOUDNESS_BINS];
+        self.bin_energy = [0.0; LOUDNESS_BINS];
+    }
+
+    /// Handles the end of a 100 ms step, adding a 400 ms block if enough steps are available
+    fn finish_step(&mut self) {
+        if self.previous_count == self.previous_energy.len() {
+            let block_energy = self.previous_energy.iter().sum::<f32>() + self.current_energy;
+            self.add_block(block_energy / (4 * self.step_length) as f32);
+            self.previous_energy.copy_within(1.., 0);
+            self.previous_energy[self.previous_count - 1] = self.current_energy;
+        } else {
+            self.previous_energy[self.previous_count] = self.current_energy;
+            self.previous_count += 1;
+        }
+        self.current_energy = 0.0;
+        self.current_count = 0;
+    }
+
+    /// Adds a block with the provided mean square to the histogram
+    fn add_block(&mut self, mean_square: f32) {
+        if mean_square <= 0.0 {
+            return;
+        }
+        let loudness = energy_to_loudness(mean_square);
+        if loudness <= LOUDNESS_ABSOLUTE_GATE {
+            return;
+        }
+        let bin = ((loudness - LOUDNESS_ABSOLUTE_GATE) * LOUDNESS_BINS_PER_LU) as usize;
+        let bin = bin.min(LOUDNESS_BINS - 1);
+        self.bin_counts[bin] += 1;
+        self.bin_energy[bin] += mean_square;
+    }
+
+    /// Returns the total mean square and number of blocks in the histogram bins starting
+    /// at first_bin
+    fn gated_energy(&self, first_bin: usize) -> (f32, u32) {
+        let first_bin = first_bin.min(LOUDNESS_BINS);
+        let energy = self.bin_energy[first_bin..].iter().sum();
+        let count = self.bin_counts[first_bin..].iter().sum();
+        (energy, count)
+    }
+}
+
+/// Converts a K-weighted mean square into a loudness in LKFS
+fn energy_to_loudness(mean_square: f32) -> f32 {
+    -0.691 + 10.0 * log10_f32(mean_square)
+}
+
+/// A level meter that measures the RMS level of blocks of samples in dBFS, with separate
+/// attack and release times
+///
+/// The RMS level of each block is calculated with `arm_rms_f32` and converted to decibels
+/// relative to a full-scale value of 1.0. The displayed level follows it with a one-pole
+/// smoother, `level = target + c * (level - target)`, where `c = exp(-block_length / (tau *
+/// sample_rate))` and tau is the attack time when the level is rising or the release time when
+/// it is falling. A short attack time and a long release time give a meter that responds quickly
+/// to peaks and falls back slowly.
+///
+/// Levels below `fast_math::DB_FLOOR` (-120 dBFS) are replaced with `DB_FLOOR`, which is also
+/// the initial level.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct LevelMeter {
+    /// Attack time constant, in samples
+    attack: f32,
+    /// Release time constant, in samples
+    release: f32,
+    /// Most recent smoothed level, in dBFS
+    level: f32,
+}
+
+impl LevelMeter {
+    /// Creates a level meter
+    ///
+    /// attack: time constant in seconds for rising levels
+    ///
+    /// release: time constant in seconds for falling levels
+    ///
+    /// sample_rate: sample rate of the signal in hertz
+    ///
+    /// A time constant of zero makes the level follow the block level immediately in that
+    /// direction. This function returns an error if either time constant is negative or NaN, or
+    /// if sample_rate is not positive.
+    pub fn new(attack: f32, release: f32, sample_rate: f32) -> Result<Self> {
+        let valid_time = |time: f32| time >= 0.0;
+        if !valid_time(attack) || !valid_time(release) || sample_rate.is_nan() || sample_rate <= 0.0
+        {
+            return Err(Error::Argument);
+        }
+        Ok(LevelMeter {
+            attack: attack * sample_rate,
+            release: release * sample_rate,
+            level: DB_FLOOR,
+        })
+    }
+
+    /// Returns the most recent smoothed level, in dBFS
+    pub fn level(&self) -> f32 {
+        self.level
+    }
+
+    /// Measures a block of samples and returns the updated smoothed level, in dBFS
+    ///
+    /// The time constants are applied to the length of the block, so blocks of any length may
+    /// be provided. An empty block does not change the level.
+    pub fn process(&mut self, block: &[f32]) -> f32 {
+        if block.is_empty() {
+            return self.level;
+        }
+        let mut rms = 0.0;
+        rms_f32(block, block.len(), &mut rms);
+        let target = if rms > 0.0 {
+            (20.0 * log10_f32(rms)).max(DB_FLOOR)
+        } else {
+            DB_FLOOR
+        };
+        let time_constant = if target > self.level {
+            self.attack
+        } else {
+            self.release
+        };
+        let coefficient = if time_constant > 0.0 {
+            exp_f32(-(block.len() as f32) / time_constant)
+        } else {
+            0.0
+        };
+        self.level = target + coefficient * (self.level - target);
+        self.level
+    }
+
+    /// Sets the level back to `DB_FLOOR`
+    pub fn reset(&mut self) {
+        self.level = DB_FLOOR;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rms() {
+        // Only the value that dst refers to is written, not one value per element
+        let mut values = [0.0, 7.0];
+        rms_f32(&[3.0, -4.0, 3.0, -4.0], 4, &mut values[0]);
+        assert!((values[0] - 12.5f32.sqrt()).abs() < 1e-6);
+        assert_eq!(values[1], 7.0);
+    }
+
+    #[test]
+    fn loudness_meter_rejects_invalid_sample_rates() {
+        for &sample_rate in &[0.0, 4.9, -48000.0, f32::NAN, f32::INFINITY] {
+            assert!(matches!(
+                LoudnessMeter::new(sample_rate),
+                Err(Error::Argument)
+            ));
+        }
+        assert!(LoudnessMeter::new(5.0).is_ok());
+    }
+
+    #[test]
+    fn loudness_meter_full_scale_sine() {
+        // ITU-R BS.1770: a full-scale 997 Hz sine has a loudness of -3.01 LKFS
+        let sample_rate = 48000.0;
+        let mut meter = LoudnessMeter::new(sample_rate).unwrap();
+        assert_eq!(meter.integrated_lufs(), f32::NEG_INFINITY);
+        let mut block = [0.0f32; 480];
+        let block_length = block.len();
+        for i in 0..400 {
+            for (j, sample) in block.iter_mut().enumerate() {
+                let n = (i * block_length + j) as f32;
+                *sample = (2.0 * PI * 997.0 * n / sample_rate).sin();
+            }
+            meter.process(&block);
+        }
+        let loudness = meter.integrated_lufs();
+        assert!((loudness + 3.01).abs() < 0.05, "loudness {}", loudness);
+
+        meter.reset();
+        assert_eq!(meter.integrated_lufs(), f32::NEG_INFINITY);
+    }
+
+    #[test]
+    fn level_meter_attack_and_release() {
+        // 10 ms attack, 1 s release, and 10 ms blocks
+        let mut meter = LevelMeter::new(0.01, 1.0, 48000.0).unwrap();
+        assert_eq!(meter.level(), DB_FLOOR);
+        let silence = [0.0f32; 480];
+        let burst = [0.5f32; 480];
+        assert_eq!(meter.process(&silence), DB_FLOOR);
+
+        // One attack time constant covers 1 - 1 / e of the distance to the burst level
+        let burst_level = 20.0 * 0.5f32.log10();
+        let expected = burst_level + (DB_FLOOR - burst_level) / core::f32::consts::E;
+        assert!((meter.process(&burst) - expected).abs() < 1e-3);
+        for _ in 0..9 {
+            meter.process(&burst);
+        }
+        assert!((meter.level() - burst_level).abs() < 0.01);
+
+        // After 100 ms of silence, the level has fallen by 1 - 1 / e^0.1 of the distance
+        for _ in 0..10 {
+            meter.process(&silence);
+        }
+        let expected = DB_FLOOR + (burst_level - DB_FLOOR) * (-0.1f32).exp();
+        assert!((meter.level() - expected).abs() < 0.05);
+        assert!(meter.level() > burst_level - 12.0);
+
+        // An empty block does not change the level
+        let level = meter.level();
+        assert_eq!(meter.process(&[]), level);
+        meter.reset();
+        assert_eq!(meter.level(), DB_FLOOR);
+    }
+
+    #[test]
+    fn level_meter_zero_time_constants_follow_immediately() {
+        let mut meter = LevelMeter::new(0.0, 0.0, 48000.0).unwrap();
+        assert!((meter.process(&[1.0, -1.0]) - 0.0).abs() < 1e-6);
+        assert_eq!(meter.process(&[0.0, 0.0]), DB_FLOOR);
+    }
+
+    #[test]
+    fn level_meter_rejects_invalid_arguments() {
+        assert!(LevelMeter::new(-0.01, 1.0, 48000.0).is_err());
+        assert!(LevelMeter::new(0.01, f32::NAN, 48000.0).is_err());
+        assert!(LevelMeter::new(0.01, 1.0, 0.0).is_err());
+        assert!(LevelMeter::new(0.01, 1.0, f32::NAN).is_err());
+    }
+
+    #[test]
+    fn find_peaks() {
+        let src = [0.0, 3.0, 1.0, 0.5, 5.0, 5.0, 2.0, 0.0, 4.0, 1.0, 9.0];
+        let mut peaks = [(0, 0.0); 4];
+        // The last value is never a peak, and the flat peak is reported at its first index
+        assert_eq!(find_peaks_f32(&src, 0.0, 0, &mut peaks), 3);
+        assert_eq!(peaks[..3], [(1, 3.0), (4, 5.0), (8, 4.0)]);
+        // Only the peaks above the threshold
+        assert_eq!(find_peaks_f32(&src, 3.5, 0, &mut peaks), 2);
+        assert_eq!(peaks[..2], [(4, 5.0), (8, 4.0)]);
+    }
+
+    #[test]
+    fn find_peaks_truncates_to_the_lowest_indices() {
+        let src = [0.0, 3.0, 1.0, 0.5, 5.0, 5.0, 2.0, 0.0, 4.0, 1.0, 9.0];
+        let mut peaks = [(0, 0.0); 2];
+        assert_eq!(find_peaks_f32(&src, 0.0, 0, &mut peaks), 2);
+        assert_eq!(peaks, [(1, 3.0), (4, 5.0)]);
+        assert_eq!(find_peaks_f32(&src, 0.0, 0, &mut []), 0);
+    }
+
+    #[test]
+    fn find_peaks_keeps_the_larger_of_close_peaks() {
+        let src = [0.0, 3.0, 1.0, 5.0, 0.0, 2.0, 0.0, 0.0, 0.0, 4.0, 0.0];
+        let mut peaks = [(0, 0.0); 4];
+        assert_eq!(find_peaks_f32(&src, 0.0, 1, &mut peaks), 4);
+        assert_eq!(find_peaks_f32(&src, 0.0, 3, &mut peaks), 2);
+        assert_eq!(peaks[..2], [(3, 5.0), (9, 4.0)]);
+    }
+
+    #[test]
+    fn find_peaks_ignores_nan() {
+        let src = [0.0, f32::NAN, 0.0, 2.0, f32::NAN];
+        let mut peaks = [(0, 0.0); 4];
+        assert_eq!(find_peaks_f32(&src, 0.0, 0, &mut peaks), 0);
+        assert_eq!(find_peaks_f32(&[1.0, 2.0], 0.0, 0, &mut peaks), 0);
+    }
+
+    #[test]
+    fn histogram_of_ramp() {
+        let ramp: [f32; 100] = core::array::from_fn(|i| i as f32);
+        let mut bins = [0; 10];
+        histogram_f32(&ramp, 0.0, 100.0, &mut bins);
+        assert_eq!(bins, [10; 10]);
+        // Counts accumulate, the bins are half as wide, and values outside [min, max) are ignored
+        histogram_f32(&ramp, 50.0, 55.0, &mut bins);
+        assert_eq!(bins, [11, 10, 11, 10, 11, 10, 11, 10, 11, 10]);
+        histogram_f32(&[f32::NAN, 49.9, 55.0], 50.0, 55.0, &mut bins);
+        assert_eq!(bins, [11, 10, 11, 10, 11, 10, 11, 10, 11, 10]);
+    }
+
+    #[test]
+    fn histogram_with_automatic_range() {
+        let ramp: [f32; 101] = core::array::from_fn(|i| i as f32 - 50.0);
+        let mut bins = [0; 4];
+        // The range is [-50, 50], and the largest value is counted in the last bin
+        histogram_f32(&ramp, 0.0, 0.0, &mut bins);
+        assert_eq!(bins, [25, 25, 25, 26]);
+
+        let mut bins = [0; 4];
+        histogram_f32(&[3.0; 5], 1.0, 1.0, &mut bins);
+        assert_eq!(bins, [5, 0, 0, 0]);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn median() {
+        assert_eq!(median_f32(&[5.0, 1.0, 4.0, 2.0, 3.0]), 3.0);
+        assert_eq!(median_f32(&[5.0, 1.0, 4.0, 2.0]), 3.0);
+        assert_eq!(median_f32(&[7.0]), 7.0);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn percentile_of_ramp() {
+        // A shuffled ramp from 0 to 100
+        let ramp: [f32; 101] = core::array::from_fn(|i| ((i * 37) % 101) as f32);
+        assert_eq!(percentile_f32(&ramp, 0.0), 0.0);
+        assert_eq!(percentile_f32(&ramp, 90.0), 90.0);
+        assert_eq!(percentile_f32(&ramp, 100.0), 100.0);
+        // Between two ranks
+        let values = [10.0, 0.0, 30.0, 20.0];
+        assert!((percentile_f32(&values, 90.0) - 27.0).abs() < 1e-4);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    #[should_panic]
+    fn percentile_rejects_out_of_range() {
+        percentile_f32(&[1.0, 2.0], 100.5);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    #[should_panic]
+    fn median_rejects_empty() {
+        median_f32(&[]);
+    }
+
+    #[test]
+    fn running_stats_match_var_f32() {
+        let values: [f32; 100] = core::array::from_fn(|i| 3.0 + (i as f32 * 0.37).sin());
+        let (mut expected_mean, mut expected_var) = (0.0, 0.0);
+        mean_f32(&values, values.len(), &mut expected_mean);
+        var_f32(&values, values.len(), &mut expected_var);
+
+        let mut single = RunningStats::new();
+        for &x in values.iter() {
+            single.push(x);
+        }
+        // Blocks of different lengths, including a single value
+        let mut blocks = RunningStats::new();
+        for chunk in [&values[..1], &values[1..40], &values[40..41], &values[41..]].iter() {
+            blocks.push_block(chunk);
+        }
+        for stats in [single, blocks].iter() {
+            assert_eq!(stats.count(), 100);
+            assert!((stats.mean() - expected_mean).abs() < 1e-5);
+            assert!((stats.variance() - expected_var).abs() < 1e-5);
+            assert!((stats.std() - expected_var.sqrt()).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn running_stats_with_few_values() {
+        let mut stats = RunningStats::new();
+        assert_eq!(
+            (stats.count(), stats.mean(), stats.variance()),
+            (0, 0.0, 0.0)
+        );
+        stats.push(2.0);
+        assert_eq!(
+            (stats.count(), stats.mean(), stats.variance()),
+            (1, 2.0, 0.0)
+        );
+        stats.push_block(&[4.0, 6.0]);
+        assert_eq!(
+            (stats.count(), stats.mean(), stats.variance()),
+            (3, 4.0, 4.0)
+        );
+        stats.reset();
+        assert_eq!(stats, RunningStats::new());
+    }
+
+    #[test]
+    fn spectral_centroid_of_single_tone() {
+        // 129 bins of a 256-point FFT at 8 kHz, 31.25 Hz apart, which spans several chunks
+        let mut magnitudes = [0.0; 129];
+        magnitudes[40] = 2.5;
+        assert!((spectral_centroid_f32(&magnitudes, 8000.0) - 1250.0).abs() < 1e-3);
+        assert!(spectral_bandwidth_f32(&magnitudes, 8000.0).abs() < 1e-3);
+        // A tone at the Nyquist frequency
+        magnitudes[40] = 0.0;
+        magnitudes[128] = 1.0;
+        assert!((spectral_centroid_f32(&magnitudes, 8000.0) - 4000.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn spectral_centroid_of_two_tones() {
+        let mut magnitudes = [0.0; 129];
+        magnitudes[10] = 1.0;
+        magnitudes[100] = 1.0;
+        // Halfway between the tones, with a deviation of half their distance
+        assert!((spectral_centroid_f32(&magnitudes, 8000.0) - 55.0 * 31.25).abs() < 1e-2);
+        assert!((spectral_bandwidth_f32(&magnitudes, 8000.0) - 45.0 * 31.25).abs() < 1e-2);
+    }
+
+    #[test]
+    fn spectral_centroid_of_silence() {
+        assert_eq!(spectral_centroid_f32(&[0.0; 33], 8000.0), 0.0);
+        assert_eq!(spectral_bandwidth_f32(&[0.0; 33], 8000.0), 0.0);
+    }
+
+    #[test]
+    fn coherent_averager_reduces_noise_by_sqrt_n() {
+        let signal: [f32; 256] =
+            core::array::from_fn(|n| (2.0 * core::f32::consts::PI * n as f32 / 32.0).sin());
+        let mut sum = [1.0; 256];
+        let mut averager = CoherentAverager::new(&mut sum);
+        let mut average = [1.0; 256];
+        averager.average(&mut average);
+        assert_eq!(average, [0.0; 256]);
+
+        let mut state = 12345u32;
+        let mut frame = [0.0; 256];
+        let mut single_error = 0.0;
+        for i in 0..64 {
+            for (x, s) in frame.iter_mut().zip(&signal) {
+                state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                *x = s + ((state >> 8) as f32 / (1 << 23) as f32 - 1.0);
+            }
+            if i == 0 {
+                single_error = rms_error(&frame, &signal);
+            }
+            averager.add_frame(&frame);
+        }
+        assert_eq!(averager.count(), 64);
+        averager.average(&mut average);
+        // Uniform noise in [-1, 1) has an RMS of 1 / sqrt(3)
+        assert!((single_error - 0.577).abs() < 0.05, "{}", single_error);
+        let ratio = single_error / rms_error(&average, &signal);
+        assert!((ratio - 8.0).abs() < 1.0, "{}", ratio);
+
+        averager.reset();
+        assert_eq!(averager.count(), 0);
+        averager.add_frame(&signal);
+        averager.average(&mut average);
+        assert_eq!(average, signal);
+    }
+
+    fn rms_error(a: &[f32], b: &[f32]) -> f32 {
+        let sum: f32 = a.iter().zip(b).map(|(a, b)| (a - b) * (a - b)).sum();
+        (sum / a.len() as f32).sqrt()
+    }
+
+    #[test]
+    fn value_returning_statistics() {
+        let values = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        assert_eq!(mean(&values), 5.0);
+        // The sample variance, with n - 1 in the denominator
+        assert!((var(&values) - 32.0 / 7.0).abs() < 1e-6);
+        assert!((std(&values) - (32.0f32 / 7.0).sqrt()).abs() < 1e-6);
+        assert_eq!(power(&values), 232.0);
+        assert!((super::rms(&values) - 29.0f32.sqrt()).abs() < 1e-6);
+
+        let mut result = 0.0;
+        mean_f32(&values, values.len(), &mut result);
+        assert_eq!(mean(&values), result);
+        var_f32(&values, values.len(), &mut result);
+        assert_eq!(var(&values), result);
+
+        assert_eq!(mean(&[-3.0]), -3.0);
+        assert_eq!(var(&[-3.0]), 0.0);
+        assert_eq!(super::rms(&[-3.0, 3.0]), 3.0);
+    }
+
+    #[test]
+    fn stereo_statistics() {
+        // Left is 1, -1, 3, 1 and right is 0, 4, 0, -4
+        let interleaved = [1.0, 0.0, -1.0, 4.0, 3.0, 0.0, 1.0, -4.0];
+        assert_eq!(mean_stereo_f32(&interleaved), (1.0, 0.0));
+        let (left, right) = rms_stereo_f32(&interleaved);
+        assert!((left - 3.0f32.sqrt()).abs() < 1e-6);
+        assert!((right - 8.0f32.sqrt()).abs() < 1e-6);
+        assert_eq!(peak_stereo_f32(&interleaved), (3.0, 4.0));
+
+        assert_eq!(peak_stereo_f32(&[0.0, -0.5]), (0.0, 0.5));
+    }
+
+    #[test]
+    #[should_panic]
+    fn stereo_statistics_reject_odd_lengths() {
+        mean_stereo_f32(&[1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn mse_known_values() {
+        let mut result = 0.0;
+        mse_f32(&[1.0, 2.0, 3.0, 4.0], &[1.0, 0.0, 4.0, 4.0], &mut result);
+        assert_eq!(result, 1.25);
+        mse_f32(&[0.5; 3], &[0.5; 3], &mut result);
+        assert_eq!(result, 0.0);
+
+        let a = [0.5, -0.25, 0.0, 0.75];
+        let b = [0.0, 0.25, 0.0, 0.25];
+        // The differences are 0.5, -0.5, 0 and 0.5, so the mean square error is 0.1875
+        let mut q31 = I1F31::ZERO;
+        mse_q31(&a.map(I1F31::from_num), &b.map(I1F31::from_num), &mut q31);
+        assert!((q31.to_num::<f32>() - 0.1875).abs() < 1e-6, "{}", q31);
+        let mut q15 = I1F15::ZERO;
+        mse_q15(&a.map(I1F15::from_num), &b.map(I1F15::from_num), &mut q15);
+        assert!((q15.to_num::<f32>() - 0.1875).abs() < 1e-4, "{}", q15);
+    }
+
+    #[test]
+    fn mse_of_full_scale_differences() {
+        // The largest difference, 2, cannot overflow, and the result saturates
+        let mut q31 = I1F31::ZERO;
+        mse_q31(&[I1F31::MAX; 4], &[I1F31::MIN; 4], &mut q31);
+        assert_eq!(q31, I1F31::MAX);
+        let mut q15 = I1F15::ZERO;
+        mse_q15(&[I1F15::MAX; 4], &[I1F15::MIN; 4], &mut q15);
+        assert_eq!(q15, I1F15::MAX);
+    }
+
+    #[test]
+    #[should_panic]
+    fn mse_rejects_empty_sources() {
+        mse_f32(&[], &[], &mut 0.0);
+    }
+
+    #[test]
+    fn short_time_energy() {
+        assert_eq!(short_time_energy_f32(&[1.0, -1.0, 2.0, 0.0]), 1.5);
+        assert_eq!(short_time_energy_f32(&[0.0; 8]), 0.0);
+        assert_eq!(short_time_energy_f32(&[]), 0.0);
+    }
+
+    #[test]
+    fn zero_crossing_rate() {
+        // Sign changes between 1 and -1, -1 and 0, and 0 and -2; zero counts as positive
+        assert_eq!(zero_crossing_rate_f32(&[1.0, -1.0, 0.0, -2.0, -3.0]), 0.6);
+        assert_eq!(zero_crossing_rate_f32(&[0.5; 4]), 0.0);
+        assert_eq!(zero_crossing_rate_f32(&[1.0]), 0.0);
+        assert_eq!(zero_crossing_rate_f32(&[]), 0.0);
+
+        // A sinusoid crosses zero about 2 * f / fs times per sample
+        let sine: [f32; 1000] =
+            core::array::from_fn(|n| (2.0 * core::f32::consts::PI * 0.05 * n as f32 + 0.1).sin());
+        assert!((zero_crossing_rate_f32(&sine) - 0.1).abs() < 0.002);
+    }
+
+    #[test]
+    fn remove_dc_subtracts_the_mean() {
+        let src = [3.0, 5.0, 4.0, 8.0];
+        let mut dst = [0.0; 4];
+        remove_dc_f32(&src, &mut dst);
+        assert_eq!(dst, [-2.0, 0.0, -1.0, 3.0]);
+        assert_eq!(mean(&dst), 0.0);
+
+        let mut dst = [0.0; 3];
+        remove_dc_f32(&[-1.5; 3], &mut dst);
+        assert_eq!(dst, [0.0; 3]);
+        remove_dc_f32(&[], &mut []);
+    }
+}
@@ -0,0 +1,873 @@
+//! Support functions: copying, filling, sorting, and conversions between data types
+
+use core::mem::{align_of, size_of};
+
+use fixed::types::{I1F15, I1F31, I1F7};
+use num_complex::Complex;
+
+use crate::basic::clip_f32;
+use crate::{check_length_or_panic, Error, Result};
+
+// The fixed-point types wrap the integer types that CMSIS-DSP uses for each format, so slices of
+// them can be passed to CMSIS-DSP functions as pointers to the integer type. These checks make
+// the build fail if that stops being true.
+const _: () =
+    assert!(size_of::<I1F31>() == size_of::<i32>() && align_of::<I1F31>() == align_of::<i32>());
+const _: () =
+    assert!(size_of::<I1F15>() == size_of::<i16>() && align_of::<I1F15>() == align_of::<i16>());
+const _: () =
+    assert!(size_of::<I1F7>() == size_of::<i8>() && align_of::<I1F7>() == align_of::<i8>());
+const _: () = assert!(
+    size_of::<Complex<I1F31>>() == 2 * size_of::<i32>()
+        && align_of::<Complex<I1F31>>() == align_of::<i32>()
+);
+const _: () = assert!(
+    size_of::<Complex<I1F15>>() == 2 * size_of::<i16>()
+        && align_of::<Complex<I1F15>>() == align_of::<i16>()
+);
+
+/// Returns a pointer to Q1.31 values, for a CMSIS-DSP function that takes `q31_t` values
+pub(crate) fn as_q31_ptr(values: &[I1F31]) -> *const i32 {
+    values.as_ptr() as *const i32
+}
+
+/// Returns a mutable pointer to Q1.31 values, for a CMSIS-DSP function that takes `q31_t`
+/// values
+pub(crate) fn as_q31_mut_ptr(values: &mut [I1F31]) -> *mut i32 {
+    values.as_mut_ptr() as *mut i32
+}
+
+/// Returns a pointer to Q1.15 values, for a CMSIS-DSP function that takes `q15_t` values
+pub(crate) fn as_q15_ptr(values: &[I1F15]) -> *const i16 {
+    values.as_ptr() as *const i16
+}
+
+/// Returns a mutable pointer to Q1.15 values, for a CMSIS-DSP function that takes `q15_t`
+/// values
+pub(crate) fn as_q15_mut_ptr(values: &mut [I1F15]) -> *mut i16 {
+    values.as_mut_ptr() as *mut i16
+}
+
+/// Returns a pointer to Q1.7 values, for a CMSIS-DSP function that takes `q7_t` values
+pub(crate) fn as_q7_ptr(values: &[I1F7]) -> *const i8 {
+    values.as_ptr() as *const i8
+}
+
+/// Returns a mutable pointer to Q1.7 values, for a CMSIS-DSP function that takes `q7_t` values
+pub(crate) fn as_q7_mut_ptr(values: &mut [I1F7]) -> *mut i8 {
+    values.as_mut_ptr() as *mut i8
+}
+
+/// Returns a mutable pointer to complex Q1.31 values, for a CMSIS-DSP function that takes
+/// interleaved real and imaginary `q31_t` values
+pub(crate) fn as_complex_q31_mut_ptr(values: &mut [Complex<I1F31>]) -> *mut i32 {
+    values.as_mut_ptr() as *mut i32
+}
+
+/// Returns a mutable pointer to complex Q1.15 values, for a CMSIS-DSP function that takes
+/// interleaved real and imaginary `q15_t` values
+pub(crate) fn as_complex_q15_mut_ptr(values: &mut [Complex<I1F15>]) -> *mut i16 {
+    values.as_mut_ptr() as *mut i16
+}
+
+/// Converts floating-point values to Q1.31 fixed-point values
+///
+/// Values outside the range [-1, 1) saturate to the minimum or maximum Q1.31 value.
+///
+/// # Panics
+///
+/// This function panics if src and dst do not have the same length.
+pub fn f32_to_q31(src: &[f32], dst: &mut [I1F31]) {
+    let length = check_length_or_panic((src.len(), dst.len()));
+    unsafe {
+        cmsis_dsp_sys::arm_float_to_q31(src.as_ptr(), as_q31_mut_ptr(dst), length);
+    }
+}
+
+/// Converts floating-point values to Q1.15 fixed-point values
+///
+/// Values outside the range [-1, 1) saturate to the minimum or maximum Q1.15 value.
+///
+/// # Panics
+///
+/// This function panics if src and dst do not have the same length.
+pub fn f32_to_q15(src: &[f32], dst: &mut [I1F15]) {
+    let length = check_length_or_panic((src.len(), dst.len()));
+    unsafe {
+        cmsis_dsp_sys::arm_float_to_q15(src.as_ptr(), as_q15_mut_ptr(dst), length);
+    }
+}
+
+/// Converts floating-point values to Q1.7 fixed-point values
+///
+/// Values outside the range [-1, 1) saturate to the minimum or maximum Q1.7 value.
+///
+/// # Panics
+///
+/// This function panics if src and dst do not have the same length.
+pub fn f32_to_q7(src: &[f32], dst: &mut [I1F7]) {
+    let length = check_length_or_panic((src.len(), dst.len()));
+    unsafe {
+        cmsis_dsp_sys::arm_float_to_q7(src.as_ptr(), as_q7_mut_ptr(dst), length);
+    }
+}
+
+/// How to handle floating-point values that are outside the range of a fixed-point format
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SaturationPolicy {
+    /// Replace values outside the range with the minimum or maximum fixed-point value
+    Saturate,
+    /// Return an error if any value is outside the range
+    Error,
+    /// Discard the high bits of each value, so values outside the range wrap around
+    Wrap,
+}
+
+/// Converts floating-point values to Q1.31 fixed-point values, handling values outside the
+/// range [-1, 1) according to policy
+///
+/// With `SaturationPolicy::Error`, this function returns an error and does not modify dst if
+/// any value is outside the range or is NaN. With `SaturationPolicy::Wrap`, each value is
+/// rounded to the nearest Q1.31 value like the CMSIS-DSP conversion that the other policies use,
+/// and then wraps around: for example, 1.5 becomes -0.5.
+///
+/// # Panics
+///
+/// This function panics if src and dst do not have the same length.
+pub fn f32_to_q31_with_policy(
+    src: &[f32],
+    dst: &mut [I1F31],
+    policy: SaturationPolicy,
+) -> Result<()> {
+    match policy {
+        SaturationPolicy::Saturate => f32_to_q31(src, dst),
+        SaturationPolicy::Error => {
+            check_fixed_range(src)?;
+            f32_to_q31(src, dst);
+        }
+        SaturationPolicy::Wrap => {
+            check_length_or_panic::<(usize, usize), usize>((src.len(), dst.len()));
+            for (value, out) in src.iter().zip(dst.iter_mut()) {
+                *out = I1F31::from_bits(scale_and_round(*value, 2147483648.0) as i32);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Converts floating-point values to Q1.15 fixed-point values, handling values outside the
+/// range [-1, 1) according to policy
+///
+/// With `SaturationPolicy::Error`, this function returns an error and does not modify dst if
+/// any value is outside the range or is NaN. With `SaturationPolicy::Wrap`, each value is
+/// rounded to the nearest Q1.15 value like the CMSIS-DSP conversion that the other policies use,
+/// and then wraps around: for example, 1.5 becomes -0.5.
+///
+/// # Panics
+///
+/// This function panics if src and dst do not have the same length.
+pub fn f32_to_q15_with_policy(
+    src: &[f32],
+    dst: &mut [I1F15],
+    policy: SaturationPolicy,
+) -> Result<()> {
+    match policy {
+        SaturationPolicy::Saturate => f32_to_q15(src, dst),
+        SaturationPolicy::Error => {
+            check_fixed_range(src)?;
+            f32_to_q15(src, dst);
+        }
+        SaturationPolicy::Wrap => {
+            check_length_or_panic::<(usize, usize), usize>((src.len(), dst.len()));
+            for (value, out) in src.iter().zip(dst.iter_mut()) {
+                *out = I1F15::from_bits(scale_and_round(*value, 32768.0) as i16);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Converts floating-point values to Q1.7 fixed-point values, handling values outside the
+/// range [-1, 1) according to policy
+///
+/// With `SaturationPolicy::Error`, this function returns an error and does not modify dst if
+/// any value is outside the range or is NaN. With `SaturationPolicy::Wrap`, each value is
+/// rounded to the nearest Q1.7 value like the CMSIS-DSP conversion that the other policies use,
+/// and then wraps around: for example, 1.5 becomes -0.5.
+///
+/// # Panics
+///
+/// This function panics if src and dst do not have the same length.
+pub fn f32_to_q7_with_policy(
+    src: &[f32],
+    dst: &mut [I1F7],
+    policy: SaturationPolicy,
+) -> Result<()> {
+    match policy {
+        SaturationPolicy::Saturate => f32_to_q7(src, dst),
+        SaturationPolicy::Error => {
+            check_fixed_range(src)?;
+            f32_to_q7(src, dst);
+        }
+        SaturationPolicy::Wrap => {
+            check_length_or_panic::<(usize, usize), usize>((src.len(), dst.len()));
+            for (value, out) in src.iter().zip(dst.iter_mut()) {
+                *out = I1F7::from_bits(scale_and_round(*value, 128.0) as i8);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Multiplies value by scale and rounds the result to the nearest integer, with ties rounded
+/// away from zero
+fn scale_and_round(value: f32, scale: f64) -> i64 {
+    let scaled = f64::from(value) * scale;
+    (scaled + if scaled > 0.0 { 0.5 } else { -0.5 }) as i64
+}
+
+/// Returns an error if any value is outside the range [-1, 1) of the Q1.x formats
+fn check_fixed_range(src: &[f32]) -> Result<()> {
+    if src.iter().all(|value| (-1.0..1.0).contains(value)) {
+        Ok(())
+    } else {
+        Err(Error::Argument)
+    }
+}
+
+/// Converts Q1.31 fixed-point values to floating-point values
+///
+/// # Panics
+///
+/// This function panics if src and dst do not have the same length.
+pub fn q31_to_f32(src: &[I1F31], dst: &mut [f32]) {
+    let length = check_length_or_panic((src.len(), dst.len()));
+    unsafe {
+        cmsis_dsp_sys::arm_q31_to_float(as_q31_ptr(src), dst.as_mut_ptr(), length);
+    }
+}
+
+/// Converts Q1.15 fixed-point values to floating-point values
+///
+/// # Panics
+///
+/// This function panics if src and dst do not have the same length.
+pub fn q15_to_f32(src: &[I1F15], dst: &mut [f32]) {
+    let length = check_length_or_panic((src.len(), dst.len()));
+    unsafe {
+        cmsis_dsp_sys::arm_q15_to_float(as_q15_ptr(src), dst.as_mut_ptr(), length);
+    }
+}
+
+/// Converts Q1.7 fixed-point values to floating-point values
+///
+/// # Panics
+///
+/// This function panics if src and dst do not have the same length.
+pub fn q7_to_f32(src: &[I1F7], dst: &mut [f32]) {
+    let length = check_length_or_panic((src.len(), dst.len()));
+    unsafe {
+        cmsis_dsp_sys::arm_q7_to_float(as_q7_ptr(src), dst.as_mut_ptr(), length);
+    }
+}
+
+/// Number of samples that `clamp_to_q15` clips at a time
+const CLAMP_CHUNK: usize = 64;
+
+/// Limits floating-point values to the range [low, high] and converts them to Q1.15 fixed-point
+/// values, for example to drive a DAC
+///
+/// This combines `basic::clip_f32` and `f32_to_q15` without an intermediate buffer of the full
+/// length: the values are clipped into a small buffer on the stack, which is then converted.
+/// Values that are still outside the range [-1, 1) after clipping saturate to the minimum or
+/// maximum Q1.15 value.
+///
+/// # Panics
+///
+/// This function panics if src and dst do not have the same length, or if low is greater than
+/// high.
+pub fn clamp_to_q15(src: &[f32], low: f32, high: f32, dst: &mut [I1F15]) {
+    check_length_or_panic::<(usize, usize), usize>((src.len(), dst.len()));
+    assert!(low <= high, "low must be less than or equal to high");
+    let mut clipped = [0.0; CLAMP_CHUNK];
+    for (src, dst) in src.chunks(CLAMP_CHUNK).zip(dst.chunks_mut(CLAMP_CHUNK)) {
+        let clipped = &mut clipped[..src.len()];
+        clip_f32(src, low, high, clipped);
+        f32_to_q15(clipped, dst);
+    }
+}
+
+/// Converts Q1.15 fixed-point values to floating-point values and limits them to the range
+/// [low, high], for example to read an ADC
+///
+/// This is the inverse of `clamp_to_q15`.
+///
+/// # Panics
+///
+/// This function panics if src and dst do not have the same length, or if low is greater than
+/// high.
+pub fn q15_to_clamped_f32(src: &[I1F15], low: f32, high: f32, dst: &mut [f32]) {
+    assert!(low <= high, "low must be less than or equal to high");
+    q15_to_f32(src, dst);
+    for value in dst.iter_mut() {
+        *value = value.max(low).min(high);
+    }
+}
+
+/// Converts fixed-point values from one format to another
+///
+/// This supports all conversions between Q1.31, Q1.15, and Q1.7 values, and calls the appropriate
+/// CMSIS-DSP function for the source and destination types.
+///
+/// All of these formats represent values in the range [-1, 1), so a conversion never overflows.
+/// Conversions to a narrower format discard the least significant bits (rounding toward negative
+/// infinity), and conversions to a wider format are exact.
+///
+/// # Panics
+///
+/// This function panics if src and dst do not have the same length.
+pub fn convert_fixed<F, T>(src: &[F], dst: &mut [T])
+where
+    F: ConvertFixed<T>,
+{
+    F::convert_fixed(src, dst)
+}
+
+/// A fixed-point type that can be converted into another fixed-point type
+pub trait ConvertFixed<T>: Sized {
+    /// Converts each value in src into the corresponding value in dst
+    ///
+    /// # Panics
+    ///
+    /// This function panics if src and dst do not have the same length.
+    fn convert_fixed(src: &[Self], dst: &mut [T]);
+}
+
+macro_rules! convert_fixed_impl {
+    { $( $from:ty => $to:ty : $function:ident ($src_ptr:ident, $dst_ptr:ident) ,)+ } => {
+        $(
+            impl ConvertFixed<$to> for $from {
+                fn convert_fixed(src: &[Self], dst: &mut [$to]) {
+                    let length = check_length_or_panic((src.len(), dst.len()));
+                    unsafe {
+                        cmsis_dsp_sys::$function($src_ptr(src), $dst_ptr(dst), length);
+                    }
+                }
+            }
+        )+
+    };
+}
+
+convert_fixed_impl! {
+    I1F31 => I1F31: arm_copy_q31(as_q31_ptr, as_q31_mut_ptr),
+    I1F31 => I1F15: arm_q31_to_q15(as_q31_ptr, as_q15_mut_ptr),
+    I1F31 => I1F7: arm_q31_to_q7(as_q31_ptr, as_q7_mut_ptr),
+    I1F15 => I1F31: arm_q15_to_q31(as_q15_ptr, as_q31_mut_ptr),
+    I1F15 => I1F15: arm_copy_q15(as_q15_ptr, as_q15_mut_ptr),
+    I1F15 => I1F7: arm_q15_to_q7(as_q15_ptr, as_q7_mut_ptr),
+    I1F7 => I1F31: arm_q7_to_q31(as_q7_ptr, as_q31_mut_ptr),
+    I1F7 => I1F15: arm_q7_to_q15(as_q7_ptr, as_q15_mut_ptr),
+    I1F7 => I1F7: arm_copy_q7(as_q7_ptr, as_q7_mut_ptr),
+}
+
+/// Copies values from one slice into another
+///
+/// This is functionally equivalent to `dst.copy_from_slice(src)`, but uses the CMSIS-DSP
+/// implementation.
+///
+/// # Panics
+///
+/// This function panics if src and dst do not have the same length.
+pub fn copy_f32(src: &[f32], dst: &mut [f32]) {
+    let length = check_length_or_panic((src.len(), dst.len()));
+    unsafe {
+        cmsis_dsp_sys::arm_copy_f32(src.as_ptr(), dst.as_mut_ptr(), length);
+    }
+}
+
+/// Sets every value in a slice to the provided value
+///
+/// This is functionally equivalent to performing `dst[i] = value` for all values of i in range.
+pub fn fill_f32(value: f32, dst: &mut [f32]) {
+    let length = check_length_or_panic(dst.len());
+    unsafe {
+        cmsis_dsp_sys::arm_fill_f32(value, dst.as_mut_ptr(), length);
+    }
+}
+
+/// A sorting algorithm that a `Sorter` can use
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SortAlgorithm {
+    /// Bitonic sort, which requires the number of values to be a power of two
+    Bitonic,
+    /// Bubble sort
+    Bubble,
+    /// Heap sort
+    Heap,
+    /// Insertion sort
+    Insertion,
+    /// Quick sort
+    Quick,
+    /// Selection sort
+    Selection,
+}
+
+/// The order that a `Sorter` places values in
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SortOrder {
+    /// Smallest value first
+    Ascending,
+    /// Largest value first
+    Descending,
+}
+
+/// Sorts f32 values with one of the CMSIS-DSP sorting algorithms
+pub struct Sorter(cmsis_dsp_sys::arm_sort_instance_f32);
+
+impl Sorter {
+    /// Creates a sorter that uses the provided algorithm and order
+    pub fn new(algorithm: SortAlgorithm, order: SortOrder) -> Self {
+        use cmsis_dsp_sys::{arm_sort_alg, arm_sort_dir};
+        let alg = match algorithm {
+            SortAlgorithm::Bitonic => arm_sort_alg::ARM_SORT_BITONIC,
+            SortAlgorithm::Bubble => arm_sort_alg::ARM_SORT_BUBBLE,
+            SortAlgorithm::Heap => arm_sort_alg::ARM_SORT_HEAP,
+            SortAlgorithm::Insertion => arm_sort_alg::ARM_SORT_INSERTION,
+            SortAlgorithm::Quick => arm_sort_alg::ARM_SORT_QUICK,
+            SortAlgorithm::Selection => arm_sort_alg::ARM_SORT_SELECTION,
+        };
+        let dir = match order {
+            SortOrder::Ascending => arm_sort_dir::ARM_SORT_ASCENDING,
+            SortOrder::Descending => arm_sort_dir::ARM_SORT_DESCENDING,
+        };
+        let mut instance = cmsis_dsp_sys::arm_sort_instance_f32 { alg, dir };
+        unsafe {
+            cmsis_dsp_sys::arm_sort_init_f32(&mut instance, alg, dir);
+        }
+        Sorter(instance)
+    }
+
+    /// Copies the values in src into dst, sorted
+    ///
+    /// # Panics
+    ///
+    /// This function panics if src and dst do not have the same length, or if this sorter uses
+    /// bitonic sort and the length is not a power of two.
+    pub fn sort(&self, src: &[f32], dst: &mut [f32]) {
+        let length = check_length_or_panic((src.len(), dst.len()));
+        self.check_bitonic_length(length);
+        // arm_sort_f32 copies the source into the destination and then only modifies the
+        // destination, so the source is not written even though the pointer is mutable.
+        unsafe {
+            cmsis_dsp_sys::arm_sort_f32(&self.0, src.as_ptr() as *mut _, dst.as_mut_ptr(), length);
+        }
+    }
+
+    /// Sorts values in place
+    ///
+    /// # Panics
+    ///
+    /// This function panics if this sorter uses bitonic sort and the length of values is not a
+    /// power of two.
+    pub fn sort_in_place(&self, values: &mut [f32]) {
+        let length = check_length_or_panic(values.len());
+        self.check_bitonic_length(length);
+        unsafe {
+            let ptr = values.as_mut_ptr();
+            cmsis_dsp_sys::arm_sort_f32(&self.0, ptr, ptr, length);
+        }
+    }
+
+    fn check_bitonic_length(&self, length: u32) {
+        if self.0.alg == cmsis_dsp_sys::arm_sort_alg::ARM_SORT_BITONIC {
+            assert!(
+                length.is_power_of_two(),
+                "bitonic sort requires a power of two number of values"
+            );
+        }
+    }
+}
+
+/// Calculates the weighted average of a set of values
+///
+/// The returned value is the sum of `values[i] * weights[i]` divided by the sum of `weights[i]`
+/// over all values of i in range.
+///
+/// # Panics
+///
+/// This function panics if values and weights do not have the same length.
+pub fn weighted_average_f32(values: &[f32], weights: &[f32]) -> f32 {
+    let length = check_length_or_panic((values.len(), weights.len()));
+    unsafe { cmsis_dsp_sys::arm_weighted_sum_f32(values.as_ptr(), weights.as_ptr(), length) }
+}
+
+/// Calculates the weighted centroid (barycenter) of a set of vectors
+///
+/// vectors contains `weights.len()` vectors, each with `dim` elements, stored one after
+/// another. The result, which has `dim` elements, is written to out.
+///
+/// # Panics
+///
+/// This function panics if vectors does not have `weights.len() * dim` elements, or if out
+/// does not have `dim` elements.
+pub fn barycenter_f32(vectors: &[f32], weights: &[f32], dim: usize, out: &mut [f32]) {
+    check_length_or_panic::<(usize, usize), u32>((vectors.len(), weights.len() * dim));
+    let vector_count = check_length_or_panic(weights.len());
+    let dim = check_length_or_panic((dim, out.len()));
+    unsafe {
+        cmsis_dsp_sys::arm_barycenter_f32(
+            vectors.as_ptr(),
+            weights.as_ptr(),
+            out.as_mut_ptr(),
+            vector_count,
+            dim,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clamp_to_q15_clips_before_converting() {
+        // Longer than one chunk, so the stack buffer is reused. The values are multiples of
+        // 1 / 64, so they convert exactly.
+        let src: [f32; 150] = core::array::from_fn(|i| (i as f32 - 75.0) / 64.0);
+        let mut dst = [I1F15::ZERO; 150];
+        clamp_to_q15(&src, -0.5, 0.5, &mut dst);
+        for (value, out) in src.iter().zip(dst.iter()) {
+            let expected = I1F15::from_num(value.clamp(-0.5, 0.5));
+            assert_eq!(*out, expected, "value {}", value);
+        }
+    }
+
+    #[test]
+    fn clamp_to_q15_saturates() {
+        let mut dst = [I1F15::ZERO; 4];
+        clamp_to_q15(&[-3.0, -1.0, 1.0, 3.0], -2.0, 2.0, &mut dst);
+        assert_eq!(dst, [I1F15::MIN, I1F15::MIN, I1F15::MAX, I1F15::MAX]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn clamp_to_q15_rejects_reversed_range() {
+        clamp_to_q15(&[0.0], 0.5, -0.5, &mut [I1F15::ZERO]);
+    }
+
+    #[test]
+    fn q15_to_clamped_f32_clips_after_converting() {
+        let src = [I1F15::MIN, I1F15::from_num(-0.25), I1F15::ZERO, I1F15::MAX];
+        let mut dst = [0.0; 4];
+        q15_to_clamped_f32(&src, -0.5, 0.5, &mut dst);
+        assert_eq!(dst, [-0.5, -0.25, 0.0, 0.5]);
+    }
+
+    #[test]
+    fn convert_fixed_narrows_at_the_limits() {
+        let src = [I1F31::MIN, I1F31::MAX, I1F31::from_bits(-1), I1F31::ZERO];
+        let mut q15 = [I1F15::ZERO; 4];
+        convert_fixed(&src, &mut q15);
+        // The discarded bits round toward negative infinity
+        assert_eq!(
+            q15,
+            [I1F15::MIN, I1F15::MAX, I1F15::from_bits(-1), I1F15::ZERO]
+        );
+        let mut q7 = [I1F7::ZERO; 4];
+        convert_fixed(&src, &mut q7);
+        assert_eq!(q7, [I1F7::MIN, I1F7::MAX, I1F7::from_bits(-1), I1F7::ZERO]);
+        let mut q7_from_q15 = [I1F7::ZERO; 4];
+        convert_fixed(&q15, &mut q7_from_q15);
+        assert_eq!(q7_from_q15, q7);
+    }
+
+    #[test]
+    fn convert_fixed_widens_exactly() {
+        let q7 = [I1F7::MIN, I1F7::MAX, I1F7::from_bits(-1)];
+        let mut q15 = [I1F15::ZERO; 3];
+        convert_fixed(&q7, &mut q15);
+        assert_eq!(
+            q15,
+            [
+                I1F15::MIN,
+                I1F15::from_num(I1F7::MAX),
+                I1F15::from_bits(-256)
+            ]
+        );
+        let mut q31 = [I1F31::ZERO; 3];
+        convert_fixed(&q15, &mut q31);
+        assert_eq!(
+            q31,
+            [
+                I1F31::MIN,
+                I1F31::from_num(I1F7::MAX),
+                I1F31::from_bits(-1 << 24)
+            ]
+        );
+        let mut q31_from_q7 = [I1F31::ZERO; 3];
+        convert_fixed(&q7, &mut q31_from_q7);
+        assert_eq!(q31_from_q7, q31);
+
+        // Narrowing the widened values gives back the original values
+        let mut narrowed = [I1F7::ZERO; 3];
+        convert_fixed(&q31, &mut narrowed);
+        assert_eq!(narrowed, q7);
+    }
+
+    #[test]
+    fn convert_fixed_copies_the_same_format() {
+        let q31 = [I1F31::MIN, I1F31::from_bits(12345), I1F31::MAX];
+        let mut copy = [I1F31::ZERO; 3];
+        convert_fixed(&q31, &mut copy);
+        assert_eq!(copy, q31);
+        let q15 = [I1F15::MIN, I1F15::from_bits(123), I1F15::MAX];
+        let mut copy = [I1F15::ZERO; 3];
+        convert_fixed(&q15, &mut copy);
+        assert_eq!(copy, q15);
+        let q7 = [I1F7::MIN, I1F7::from_bits(12), I1F7::MAX];
+        let mut copy = [I1F7::ZERO; 3];
+        convert_fixed(&q7, &mut copy);
+        assert_eq!(copy, q7);
+    }
+
+    #[test]
+    #[should_panic]
+    fn convert_fixed_checks_length() {
+        convert_fixed(&[I1F31::ZERO; 2], &mut [I1F15::ZERO; 3]);
+    }
+
+    #[test]
+    fn error_policy_rejects_values_outside_the_range() {
+        for src in [[0.5, 1.0, 0.0], [0.5, -1.5, 0.0], [0.5, f32::NAN, 0.0]] {
+            let mut q31 = [I1F31::MAX; 3];
+            let result = f32_to_q31_with_policy(&src, &mut q31, SaturationPolicy::Error);
+            assert!(matches!(result, Err(Error::Argument)));
+            assert_eq!(q31, [I1F31::MAX; 3]);
+            let mut q15 = [I1F15::MAX; 3];
+            let result = f32_to_q15_with_policy(&src, &mut q15, SaturationPolicy::Error);
+            assert!(matches!(result, Err(Error::Argument)));
+            assert_eq!(q15, [I1F15::MAX; 3]);
+            let mut q7 = [I1F7::MAX; 3];
+            let result = f32_to_q7_with_policy(&src, &mut q7, SaturationPolicy::Error);
+            assert!(matches!(result, Err(Error::Argument)));
+            assert_eq!(q7, [I1F7::MAX; 3]);
+        }
+
+        let mut q15 = [I1F15::ZERO; 2];
+        f32_to_q15_with_policy(&[-1.0, 0.5], &mut q15, SaturationPolicy::Error).unwrap();
+        assert_eq!(q15, [I1F15::MIN, I1F15::from_num(0.5)]);
+    }
+
+    #[test]
+    fn saturate_policy_clamps() {
+        let src = [1.5, -2.0, 1.0, 0.25];
+        let mut q31 = [I1F31::ZERO; 4];
+        f32_to_q31_with_policy(&src, &mut q31, SaturationPolicy::Saturate).unwrap();
+        assert_eq!(
+            q31,
+            [I1F31::MAX, I1F31::MIN, I1F31::MAX, I1F31::from_num(0.25)]
+        );
+        let mut q15 = [I1F15::ZERO; 4];
+        f32_to_q15_with_policy(&src, &mut q15, SaturationPolicy::Saturate).unwrap();
+        assert_eq!(
+            q15,
+            [I1F15::MAX, I1F15::MIN, I1F15::MAX, I1F15::from_num(0.25)]
+        );
+        let mut q7 = [I1F7::ZERO; 4];
+        f32_to_q7_with_policy(&src, &mut q7, SaturationPolicy::Saturate).unwrap();
+        assert_eq!(q7, [I1F7::MAX, I1F7::MIN, I1F7::MAX, I1F7::from_num(0.25)]);
+    }
+
+    #[test]
+    fn wrap_policy_wraps() {
+        let src = [1.5, -1.5, 1.0, 0.25];
+        let expected = [-0.5, 0.5, -1.0, 0.25];
+        let mut q31 = [I1F31::ZERO; 4];
+        f32_to_q31_with_policy(&src, &mut q31, SaturationPolicy::Wrap).unwrap();
+        assert_eq!(q31, expected.map(I1F31::from_num));
+        let mut q15 = [I1F15::ZERO; 4];
+        f32_to_q15_with_policy(&src, &mut q15, SaturationPolicy::Wrap).unwrap();
+        assert_eq!(q15, expected.map(I1F15::from_num));
+        let mut q7 = [I1F7::ZERO; 4];
+        f32_to_q7_with_policy(&src, &mut q7, SaturationPolicy::Wrap).unwrap();
+        assert_eq!(q7, expected.map(I1F7::from_num));
+    }
+
+    #[test]
+    fn wrap_policy_rounds_like_saturate() {
+        // Values that are not multiples of the resolution of any of the formats
+        let src: [f32; 8] = [0.3, -0.3, 0.7, -0.7, 0.1, -0.1, 0.99, -0.99];
+        let mut wrapped = [I1F31::ZERO; 8];
+        let mut saturated = [I1F31::ZERO; 8];
+        f32_to_q31_with_policy(&src, &mut wrapped, SaturationPolicy::Wrap).unwrap();
+        f32_to_q31_with_policy(&src, &mut saturated, SaturationPolicy::Saturate).unwrap();
+        assert_eq!(wrapped, saturated);
+        let mut wrapped = [I1F15::ZERO; 8];
+        let mut saturated = [I1F15::ZERO; 8];
+        f32_to_q15_with_policy(&src, &mut wrapped, SaturationPolicy::Wrap).unwrap();
+        f32_to_q15_with_policy(&src, &mut saturated, SaturationPolicy::Saturate).unwrap();
+        assert_eq!(wrapped, saturated);
+        // 0.3 * 32768 = 9830.4, which rounds down, and 0.7 * 32768 = 22937.6, which rounds up
+        assert_eq!(wrapped[0].to_bits(), 9830);
+        assert_eq!(wrapped[2].to_bits(), 22938);
+        let mut wrapped = [I1F7::ZERO; 8];
+        let mut saturated = [I1F7::ZERO; 8];
+        f32_to_q7_with_policy(&src, &mut wrapped, SaturationPolicy::Wrap).unwrap();
+        f32_to_q7_with_policy(&src, &mut saturated, SaturationPolicy::Saturate).unwrap();
+        assert_eq!(wrapped, saturated);
+    }
+
+    #[test]
+    fn fixed_point_round_trips() {
+        // Multiples of 1 / 128 convert exactly in all three formats
+        let src: [f32; 9] = [
+            -1.0,
+            -0.5,
+            -0.25,
+            -1.0 / 128.0,
+            0.0,
+            1.0 / 128.0,
+            0.25,
+            0.5,
+            127.0 / 128.0,
+        ];
+        let mut restored = [0.0; 9];
+
+        let mut q31 = [I1F31::ZERO; 9];
+        f32_to_q31(&src, &mut q31);
+        assert_eq!(q31, src.map(I1F31::from_num));
+        q31_to_f32(&q31, &mut restored);
+        assert_eq!(restored, src);
+
+        let mut q15 = [I1F15::ZERO; 9];
+        f32_to_q15(&src, &mut q15);
+        assert_eq!(q15, src.map(I1F15::from_num));
+        q15_to_f32(&q15, &mut restored);
+        assert_eq!(restored, src);
+
+        let mut q7 = [I1F7::ZERO; 9];
+        f32_to_q7(&src, &mut q7);
+        assert_eq!(q7, src.map(I1F7::from_num));
+        q7_to_f32(&q7, &mut restored);
+        assert_eq!(restored, src);
+    }
+
+    #[test]
+    fn q15_round_trip_error_is_at_most_half_a_step() {
+        let src: [f32; 200] = core::array::from_fn(|i| (i as f32 - 100.0) * 0.00987);
+        let mut q15 = [I1F15::ZERO; 200];
+        f32_to_q15(&src, &mut q15);
+        let mut restored = [0.0; 200];
+        q15_to_f32(&q15, &mut restored);
+        for (x, y) in src.iter().zip(restored.iter()) {
+            assert!((x - y).abs() <= 0.5 / 32768.0 + 1e-7, "{} -> {}", x, y);
+        }
+    }
+
+    #[test]
+    fn conversion_saturates_outside_the_range() {
+        let src = [1.0, 2.5, -1.5];
+        let mut q31 = [I1F31::ZERO; 3];
+        f32_to_q31(&src, &mut q31);
+        assert_eq!(q31, [I1F31::MAX, I1F31::MAX, I1F31::MIN]);
+        let mut q15 = [I1F15::ZERO; 3];
+        f32_to_q15(&src, &mut q15);
+        assert_eq!(q15, [I1F15::MAX, I1F15::MAX, I1F15::MIN]);
+        let mut q7 = [I1F7::ZERO; 3];
+        f32_to_q7(&src, &mut q7);
+        assert_eq!(q7, [I1F7::MAX, I1F7::MAX, I1F7::MIN]);
+    }
+
+    #[test]
+    fn copy_and_fill() {
+        let src = [1.5, -2.0, 0.0, f32::INFINITY, 3.25];
+        let mut dst = [0.0; 5];
+        copy_f32(&src, &mut dst);
+        assert_eq!(dst, src);
+        fill_f32(-0.75, &mut dst);
+        assert_eq!(dst, [-0.75; 5]);
+        fill_f32(2.0, &mut dst[1..3]);
+        assert_eq!(dst, [-0.75, 2.0, 2.0, -0.75, -0.75]);
+
+        copy_f32(&[], &mut []);
+        fill_f32(1.0, &mut []);
+    }
+
+    #[test]
+    #[should_panic]
+    fn copy_checks_length() {
+        copy_f32(&[1.0, 2.0], &mut [0.0; 3]);
+    }
+
+    #[test]
+    fn weighted_average() {
+        assert_eq!(
+            weighted_average_f32(&[1.0, 2.0, 3.0], &[1.0, 1.0, 1.0]),
+            2.0
+        );
+        assert_eq!(
+            weighted_average_f32(&[1.0, 2.0, 4.0], &[3.0, 0.0, 1.0]),
+            1.75
+        );
+        // The weights do not need to sum to 1
+        assert_eq!(weighted_average_f32(&[-2.0, 6.0], &[10.0, 30.0]), 4.0);
+        assert_eq!(weighted_average_f32(&[5.0], &[0.25]), 5.0);
+    }
+
+    #[test]
+    fn barycenter() {
+        // Three 2-dimensional points
+        let vectors = [0.0, 0.0, 4.0, 0.0, 0.0, 8.0];
+        let mut out = [0.0; 2];
+        barycenter_f32(&vectors, &[1.0, 1.0, 2.0], 2, &mut out);
+        assert_eq!(out, [1.0, 4.0]);
+        barycenter_f32(&vectors, &[0.0, 3.0, 1.0], 2, &mut out);
+        assert_eq!(out, [3.0, 2.0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn barycenter_checks_length() {
+        barycenter_f32(&[0.0; 5], &[1.0, 1.0, 1.0], 2, &mut [0.0; 2]);
+    }
+
+    #[test]
+    fn fixed_point_pointers_read_the_raw_bits() {
+        let q31 = [I1F31::from_bits(-5), I1F31::MAX, I1F31::MIN];
+        let raw = unsafe { core::slice::from_raw_parts(as_q31_ptr(&q31), 3) };
+        assert_eq!(raw, [-5, i32::MAX, i32::MIN]);
+        let q15 = [I1F15::from_bits(7), I1F15::MIN];
+        let raw = unsafe { core::slice::from_raw_parts(as_q15_ptr(&q15), 2) };
+        assert_eq!(raw, [7, i16::MIN]);
+        let q7 = [I1F7::from_bits(-3), I1F7::MAX];
+        let raw = unsafe { core::slice::from_raw_parts(as_q7_ptr(&q7), 2) };
+        assert_eq!(raw, [-3, i8::MAX]);
+
+        // Writes through the mutable pointers change the values in place
+        let mut q31 = [I1F31::ZERO; 2];
+        unsafe { *as_q31_mut_ptr(&mut q31).add(1) = 1 << 30 };
+        assert_eq!(q31, [I1F31::ZERO, I1F31::from_num(0.5)]);
+        let mut q15 = [I1F15::ZERO; 2];
+        unsafe { *as_q15_mut_ptr(&mut q15).add(1) = -(1 << 14) };
+        assert_eq!(q15, [I1F15::ZERO, I1F15::from_num(-0.5)]);
+        let mut q7 = [I1F7::ZERO; 2];
+        unsafe { *as_q7_mut_ptr(&mut q7) = 1 << 5 };
+        assert_eq!(q7, [I1F7::from_num(0.25), I1F7::ZERO]);
+    }
+
+    #[test]
+    fn complex_fixed_point_pointers_interleave_parts() {
+        let mut q31 = [
+            Complex::new(I1F31::from_bits(1), I1F31::from_bits(2)),
+            Complex::new(I1F31::from_bits(3), I1F31::from_bits(4)),
+        ];
+        let raw = unsafe { core::slice::from_raw_parts_mut(as_complex_q31_mut_ptr(&mut q31), 4) };
+        assert_eq!(raw, [1, 2, 3, 4]);
+        raw[3] = -4;
+        assert_eq!(q31[1].im, I1F31::from_bits(-4));
+
+        let mut q15 = [Complex::new(I1F15::from_bits(5), I1F15::from_bits(-6))];
+        let raw = unsafe { core::slice::from_raw_parts(as_complex_q15_mut_ptr(&mut q15), 2) };
+        assert_eq!(raw, [5, -6]);
+    }
+}
@@ -0,0 +1,365 @@
+//! Support vector machine classifiers
+//!
+//! Each classifier uses a model trained elsewhere (for example, with scikit-learn) to choose
+//! between two classes. A model contains `num_vectors` support vectors with `dimension` elements
+//! each, stored one after another, one dual coefficient for each support vector, an intercept,
+//! and the labels of the two classes.
+//!
+//! The classifiers borrow the model data, so the data usually lives in static arrays.
+
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
+
+use crate::check_length_or_panic;
+
+/// Checks that the lengths of the model data match the number of support vectors and dimensions
+fn check_model(
+    num_vectors: u32,
+    dimension: u32,
+    dual_coefficients: &[f32],
+    support_vectors: &[f32],
+) {
+    check_length_or_panic::<(usize, usize), u32>((dual_coefficients.len(), num_vectors as usize));
+    check_length_or_panic::<(usize, usize), u32>((
+        support_vectors.len(),
+        num_vectors as usize * dimension as usize,
+    ));
+}
+
+/// A support vector machine classifier with a linear kernel
+pub struct LinearSvm<'a> {
+    instance: cmsis_dsp_sys::arm_svm_linear_instance_f32,
+    _model: PhantomData<&'a [f32]>,
+}
+
+impl<'a> LinearSvm<'a> {
+    /// Creates a classifier
+    ///
+    /// # Panics
+    ///
+    /// This function panics if dual_coefficients does not have num_vectors elements, or if
+    /// support_vectors does not have `num_vectors * dimension` elements.
+    pub fn new(
+        num_vectors: u32,
+        dimension: u32,
+        intercept: f32,
+        dual_coefficients: &'a [f32],
+        support_vectors: &'a [f32],
+        classes: &'a [i32; 2],
+    ) -> Self {
+        check_model(num_vectors, dimension, dual_coefficients, support_vectors);
+        let mut instance = MaybeUninit::<cmsis_dsp_sys::arm_svm_linear_instance_f32>::uninit();
+        unsafe {
+            cmsis_dsp_sys::arm_svm_linear_init_f32(
+                instance.as_mut_ptr(),
+                num_vectors,
+                dimension,
+                intercept,
+                dual_coefficients.as_ptr(),
+                support_vectors.as_ptr(),
+                classes.as_ptr(),
+            );
+            LinearSvm {
+                instance: instance.assume_init(),
+                _model: PhantomData,
+            }
+        }
+    }
+
+    /// Classifies an input vector and returns the label of its class
+    ///
+    /// # Panics
+    ///
+    /// This function panics if input does not have the number of dimensions of the model.
+    pub fn predict(&self, input: &[f32]) -> i32 {
+        check_length_or_panic::<(usize, usize), u32>((
+            input.len(),
+            self.instance.vectorDimension as usize,
+        ));
+        let mut result = 0;
+        unsafe {
+            cmsis_dsp_sys::arm_svm_linear_predict_f32(&self.instance, input.as_ptr(), &mut result);
+        }
+        result
+    }
+}
+
+/// A support vector machine classifier with a polynomial kernel,
+/// `(gamma * <x, y> + coef0)^degree`
+pub struct PolynomialSvm<'a> {
+    instance: cmsis_dsp_sys::arm_svm_polynomial_instance_f32,
+    _model: PhantomData<&'a [f32]>,
+}
+
+impl<'a> PolynomialSvm<'a> {
+    /// Creates a classifier
+    ///
+    /// # Panics
+    ///
+    /// This function panics if dual_coefficients does not have num_vectors elements, or if
+    /// support_vectors does not have `num_vectors * dimension` elements.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        num_vectors: u32,
+        dimension: u32,
+        intercept: f32,
+        dual_coefficients: &'a [f32],
+        support_vectors: &'a [f32],
+        classes: &'a [i32; 2],
+        degree: i32,
+        coef0: f32,
+        gamma: f32,
+    ) -> Self {
+        check_model(num_vectors, dimension, dual_coefficients, support_vectors);
+        let mut instance = MaybeUninit::<cmsis_dsp_sys::arm_svm_polynomial_instance_f32>::uninit();
+        unsafe {
+            cmsis_dsp_sys::arm_svm_polynomial_init_f32(
+                instance.as_mut_ptr(),
+                num_vectors,
+                dimension,
+                intercept,
+                dual_coefficients.as_ptr(),
+                support_vectors.as_ptr(),
+                classes.as_ptr(),
+                degree,
+                coef0,
+                gamma,
+            );
+            PolynomialSvm {
+                instance: instance.assume_init(),
+                _model: PhantomData,
+            }
+        }
+    }
+
+    /// Classifies an input vector and returns the label of its class
+    ///
+    /// # Panics
+    ///
+    /// This function panics if input does not have the number of dimensions of the model.
+    pub fn predict(&self, input: &[f32]) -> i32 {
+        check_length_or_panic::<(usize, usize), u32>((
+            input.len(),
+            self.instance.vectorDimension as usize,
+        ));
+        let mut result = 0;
+        unsafe {
+            cmsis_dsp_sys::arm_svm_polynomial_predict_f32(
+                &self.instance,
+                input.as_ptr(),
+                &mut result,
+            );
+        }
+        result
+    }
+}
+
+/// A support vector machine classifier with a radial basis function kernel,
+/// `exp(-gamma * |x - y|^2)`
+pub struct RbfSvm<'a> {
+    instance: cmsis_dsp_sys::arm_svm_rbf_instance_f32,
+    _model: PhantomData<&'a [f32]>,
+}
+
+impl<'a> RbfSvm<'a> {
+    /// Creates a classifier
+    ///
+    /// # Panics
+    ///
+    /// This function panics if dual_coefficients does not have num_vectors elements, or if
+    /// support_vectors does not have `num_vectors * dimension` elements.
+    pub fn new(
+        num_vectors: u32,
+        dimension: u32,
+        intercept: f32,
+        dual_coefficients: &'a [f32],
+        support_vectors: &'a [f32],
+        classes: &'a [i32; 2],
+        gamma: f32,
+    ) -> Self {
+        check_model(num_vectors, dimension, dual_coefficients, support_vectors);
+        let mut instance = MaybeUninit::<cmsis_dsp_sys::arm_svm_rbf_instance_f32>::uninit();
+        unsafe {
+            cmsis_dsp_sys::arm_svm_rbf_init_f32(
+                instance.as_mut_ptr(),
+                num_vectors,
+                dimension,
+                intercept,
+                dual_coefficients.as_ptr(),
+                support_vectors.as_ptr(),
+                classes.as_ptr(),
+                gamma,
+            );
+            RbfSvm {
+                instance: instance.assume_init(),
+                _model: PhantomData,
+            }
+        }
+    }
+
+    /// Classifies an input vector and returns the label of its class
+    ///
+    /// # Panics
+    ///
+    /// This function panics if input does not have the number of dimensions of the model.
+    pub fn predict(&self, input: &[f32]) -> i32 {
+        check_length_or_panic::<(usize, usize), u32>((
+            input.len(),
+            self.instance.vectorDimension as usize,
+        ));
+        let mut result = 0;
+        unsafe {
+            cmsis_dsp_sys::arm_svm_rbf_predict_f32(&self.instance, input.as_ptr(), &mut result);
+        }
+        result
+    }
+}
+
+/// A support vector machine classifier with a sigmoid kernel, `tanh(gamma * <x, y> + coef0)`
+pub struct SigmoidSvm<'a> {
+    instance: cmsis_dsp_sys::arm_svm_sigmoid_instance_f32,
+    _model: PhantomData<&'a [f32]>,
+}
+
+impl<'a> SigmoidSvm<'a> {
+    /// Creates a classifier
+    ///
+    /// # Panics
+    ///
+    /// This function panics if dual_coefficients does not have num_vectors elements, or if
+    /// support_vectors does not have `num_vectors * dimension` elements.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        num_vectors: u32,
+        dimension: u32,
+        intercept: f32,
+        dual_coefficients: &'a [f32],
+        support_vectors: &'a [f32],
+        classes: &'a [i32; 2],
+        coef0: f32,
+        gamma: f32,
+    ) -> Self {
+        check_model(num_vectors, dimension, dual_coefficients, support_vectors);
+        let mut instance = MaybeUninit::<cmsis_dsp_sys::arm_svm_sigmoid_instance_f32>::uninit();
+        unsafe {
+            cmsis_dsp_sys::arm_svm_sigmoid_init_f32(
+                instance.as_mut_ptr(),
+                num_vectors,
+                dimension,
+                intercept,
+                dual_coefficients.as_ptr(),
+                support_vectors.as_ptr(),
+                classes.as_ptr(),
+                coef0,
+                gamma,
+            );
+            SigmoidSvm {
+                instance: instance.assume_init(),
+                _model: PhantomData,
+            }
+        }
+    }
+
+    /// Classifies an input vector and returns the label of its class
+    ///
+    /// # Panics
+    ///
+    /// This function panics if input does not have the number of dimensions of the model.
+    pub fn predict(&self, input: &[f32]) -> i32 {
+        check_length_or_panic::<(usize, usize), u32>((
+            input.len(),
+            self.instance.vectorDimension as usize,
+        ));
+        let mut result = 0;
+        unsafe {
+            cmsis_dsp_sys::arm_svm_sigmoid_predict_f32(&self.instance, input.as_ptr(), &mut result);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLASSES: [i32; 2] = [3, 7];
+    /// Support vectors on either side of the x1 axis
+    const SUPPORT_VECTORS: [f32; 4] = [1.0, 0.0, -1.0, 0.0];
+    const DUAL_COEFFICIENTS: [f32; 2] = [1.0, -1.0];
+
+    #[test]
+    fn linear_svm() {
+        let svm = LinearSvm::new(2, 2, 0.0, &DUAL_COEFFICIENTS, &SUPPORT_VECTORS, &CLASSES);
+        assert_eq!(svm.predict(&[0.5, 3.0]), 7);
+        assert_eq!(svm.predict(&[-0.5, 3.0]), 3);
+        // A decision value of zero gives the first class
+        assert_eq!(svm.predict(&[0.0, 3.0]), 3);
+        // The intercept moves the boundary to x0 = -0.25
+        let svm = LinearSvm::new(2, 2, 0.5, &DUAL_COEFFICIENTS, &SUPPORT_VECTORS, &CLASSES);
+        assert_eq!(svm.predict(&[-0.2, 0.0]), 7);
+    }
+
+    #[test]
+    fn polynomial_svm() {
+        // x0^2 - x1^2
+        let support_vectors = [1.0, 0.0, 0.0, 1.0];
+        let svm = PolynomialSvm::new(
+            2,
+            2,
+            0.0,
+            &DUAL_COEFFICIENTS,
+            &support_vectors,
+            &CLASSES,
+            2,
+            0.0,
+            1.0,
+        );
+        assert_eq!(svm.predict(&[-2.0, 1.0]), 7);
+        assert_eq!(svm.predict(&[1.0, -2.0]), 3);
+    }
+
+    #[test]
+    fn rbf_svm() {
+        let support_vectors = [0.0, 0.0, 3.0, 3.0];
+        let svm = RbfSvm::new(
+            2,
+            2,
+            0.0,
+            &DUAL_COEFFICIENTS,
+            &support_vectors,
+            &CLASSES,
+            1.0,
+        );
+        assert_eq!(svm.predict(&[0.5, 1.0]), 7);
+        assert_eq!(svm.predict(&[2.5, 2.0]), 3);
+    }
+
+    #[test]
+    fn sigmoid_svm() {
+        let svm = SigmoidSvm::new(
+            2,
+            2,
+            0.0,
+            &DUAL_COEFFICIENTS,
+            &SUPPORT_VECTORS,
+            &CLASSES,
+            0.0,
+            1.0,
+        );
+        assert_eq!(svm.predict(&[0.5, 3.0]), 7);
+        assert_eq!(svm.predict(&[-0.5, 3.0]), 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_checks_model_length() {
+        LinearSvm::new(2, 3, 0.0, &DUAL_COEFFICIENTS, &SUPPORT_VECTORS, &CLASSES);
+    }
+
+    #[test]
+    #[should_panic]
+    fn predict_checks_dimension() {
+        let svm = LinearSvm::new(2, 2, 0.0, &DUAL_COEFFICIENTS, &SUPPORT_VECTORS, &CLASSES);
+        svm.predict(&[0.0; 3]);
+    }
+}
@@ -0,0 +1,1437 @@
+//! Rust versions of CMSIS-DSP functions for unit tests on the host
+//!
+//! The CMSIS-DSP library is only linked for Cortex-M targets, so unit tests that run on a
+//! development machine cannot call it. This module defines the CMSIS-DSP functions that the
+//! tests use, with the same names and signatures, so that the tests can check the Rust wrappers
+//! around them. Each function follows the reference C implementation of CMSIS-DSP 5.7.0 without
+//! the loop unrolling and other optimizations, except that the FFTs are calculated directly from
+//! the definition of the DFT.
+//!
+//! This module is only compiled for tests on targets other than ARM.
+
+#![allow(non_snake_case)]
+
+use cmsis_dsp_sys::{
+    arm_biquad_cascade_df2T_instance_f32, arm_biquad_casd_df1_inst_q15,
+    arm_biquad_casd_df1_inst_q31, arm_cfft_instance_f32, arm_fir_decimate_instance_f32,
+    arm_fir_instance_f32, arm_fir_interpolate_instance_f32, arm_fir_lattice_instance_f32,
+    arm_fir_sparse_instance_f32, arm_gaussian_naive_bayes_instance_f32,
+    arm_iir_lattice_instance_f32, arm_matrix_instance_f32, arm_matrix_instance_f64,
+    arm_rfft_fast_instance_f32, arm_sort_alg, arm_sort_dir, arm_sort_instance_f32, arm_status,
+    arm_svm_linear_instance_f32, arm_svm_polynomial_instance_f32, arm_svm_rbf_instance_f32,
+    arm_svm_sigmoid_instance_f32, float32_t, float64_t, q15_t, q31_t, q63_t, q7_t,
+};
+
+#[no_mangle]
+unsafe extern "C" fn arm_biquad_cascade_df2T_init_f32(
+    S: *mut arm_biquad_cascade_df2T_instance_f32,
+    numStages: u8,
+    pCoeffs: *const float32_t,
+    pState: *mut float32_t,
+) {
+    core::ptr::write_bytes(pState, 0, 2 * usize::from(numStages));
+    S.write(arm_biquad_cascade_df2T_instance_f32 {
+        numStages,
+        pState,
+        pCoeffs,
+    });
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_biquad_cascade_df2T_f32(
+    S: *const arm_biquad_cascade_df2T_instance_f32,
+    pSrc: *const float32_t,
+    pDst: *mut float32_t,
+    blockSize: u32,
+) {
+    let S = &*S;
+    let stages = usize::from(S.numStages);
+    let coeffs = core::slice::from_raw_parts(S.pCoeffs, 5 * stages);
+    let state = core::slice::from_raw_parts_mut(S.pState, 2 * stages);
+    let block_size = blockSize as usize;
+    // The first stage reads from the source, and the other stages work in place on the
+    // destination
+    for i in 0..block_size {
+        *pDst.add(i) = *pSrc.add(i);
+    }
+    let dst = core::slice::from_raw_parts_mut(pDst, block_size);
+    for (stage, d) in coeffs.chunks_exact(5).zip(state.chunks_exact_mut(2)) {
+        let (b0, b1, b2, a1, a2) = (stage[0], stage[1], stage[2], stage[3], stage[4]);
+        for sample in dst.iter_mut() {
+            let x = *sample;
+            let y = b0 * x + d[0];
+            d[0] = b1 * x + a1 * y + d[1];
+            d[1] = b2 * x + a2 * y;
+            *sample = y;
+        }
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_biquad_cascade_df1_init_q31(
+    S: *mut arm_biquad_casd_df1_inst_q31,
+    numStages: u8,
+    pCoeffs: *const q31_t,
+    pState: *mut q31_t,
+    postShift: i8,
+) {
+    core::ptr::write_bytes(pState, 0, 4 * usize::from(numStages));
+    S.write(arm_biquad_casd_df1_inst_q31 {
+        numStages: u32::from(numStages),
+        pState,
+        pCoeffs,
+        postShift: postShift as u8,
+    });
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_biquad_cascade_df1_q31(
+    S: *const arm_biquad_casd_df1_inst_q31,
+    pSrc: *const q31_t,
+    pDst: *mut q31_t,
+    blockSize: u32,
+) {
+    let S = &*S;
+    let stages = S.numStages as usize;
+    let coeffs = core::slice::from_raw_parts(S.pCoeffs, 5 * stages);
+    let state = core::slice::from_raw_parts_mut(S.pState, 4 * stages);
+    let shift = 31 - u32::from(S.postShift);
+    let block_size = blockSize as usize;
+    for i in 0..block_size {
+        *pDst.add(i) = *pSrc.add(i);
+    }
+    let dst = core::slice::from_raw_parts_mut(pDst, block_size);
+    for (b, d) in coeffs.chunks_exact(5).zip(state.chunks_exact_mut(4)) {
+        for sample in dst.iter_mut() {
+            let x = *sample;
+            let acc = q63_t::from(b[0]) * q63_t::from(x)
+                + q63_t::from(b[1]) * q63_t::from(d[0])
+                + q63_t::from(b[2]) * q63_t::from(d[1])
+                + q63_t::from(b[3]) * q63_t::from(d[2])
+                + q63_t::from(b[4]) * q63_t::from(d[3]);
+            let y = (acc >> shift) as q31_t;
+            d[1] = d[0];
+            d[0] = x;
+            d[3] = d[2];
+            d[2] = y;
+            *sample = y;
+        }
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_biquad_cascade_df1_init_q15(
+    S: *mut arm_biquad_casd_df1_inst_q15,
+    numStages: u8,
+    pCoeffs: *const q15_t,
+    pState: *mut q15_t,
+    postShift: i8,
+) {
+    core::ptr::write_bytes(pState, 0, 4 * usize::from(numStages));
+    S.write(arm_biquad_casd_df1_inst_q15 {
+        numStages: numStages as i8,
+        pState,
+        pCoeffs,
+        postShift,
+    });
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_biquad_cascade_df1_q15(
+    S: *const arm_biquad_casd_df1_inst_q15,
+    pSrc: *const q15_t,
+    pDst: *mut q15_t,
+    blockSize: u32,
+) {
+    let S = &*S;
+    let stages = S.numStages as u8 as usize;
+    let coeffs = core::slice::from_raw_parts(S.pCoeffs, 6 * stages);
+    let state = core::slice::from_raw_parts_mut(S.pState, 4 * stages);
+    let shift = 15 - i32::from(S.postShift);
+    let block_size = blockSize as usize;
+    for i in 0..block_size {
+        *pDst.add(i) = *pSrc.add(i);
+    }
+    let dst = core::slice::from_raw_parts_mut(pDst, block_size);
+    // Each stage has a zero after its first coefficient
+    for (b, d) in coeffs.chunks_exact(6).zip(state.chunks_exact_mut(4)) {
+        for sample in dst.iter_mut() {
+            let x = *sample;
+            let acc = q63_t::from(b[0]) * q63_t::from(x)
+                + q63_t::from(b[2]) * q63_t::from(d[0])
+                + q63_t::from(b[3]) * q63_t::from(d[1])
+                + q63_t::from(b[4]) * q63_t::from(d[2])
+                + q63_t::from(b[5]) * q63_t::from(d[3]);
+            let y = (acc >> shift).clamp(q15_t::MIN.into(), q15_t::MAX.into()) as q15_t;
+            d[1] = d[0];
+            d[0] = x;
+            d[3] = d[2];
+            d[2] = y;
+            *sample = y;
+        }
+    }
+}
+
+#[no_mangle]
+extern "C" fn arm_sin_f32(x: float32_t) -> float32_t {
+    x.sin()
+}
+
+#[no_mangle]
+extern "C" fn arm_cos_f32(x: float32_t) -> float32_t {
+    x.cos()
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_vlog_f32(pSrc: *const float32_t, pDst: *mut float32_t, blockSize: u32) {
+    for i in 0..blockSize as usize {
+        *pDst.add(i) = (*pSrc.add(i)).ln();
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_vexp_f32(pSrc: *const float32_t, pDst: *mut float32_t, blockSize: u32) {
+    for i in 0..blockSize as usize {
+        *pDst.add(i) = (*pSrc.add(i)).exp();
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_power_f32(
+    pSrc: *const float32_t,
+    blockSize: u32,
+    pResult: *mut float32_t,
+) {
+    let src = core::slice::from_raw_parts(pSrc, blockSize as usize);
+    *pResult = src.iter().map(|x| x * x).sum();
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_fir_init_f32(
+    S: *mut arm_fir_instance_f32,
+    numTaps: u16,
+    pCoeffs: *const float32_t,
+    pState: *mut float32_t,
+    blockSize: u32,
+) {
+    core::ptr::write_bytes(pState, 0, usize::from(numTaps) + blockSize as usize - 1);
+    S.write(arm_fir_instance_f32 {
+        numTaps,
+        pState,
+        pCoeffs,
+    });
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_fir_f32(
+    S: *const arm_fir_instance_f32,
+    pSrc: *const float32_t,
+    pDst: *mut float32_t,
+    blockSize: u32,
+) {
+    let S = &*S;
+    let num_taps = usize::from(S.numTaps);
+    let block_size = blockSize as usize;
+    let coeffs = core::slice::from_raw_parts(S.pCoeffs, num_taps);
+    let state = core::slice::from_raw_parts_mut(S.pState, num_taps + block_size - 1);
+    // The state holds the last numTaps - 1 input samples, followed by the new block
+    for i in 0..block_size {
+        state[num_taps - 1 + i] = *pSrc.add(i);
+    }
+    for i in 0..block_size {
+        *pDst.add(i) = state[i..i + num_taps]
+            .iter()
+            .zip(coeffs)
+            .map(|(x, b)| x * b)
+            .sum();
+    }
+    state.copy_within(block_size..block_size + num_taps - 1, 0);
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_fir_interpolate_init_f32(
+    S: *mut arm_fir_interpolate_instance_f32,
+    L: u8,
+    numTaps: u16,
+    pCoeffs: *const float32_t,
+    pState: *mut float32_t,
+    blockSize: u32,
+) -> arm_status::Type {
+    if L == 0 || !numTaps.is_multiple_of(u16::from(L)) {
+        return arm_status::ARM_MATH_LENGTH_ERROR;
+    }
+    let phaseLength = numTaps / u16::from(L);
+    core::ptr::write_bytes(pState, 0, blockSize as usize + usize::from(phaseLength) - 1);
+    S.write(arm_fir_interpolate_instance_f32 {
+        L,
+        phaseLength,
+        pCoeffs,
+        pState,
+    });
+    arm_status::ARM_MATH_SUCCESS
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_fir_interpolate_f32(
+    S: *const arm_fir_interpolate_instance_f32,
+    pSrc: *const float32_t,
+    pDst: *mut float32_t,
+    blockSize: u32,
+) {
+    let S = &*S;
+    let l = usize::from(S.L);
+    let phase_length = usize::from(S.phaseLength);
+    let block_size = blockSize as usize;
+    let coeffs = core::slice::from_raw_parts(S.pCoeffs, l * phase_length);
+    let state = core::slice::from_raw_parts_mut(S.pState, phase_length + block_size - 1);
+    // The state holds the last phaseLength - 1 input samples, followed by the new block. Output
+    // j of each input uses every L-th coefficient, starting at L - j.
+    for i in 0..block_size {
+        state[phase_length - 1 + i] = *pSrc.add(i);
+    }
+    for i in 0..block_size {
+        for j in 1..=l {
+            *pDst.add(i * l + j - 1) = state[i..i + phase_length]
+                .iter()
+                .zip(coeffs[l - j..].iter().step_by(l))
+                .map(|(x, b)| x * b)
+                .sum();
+        }
+    }
+    state.copy_within(block_size..block_size + phase_length - 1, 0);
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_fir_decimate_init_f32(
+    S: *mut arm_fir_decimate_instance_f32,
+    numTaps: u16,
+    M: u8,
+    pCoeffs: *const float32_t,
+    pState: *mut float32_t,
+    blockSize: u32,
+) -> arm_status::Type {
+    if M == 0 || !blockSize.is_multiple_of(u32::from(M)) {
+        return arm_status::ARM_MATH_LENGTH_ERROR;
+    }
+    core::ptr::write_bytes(pState, 0, usize::from(numTaps) + blockSize as usize - 1);
+    S.write(arm_fir_decimate_instance_f32 {
+        M,
+        numTaps,
+        pCoeffs,
+        pState,
+    });
+    arm_status::ARM_MATH_SUCCESS
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_fir_decimate_f32(
+    S: *const arm_fir_decimate_instance_f32,
+    pSrc: *const float32_t,
+    pDst: *mut float32_t,
+    blockSize: u32,
+) {
+    let S = &*S;
+    let m = usize::from(S.M);
+    let num_taps = usize::from(S.numTaps);
+    let block_size = blockSize as usize;
+    let coeffs = core::slice::from_raw_parts(S.pCoeffs, num_taps);
+    let state = core::slice::from_raw_parts_mut(S.pState, num_taps + block_size - 1);
+    for i in 0..block_size {
+        state[num_taps - 1 + i] = *pSrc.add(i);
+    }
+    // Only every M-th output of the FIR filter is calculated, starting with the first
+    for i in 0..block_size / m {
+        *pDst.add(i) = state[i * m..i * m + num_taps]
+            .iter()
+            .zip(coeffs)
+            .map(|(x, b)| x * b)
+            .sum();
+    }
+    state.copy_within(block_size..block_size + num_taps - 1, 0);
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_fir_lattice_init_f32(
+    S: *mut arm_fir_lattice_instance_f32,
+    numStages: u16,
+    pCoeffs: *const float32_t,
+    pState: *mut float32_t,
+) {
+    core::ptr::write_bytes(pState, 0, usize::from(numStages));
+    S.write(arm_fir_lattice_instance_f32 {
+        numStages,
+        pState,
+        pCoeffs,
+    });
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_fir_lattice_f32(
+    S: *const arm_fir_lattice_instance_f32,
+    pSrc: *const float32_t,
+    pDst: *mut float32_t,
+    blockSize: u32,
+) {
+    let S = &*S;
+    let num_stages = usize::from(S.numStages);
+    let coeffs = core::slice::from_raw_parts(S.pCoeffs, num_stages);
+    // The state holds the previous backward prediction error g(n-1) of each stage
+    let state = core::slice::from_raw_parts_mut(S.pState, num_stages);
+    for i in 0..blockSize as usize {
+        let mut f = *pSrc.add(i);
+        let mut g = f;
+        for (k, previous) in coeffs.iter().zip(state.iter_mut()) {
+            let g_previous = core::mem::replace(previous, g);
+            let f_next = f + k * g_previous;
+            g = f * k + g_previous;
+            f = f_next;
+        }
+        *pDst.add(i) = f;
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_iir_lattice_init_f32(
+    S: *mut arm_iir_lattice_instance_f32,
+    numStages: u16,
+    pkCoeffs: *mut float32_t,
+    pvCoeffs: *mut float32_t,
+    pState: *mut float32_t,
+    blockSize: u32,
+) {
+    core::ptr::write_bytes(pState, 0, usize::from(numStages) + blockSize as usize);
+    S.write(arm_iir_lattice_instance_f32 {
+        numStages,
+        pState,
+        pkCoeffs,
+        pvCoeffs,
+    });
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_iir_lattice_f32(
+    S: *const arm_iir_lattice_instance_f32,
+    pSrc: *const float32_t,
+    pDst: *mut float32_t,
+    blockSize: u32,
+) {
+    let S = &*S;
+    let num_stages = usize::from(S.numStages);
+    let block_size = blockSize as usize;
+    // The coefficients are in time-reversed order
+    let k = core::slice::from_raw_parts(S.pkCoeffs, num_stages);
+    let v = core::slice::from_raw_parts(S.pvCoeffs, num_stages + 1);
+    let state = core::slice::from_raw_parts_mut(S.pState, num_stages + block_size);
+    for i in 0..block_size {
+        // Each sample reads the backward prediction errors of the previous sample at
+        // state[i..i + num_stages], and writes its own errors one position earlier
+        let mut f = *pSrc.add(i);
+        let mut acc = 0.0;
+        for j in 0..num_stages {
+            let g = state[i + j];
+            f -= k[j] * g;
+            let g_next = f * k[j] + g;
+            acc += g_next * v[j];
+            state[i + j] = g_next;
+        }
+        acc += f * v[num_stages];
+        state[i + num_stages] = f;
+        *pDst.add(i) = acc;
+    }
+    state.copy_within(block_size..block_size + num_stages, 0);
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_fir_sparse_init_f32(
+    S: *mut arm_fir_sparse_instance_f32,
+    numTaps: u16,
+    pCoeffs: *const float32_t,
+    pState: *mut float32_t,
+    pTapDelay: *mut i32,
+    maxDelay: u16,
+    blockSize: u32,
+) {
+    core::ptr::write_bytes(pState, 0, usize::from(maxDelay) + blockSize as usize);
+    S.write(arm_fir_sparse_instance_f32 {
+        numTaps,
+        stateIndex: 0,
+        pState,
+        pCoeffs,
+        maxDelay,
+        pTapDelay,
+    });
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_fir_sparse_f32(
+    S: *mut arm_fir_sparse_instance_f32,
+    pSrc: *const float32_t,
+    pDst: *mut float32_t,
+    pScratchIn: *mut float32_t,
+    blockSize: u32,
+) {
+    let S = &mut *S;
+    let num_taps = usize::from(S.numTaps);
+    let block_size = blockSize as usize;
+    // The state is a circular buffer, and stateIndex is the position of the next input sample
+    let delay_size = usize::from(S.maxDelay) + block_size;
+    let state = core::slice::from_raw_parts_mut(S.pState, delay_size);
+    let coeffs = core::slice::from_raw_parts(S.pCoeffs, num_taps);
+    let tap_delay = core::slice::from_raw_parts(S.pTapDelay, num_taps);
+    let scratch = core::slice::from_raw_parts_mut(pScratchIn, block_size);
+    let mut index = usize::from(S.stateIndex);
+    for i in 0..block_size {
+        state[index] = *pSrc.add(i);
+        index = (index + 1) % delay_size;
+    }
+    S.stateIndex = index as u16;
+    for i in 0..block_size {
+        *pDst.add(i) = 0.0;
+    }
+    for (coeff, &delay) in coeffs.iter().zip(tap_delay) {
+        // Position of the first sample of the block, delayed by the tap delay
+        let start = (index + 2 * delay_size - block_size - delay as usize) % delay_size;
+        for (n, value) in scratch.iter_mut().enumerate() {
+            *value = state[(start + n) % delay_size];
+        }
+        for (n, value) in scratch.iter().enumerate() {
+            *pDst.add(n) += value * coeff;
+        }
+    }
+}
+
+/// Calculates the output directly with zero padding, which gives the same result as the
+/// reference implementation for either order of the lengths
+#[no_mangle]
+unsafe extern "C" fn arm_correlate_f32(
+    pSrcA: *const float32_t,
+    srcALen: u32,
+    pSrcB: *const float32_t,
+    srcBLen: u32,
+    pDst: *mut float32_t,
+) {
+    let a = core::slice::from_raw_parts(pSrcA, srcALen as usize);
+    let b = core::slice::from_raw_parts(pSrcB, srcBLen as usize);
+    let max_length = a.len().max(b.len()) as isize;
+    for k in 0..2 * max_length - 1 {
+        let lag = k - (max_length - 1);
+        let mut sum = 0.0;
+        for (n, b) in b.iter().enumerate() {
+            let index = n as isize + lag;
+            if index >= 0 && index < a.len() as isize {
+                sum += a[index as usize] * b;
+            }
+        }
+        *pDst.add(k as usize) = sum;
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_rms_f32(pSrc: *const float32_t, blockSize: u32, pResult: *mut float32_t) {
+    let src = core::slice::from_raw_parts(pSrc, blockSize as usize);
+    let sum: f32 = src.iter().map(|x| x * x).sum();
+    *pResult = (sum / blockSize as f32).sqrt();
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_mean_f32(pSrc: *const float32_t, blockSize: u32, pResult: *mut float32_t) {
+    let src = core::slice::from_raw_parts(pSrc, blockSize as usize);
+    *pResult = src.iter().sum::<f32>() / blockSize as f32;
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_var_f32(pSrc: *const float32_t, blockSize: u32, pResult: *mut float32_t) {
+    if blockSize <= 1 {
+        *pResult = 0.0;
+        return;
+    }
+    let src = core::slice::from_raw_parts(pSrc, blockSize as usize);
+    let mean = src.iter().sum::<f32>() / blockSize as f32;
+    let sum: f32 = src.iter().map(|x| (x - mean) * (x - mean)).sum();
+    *pResult = sum / (blockSize - 1) as f32;
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_std_f32(pSrc: *const float32_t, blockSize: u32, pResult: *mut float32_t) {
+    arm_var_f32(pSrc, blockSize, pResult);
+    *pResult = (*pResult).sqrt();
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_cfft_init_f32(
+    S: *mut arm_cfft_instance_f32,
+    fftLen: u16,
+) -> arm_status::Type {
+    if !(16..=4096).contains(&fftLen) || !fftLen.is_power_of_two() {
+        return arm_status::ARM_MATH_ARGUMENT_ERROR;
+    }
+    S.write(arm_cfft_instance_f32 {
+        fftLen,
+        pTwiddle: core::ptr::null(),
+        pBitRevTable: core::ptr::null(),
+        bitRevLength: 0,
+    });
+    arm_status::ARM_MATH_SUCCESS
+}
+
+/// A constant FFT instance, which can be shared between threads because it is never modified
+#[repr(transparent)]
+struct ConstCfftInstance(arm_cfft_instance_f32);
+
+unsafe impl Sync for ConstCfftInstance {}
+
+/// Defines the constant instance arm_cfft_sR_f32_lenN for an FFT of a length
+macro_rules! const_cfft {
+    ($name:ident, $length:expr) => {
+        #[no_mangle]
+        static $name: ConstCfftInstance = ConstCfftInstance(arm_cfft_instance_f32 {
+            fftLen: $length,
+            pTwiddle: core::ptr::null(),
+            pBitRevTable: core::ptr::null(),
+            bitRevLength: 0,
+        });
+    };
+}
+
+const_cfft!(arm_cfft_sR_f32_len16, 16);
+const_cfft!(arm_cfft_sR_f32_len32, 32);
+const_cfft!(arm_cfft_sR_f32_len64, 64);
+const_cfft!(arm_cfft_sR_f32_len128, 128);
+const_cfft!(arm_cfft_sR_f32_len256, 256);
+const_cfft!(arm_cfft_sR_f32_len512, 512);
+const_cfft!(arm_cfft_sR_f32_len1024, 1024);
+const_cfft!(arm_cfft_sR_f32_len2048, 2048);
+const_cfft!(arm_cfft_sR_f32_len4096, 4096);
+
+#[no_mangle]
+unsafe extern "C" fn arm_cfft_f32(
+    S: *const arm_cfft_instance_f32,
+    p1: *mut float32_t,
+    ifftFlag: u8,
+    bitReverseFlag: u8,
+) {
+    let n = usize::from((*S).fftLen);
+    let data = core::slice::from_raw_parts_mut(p1, 2 * n);
+    let mut result = [(0.0f64, 0.0f64); 4096];
+    let sign = if ifftFlag == 0 { -1.0 } else { 1.0 };
+    for (k, bin) in result[..n].iter_mut().enumerate() {
+        for i in 0..n {
+            let angle = sign * 2.0 * core::f64::consts::PI * ((k * i) % n) as f64 / n as f64;
+            let (re, im) = (f64::from(data[2 * i]), f64::from(data[2 * i + 1]));
+            bin.0 += re * angle.cos() - im * angle.sin();
+            bin.1 += re * angle.sin() + im * angle.cos();
+        }
+    }
+    let scale = if ifftFlag == 0 { 1.0 } else { 1.0 / n as f64 };
+    let bits = n.trailing_zeros();
+    for (k, bin) in result[..n].iter().enumerate() {
+        // Without bit reversal, the output stays in bit-reversed order
+        let index = if bitReverseFlag == 0 {
+            k.reverse_bits() >> (usize::BITS - bits)
+        } else {
+            k
+        };
+        data[2 * index] = (bin.0 * scale) as f32;
+        data[2 * index + 1] = (bin.1 * scale) as f32;
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_rfft_fast_init_f32(
+    S: *mut arm_rfft_fast_instance_f32,
+    fftLen: u16,
+) -> arm_status::Type {
+    if !(32..=4096).contains(&fftLen) || !fftLen.is_power_of_two() {
+        return arm_status::ARM_MATH_ARGUMENT_ERROR;
+    }
+    S.write(arm_rfft_fast_instance_f32 {
+        Sint: arm_cfft_instance_f32 {
+            fftLen: fftLen / 2,
+            pTwiddle: core::ptr::null(),
+            pBitRevTable: core::ptr::null(),
+            bitRevLength: 0,
+        },
+        fftLenRFFT: fftLen,
+        pTwiddleRFFT: core::ptr::null(),
+    });
+    arm_status::ARM_MATH_SUCCESS
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_rfft_fast_f32(
+    S: *const arm_rfft_fast_instance_f32,
+    p: *mut float32_t,
+    pOut: *mut float32_t,
+    ifftFlag: u8,
+) {
+    let n = usize::from((*S).fftLenRFFT);
+    let src = core::slice::from_raw_parts(p, n);
+    let dst = core::slice::from_raw_parts_mut(pOut, n);
+    let angle = |k: usize, i: usize| 2.0 * core::f64::consts::PI * ((k * i) % n) as f64 / n as f64;
+    if ifftFlag == 0 {
+        // Packed output: the real DC and Nyquist bins, then bins 1 to n / 2 - 1
+        for k in 0..n / 2 {
+            let (mut re, mut im) = (0.0f64, 0.0f64);
+            for (i, x) in src.iter().enumerate() {
+                re += f64::from(*x) * angle(k, i).cos();
+                im -= f64::from(*x) * angle(k, i).sin();
+            }
+            if k == 0 {
+                dst[0] = re as f32;
+            } else {
+                dst[2 * k] = re as f32;
+                dst[2 * k + 1] = im as f32;
+            }
+        }
+        let nyquist: f64 = src
+            .iter()
+            .enumerate()
+            .map(|(i, x)| {
+                if i % 2 == 0 {
+                    f64::from(*x)
+                } else {
+                    -f64::from(*x)
+                }
+            })
+            .sum();
+        dst[1] = nyquist as f32;
+    } else {
+        // The inverse is scaled by 1 / n, and bins above n / 2 are the conjugates of the
+        // bins below it
+        for (i, out) in dst.iter_mut().enumerate() {
+            let mut sum = f64::from(src[0]);
+            sum += f64::from(src[1]) * if i % 2 == 0 { 1.0 } else { -1.0 };
+            for k in 1..n / 2 {
+                let (re, im) = (f64::from(src[2 * k]), f64::from(src[2 * k + 1]));
+                sum += 2.0 * (re * angle(k, i).cos() - im * angle(k, i).sin());
+            }
+            *out = (sum / n as f64) as f32;
+        }
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_dot_prod_f32(
+    pSrcA: *const float32_t,
+    pSrcB: *const float32_t,
+    blockSize: u32,
+    result: *mut float32_t,
+) {
+    let a = core::slice::from_raw_parts(pSrcA, blockSize as usize);
+    let b = core::slice::from_raw_parts(pSrcB, blockSize as usize);
+    *result = a.iter().zip(b).map(|(a, b)| a * b).sum();
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_mat_init_f32(
+    S: *mut arm_matrix_instance_f32,
+    nRows: u16,
+    nColumns: u16,
+    pData: *mut float32_t,
+) {
+    S.write(arm_matrix_instance_f32 {
+        numRows: nRows,
+        numCols: nColumns,
+        pData,
+    });
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_mat_mult_f32(
+    pSrcA: *const arm_matrix_instance_f32,
+    pSrcB: *const arm_matrix_instance_f32,
+    pDst: *mut arm_matrix_instance_f32,
+) -> arm_status::Type {
+    let (a, b, dst) = (&*pSrcA, &*pSrcB, &*pDst);
+    if a.numCols != b.numRows || dst.numRows != a.numRows || dst.numCols != b.numCols {
+        return arm_status::ARM_MATH_SIZE_MISMATCH;
+    }
+    let (rows, inner, cols) = (
+        usize::from(a.numRows),
+        usize::from(a.numCols),
+        usize::from(b.numCols),
+    );
+    for row in 0..rows {
+        for col in 0..cols {
+            *dst.pData.add(row * cols + col) = (0..inner)
+                .map(|k| *a.pData.add(row * inner + k) * *b.pData.add(k * cols + col))
+                .sum();
+        }
+    }
+    arm_status::ARM_MATH_SUCCESS
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_mat_scale_f32(
+    pSrc: *const arm_matrix_instance_f32,
+    scale: float32_t,
+    pDst: *mut arm_matrix_instance_f32,
+) -> arm_status::Type {
+    let (src, dst) = (&*pSrc, &*pDst);
+    if src.numRows != dst.numRows || src.numCols != dst.numCols {
+        return arm_status::ARM_MATH_SIZE_MISMATCH;
+    }
+    for i in 0..usize::from(src.numRows) * usize::from(src.numCols) {
+        *dst.pData.add(i) = *src.pData.add(i) * scale;
+    }
+    arm_status::ARM_MATH_SUCCESS
+}
+
+/// Defines arm_mat_add_f32 or arm_mat_sub_f32, which combine corresponding elements
+macro_rules! mat_elementwise {
+    ($name:ident, $op:tt) => {
+        #[no_mangle]
+        unsafe extern "C" fn $name(
+            pSrcA: *const arm_matrix_instance_f32,
+            pSrcB: *const arm_matrix_instance_f32,
+            pDst: *mut arm_matrix_instance_f32,
+        ) -> arm_status::Type {
+            let (a, b, dst) = (&*pSrcA, &*pSrcB, &*pDst);
+            if a.numRows != b.numRows
+                || a.numCols != b.numCols
+                || a.numRows != dst.numRows
+                || a.numCols != dst.numCols
+            {
+                return arm_status::ARM_MATH_SIZE_MISMATCH;
+            }
+            for i in 0..usize::from(a.numRows) * usize::from(a.numCols) {
+                *dst.pData.add(i) = *a.pData.add(i) $op *b.pData.add(i);
+            }
+            arm_status::ARM_MATH_SUCCESS
+        }
+    };
+}
+
+mat_elementwise!(arm_mat_add_f32, +);
+mat_elementwise!(arm_mat_sub_f32, -);
+
+/// Defines arm_mat_inverse_f32 or arm_mat_inverse_f64, which use Gauss-Jordan elimination and
+/// only swap rows when a pivot is zero
+macro_rules! mat_inverse {
+    ($name:ident, $instance:ty, $float:ty) => {
+        #[no_mangle]
+        unsafe extern "C" fn $name(src: *const $instance, dst: *mut $instance) -> arm_status::Type {
+            let (src, dst) = (&*src, &*dst);
+            if src.numRows != src.numCols
+                || dst.numRows != src.numRows
+                || dst.numCols != src.numCols
+            {
+                return arm_status::ARM_MATH_SIZE_MISMATCH;
+            }
+            let n = usize::from(src.numRows);
+            let a = core::slice::from_raw_parts_mut(src.pData, n * n);
+            let inv = core::slice::from_raw_parts_mut(dst.pData, n * n);
+            for (i, value) in inv.iter_mut().enumerate() {
+                *value = if i % (n + 1) == 0 { 1.0 } else { 0.0 };
+            }
+            for col in 0..n {
+                if a[col * n + col] == 0.0 {
+                    let swap = (col + 1..n).find(|row| a[row * n + col] != 0.0);
+                    match swap {
+                        Some(row) => {
+                            for k in 0..n {
+                                a.swap(col * n + k, row * n + k);
+                                inv.swap(col * n + k, row * n + k);
+                            }
+                        }
+                        None => return arm_status::ARM_MATH_SINGULAR,
+                    }
+                }
+                let pivot: $float = a[col * n + col];
+                for k in 0..n {
+                    a[col * n + k] /= pivot;
+                    inv[col * n + k] /= pivot;
+                }
+                for row in (0..n).filter(|row| *row != col) {
+                    let factor = a[row * n + col];
+                    for k in 0..n {
+                        a[row * n + k] -= factor * a[col * n + k];
+                        inv[row * n + k] -= factor * inv[col * n + k];
+                    }
+                }
+            }
+            arm_status::ARM_MATH_SUCCESS
+        }
+    };
+}
+
+mat_inverse!(arm_mat_inverse_f32, arm_matrix_instance_f32, float32_t);
+mat_inverse!(arm_mat_inverse_f64, arm_matrix_instance_f64, float64_t);
+
+#[no_mangle]
+unsafe extern "C" fn arm_dot_prod_q31(
+    pSrcA: *const q31_t,
+    pSrcB: *const q31_t,
+    blockSize: u32,
+    result: *mut q63_t,
+) {
+    let a = core::slice::from_raw_parts(pSrcA, blockSize as usize);
+    let b = core::slice::from_raw_parts(pSrcB, blockSize as usize);
+    *result = a
+        .iter()
+        .zip(b)
+        .map(|(a, b)| (i64::from(*a) * i64::from(*b)) >> 14)
+        .sum();
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_dot_prod_q15(
+    pSrcA: *const q15_t,
+    pSrcB: *const q15_t,
+    blockSize: u32,
+    result: *mut q63_t,
+) {
+    let a = core::slice::from_raw_parts(pSrcA, blockSize as usize);
+    let b = core::slice::from_raw_parts(pSrcB, blockSize as usize);
+    *result = a
+        .iter()
+        .zip(b)
+        .map(|(a, b)| i64::from(i32::from(*a) * i32::from(*b)))
+        .sum();
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_abs_f32(pSrc: *const float32_t, pDst: *mut float32_t, blockSize: u32) {
+    for i in 0..blockSize as usize {
+        *pDst.add(i) = (*pSrc.add(i)).abs();
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_copy_f32(pSrc: *const float32_t, pDst: *mut float32_t, blockSize: u32) {
+    for i in 0..blockSize as usize {
+        *pDst.add(i) = *pSrc.add(i);
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_fill_f32(value: float32_t, pDst: *mut float32_t, blockSize: u32) {
+    for i in 0..blockSize as usize {
+        *pDst.add(i) = value;
+    }
+}
+
+/// Defines arm_max_f32 or arm_min_f32, which return the first index of the extreme value
+macro_rules! extreme {
+    ($name:ident, $replace:tt) => {
+        #[no_mangle]
+        unsafe extern "C" fn $name(
+            pSrc: *const float32_t,
+            blockSize: u32,
+            pResult: *mut float32_t,
+            pIndex: *mut u32,
+        ) {
+            let src = core::slice::from_raw_parts(pSrc, blockSize as usize);
+            let mut index = 0;
+            for (i, value) in src.iter().enumerate() {
+                if *value $replace src[index] {
+                    index = i;
+                }
+            }
+            *pResult = src[index];
+            *pIndex = index as u32;
+        }
+    };
+}
+
+extreme!(arm_max_f32, >);
+extreme!(arm_min_f32, <);
+
+#[no_mangle]
+unsafe extern "C" fn arm_scale_f32(
+    pSrc: *const float32_t,
+    scale: float32_t,
+    pDst: *mut float32_t,
+    blockSize: u32,
+) {
+    for i in 0..blockSize as usize {
+        *pDst.add(i) = *pSrc.add(i) * scale;
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_scale_q31(
+    pSrc: *const q31_t,
+    scaleFract: q31_t,
+    shift: i8,
+    pDst: *mut q31_t,
+    blockSize: u32,
+) {
+    let shift = i32::from(shift) + 1;
+    for i in 0..blockSize as usize {
+        let scaled = ((i64::from(*pSrc.add(i)) * i64::from(scaleFract)) >> 32) as i32;
+        *pDst.add(i) = if shift >= 0 {
+            let out = scaled << shift;
+            if scaled != out >> shift {
+                0x7FFF_FFFF ^ (scaled >> 31)
+            } else {
+                out
+            }
+        } else {
+            scaled >> -shift
+        };
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_scale_q15(
+    pSrc: *const q15_t,
+    scaleFract: q15_t,
+    shift: i8,
+    pDst: *mut q15_t,
+    blockSize: u32,
+) {
+    let shift = 15 - i32::from(shift);
+    for i in 0..blockSize as usize {
+        let scaled = (i32::from(*pSrc.add(i)) * i32::from(scaleFract)) >> shift;
+        *pDst.add(i) = scaled.clamp(i32::from(i16::MIN), i32::from(i16::MAX)) as i16;
+    }
+}
+
+/// Defines arm_shift_q31, arm_shift_q15, or arm_shift_q7, which saturate left shifts
+macro_rules! shift {
+    ($name:ident, $fixed:ty, $wide:ty) => {
+        #[no_mangle]
+        unsafe extern "C" fn $name(
+            pSrc: *const $fixed,
+            shiftBits: i8,
+            pDst: *mut $fixed,
+            blockSize: u32,
+        ) {
+            for i in 0..blockSize as usize {
+                let value = *pSrc.add(i);
+                *pDst.add(i) = if shiftBits >= 0 {
+                    let shifted = <$wide>::from(value) << shiftBits;
+                    shifted.clamp(<$wide>::from(<$fixed>::MIN), <$wide>::from(<$fixed>::MAX))
+                        as $fixed
+                } else {
+                    value >> -shiftBits
+                };
+            }
+        }
+    };
+}
+
+shift!(arm_shift_q31, q31_t, i64);
+shift!(arm_shift_q15, q15_t, i32);
+shift!(arm_shift_q7, q7_t, i16);
+
+#[no_mangle]
+unsafe extern "C" fn arm_offset_f32(
+    pSrc: *const float32_t,
+    offset: float32_t,
+    pDst: *mut float32_t,
+    blockSize: u32,
+) {
+    for i in 0..blockSize as usize {
+        *pDst.add(i) = *pSrc.add(i) + offset;
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_add_f32(
+    pSrcA: *const float32_t,
+    pSrcB: *const float32_t,
+    pDst: *mut float32_t,
+    blockSize: u32,
+) {
+    for i in 0..blockSize as usize {
+        *pDst.add(i) = *pSrcA.add(i) + *pSrcB.add(i);
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_sub_f32(
+    pSrcA: *const float32_t,
+    pSrcB: *const float32_t,
+    pDst: *mut float32_t,
+    blockSize: u32,
+) {
+    for i in 0..blockSize as usize {
+        *pDst.add(i) = *pSrcA.add(i) - *pSrcB.add(i);
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_mult_f32(
+    pSrcA: *const float32_t,
+    pSrcB: *const float32_t,
+    pDst: *mut float32_t,
+    blockSize: u32,
+) {
+    for i in 0..blockSize as usize {
+        *pDst.add(i) = *pSrcA.add(i) * *pSrcB.add(i);
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_cmplx_mag_f32(
+    pSrc: *const float32_t,
+    pDst: *mut float32_t,
+    numSamples: u32,
+) {
+    for i in 0..numSamples as usize {
+        let (re, im) = (*pSrc.add(2 * i), *pSrc.add(2 * i + 1));
+        *pDst.add(i) = (re * re + im * im).sqrt();
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_cmplx_mag_squared_f32(
+    pSrc: *const float32_t,
+    pDst: *mut float32_t,
+    numSamples: u32,
+) {
+    for i in 0..numSamples as usize {
+        let (re, im) = (*pSrc.add(2 * i), *pSrc.add(2 * i + 1));
+        *pDst.add(i) = re * re + im * im;
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_cmplx_conj_f32(
+    pSrc: *const float32_t,
+    pDst: *mut float32_t,
+    numSamples: u32,
+) {
+    for i in 0..numSamples as usize {
+        *pDst.add(2 * i) = *pSrc.add(2 * i);
+        *pDst.add(2 * i + 1) = -*pSrc.add(2 * i + 1);
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_cmplx_mult_cmplx_f32(
+    pSrcA: *const float32_t,
+    pSrcB: *const float32_t,
+    pDst: *mut float32_t,
+    numSamples: u32,
+) {
+    for i in 0..numSamples as usize {
+        let (a_re, a_im) = (*pSrcA.add(2 * i), *pSrcA.add(2 * i + 1));
+        let (b_re, b_im) = (*pSrcB.add(2 * i), *pSrcB.add(2 * i + 1));
+        *pDst.add(2 * i) = a_re * b_re - a_im * b_im;
+        *pDst.add(2 * i + 1) = a_re * b_im + a_im * b_re;
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_sort_init_f32(
+    S: *mut arm_sort_instance_f32,
+    alg: arm_sort_alg::Type,
+    dir: arm_sort_dir::Type,
+) {
+    (*S).alg = alg;
+    (*S).dir = dir;
+}
+
+/// Sorts with insertion sort, which gives the same result as the other algorithms
+#[no_mangle]
+unsafe extern "C" fn arm_sort_f32(
+    S: *const arm_sort_instance_f32,
+    pSrc: *mut float32_t,
+    pDst: *mut float32_t,
+    blockSize: u32,
+) {
+    if pSrc != pDst {
+        core::ptr::copy_nonoverlapping(pSrc, pDst, blockSize as usize);
+    }
+    let dst = core::slice::from_raw_parts_mut(pDst, blockSize as usize);
+    let ascending = (*S).dir == arm_sort_dir::ARM_SORT_ASCENDING;
+    let out_of_order = |a: f32, b: f32| if ascending { a > b } else { a < b };
+    for i in 1..dst.len() {
+        let mut j = i;
+        while j > 0 && out_of_order(dst[j - 1], dst[j]) {
+            dst.swap(j - 1, j);
+            j -= 1;
+        }
+    }
+}
+
+/// Defines arm_float_to_q31, arm_float_to_q15, or arm_float_to_q7
+///
+/// These follow the ARM_MATH_ROUNDING versions, which round to the nearest value with ties
+/// away from zero, and then saturate.
+macro_rules! float_to_fixed {
+    ($name:ident, $fixed:ty, $scale:expr) => {
+        #[no_mangle]
+        unsafe extern "C" fn $name(pSrc: *const float32_t, pDst: *mut $fixed, blockSize: u32) {
+            for i in 0..blockSize as usize {
+                let value = f64::from(*pSrc.add(i)) * $scale;
+                let value = (value + if value > 0.0 { 0.5 } else { -0.5 }) as i64;
+                *pDst.add(i) = value.clamp(<$fixed>::MIN.into(), <$fixed>::MAX.into()) as $fixed;
+            }
+        }
+    };
+}
+
+float_to_fixed!(arm_float_to_q31, q31_t, 2147483648.0);
+float_to_fixed!(arm_float_to_q15, q15_t, 32768.0);
+float_to_fixed!(arm_float_to_q7, q7_t, 128.0);
+
+/// Defines arm_q31_to_float, arm_q15_to_float, or arm_q7_to_float
+macro_rules! fixed_to_float {
+    ($name:ident, $fixed:ty, $scale:expr) => {
+        #[no_mangle]
+        unsafe extern "C" fn $name(pSrc: *const $fixed, pDst: *mut float32_t, blockSize: u32) {
+            for i in 0..blockSize as usize {
+                *pDst.add(i) = *pSrc.add(i) as f32 / $scale;
+            }
+        }
+    };
+}
+
+fixed_to_float!(arm_q31_to_float, q31_t, 2147483648.0);
+fixed_to_float!(arm_q15_to_float, q15_t, 32768.0);
+fixed_to_float!(arm_q7_to_float, q7_t, 128.0);
+
+/// Defines a function that converts between fixed-point formats or copies values
+macro_rules! convert_fixed {
+    ($name:ident, $from:ty, $to:ty, |$x:ident| $convert:expr) => {
+        #[no_mangle]
+        unsafe extern "C" fn $name(pSrc: *const $from, pDst: *mut $to, blockSize: u32) {
+            for i in 0..blockSize as usize {
+                let $x = *pSrc.add(i);
+                *pDst.add(i) = $convert;
+            }
+        }
+    };
+}
+
+convert_fixed!(arm_copy_q31, q31_t, q31_t, |x| x);
+convert_fixed!(arm_q31_to_q15, q31_t, q15_t, |x| (x >> 16) as q15_t);
+convert_fixed!(arm_q31_to_q7, q31_t, q7_t, |x| (x >> 24) as q7_t);
+convert_fixed!(arm_q15_to_q31, q15_t, q31_t, |x| q31_t::from(x) << 16);
+convert_fixed!(arm_copy_q15, q15_t, q15_t, |x| x);
+convert_fixed!(arm_q15_to_q7, q15_t, q7_t, |x| (x >> 8) as q7_t);
+convert_fixed!(arm_q7_to_q31, q7_t, q31_t, |x| q31_t::from(x) << 24);
+convert_fixed!(arm_q7_to_q15, q7_t, q15_t, |x| q15_t::from(x) << 8);
+convert_fixed!(arm_copy_q7, q7_t, q7_t, |x| x);
+
+/// Defines arm_and_u32, arm_or_u32, or arm_xor_u32
+macro_rules! bitwise {
+    ($name:ident, $op:tt) => {
+        #[no_mangle]
+        unsafe extern "C" fn $name(pSrcA: *const u32, pSrcB: *const u32, pDst: *mut u32, blockSize: u32) {
+            for i in 0..blockSize as usize {
+                *pDst.add(i) = *pSrcA.add(i) $op *pSrcB.add(i);
+            }
+        }
+    };
+}
+
+bitwise!(arm_and_u32, &);
+bitwise!(arm_or_u32, |);
+bitwise!(arm_xor_u32, ^);
+
+#[no_mangle]
+unsafe extern "C" fn arm_not_u32(pSrc: *const u32, pDst: *mut u32, blockSize: u32) {
+    for i in 0..blockSize as usize {
+        *pDst.add(i) = !*pSrc.add(i);
+    }
+}
+
+/// Returns the pairs of corresponding elements of two vectors
+unsafe fn element_pairs<'a>(
+    pA: *const float32_t,
+    pB: *const float32_t,
+    blockSize: u32,
+) -> impl Iterator<Item = (f32, f32)> + 'a {
+    let a = core::slice::from_raw_parts(pA, blockSize as usize);
+    let b = core::slice::from_raw_parts(pB, blockSize as usize);
+    a.iter().copied().zip(b.iter().copied())
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_weighted_sum_f32(
+    in_: *const float32_t,
+    weigths: *const float32_t,
+    blockSize: u32,
+) -> float32_t {
+    let values = core::slice::from_raw_parts(in_, blockSize as usize);
+    let weights = core::slice::from_raw_parts(weigths, blockSize as usize);
+    let sum: f32 = values.iter().zip(weights).map(|(x, w)| x * w).sum();
+    sum / weights.iter().sum::<f32>()
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_barycenter_f32(
+    in_: *const float32_t,
+    weights: *const float32_t,
+    out: *mut float32_t,
+    nbVectors: u32,
+    vecDim: u32,
+) {
+    let dim = vecDim as usize;
+    let vectors = core::slice::from_raw_parts(in_, nbVectors as usize * dim);
+    let weights = core::slice::from_raw_parts(weights, nbVectors as usize);
+    let out = core::slice::from_raw_parts_mut(out, dim);
+    for value in out.iter_mut() {
+        *value = 0.0;
+    }
+    for (vector, weight) in vectors.chunks_exact(dim).zip(weights) {
+        for (value, x) in out.iter_mut().zip(vector) {
+            *value += x * weight;
+        }
+    }
+    let scale = 1.0 / weights.iter().sum::<f32>();
+    for value in out.iter_mut() {
+        *value *= scale;
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_euclidean_distance_f32(
+    pA: *const float32_t,
+    pB: *const float32_t,
+    blockSize: u32,
+) -> float32_t {
+    element_pairs(pA, pB, blockSize)
+        .map(|(a, b)| (a - b) * (a - b))
+        .sum::<f32>()
+        .sqrt()
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_cityblock_distance_f32(
+    pA: *const float32_t,
+    pB: *const float32_t,
+    blockSize: u32,
+) -> float32_t {
+    element_pairs(pA, pB, blockSize)
+        .map(|(a, b)| (a - b).abs())
+        .sum()
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_cosine_distance_f32(
+    pA: *const float32_t,
+    pB: *const float32_t,
+    blockSize: u32,
+) -> float32_t {
+    let (mut dot, mut power_a, mut power_b) = (0.0f32, 0.0f32, 0.0f32);
+    for (a, b) in element_pairs(pA, pB, blockSize) {
+        dot += a * b;
+        power_a += a * a;
+        power_b += b * b;
+    }
+    1.0 - dot / (power_a.sqrt() * power_b.sqrt())
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_chebyshev_distance_f32(
+    pA: *const float32_t,
+    pB: *const float32_t,
+    blockSize: u32,
+) -> float32_t {
+    element_pairs(pA, pB, blockSize)
+        .map(|(a, b)| (a - b).abs())
+        .fold(0.0, f32::max)
+}
+
+#[no_mangle]
+unsafe extern "C" fn arm_gaussian_naive_bayes_predict_f32(
+    S: *const arm_gaussian_naive_bayes_instance_f32,
+    in_: *const float32_t,
+    pBuffer: *mut float32_t,
+) -> u32 {
+    let s = &*S;
+    let (dimension, classes) = (s.vectorDimension as usize, s.numberOfClasses as usize);
+    let input = core::slice::from_raw_parts(in_, dimension);
+    let scores = core::slice::from_raw_parts_mut(pBuffer, classes);
+    for (class, score) in scores.iter_mut().enumerate() {
+        let (mut acc1, mut acc2) = (0.0f32, 0.0f32);
+        for (dim, x) in input.iter().enumerate() {
+            let theta = *s.theta.add(class * dimension + dim);
+            let sigma = *s.sigma.add(class * dimension + dim) + s.epsilon;
+            acc1 += (2.0 * core::f32::consts::PI * sigma).ln();
+            acc2 += (x - theta) * (x - theta) / sigma;
+        }
+        *score = -0.5 * acc1 - 0.5 * acc2 + (*s.classPriors.add(class)).ln();
+    }
+    let mut index = 0;
+    for (i, score) in scores.iter().enumerate() {
+        if *score > scores[index] {
+            index = i;
+        }
+    }
+    index as u32
+}
+
+/// Defines the init and predict functions of an SVM classifier. The kernel is calculated from
+/// the instance s, the input vector x, and a support vector y.
+macro_rules! svm {
+    ($init:ident, $predict:ident, $instance:ident, ($($param:ident: $type:ty),*), $kernel:expr) => {
+        #[no_mangle]
+        unsafe extern "C" fn $init(
+            S: *mut $instance,
+            nbOfSupportVectors: u32,
+            vectorDimension: u32,
+            intercept: float32_t,
+            dualCoefficients: *const float32_t,
+            supportVectors: *const float32_t,
+            classes: *const i32,
+            $($param: $type),*
+        ) {
+            *S = $instance {
+                nbOfSupportVectors,
+                vectorDimension,
+                intercept,
+                dualCoefficients,
+                supportVectors,
+                classes,
+                $($param),*
+            };
+        }
+
+        #[no_mangle]
+        unsafe extern "C" fn $predict(S: *const $instance, in_: *const float32_t, pResult: *mut i32) {
+            let s = &*S;
+            let dimension = s.vectorDimension as usize;
+            let x = core::slice::from_raw_parts(in_, dimension);
+            let mut sum = s.intercept;
+            for i in 0..s.nbOfSupportVectors as usize {
+                let y = core::slice::from_raw_parts(s.supportVectors.add(i * dimension), dimension);
+                let kernel: fn(&$instance, &[f32], &[f32]) -> f32 = $kernel;
+                sum += *s.dualCoefficients.add(i) * kernel(s, x, y);
+            }
+            *pResult = *s.classes.add(if sum <= 0.0 { 0 } else { 1 });
+        }
+    };
+}
+
+fn svm_dot(x: &[f32], y: &[f32]) -> f32 {
+    x.iter().zip(y.iter()).map(|(a, b)| a * b).sum()
+}
+
+svm!(
+    arm_svm_linear_init_f32,
+    arm_svm_linear_predict_f32,
+    arm_svm_linear_instance_f32,
+    (),
+    |_, x, y| svm_dot(x, y)
+);
+svm!(
+    arm_svm_polynomial_init_f32,
+    arm_svm_polynomial_predict_f32,
+    arm_svm_polynomial_instance_f32,
+    (degree: i32, coef0: float32_t, gamma: float32_t),
+    |s, x, y| (s.gamma * svm_dot(x, y) + s.coef0).powi(s.degree)
+);
+svm!(
+    arm_svm_rbf_init_f32,
+    arm_svm_rbf_predict_f32,
+    arm_svm_rbf_instance_f32,
+    (gamma: float32_t),
+    |s, x, y| {
+        let distance: f32 = x.iter().zip(y.iter()).map(|(a, b)| (a - b) * (a - b)).sum();
+        (-s.gamma * distance).exp()
+    }
+);
+svm!(
+    arm_svm_sigmoid_init_f32,
+    arm_svm_sigmoid_predict_f32,
+    arm_svm_sigmoid_instance_f32,
+    (coef0: float32_t, gamma: float32_t),
+    |s, x, y| (s.gamma * svm_dot(x, y) + s.coef0).tanh()
+);
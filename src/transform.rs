@@ -1,14 +1,23 @@
-//! Fast Fourier Transforms
+//! Fast Fourier Transforms, discrete cosine transforms, and discrete wavelet transforms
 
 use core::convert::TryInto;
+use core::f32::consts::{FRAC_1_SQRT_2, PI};
 use core::fmt::Debug;
+use core::marker::PhantomData;
 use core::mem::MaybeUninit;
-use core::u16;
 
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 use fixed::types::{I1F15, I1F31};
 use num_complex::{Complex, Complex32};
 
-use crate::{Error, Result, StatusCode};
+use crate::fast_math::{cos_f32, sqrt_f32};
+use crate::support::{as_complex_q15_mut_ptr, as_complex_q31_mut_ptr, as_q15_ptr, as_q31_ptr};
+#[cfg(feature = "alloc")]
+use crate::window::Window;
+use crate::{check_length, check_length_or_panic, Error, Result, StatusCode};
 
 /// FFT directions
 #[derive(Debug, Copy, Clone)]
@@ -36,6 +45,67 @@ impl Default for OutputOrder {
     }
 }
 
+/// A size of a complex FFT, which is a power of two from 16 to 4096
+///
+/// The complex FFTs can be created from an `FftLength` with `with_length`, which cannot fail
+/// because every value is a valid size.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FftLength {
+    /// 16 values
+    N16,
+    /// 32 values
+    N32,
+    /// 64 values
+    N64,
+    /// 128 values
+    N128,
+    /// 256 values
+    N256,
+    /// 512 values
+    N512,
+    /// 1024 values
+    N1024,
+    /// 2048 values
+    N2048,
+    /// 4096 values
+    N4096,
+}
+
+impl FftLength {
+    /// Converts a number of values into an FFT length
+    ///
+    /// This function returns `Error::Argument` if n is not a power of two from 16 to 4096.
+    pub fn from_usize(n: usize) -> Result<Self> {
+        match n {
+            16 => Ok(FftLength::N16),
+            32 => Ok(FftLength::N32),
+            64 => Ok(FftLength::N64),
+            128 => Ok(FftLength::N128),
+            256 => Ok(FftLength::N256),
+            512 => Ok(FftLength::N512),
+            1024 => Ok(FftLength::N1024),
+            2048 => Ok(FftLength::N2048),
+            4096 => Ok(FftLength::N4096),
+            _ => Err(Error::Argument),
+        }
+    }
+
+    /// Returns the number of values in an FFT of this length
+    pub fn as_usize(self) -> usize {
+        match self {
+            FftLength::N16 => 16,
+            FftLength::N32 => 32,
+            FftLength::N64 => 64,
+            FftLength::N128 => 128,
+            FftLength::N256 => 256,
+            FftLength::N512 => 512,
+            FftLength::N1024 => 1024,
+            FftLength::N2048 => 2048,
+            FftLength::N4096 => 4096,
+        }
+    }
+}
+
 /// Runs an FFT on floating-point real numbers
 pub struct FloatRealFft(cmsis_dsp_sys::arm_rfft_fast_instance_f32);
 
@@ -54,8 +124,24 @@ impl FloatRealFft {
         }
     }
 
+    /// Initializes an FFT with the specified length
+    ///
+    /// The real FFT does not support a length of 16, so this function returns an error for
+    /// `FftLength::N16`.
+    pub fn with_length(length: FftLength) -> Result<Self> {
+        if length == FftLength::N16 {
+            return Err(Error::Argument);
+        }
+        Self::new(length.as_usize() as u16)
+    }
+
     /// Runs a forward FFT on a set of values, placing the results in output
     ///
+    /// The output is the first half of the spectrum in a packed format. The DC and Nyquist bins
+    /// are real, so output[0] is the DC bin and output[1] is the Nyquist bin, and the other bins
+    /// follow as pairs of real and imaginary parts. `unpack_rfft_f32` converts this format into
+    /// complex values.
+    ///
     /// # Panics
     ///
     /// This function panics if input or output has a length not equal to the size of this FFT.
@@ -71,6 +157,23 @@ impl FloatRealFft {
         self.run_inner(input, output, Direction::Inverse);
     }
 
+    /// Calculates the magnitude of one bin of the forward FFT of a set of values, without
+    /// calculating the other bins
+    ///
+    /// This uses the Goertzel algorithm, which is faster than a full FFT when only a few bins
+    /// are needed. The result is the same as the magnitude of the corresponding bin of the
+    /// output of run, apart from rounding. Unlike run, this function does not modify input.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if input has a length not equal to the size of this FFT, or if bin
+    /// is not less than the size.
+    pub fn bin_magnitude(&self, input: &[f32], bin: usize) -> f32 {
+        check_fft_size(self.0.fftLenRFFT, input.len());
+        assert!(bin < input.len(), "Bin index out of range");
+        goertzel_f32(input, bin as f32, input.len() as f32)
+    }
+
     fn run_inner(&self, input: &[f32], output: &mut [f32], direction: Direction) {
         // Check length
         check_fft_size(self.0.fftLenRFFT, input.len());
@@ -87,6 +190,511 @@ impl FloatRealFft {
     }
 }
 
+/// Converts the packed output of a real FFT into the bins from DC to the Nyquist frequency
+///
+/// packed: output of `FloatRealFft::run` with N elements, where `packed[0]` is the real DC bin,
+/// `packed[1]` is the real Nyquist bin, and `packed[2 * k]` and `packed[2 * k + 1]` are the real
+/// and imaginary parts of bin k for `0 < k < N / 2`
+///
+/// spectrum: receives bins 0 through N / 2. The imaginary parts of the DC and Nyquist bins are
+/// zero.
+///
+/// # Panics
+///
+/// This function panics if the length of packed is zero or odd, or if the length of spectrum is
+/// not `packed.len() / 2 + 1`.
+pub fn unpack_rfft_f32(packed: &[f32], spectrum: &mut [Complex32]) {
+    check_packed_lengths(packed.len(), spectrum.len());
+    let last = spectrum.len() - 1;
+    spectrum[0] = Complex32::new(packed[0], 0.0);
+    spectrum[last] = Complex32::new(packed[1], 0.0);
+    for (bin, pair) in spectrum[1..last]
+        .iter_mut()
+        .zip(packed[2..].chunks_exact(2))
+    {
+        *bin = Complex32::new(pair[0], pair[1]);
+    }
+}
+
+/// Converts the bins from DC to the Nyquist frequency into the packed input format of an inverse
+/// real FFT
+///
+/// This is the inverse of `unpack_rfft_f32`. The spectrum of a real signal has real DC and
+/// Nyquist bins, so the imaginary parts of the first and last elements of spectrum are ignored.
+///
+/// # Panics
+///
+/// This function panics if the length of packed is zero or odd, or if the length of spectrum is
+/// not `packed.len() / 2 + 1`.
+pub fn pack_rfft_f32(spectrum: &[Complex32], packed: &mut [f32]) {
+    check_packed_lengths(packed.len(), spectrum.len());
+    let last = spectrum.len() - 1;
+    packed[0] = spectrum[0].re;
+    packed[1] = spectrum[last].re;
+    for (bin, pair) in spectrum[1..last]
+        .iter()
+        .zip(packed[2..].chunks_exact_mut(2))
+    {
+        pair[0] = bin.re;
+        pair[1] = bin.im;
+    }
+}
+
+/// Checks the lengths of a packed real FFT spectrum and the corresponding complex bins
+fn check_packed_lengths(packed: usize, spectrum: usize) {
+    assert!(
+        packed != 0 && packed.is_multiple_of(2),
+        "Packed spectrum length must be even and non-zero"
+    );
+    check_length_or_panic::<(usize, usize), usize>((packed / 2 + 1, spectrum));
+}
+
+/// Fills out with the center frequency of each bin of a real FFT, from DC to the Nyquist
+/// frequency
+///
+/// Bin k of an FFT of size n has the frequency `k * sample_rate / n`.
+///
+/// # Panics
+///
+/// This function panics if n is zero, or if the length of out is not `n / 2 + 1`.
+pub fn fft_bin_frequencies(n: usize, sample_rate: f32, out: &mut [f32]) {
+    assert!(n != 0, "FFT size must not be zero");
+    check_length_or_panic::<(usize, usize), usize>((n / 2 + 1, out.len()));
+    let spacing = sample_rate / n as f32;
+    for (bin, frequency) in out.iter_mut().enumerate() {
+        *frequency = bin as f32 * spacing;
+    }
+}
+
+/// Returns the bin of a real FFT of size n whose center frequency is closest to freq
+///
+/// Frequencies below zero give bin 0, and frequencies above the Nyquist frequency give bin
+/// `n / 2`.
+pub fn frequency_to_bin(freq: f32, n: usize, sample_rate: f32) -> usize {
+    let position = freq * n as f32 / sample_rate;
+    // Conversion to usize saturates at zero for negative values and NaN
+    let bin = (position + 0.5) as usize;
+    bin.min(n / 2)
+}
+
+/// Rotates a spectrum so that the DC bin is in the middle, for display
+///
+/// This moves bin 0 to index `n / 2`, like `numpy.fft.fftshift`. For an even length, applying
+/// this twice returns the original spectrum. For an odd length, use `ifft_shift_f32` to undo it.
+pub fn fft_shift_f32(spectrum: &mut [Complex32]) {
+    let half = spectrum.len() / 2;
+    spectrum.rotate_right(half);
+}
+
+/// Undoes `fft_shift_f32`, moving the DC bin from the middle back to index 0
+///
+/// This is the same as `numpy.fft.ifftshift`, and differs from `fft_shift_f32` only for odd
+/// lengths.
+pub fn ifft_shift_f32(spectrum: &mut [Complex32]) {
+    let half = spectrum.len() / 2;
+    spectrum.rotate_left(half);
+}
+
+/// Convolves a stream of blocks with a fixed filter using FFTs and overlap-add
+///
+/// The FFT of the filter is calculated once when the convolver is created. Each block of input
+/// is zero-padded to the FFT size, transformed, multiplied by the filter spectrum, and
+/// transformed back. The part of the result that extends past the end of the block is saved
+/// and added to the start of the next block, so the output is the same as the direct
+/// convolution of the whole stream with the filter, apart from rounding.
+///
+/// For a filter with M coefficients, this takes O(N log N) operations per block of an FFT of
+/// size N, compared to O(M) operations per sample for `filter::conv_f32`, so it is faster for
+/// long filters.
+///
+/// This type is available when the `alloc` feature is enabled.
+#[cfg(feature = "alloc")]
+pub struct FftConvolver {
+    fft: FloatRealFft,
+    block_size: usize,
+    /// Packed FFT of the zero-padded filter
+    filter_spectrum: Vec<f32>,
+    /// Tail of the previous result, which overlaps the next block
+    overlap: Vec<f32>,
+    /// Scratch buffers with the FFT size
+    time: Vec<f32>,
+    spectrum: Vec<f32>,
+}
+
+#[cfg(feature = "alloc")]
+impl FftConvolver {
+    /// Creates a convolver for a filter and a maximum block size
+    ///
+    /// The FFT size is the smallest power of two that is at least
+    /// `block_size + filter.len() - 1` and is one of the sizes that `FloatRealFft` supports
+    /// (32 to 4096).
+    ///
+    /// This function returns `Error::Argument` if filter is empty or block_size is zero, and
+    /// `Error::Length` if the required FFT size is larger than 4096.
+    pub fn new(filter: &[f32], block_size: usize) -> Result<Self> {
+        if filter.is_empty() || block_size == 0 {
+            return Err(Error::Argument);
+        }
+        let size = (block_size + filter.len() - 1).next_power_of_two().max(32);
+        if size > 4096 {
+            return Err(Error::Length);
+        }
+        let fft = FloatRealFft::new(size as u16)?;
+        let mut time = vec![0.0; size];
+        time[..filter.len()].copy_from_slice(filter);
+        let mut filter_spectrum = vec![0.0; size];
+        fft.run(&time, &mut filter_spectrum);
+        Ok(FftConvolver {
+            fft,
+            block_size,
+            filter_spectrum,
+            overlap: vec![0.0; filter.len() - 1],
+            time,
+            spectrum: vec![0.0; size],
+        })
+    }
+
+    /// Returns the largest number of samples that process accepts in one block
+    pub fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    /// Returns the size of the FFTs that this convolver uses
+    pub fn fft_size(&self) -> usize {
+        self.time.len()
+    }
+
+    /// Convolves a block of input with the filter
+    ///
+    /// The output continues the convolution of the previous blocks, so a long signal can be
+    /// processed in blocks of any length up to the block size.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if input and output do not have the same length, or if that length
+    /// is larger than the block size.
+    pub fn process(&mut self, input: &[f32], output: &mut [f32]) {
+        let length = check_length_or_panic::<(usize, usize), usize>((input.len(), output.len()));
+        assert!(
+            length <= self.block_size,
+            "Block must not be larger than the block size"
+        );
+        if length == 0 {
+            return;
+        }
+        let size = self.time.len();
+
+        self.time[..length].copy_from_slice(input);
+        for value in self.time[length..].iter_mut() {
+            *value = 0.0;
+        }
+        self.fft.run(&self.time, &mut self.spectrum);
+
+        // Multiply the packed spectra. The DC and Nyquist bins are real, and the other bins are
+        // complex pairs.
+        self.time[0] = self.spectrum[0] * self.filter_spectrum[0];
+        self.time[1] = self.spectrum[1] * self.filter_spectrum[1];
+        unsafe {
+            cmsis_dsp_sys::arm_cmplx_mult_cmplx_f32(
+                self.spectrum[2..].as_ptr(),
+                self.filter_spectrum[2..].as_ptr(),
+                self.time[2..].as_mut_ptr(),
+                (size as u32 - 2) / 2,
+            );
+        }
+        self.fft.run_inverse(&self.time, &mut self.spectrum);
+        let result = &self.spectrum;
+
+        for (i, out) in output.iter_mut().enumerate() {
+            *out = result[i] + self.overlap.get(i).copied().unwrap_or(0.0);
+        }
+        // The new overlap is the tail of this result plus the part of the old overlap that
+        // extends past this block. Each element only reads elements after it, which have not
+        // been updated yet.
+        let overlap_length = self.overlap.len();
+        for j in 0..overlap_length {
+            let carried = if length + j < overlap_length {
+                self.overlap[length + j]
+            } else {
+                0.0
+            };
+            self.overlap[j] = result[length + j] + carried;
+        }
+    }
+
+    /// Clears the saved overlap, so that the next block starts a new convolution
+    pub fn reset(&mut self) {
+        for value in self.overlap.iter_mut() {
+            *value = 0.0;
+        }
+    }
+}
+
+/// Reduces stationary noise in a signal with spectral subtraction
+///
+/// During a learning phase, the average magnitude spectrum of frames that contain only noise is
+/// recorded. Each processed frame is then transformed with a real FFT, the noise magnitude
+/// multiplied by an over-subtraction factor is subtracted from the magnitude of each bin, and
+/// the frame is transformed back with the phase of each bin unchanged. The magnitude of each bin
+/// is limited below by the noise magnitude multiplied by a spectral floor, which reduces the
+/// "musical noise" that full subtraction leaves.
+///
+/// Frames are processed independently, without a window or overlap. For continuous audio,
+/// apply a window and overlap-add the processed frames.
+///
+/// This type is available when the `alloc` feature is enabled.
+#[cfg(feature = "alloc")]
+pub struct SpectralSubtractor {
+    fft: FloatRealFft,
+    over_subtraction: f32,
+    floor: f32,
+    /// Average noise magnitude of each bin from DC to the Nyquist frequency
+    noise: Vec<f32>,
+    /// Number of frames included in noise
+    noise_frames: u32,
+    /// Scratch buffers with the FFT size
+    time: Vec<f32>,
+    spectrum: Vec<f32>,
+}
+
+#[cfg(feature = "alloc")]
+impl SpectralSubtractor {
+    /// Creates a subtractor with no noise profile
+    ///
+    /// fft_size: number of samples in each frame, which must be a size that `FloatRealFft`
+    /// supports
+    ///
+    /// over_subtraction: factor by which the noise magnitude is multiplied before it is
+    /// subtracted. Values from 1 to about 4 are typical; larger values remove more noise and
+    /// more of the signal.
+    ///
+    /// floor: smallest magnitude of a bin after subtraction, as a fraction of the noise
+    /// magnitude, for example 0.01
+    ///
+    /// This function returns an error if fft_size is not valid, or if over_subtraction or floor
+    /// is negative or not finite.
+    pub fn new(fft_size: u16, over_subtraction: f32, floor: f32) -> Result<Self> {
+        let valid = |value: f32| value.is_finite() && value >= 0.0;
+        if !valid(over_subtraction) || !valid(floor) {
+            return Err(Error::Argument);
+        }
+        let fft = FloatRealFft::new(fft_size)?;
+        let size = usize::from(fft_size);
+        Ok(SpectralSubtractor {
+            fft,
+            over_subtraction,
+            floor,
+            noise: vec![0.0; size / 2 + 1],
+            noise_frames: 0,
+            time: vec![0.0; size],
+            spectrum: vec![0.0; size],
+        })
+    }
+
+    /// Returns the average noise magnitude of each bin from DC to the Nyquist frequency
+    pub fn noise_profile(&self) -> &[f32] {
+        &self.noise
+    }
+
+    /// Adds a frame that contains only noise to the noise profile
+    ///
+    /// The profile is the average of the magnitude spectra of all frames passed to this function
+    /// since the subtractor was created or the profile was cleared.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the length of frame is not equal to the FFT size.
+    pub fn learn_noise(&mut self, frame: &[f32]) {
+        self.transform(frame);
+        self.noise_frames += 1;
+        let weight = 1.0 / self.noise_frames as f32;
+        let half = self.time.len() / 2;
+        for (bin, noise) in self.noise.iter_mut().enumerate() {
+            let magnitude = packed_bin_magnitude(&self.spectrum, bin, half);
+            *noise += weight * (magnitude - *noise);
+        }
+    }
+
+    /// Clears the noise profile
+    pub fn clear_noise(&mut self) {
+        for noise in self.noise.iter_mut() {
+            *noise = 0.0;
+        }
+        self.noise_frames = 0;
+    }
+
+    /// Removes the learned noise from a frame, in place
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the length of frame is not equal to the FFT size.
+    pub fn process(&mut self, frame: &mut [f32]) {
+        self.transform(frame);
+        let half = self.time.len() / 2;
+        for (bin, noise) in self.noise.iter().enumerate() {
+            let magnitude = packed_bin_magnitude(&self.spectrum, bin, half);
+            let target = (magnitude - self.over_subtraction * noise).max(self.floor * noise);
+            let gain = if magnitude > 0.0 {
+                target / magnitude
+            } else {
+                0.0
+            };
+            match bin {
+                0 => self.spectrum[0] *= gain,
+                bin if bin == half => self.spectrum[1] *= gain,
+                bin => {
+                    self.spectrum[2 * bin] *= gain;
+                    self.spectrum[2 * bin + 1] *= gain;
+                }
+            }
+        }
+        self.fft.run_inverse(&self.spectrum, &mut self.time);
+        frame.copy_from_slice(&self.time);
+    }
+
+    /// Calculates the packed spectrum of a frame into the spectrum buffer
+    fn transform(&mut self, frame: &[f32]) {
+        check_length_or_panic::<(usize, usize), usize>((self.time.len(), frame.len()));
+        self.time.copy_from_slice(frame);
+        self.fft.run(&self.time, &mut self.spectrum);
+    }
+}
+
+/// Estimates the power spectral density of a signal with Welch's method
+///
+/// The signal is split into segments that overlap by a fixed fraction of their length. Each
+/// segment is multiplied by a window and transformed with a real FFT, and the squared magnitudes
+/// of the spectra are averaged. Averaging reduces the variance of the estimate compared to a
+/// single periodogram of the whole signal, at the cost of frequency resolution.
+///
+/// The estimate is one-sided and normalized to a sample rate of 1: bin k covers the frequency
+/// `k / segment_length` times the sample rate, and the powers of all bins add up to about
+/// segment_length times the mean square of the signal. To get a density per hertz, divide each
+/// bin by the sample rate. For white noise with variance v, each bin other than DC and Nyquist
+/// is about `2 * v`.
+///
+/// This type is available when the `alloc` feature is enabled.
+#[cfg(feature = "alloc")]
+pub struct Welch {
+    fft: FloatRealFft,
+    window: Vec<f32>,
+    /// Sum of the squared window values
+    window_power: f32,
+    /// Distance between the starts of consecutive segments
+    hop: usize,
+    /// Scratch buffers with the segment length
+    time: Vec<f32>,
+    spectrum: Vec<f32>,
+}
+
+#[cfg(feature = "alloc")]
+impl Welch {
+    /// Creates an estimator
+    ///
+    /// segment_length: number of samples in each segment, which must be a size that
+    /// `FloatRealFft` supports
+    ///
+    /// overlap: fraction of each segment that overlaps the next one, in the range [0, 1). 0.5 is
+    /// typical with a Hanning window.
+    ///
+    /// window: the window applied to each segment
+    ///
+    /// This function returns an error if segment_length is not valid or overlap is not in the
+    /// range [0, 1).
+    pub fn new(segment_length: u16, overlap: f32, window: Window) -> Result<Self> {
+        if !(0.0..1.0).contains(&overlap) {
+            return Err(Error::Argument);
+        }
+        let fft = FloatRealFft::new(segment_length)?;
+        let size = usize::from(segment_length);
+        let mut window_values = vec![0.0; size];
+        window.fill(&mut window_values);
+        let window_power = window_values.iter().map(|w| w * w).sum();
+        let hop = ((size as f32 * (1.0 - overlap) + 0.5) as usize).max(1);
+        Ok(Welch {
+            fft,
+            window: window_values,
+            window_power,
+            hop,
+            time: vec![0.0; size],
+            spectrum: vec![0.0; size],
+        })
+    }
+
+    /// Returns the number of samples in each segment
+    pub fn segment_length(&self) -> usize {
+        self.window.len()
+    }
+
+    /// Returns the number of segments that a signal of signal_length samples is split into
+    ///
+    /// Samples after the end of the last complete segment are not used.
+    pub fn segments(&self, signal_length: usize) -> usize {
+        if signal_length < self.window.len() {
+            0
+        } else {
+            (signal_length - self.window.len()) / self.hop + 1
+        }
+    }
+
+    /// Estimates the power spectral density of signal, placing the `segment_length / 2 + 1`
+    /// bins from DC to the Nyquist frequency in psd
+    ///
+    /// # Panics
+    ///
+    /// This function panics if signal is shorter than one segment, or if the length of psd is
+    /// not `segment_length / 2 + 1`.
+    pub fn process(&mut self, signal: &[f32], psd: &mut [f32]) {
+        let size = self.window.len();
+        let half = size / 2;
+        check_length_or_panic::<(usize, usize), usize>((half + 1, psd.len()));
+        let segments = self.segments(signal.len());
+        assert!(segments != 0, "Signal must contain at least one segment");
+
+        for value in psd.iter_mut() {
+            *value = 0.0;
+        }
+        for segment in signal.windows(size).step_by(self.hop) {
+            for ((value, x), w) in self.time.iter_mut().zip(segment).zip(self.window.iter()) {
+                *value = x * w;
+            }
+            self.fft.run(&self.time, &mut self.spectrum);
+            for (bin, value) in psd.iter_mut().enumerate() {
+                *value += packed_bin_power(&self.spectrum, bin, half);
+            }
+        }
+
+        let scale = 1.0 / (segments as f32 * self.window_power);
+        for (bin, value) in psd.iter_mut().enumerate() {
+            // Fold the negative frequencies into the positive ones. DC and Nyquist have no
+            // negative counterpart.
+            let one_sided = if bin == 0 || bin == half { 1.0 } else { 2.0 };
+            *value *= scale * one_sided;
+        }
+    }
+}
+
+/// Returns the magnitude of a bin of a packed real FFT spectrum, where half is the index of the
+/// Nyquist bin
+#[cfg(feature = "alloc")]
+fn packed_bin_magnitude(spectrum: &[f32], bin: usize, half: usize) -> f32 {
+    sqrt_f32(packed_bin_power(spectrum, bin, half))
+}
+
+/// Returns the squared magnitude of a bin of a packed real FFT spectrum, where half is the index
+/// of the Nyquist bin
+#[cfg(feature = "alloc")]
+fn packed_bin_power(spectrum: &[f32], bin: usize, half: usize) -> f32 {
+    match bin {
+        0 => spectrum[0] * spectrum[0],
+        bin if bin == half => spectrum[1] * spectrum[1],
+        bin => {
+            let (re, im) = (spectrum[2 * bin], spectrum[2 * bin + 1]);
+            re * re + im * im
+        }
+    }
+}
+
 /// Runs an FFT on Q1.15 fixed-point real numbers
 pub struct Q15RealFft(cmsis_dsp_sys::arm_rfft_instance_q15);
 
@@ -121,7 +729,7 @@ impl Q15RealFft {
         check_fft_size(self.0.fftLenReal, output.len());
 
         unsafe {
-            cmsis_dsp_sys::arm_rfft_q15(&self.0, input.as_ptr() as *mut _, output.as_mut_ptr());
+            cmsis_dsp_sys::arm_rfft_q15(&self.0, as_q15_ptr(input) as *mut _, output.as_mut_ptr());
         }
     }
 }
@@ -159,7 +767,7 @@ impl Q31RealFft {
         check_fft_size(self.0.fftLenReal, output.len());
 
         unsafe {
-            cmsis_dsp_sys::arm_rfft_q31(&self.0, input.as_ptr() as *mut _, output.as_mut_ptr());
+            cmsis_dsp_sys::arm_rfft_q31(&self.0, as_q31_ptr(input) as *mut _, output.as_mut_ptr());
         }
     }
 }
@@ -175,24 +783,28 @@ unsafe impl Send for FloatFft {}
 impl FloatFft {
     /// Initializes an FFT with the specified size
     ///
-    /// Valid size values are 32, 64, 128, 256, 512, 1024, 2048, and 4096. This function returns
-    /// an error if the size value is not valid.
+    /// Valid size values are 16, 32, 64, 128, 256, 512, 1024, 2048, and 4096. This function
+    /// returns an error if the size value is not valid.
     pub fn new(size: u16) -> Result<Self> {
+        Ok(Self::with_length(FftLength::from_usize(usize::from(size))?))
+    }
+
+    /// Initializes an FFT with the specified length
+    pub fn with_length(length: FftLength) -> Self {
         let instance = unsafe {
-            match size {
-                16 => &cmsis_dsp_sys::arm_cfft_sR_f32_len16,
-                32 => &cmsis_dsp_sys::arm_cfft_sR_f32_len32,
-                64 => &cmsis_dsp_sys::arm_cfft_sR_f32_len64,
-                128 => &cmsis_dsp_sys::arm_cfft_sR_f32_len128,
-                256 => &cmsis_dsp_sys::arm_cfft_sR_f32_len256,
-                512 => &cmsis_dsp_sys::arm_cfft_sR_f32_len512,
-                1024 => &cmsis_dsp_sys::arm_cfft_sR_f32_len1024,
-                2048 => &cmsis_dsp_sys::arm_cfft_sR_f32_len2048,
-                4096 => &cmsis_dsp_sys::arm_cfft_sR_f32_len4096,
-                _ => return Err(Error::Argument),
+            match length {
+                FftLength::N16 => &cmsis_dsp_sys::arm_cfft_sR_f32_len16,
+                FftLength::N32 => &cmsis_dsp_sys::arm_cfft_sR_f32_len32,
+                FftLength::N64 => &cmsis_dsp_sys::arm_cfft_sR_f32_len64,
+                FftLength::N128 => &cmsis_dsp_sys::arm_cfft_sR_f32_len128,
+                FftLength::N256 => &cmsis_dsp_sys::arm_cfft_sR_f32_len256,
+                FftLength::N512 => &cmsis_dsp_sys::arm_cfft_sR_f32_len512,
+                FftLength::N1024 => &cmsis_dsp_sys::arm_cfft_sR_f32_len1024,
+                FftLength::N2048 => &cmsis_dsp_sys::arm_cfft_sR_f32_len2048,
+                FftLength::N4096 => &cmsis_dsp_sys::arm_cfft_sR_f32_len4096,
             }
         };
-        Ok(FloatFft { instance })
+        FloatFft { instance }
     }
 
     /// Runs the FFT in-place on a buffer of values
@@ -211,6 +823,58 @@ impl FloatFft {
     }
 }
 
+/// Runs an FFT on floating-point complex numbers, with an instance that is configured at run time
+///
+/// `FloatFft` refers to one of the constant instances that CMSIS-DSP provides for each length.
+/// This type instead contains an instance that `arm_cfft_init_f32` configures for the requested
+/// length. The instance refers to the same constant tables, so the results are the same as those
+/// of a `FloatFft` with the same length.
+pub struct DynamicComplexFft(cmsis_dsp_sys::arm_cfft_instance_f32);
+
+unsafe impl Send for DynamicComplexFft {}
+
+impl DynamicComplexFft {
+    /// Initializes an FFT with the specified number of complex values
+    ///
+    /// This function returns `Error::Unsupported` if the linked version of CMSIS-DSP does not
+    /// support the length. In this version, the supported lengths are the powers of two from 16
+    /// to 4096.
+    pub fn new(length: u16) -> Result<Self> {
+        let mut data = MaybeUninit::<cmsis_dsp_sys::arm_cfft_instance_f32>::uninit();
+        unsafe {
+            cmsis_dsp_sys::arm_cfft_init_f32(data.as_mut_ptr(), length)
+                .check_status()
+                .map_err(|error| match error {
+                    Error::Argument => Error::Unsupported,
+                    other => other,
+                })?;
+            Ok(DynamicComplexFft(data.assume_init()))
+        }
+    }
+
+    /// Returns the size of this FFT, the number of complex values that it processes
+    pub fn size(&self) -> usize {
+        usize::from(self.0.fftLen)
+    }
+
+    /// Runs the FFT in-place on a buffer of values
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the length of data is not equal to the length of this FFT.
+    pub fn run(&self, data: &mut [Complex32], direction: Direction, output_order: OutputOrder) {
+        check_fft_size(self.0.fftLen, data.len());
+        unsafe {
+            cmsis_dsp_sys::arm_cfft_f32(
+                &self.0,
+                data.as_mut_ptr() as *mut f32,
+                direction as _,
+                output_order as _,
+            );
+        }
+    }
+}
+
 /// Runs a 128-bin FFT on floating-point data
 ///
 /// This can offer slightly better performance than FloatFft because it skips the data
@@ -363,40 +1027,46 @@ unsafe impl Send for Q15Fft {}
 impl Q15Fft {
     /// Initializes an FFT with the specified size
     ///
-    /// Valid size values are 32, 64, 128, 256, 512, 1024, 2048, and 4096. This function returns
-    /// an error if the size value is not valid.
+    /// Valid size values are 16, 32, 64, 128, 256, 512, 1024, 2048, and 4096. This function
+    /// returns an error if the size value is not valid.
     pub fn new(size: u16, direction: Direction, output_order: OutputOrder) -> Result<Self> {
+        Ok(Self::with_length(
+            FftLength::from_usize(usize::from(size))?,
+            direction,
+            output_order,
+        ))
+    }
+
+    /// Initializes an FFT with the specified length
+    pub fn with_length(length: FftLength, direction: Direction, output_order: OutputOrder) -> Self {
         let instance = unsafe {
-            match size {
-                16 => &cmsis_dsp_sys::arm_cfft_sR_q15_len16,
-                32 => &cmsis_dsp_sys::arm_cfft_sR_q15_len32,
-                64 => &cmsis_dsp_sys::arm_cfft_sR_q15_len64,
-                128 => &cmsis_dsp_sys::arm_cfft_sR_q15_len128,
-                256 => &cmsis_dsp_sys::arm_cfft_sR_q15_len256,
-                512 => &cmsis_dsp_sys::arm_cfft_sR_q15_len512,
-                1024 => &cmsis_dsp_sys::arm_cfft_sR_q15_len1024,
-                2048 => &cmsis_dsp_sys::arm_cfft_sR_q15_len2048,
-                4096 => &cmsis_dsp_sys::arm_cfft_sR_q15_len4096,
-                _ => return Err(Error::Argument),
+            match length {
+                FftLength::N16 => &cmsis_dsp_sys::arm_cfft_sR_q15_len16,
+                FftLength::N32 => &cmsis_dsp_sys::arm_cfft_sR_q15_len32,
+                FftLength::N64 => &cmsis_dsp_sys::arm_cfft_sR_q15_len64,
+                FftLength::N128 => &cmsis_dsp_sys::arm_cfft_sR_q15_len128,
+                FftLength::N256 => &cmsis_dsp_sys::arm_cfft_sR_q15_len256,
+                FftLength::N512 => &cmsis_dsp_sys::arm_cfft_sR_q15_len512,
+                FftLength::N1024 => &cmsis_dsp_sys::arm_cfft_sR_q15_len1024,
+                FftLength::N2048 => &cmsis_dsp_sys::arm_cfft_sR_q15_len2048,
+                FftLength::N4096 => &cmsis_dsp_sys::arm_cfft_sR_q15_len4096,
             }
         };
-
-        Ok(Q15Fft {
+        Q15Fft {
             instance,
             direction,
             output_order,
-        })
+        }
     }
 
     /// Runs the FFT in-place on a buffer of values
     pub fn run(&self, data: &mut [Complex<I1F15>]) {
         unsafe {
             // FFT size is number of complex values. arm_cfft_q15 expects size * 2 u16 values.
-            // Complex<I1F15> is layout-compatible.
             check_fft_size((*self.instance).fftLen, data.len());
             cmsis_dsp_sys::arm_cfft_q15(
                 self.instance,
-                data.as_mut_ptr() as *mut _,
+                as_complex_q15_mut_ptr(data),
                 self.direction as _,
                 self.output_order as _,
             );
@@ -415,24 +1085,28 @@ unsafe impl Send for Q31Fft {}
 impl Q31Fft {
     /// Initializes an FFT with the specified size
     ///
-    /// Valid size values are 32, 64, 128, 256, 512, 1024, 2048, and 4096. This function returns
-    /// an error if the size value is not valid.
+    /// Valid size values are 16, 32, 64, 128, 256, 512, 1024, 2048, and 4096. This function
+    /// returns an error if the size value is not valid.
     pub fn new(size: u16) -> Result<Self> {
+        Ok(Self::with_length(FftLength::from_usize(usize::from(size))?))
+    }
+
+    /// Initializes an FFT with the specified length
+    pub fn with_length(length: FftLength) -> Self {
         let instance = unsafe {
-            match size {
-                16 => &cmsis_dsp_sys::arm_cfft_sR_q31_len16,
-                32 => &cmsis_dsp_sys::arm_cfft_sR_q31_len32,
-                64 => &cmsis_dsp_sys::arm_cfft_sR_q31_len64,
-                128 => &cmsis_dsp_sys::arm_cfft_sR_q31_len128,
-                256 => &cmsis_dsp_sys::arm_cfft_sR_q31_len256,
-                512 => &cmsis_dsp_sys::arm_cfft_sR_q31_len512,
-                1024 => &cmsis_dsp_sys::arm_cfft_sR_q31_len1024,
-                2048 => &cmsis_dsp_sys::arm_cfft_sR_q31_len2048,
-                4096 => &cmsis_dsp_sys::arm_cfft_sR_q31_len4096,
-                _ => return Err(Error::Argument),
+            match length {
+                FftLength::N16 => &cmsis_dsp_sys::arm_cfft_sR_q31_len16,
+                FftLength::N32 => &cmsis_dsp_sys::arm_cfft_sR_q31_len32,
+                FftLength::N64 => &cmsis_dsp_sys::arm_cfft_sR_q31_len64,
+                FftLength::N128 => &cmsis_dsp_sys::arm_cfft_sR_q31_len128,
+                FftLength::N256 => &cmsis_dsp_sys::arm_cfft_sR_q31_len256,
+                FftLength::N512 => &cmsis_dsp_sys::arm_cfft_sR_q31_len512,
+                FftLength::N1024 => &cmsis_dsp_sys::arm_cfft_sR_q31_len1024,
+                FftLength::N2048 => &cmsis_dsp_sys::arm_cfft_sR_q31_len2048,
+                FftLength::N4096 => &cmsis_dsp_sys::arm_cfft_sR_q31_len4096,
             }
         };
-        Ok(Q31Fft { instance })
+        Q31Fft { instance }
     }
 
     /// Runs the FFT in-place on a buffer of values
@@ -444,11 +1118,10 @@ impl Q31Fft {
     ) {
         unsafe {
             // FFT size is number of complex values. arm_cfft_q31 expects size * 2 u32 values.
-            // Complex<I1F31> is layout-compatible.
             check_fft_size((*self.instance).fftLen, data.len());
             cmsis_dsp_sys::arm_cfft_q31(
                 self.instance,
-                data.as_mut_ptr() as *mut _,
+                as_complex_q31_mut_ptr(data),
                 direction as _,
                 output_order as _,
             );
@@ -456,15 +1129,1253 @@ impl Q31Fft {
     }
 }
 
-/// Checks that an FFT size is equal to the number of values in an input or output slice
-fn check_fft_size<N>(size: N, value_count: usize)
-where
-    usize: TryInto<N>,
-    <usize as TryInto<N>>::Error: Debug,
-    N: Debug + PartialEq,
-{
-    let value_count = value_count
-        .try_into()
-        .expect("Value count too large for FFT size type");
-    assert_eq!(size, value_count);
+/// Calculates the analytic signal of a real signal
+///
+/// The real part of the analytic signal is the input, and the imaginary part is its Hilbert
+/// transform. The magnitude of each output value is the instantaneous envelope of the input,
+/// and the phase is the instantaneous phase.
+///
+/// This function transforms the input with an FFT, removes the negative-frequency bins, doubles
+/// the positive-frequency bins (leaving the DC and Nyquist bins unchanged), and transforms the
+/// result back. Because the FFT treats the input as periodic, values near the start and end of
+/// the output are affected by the discontinuity between the last and first input samples.
+///
+/// The input length must be one of the valid sizes of a [`FloatFft`]. This function returns an
+/// error if it is not.
+///
+/// # Panics
+///
+/// This function panics if input and output do not have the same length.
+pub fn analytic_signal_f32(input: &[f32], output: &mut [Complex32]) -> Result<()> {
+    let size = check_length_or_panic::<(usize, usize), usize>((input.len(), output.len()));
+    let fft = FloatFft::new(size.try_into().map_err(|_| Error::Argument)?)?;
+    for (value, sample) in output.iter_mut().zip(input.iter()) {
+        *value = Complex32::new(*sample, 0.0);
+    }
+    fft.run(output, Direction::Forward, OutputOrder::Standard);
+    let half = size / 2;
+    for value in output[1..half].iter_mut() {
+        *value *= 2.0;
+    }
+    for value in output[half + 1..].iter_mut() {
+        *value = Complex32::new(0.0, 0.0);
+    }
+    fft.run(output, Direction::Inverse, OutputOrder::Standard);
+    Ok(())
+}
+
+/// Storage for the FFT instances that a floating-point DCT4 uses internally
+///
+/// The DCT4 refers to these instances, so this storage must remain in place while the DCT4 exists.
+pub struct Dct4Storage {
+    rfft: MaybeUninit<cmsis_dsp_sys::arm_rfft_instance_f32>,
+    cfft: MaybeUninit<cmsis_dsp_sys::arm_cfft_radix4_instance_f32>,
+}
+
+impl Dct4Storage {
+    /// Creates uninitialized storage
+    pub fn new() -> Self {
+        Dct4Storage {
+            rfft: MaybeUninit::uninit(),
+            cfft: MaybeUninit::uninit(),
+        }
+    }
+}
+
+impl Default for Dct4Storage {
+    fn default() -> Self {
+        Dct4Storage::new()
+    }
+}
+
+/// Runs a type IV discrete cosine transform on floating-point values
+///
+/// The DCT4 is its own inverse, up to a scale factor. With a normalizing factor of
+/// `sqrt(2 / size)`, running the transform twice returns the original values.
+pub struct FloatDct4<'s> {
+    instance: cmsis_dsp_sys::arm_dct4_instance_f32,
+    /// The storage that contains the FFT instances that instance points to
+    _storage: PhantomData<&'s mut Dct4Storage>,
+}
+
+unsafe impl Send for FloatDct4<'_> {}
+
+impl<'s> FloatDct4<'s> {
+    /// Initializes a DCT4 with the specified size
+    ///
+    /// Valid size values are 128, 512, 2048, and 8192. This function returns an error if the size
+    /// value is not valid.
+    ///
+    /// normalize: factor that all output values are multiplied by
+    ///
+    /// storage: storage for the FFT instances used by the DCT4
+    pub fn new(size: u16, normalize: f32, storage: &'s mut Dct4Storage) -> Result<Self> {
+        let mut data = MaybeUninit::<cmsis_dsp_sys::arm_dct4_instance_f32>::uninit();
+        unsafe {
+            cmsis_dsp_sys::arm_dct4_init_f32(
+                data.as_mut_ptr(),
+                storage.rfft.as_mut_ptr(),
+                storage.cfft.as_mut_ptr(),
+                size,
+                size / 2,
+                normalize,
+            )
+            .check_status()?;
+            Ok(FloatDct4 {
+                instance: data.assume_init(),
+                _storage: PhantomData,
+            })
+        }
+    }
+
+    /// Runs the DCT4 in-place on a buffer of values
+    ///
+    /// state: scratch buffer with twice as many elements as the size of this DCT4
+    ///
+    /// # Panics
+    ///
+    /// This function panics if data does not have a length equal to the size of this DCT4,
+    /// or if state does not have twice that length.
+    pub fn run(&self, data: &mut [f32], state: &mut [f32]) {
+        check_fft_size(self.instance.N, data.len());
+        check_fft_size(self.instance.N, state.len() / 2);
+        assert_eq!(state.len() % 2, 0);
+        unsafe {
+            cmsis_dsp_sys::arm_dct4_f32(&self.instance, state.as_mut_ptr(), data.as_mut_ptr());
+        }
+    }
+}
+
+/// Calculates mel-frequency cepstral coefficients (MFCCs) of frames of audio
+///
+/// This follows `arm_mfcc_f32` from later versions of CMSIS-DSP. Each frame is scaled so that
+/// its largest absolute value is 1, multiplied by a window, and transformed with a real FFT. The
+/// magnitudes of the bins from DC to the Nyquist frequency are weighted with each mel filter and
+/// summed, and the logarithms of the sums (plus 1e-6, so that silence does not give negative
+/// infinity) are multiplied by a DCT matrix.
+///
+/// The MFCC borrows precomputed tables, like the ones that the CMSIS-DSP `mfccdata.py` script
+/// generates. Mel filter i covers `filter_lengths[i]` bins starting at bin `filter_pos[i]`, and
+/// its weights are stored one filter after another in `filter_coefs`.
+pub struct Mfcc<'a> {
+    fft: FloatRealFft,
+    /// Number of MFCCs calculated for each frame
+    nb_dct_outputs: u32,
+    /// DCT matrix with one row per output and one column per mel filter, in row-major order
+    dct_coefs: &'a [f32],
+    /// First FFT bin of each mel filter
+    filter_pos: &'a [u32],
+    /// Number of FFT bins in each mel filter
+    filter_lengths: &'a [u32],
+    /// Weights of all mel filters
+    filter_coefs: &'a [f32],
+    /// Window applied to each frame
+    window_coefs: &'a [f32],
+}
+
+impl<'a> Mfcc<'a> {
+    /// Creates an MFCC calculator
+    ///
+    /// fft_len: frame length and FFT size. Valid values are 32, 64, 128, 256, 512, 1024, 2048,
+    /// and 4096.
+    ///
+    /// nb_mel_filters: number of mel filters, which must be between 1 and `fft_len + 2`
+    ///
+    /// nb_dct_outputs: number of MFCCs calculated for each frame, which must not be zero
+    ///
+    /// dct_coefs: DCT matrix with nb_dct_outputs rows and nb_mel_filters columns, in row-major
+    /// order
+    ///
+    /// filter_pos: first FFT bin of each mel filter, with nb_mel_filters elements
+    ///
+    /// filter_lengths: number of FFT bins in each mel filter, with nb_mel_filters elements
+    ///
+    /// filter_coefs: weights of the mel filters, with the sum of filter_lengths elements
+    ///
+    /// window_coefs: window with fft_len elements
+    ///
+    /// This function returns `Error::LengthMismatch` if a table does not have the length above,
+    /// `Error::Argument` if a count is not valid or a mel filter extends past the Nyquist bin
+    /// `fft_len / 2`, or an error if fft_len is not valid.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        fft_len: u16,
+        nb_mel_filters: u32,
+        nb_dct_outputs: u32,
+        dct_coefs: &'a [f32],
+        filter_pos: &'a [u32],
+        filter_lengths: &'a [u32],
+        filter_coefs: &'a [f32],
+        window_coefs: &'a [f32],
+    ) -> Result<Self> {
+        let fft = FloatRealFft::new(fft_len)?;
+        let nb_mel = nb_mel_filters as usize;
+        // The mel filter outputs are stored in the scratch buffer
+        if nb_mel == 0 || nb_mel > usize::from(fft_len) + 2 || nb_dct_outputs == 0 {
+            return Err(Error::Argument);
+        }
+        check_length::<(usize, usize), usize>((nb_dct_outputs as usize * nb_mel, dct_coefs.len()))?;
+        check_length::<(usize, usize), usize>((nb_mel, filter_pos.len()))?;
+        check_length::<(usize, usize), usize>((nb_mel, filter_lengths.len()))?;
+        let total_length = filter_lengths.iter().map(|length| *length as usize).sum();
+        check_length::<(usize, usize), usize>((total_length, filter_coefs.len()))?;
+        check_length::<(usize, usize), usize>((usize::from(fft_len), window_coefs.len()))?;
+        let bins = usize::from(fft_len) / 2 + 1;
+        let in_range = filter_pos
+            .iter()
+            .zip(filter_lengths)
+            .all(|(pos, length)| *pos as usize + *length as usize <= bins);
+        if !in_range {
+            return Err(Error::Argument);
+        }
+        Ok(Mfcc {
+            fft,
+            nb_dct_outputs,
+            dct_coefs,
+            filter_pos,
+            filter_lengths,
+            filter_coefs,
+            window_coefs,
+        })
+    }
+
+    /// Calculates the MFCCs of a frame
+    ///
+    /// input: frame with fft_len samples, which is used as a scratch buffer and modified. If all
+    /// samples are zero, the frame is not scaled.
+    ///
+    /// output: receives nb_dct_outputs MFCCs
+    ///
+    /// scratch: scratch buffer with `fft_len + 2` elements
+    ///
+    /// # Panics
+    ///
+    /// This function panics if input, output, or scratch does not have the length above.
+    pub fn process(&self, input: &mut [f32], output: &mut [f32], scratch: &mut [f32]) {
+        let fft_len = usize::from(self.fft.0.fftLenRFFT);
+        let length = check_length_or_panic::<(usize, usize), u32>((fft_len, input.len()));
+        let nb_mel = self.filter_pos.len();
+        check_length_or_panic::<(usize, usize), usize>((
+            self.nb_dct_outputs as usize,
+            output.len(),
+        ));
+        check_length_or_panic::<(usize, usize), usize>((fft_len + 2, scratch.len()));
+
+        // Scale to a peak of 1 and apply the window
+        let (mut max, mut min, mut index) = (0.0, 0.0, 0);
+        unsafe {
+            cmsis_dsp_sys::arm_max_f32(input.as_ptr(), length, &mut max, &mut index);
+            cmsis_dsp_sys::arm_min_f32(input.as_ptr(), length, &mut min, &mut index);
+        }
+        let peak = if -min > max { -min } else { max };
+        let ptr = input.as_mut_ptr();
+        unsafe {
+            if peak != 0.0 {
+                cmsis_dsp_sys::arm_scale_f32(ptr, 1.0 / peak, ptr, length);
+            }
+            cmsis_dsp_sys::arm_mult_f32(ptr, self.window_coefs.as_ptr(), ptr, length);
+        }
+
+        // Magnitudes of bins 0 through fft_len / 2. The Nyquist bin moves from the imaginary part
+        // of the DC bin to the end of the spectrum.
+        self.fft.run(input, &mut scratch[..fft_len]);
+        scratch[fft_len] = scratch[1];
+        scratch[fft_len + 1] = 0.0;
+        scratch[1] = 0.0;
+        let bins = fft_len / 2 + 1;
+        unsafe {
+            cmsis_dsp_sys::arm_cmplx_mag_f32(scratch.as_ptr(), input.as_mut_ptr(), bins as u32);
+        }
+
+        // Mel filters, followed by the logarithm
+        let mut coefs = self.filter_coefs;
+        for ((pos, length), energy) in self
+            .filter_pos
+            .iter()
+            .zip(self.filter_lengths)
+            .zip(scratch.iter_mut())
+        {
+            let (filter, rest) = coefs.split_at(*length as usize);
+            unsafe {
+                cmsis_dsp_sys::arm_dot_prod_f32(
+                    input[*pos as usize..].as_ptr(),
+                    filter.as_ptr(),
+                    *length,
+                    energy,
+                );
+            }
+            coefs = rest;
+        }
+        let energies = scratch.as_mut_ptr();
+        unsafe {
+            cmsis_dsp_sys::arm_offset_f32(energies, 1.0e-6, energies, nb_mel as u32);
+            cmsis_dsp_sys::arm_vlog_f32(energies, energies, nb_mel as u32);
+        }
+
+        // DCT
+        for (row, value) in self.dct_coefs.chunks_exact(nb_mel).zip(output.iter_mut()) {
+            unsafe {
+                cmsis_dsp_sys::arm_dot_prod_f32(row.as_ptr(), energies, nb_mel as u32, value);
+            }
+        }
+    }
+}
+
+/// DTMF row frequencies in hertz
+const DTMF_ROWS: [f32; 4] = [697.0, 770.0, 852.0, 941.0];
+/// DTMF column frequencies in hertz
+const DTMF_COLUMNS: [f32; 4] = [1209.0, 1336.0, 1477.0, 1633.0];
+/// DTMF keys, indexed by row and then column
+const DTMF_KEYS: [[char; 4]; 4] = [
+    ['1', '2', '3', 'A'],
+    ['4', '5', '6', 'B'],
+    ['7', '8', '9', 'C'],
+    ['*', '0', '#', 'D'],
+];
+
+/// Decodes dual-tone multi-frequency (telephone keypad) signals
+///
+/// The decoder runs a Goertzel filter at each of the eight DTMF frequencies. A key is detected
+/// when the strongest row tone and the strongest column tone each contain a significant
+/// fraction of the signal energy.
+///
+/// Each block of samples should contain at least one tone period of the lowest frequency
+/// difference between DTMF tones (about 205 samples at 8 kHz).
+pub struct DtmfDecoder {
+    /// Goertzel coefficients for the row frequencies
+    row_coeffs: [f32; 4],
+    /// Goertzel coefficients for the column frequencies
+    column_coeffs: [f32; 4],
+    /// Minimum mean square of a block that can contain a key
+    min_energy: f32,
+}
+
+impl DtmfDecoder {
+    /// Creates a decoder for the provided sample rate in hertz
+    ///
+    /// Blocks with a mean square value below min_energy are treated as silence.
+    pub fn new(sample_rate: f32, min_energy: f32) -> Self {
+        let mut row_coeffs = [0.0; 4];
+        let mut column_coeffs = [0.0; 4];
+        for (coeff, frequency) in row_coeffs.iter_mut().zip(DTMF_ROWS.iter()) {
+            *coeff = goertzel_coeff(*frequency, sample_rate);
+        }
+        for (coeff, frequency) in column_coeffs.iter_mut().zip(DTMF_COLUMNS.iter()) {
+            *coeff = goertzel_coeff(*frequency, sample_rate);
+        }
+        DtmfDecoder {
+            row_coeffs,
+            column_coeffs,
+            min_energy,
+        }
+    }
+
+    /// Processes a block of samples and returns the key that they contain, or None if they
+    /// do not contain a key
+    pub fn process(&self, samples: &[f32]) -> Option<char> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut energy = 0.0;
+        unsafe {
+            cmsis_dsp_sys::arm_power_f32(samples.as_ptr(), samples.len() as u32, &mut energy);
+        }
+        if energy / (samples.len() as f32) < self.min_energy {
+            return None;
+        }
+        // For a sinusoid that contains all the signal energy, the Goertzel power is
+        // about `samples.len() * energy / 2`.
+        let full_scale = samples.len() as f32 * energy / 2.0;
+        let row = strongest_tone(samples, &self.row_coeffs, full_scale)?;
+        let column = strongest_tone(samples, &self.column_coeffs, full_scale)?;
+        Some(DTMF_KEYS[row][column])
+    }
+}
+
+/// Returns the index of the strongest tone, if it contains at least 20% of full_scale
+/// and at least twice as much power as every other tone
+fn strongest_tone(samples: &[f32], coeffs: &[f32; 4], full_scale: f32) -> Option<usize> {
+    let mut powers = [0.0; 4];
+    for (power, coeff) in powers.iter_mut().zip(coeffs.iter()) {
+        *power = goertzel_power(samples, *coeff);
+    }
+    let (strongest, strongest_power) =
+        powers
+            .iter()
+            .cloned()
+            .enumerate()
+            .fold(
+                (0, 0.0),
+                |best, (i, power)| if power > best.1 { (i, power) } else { best },
+            );
+    let dominant = powers
+        .iter()
+        .enumerate()
+        .all(|(i, power)| i == strongest || *power * 2.0 <= strongest_power);
+    if dominant && strongest_power >= 0.2 * full_scale {
+        Some(strongest)
+    } else {
+        None
+    }
+}
+
+/// Calculates the magnitude of one frequency component of a signal with the Goertzel algorithm
+///
+/// This is equivalent to the magnitude of one bin of a DFT of the samples, and is faster than
+/// an FFT when only a few frequencies are needed. For a sinusoid with amplitude A at
+/// target_freq, the result is about `A * samples.len() / 2`.
+///
+/// The frequency resolution is `sample_rate / samples.len()`: frequencies closer together
+/// than this cannot be distinguished. A target frequency that is not a multiple of the
+/// resolution also picks up some energy from nearby frequencies.
+pub fn goertzel_f32(samples: &[f32], target_freq: f32, sample_rate: f32) -> f32 {
+    let power = goertzel_power(samples, goertzel_coeff(target_freq, sample_rate));
+    // Rounding can make the power slightly negative when it should be zero
+    sqrt_f32(if power > 0.0 { power } else { 0.0 })
+}
+
+/// Calculates the Goertzel coefficient `2 * cos(2 * pi * frequency / sample_rate)`
+fn goertzel_coeff(frequency: f32, sample_rate: f32) -> f32 {
+    2.0 * cos_f32(2.0 * PI * frequency / sample_rate)
+}
+
+/// Runs the Goertzel algorithm and returns the squared magnitude of the result
+fn goertzel_power(samples: &[f32], coeff: f32) -> f32 {
+    let mut s1 = 0.0;
+    let mut s2 = 0.0;
+    for sample in samples {
+        let s = sample + coeff * s1 - s2;
+        s2 = s1;
+        s1 = s;
+    }
+    s1 * s1 + s2 * s2 - coeff * s1 * s2
+}
+
+/// Wavelets supported by `Dwt`
+#[derive(Debug, Copy, Clone)]
+pub enum Wavelet {
+    /// The Haar wavelet, with 2 filter coefficients
+    Haar,
+    /// The Daubechies wavelet with 2 vanishing moments (db2), with 4 filter coefficients
+    Daubechies2,
+    /// The Daubechies wavelet with 3 vanishing moments (db3), with 6 filter coefficients
+    Daubechies3,
+}
+
+impl Wavelet {
+    /// Returns the scaling (lowpass) filter coefficients of this wavelet
+    fn scaling_filter(self) -> &'static [f32] {
+        match self {
+            Wavelet::Haar => &[FRAC_1_SQRT_2, FRAC_1_SQRT_2],
+            Wavelet::Daubechies2 => &[0.482_962_9, 0.836_516_3, 0.224_143_87, -0.129_409_52],
+            Wavelet::Daubechies3 => &[
+                0.332_670_55,
+                0.806_891_5,
+                0.459_877_5,
+                -0.135_011_02,
+                -0.085_441_27,
+                0.035_226_29,
+            ],
+        }
+    }
+}
+
+/// Runs discrete wavelet transforms with an orthogonal wavelet
+///
+/// The transforms treat the signal as periodic, so each level of the transform turns n samples
+/// into n / 2 approximation coefficients and n / 2 detail coefficients, and the inverse
+/// transform reconstructs the signal exactly (apart from rounding errors).
+pub struct Dwt {
+    /// Scaling (lowpass) filter coefficients
+    scaling: &'static [f32],
+}
+
+impl Dwt {
+    /// Creates a transform that uses the provided wavelet
+    pub fn new(wavelet: Wavelet) -> Self {
+        Dwt {
+            scaling: wavelet.scaling_filter(),
+        }
+    }
+
+    /// Runs one level of the forward transform
+    ///
+    /// # Panics
+    ///
+    /// This function panics if src has an odd length, or if approx and detail do not each have
+    /// half the length of src.
+    pub fn forward(&self, src: &[f32], approx: &mut [f32], detail: &mut [f32]) {
+        let n = check_dwt_lengths(src.len(), approx.len(), detail.len());
+        let taps = self.scaling.len();
+        for (k, (approx, detail)) in approx.iter_mut().zip(detail.iter_mut()).enumerate() {
+            let mut a = 0.0;
+            let mut d = 0.0;
+            for (i, h) in self.scaling.iter().enumerate() {
+                let x = src[(2 * k + i) % n];
+                a += h * x;
+                d += self.wavelet_coeff(taps, i) * x;
+            }
+            *approx = a;
+            *detail = d;
+        }
+    }
+
+    /// Runs one level of the inverse transform
+    ///
+    /// # Panics
+    ///
+    /// This function panics if dst has an odd length, or if approx and detail do not each have
+    /// half the length of dst.
+    pub fn inverse(&self, approx: &[f32], detail: &[f32], dst: &mut [f32]) {
+        let n = check_dwt_lengths(dst.len(), approx.len(), detail.len());
+        let taps = self.scaling.len();
+        for value in dst.iter_mut() {
+            *value = 0.0;
+        }
+        for (k, (a, d)) in approx.iter().zip(detail.iter()).enumerate() {
+            for (i, h) in self.scaling.iter().enumerate() {
+                dst[(2 * k + i) % n] += h * a + self.wavelet_coeff(taps, i) * d;
+            }
+        }
+    }
+
+    /// Runs a multi-level forward transform in place
+    ///
+    /// After the transform, data contains the approximation coefficients of the last level,
+    /// followed by the detail coefficients of each level from the last to the first. For
+    /// example, a 2-level transform of 8 samples produces `[a2, a2, d2, d2, d1, d1, d1, d1]`.
+    ///
+    /// scratch: temporary buffer. The array is of length data.len().
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the length of data is not a multiple of `2^levels`, or if
+    /// scratch does not have the same length as data.
+    pub fn forward_multilevel(&self, data: &mut [f32], levels: u32, scratch: &mut [f32]) {
+        check_multilevel_lengths(data.len(), levels, scratch.len());
+        let mut length = data.len();
+        for _ in 0..levels {
+            let (approx, detail) = scratch[..length].split_at_mut(length / 2);
+            self.forward(&data[..length], approx, detail);
+            data[..length].copy_from_slice(&scratch[..length]);
+            length /= 2;
+        }
+    }
+
+    /// Runs a multi-level inverse transform in place
+    ///
+    /// data must have the layout produced by `forward_multilevel` with the same number of
+    /// levels.
+    ///
+    /// scratch: temporary buffer. The array is of length data.len().
+    ///
+    /// # Panics
+    ///
+    /// This function panics if the length of data is not a multiple of `2^levels`, or if
+    /// scratch does not have the same length as data.
+    pub fn inverse_multilevel(&self, data: &mut [f32], levels: u32, scratch: &mut [f32]) {
+        check_multilevel_lengths(data.len(), levels, scratch.len());
+        for level in (0..levels).rev() {
+            let length = data.len() >> level;
+            let (approx, detail) = data[..length].split_at(length / 2);
+            self.inverse(approx, detail, &mut scratch[..length]);
+            data[..length].copy_from_slice(&scratch[..length]);
+        }
+    }
+
+    /// Returns coefficient i of the wavelet (highpass) filter, `(-1)^i * h[taps - 1 - i]`
+    fn wavelet_coeff(&self, taps: usize, i: usize) -> f32 {
+        let h = self.scaling[taps - 1 - i];
+        if i.is_multiple_of(2) {
+            h
+        } else {
+            -h
+        }
+    }
+}
+
+/// Checks the lengths for one level of a wavelet transform and returns the signal length
+fn check_dwt_lengths(signal: usize, approx: usize, detail: usize) -> usize {
+    assert!(signal.is_multiple_of(2), "Signal length must be even");
+    check_length_or_panic::<(usize, usize, usize), usize>((signal / 2, approx, detail));
+    signal
+}
+
+/// Checks the lengths for a multi-level wavelet transform
+fn check_multilevel_lengths(data: usize, levels: u32, scratch: usize) {
+    check_length_or_panic::<(usize, usize), usize>((data, scratch));
+    let block = 1usize
+        .checked_shl(levels)
+        .expect("Too many wavelet transform levels");
+    assert!(
+        data != 0 && data.is_multiple_of(block),
+        "Data length must be a non-zero multiple of 2^levels"
+    );
+}
+
+/// Checks that an FFT size is equal to the number of values in an input or output slice
+fn check_fft_size<N>(size: N, value_count: usize)
+where
+    usize: TryInto<N>,
+    <usize as TryInto<N>>::Error: Debug,
+    N: Debug + PartialEq,
+{
+    let value_count = value_count
+        .try_into()
+        .expect("Value count too large for FFT size type");
+    assert_eq!(size, value_count);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::sine_f32;
+
+    /// Fills dst with the sum of two sines with an amplitude of 0.5
+    fn dual_tone(dst: &mut [f32], f1: f32, f2: f32, sample_rate: f32) {
+        let mut second = [0.0; 256];
+        sine_f32(dst, f1, sample_rate, 0.5, 0.0);
+        sine_f32(&mut second[..dst.len()], f2, sample_rate, 0.5, 0.0);
+        for (value, other) in dst.iter_mut().zip(second.iter()) {
+            *value += other;
+        }
+    }
+
+    #[test]
+    fn dtmf_decodes_every_key() {
+        let decoder = DtmfDecoder::new(8000.0, 1e-4);
+        let mut block = [0.0; 256];
+        for (row, frequency_row) in DTMF_ROWS.iter().enumerate() {
+            for (column, frequency_column) in DTMF_COLUMNS.iter().enumerate() {
+                dual_tone(&mut block, *frequency_row, *frequency_column, 8000.0);
+                assert_eq!(decoder.process(&block), Some(DTMF_KEYS[row][column]));
+            }
+        }
+    }
+
+    #[test]
+    fn dtmf_rejects_silence_and_single_tones() {
+        let decoder = DtmfDecoder::new(8000.0, 1e-4);
+        assert_eq!(decoder.process(&[0.0; 256]), None);
+        assert_eq!(decoder.process(&[]), None);
+        // Quieter than min_energy
+        let mut block = [0.0; 256];
+        dual_tone(&mut block, 697.0, 1209.0, 8000.0);
+        for value in block.iter_mut() {
+            *value *= 0.001;
+        }
+        assert_eq!(decoder.process(&block), None);
+        // A row tone without a column tone
+        sine_f32(&mut block, 770.0, 8000.0, 0.5, 0.0);
+        assert_eq!(decoder.process(&block), None);
+    }
+
+    /// Mel filters for a 64-point FFT at 8 kHz, with 8 triangular filters spaced evenly on the
+    /// mel scale from 0 to 4 kHz
+    const MEL_FILTER_POS: [u32; 8] = [1, 2, 3, 5, 8, 11, 15, 20];
+    const MEL_FILTER_LENGTHS: [u32; 8] = [2, 3, 5, 6, 7, 9, 10, 12];
+    const MEL_FILTER_COEFS: [f32; 54] = [
+        0.757843, 0.582654, 0.417346, 0.975178, 0.478813, 0.024822, 0.521187, 0.985795, 0.584085,
+        0.182375, 0.014205, 0.415915, 0.817625, 0.822492, 0.497387, 0.172282, 0.177508, 0.502613,
+        0.827718, 0.87632, 0.613211, 0.350103, 0.086994, 0.12368, 0.386789, 0.649897, 0.913006,
+        0.85747, 0.644536, 0.431601, 0.218667, 0.005732, 0.14253, 0.355464, 0.568399, 0.781333,
+        0.994268, 0.832311, 0.659982, 0.487654, 0.315325, 0.142997, 0.167689, 0.340018, 0.512346,
+        0.684675, 0.857003, 0.976262, 0.836796, 0.69733, 0.557864, 0.418398, 0.278932, 0.139466,
+    ];
+    /// Orthonormal DCT-II matrix with 5 outputs and 8 inputs
+    const MFCC_DCT_COEFS: [f32; 40] = [
+        0.353553, 0.353553, 0.353553, 0.353553, 0.353553, 0.353553, 0.353553, 0.353553, 0.490393,
+        0.415735, 0.277785, 0.097545, -0.097545, -0.277785, -0.415735, -0.490393, 0.46194,
+        0.191342, -0.191342, -0.46194, -0.46194, -0.191342, 0.191342, 0.46194, 0.415735, -0.097545,
+        -0.490393, -0.277785, 0.277785, 0.490393, 0.097545, -0.415735, 0.353553, -0.353553,
+        -0.353553, 0.353553, 0.353553, -0.353553, -0.353553, 0.353553,
+    ];
+
+    #[test]
+    fn mfcc_matches_reference() {
+        let mut window = [0.0; 64];
+        crate::window::hanning_f32(&mut window);
+        let mfcc = Mfcc::new(
+            64,
+            8,
+            5,
+            &MFCC_DCT_COEFS,
+            &MEL_FILTER_POS,
+            &MEL_FILTER_LENGTHS,
+            &MEL_FILTER_COEFS,
+            &window,
+        )
+        .unwrap();
+
+        // Two tones and a deterministic broadband component
+        let mut frame = [0.0; 64];
+        for (n, value) in frame.iter_mut().enumerate() {
+            let t = n as f32 / 8000.0;
+            *value = 0.8 * (2.0 * PI * 500.0 * t).sin()
+                + 0.3 * (2.0 * PI * 1750.0 * t + 0.5).sin()
+                + 0.1 * (((n * 37) % 17) as f32 / 17.0 - 0.5);
+        }
+        let mut output = [0.0; 5];
+        let mut scratch = [0.0; 66];
+        mfcc.process(&mut frame, &mut output, &mut scratch);
+
+        // Calculated in double precision with the same definition
+        let expected = [0.635865, -0.264846, -1.997394, -1.068399, -3.986999];
+        for (value, expected) in output.iter().zip(expected.iter()) {
+            assert!((value - expected).abs() < 1e-3, "{:?}", output);
+        }
+
+        // Scaling the frame does not change the result
+        for (n, value) in frame.iter_mut().enumerate() {
+            let t = n as f32 / 8000.0;
+            *value = 0.1
+                * (0.8 * (2.0 * PI * 500.0 * t).sin()
+                    + 0.3 * (2.0 * PI * 1750.0 * t + 0.5).sin()
+                    + 0.1 * (((n * 37) % 17) as f32 / 17.0 - 0.5));
+        }
+        let mut scaled = [0.0; 5];
+        mfcc.process(&mut frame, &mut scaled, &mut scratch);
+        for (value, expected) in scaled.iter().zip(output.iter()) {
+            assert!((value - expected).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn mfcc_of_silence() {
+        let window = [1.0; 32];
+        let mfcc = Mfcc::new(32, 1, 1, &[1.0], &[0], &[17], &[1.0; 17], &window).unwrap();
+        let mut output = [0.0];
+        mfcc.process(&mut [0.0; 32], &mut output, &mut [0.0; 34]);
+        assert!((output[0] - 1.0e-6f32.ln()).abs() < 1e-4);
+    }
+
+    #[test]
+    fn mfcc_checks_tables() {
+        let window = [1.0; 32];
+        let coefs = [1.0; 17];
+        let new = |pos: &[u32], lengths: &[u32], coefs: &[f32], window: &[f32]| {
+            Mfcc::new(32, 1, 1, &[1.0], pos, lengths, coefs, window).map(|_| ())
+        };
+        assert!(new(&[0], &[17], &coefs, &window).is_ok());
+        // A filter past the Nyquist bin
+        assert!(matches!(
+            new(&[1], &[17], &coefs, &window),
+            Err(Error::Argument)
+        ));
+        // Too few coefficients for the filter lengths
+        assert!(matches!(
+            new(&[0], &[17], &coefs[..16], &window),
+            Err(Error::LengthMismatch {
+                expected: 17,
+                got: 16
+            })
+        ));
+        assert!(matches!(
+            new(&[0, 1], &[17], &coefs, &window),
+            Err(Error::LengthMismatch { .. })
+        ));
+        assert!(matches!(
+            new(&[0], &[17], &coefs, &window[..31]),
+            Err(Error::LengthMismatch { .. })
+        ));
+        assert!(matches!(
+            Mfcc::new(32, 1, 2, &[1.0], &[0], &[17], &coefs, &window),
+            Err(Error::LengthMismatch { .. })
+        ));
+        assert!(matches!(
+            Mfcc::new(32, 0, 1, &[], &[], &[], &[], &window),
+            Err(Error::Argument)
+        ));
+        assert!(Mfcc::new(48, 1, 1, &[1.0], &[0], &[17], &coefs, &window).is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn mfcc_checks_scratch_length() {
+        let window = [1.0; 32];
+        let mfcc = Mfcc::new(32, 1, 1, &[1.0], &[0], &[17], &[1.0; 17], &window).unwrap();
+        mfcc.process(&mut [0.0; 32], &mut [0.0], &mut [0.0; 32]);
+    }
+
+    /// Returns a spectrum whose bins have the values k + ki, for each k in indices
+    fn numbered_bins<const N: usize>(indices: [usize; N]) -> [Complex32; N] {
+        indices.map(|k| Complex32::new(k as f32, k as f32))
+    }
+
+    #[test]
+    fn fft_shift_even_length() {
+        let original = numbered_bins([0, 1, 2, 3, 4, 5]);
+        let mut spectrum = original;
+        fft_shift_f32(&mut spectrum);
+        assert_eq!(spectrum, numbered_bins([3, 4, 5, 0, 1, 2]));
+        fft_shift_f32(&mut spectrum);
+        assert_eq!(spectrum, original);
+        fft_shift_f32(&mut spectrum);
+        ifft_shift_f32(&mut spectrum);
+        assert_eq!(spectrum, original);
+    }
+
+    #[test]
+    fn fft_shift_odd_length() {
+        // numpy.fft.fftshift(numpy.arange(5)) is [3, 4, 0, 1, 2]
+        let original = numbered_bins([0, 1, 2, 3, 4]);
+        let mut spectrum = original;
+        fft_shift_f32(&mut spectrum);
+        assert_eq!(spectrum, numbered_bins([3, 4, 0, 1, 2]));
+        ifft_shift_f32(&mut spectrum);
+        assert_eq!(spectrum, original);
+        // Shifting twice does not return the original
+        fft_shift_f32(&mut spectrum);
+        fft_shift_f32(&mut spectrum);
+        assert_eq!(spectrum, numbered_bins([1, 2, 3, 4, 0]));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn fft_convolver_matches_direct_convolution() {
+        // The overlap of 19 samples is longer than most of the blocks, so it is carried across
+        // several blocks
+        let filter: Vec<f32> = (0..20).map(|i| 1.0 / (1.0 + i as f32)).collect();
+        let input: Vec<f32> = (0..100)
+            .map(|n| ((n * 37) % 23) as f32 / 23.0 - 0.5)
+            .collect();
+        let mut convolver = FftConvolver::new(&filter, 16).unwrap();
+        assert_eq!(convolver.fft_size(), 64);
+
+        let mut output = vec![0.0; input.len()];
+        let mut start = 0;
+        for length in [16, 5, 1, 16, 3, 0, 7, 16, 16, 11, 9].iter().cycle() {
+            let end = (start + length).min(input.len());
+            convolver.process(&input[start..end], &mut output[start..end]);
+            start = end;
+            if start == input.len() {
+                break;
+            }
+        }
+
+        let mut expected = vec![0.0; input.len() + filter.len() - 1];
+        crate::filter::conv_f32(&input, &filter, &mut expected);
+        for (value, expected) in output.iter().zip(expected.iter()) {
+            assert!((value - expected).abs() < 1e-4, "{} != {}", value, expected);
+        }
+    }
+
+    #[test]
+    fn unpack_and_pack_rfft_round_trip() {
+        // DC of 1, Nyquist of 0.5, a cosine in bin 1 and a sine in bin 3
+        let mut signal = [0.0; 32];
+        for (n, value) in signal.iter_mut().enumerate() {
+            let phase = 2.0 * PI * n as f32 / 32.0;
+            let alternating = if n % 2 == 0 { 0.5 } else { -0.5 };
+            *value = 1.0 + alternating + phase.cos() + (3.0 * phase).sin();
+        }
+        let fft = FloatRealFft::new(32).unwrap();
+        let mut packed = [0.0; 32];
+        fft.run(&signal, &mut packed);
+
+        let mut spectrum = [Complex32::new(1.0, 1.0); 17];
+        unpack_rfft_f32(&packed, &mut spectrum);
+        assert_eq!(spectrum[0], Complex32::new(packed[0], 0.0));
+        assert_eq!(spectrum[16], Complex32::new(packed[1], 0.0));
+        let expected = |k: usize| match k {
+            0 => Complex32::new(32.0, 0.0),
+            1 => Complex32::new(16.0, 0.0),
+            3 => Complex32::new(0.0, -16.0),
+            16 => Complex32::new(16.0, 0.0),
+            _ => Complex32::new(0.0, 0.0),
+        };
+        for (k, bin) in spectrum.iter().enumerate() {
+            assert!((bin - expected(k)).l1_norm() < 1e-4, "bin {}: {}", k, bin);
+        }
+
+        // The imaginary parts of the DC and Nyquist bins are ignored
+        spectrum[0].im = 5.0;
+        spectrum[16].im = -5.0;
+        let mut repacked = [0.0; 32];
+        pack_rfft_f32(&spectrum, &mut repacked);
+        assert_eq!(repacked, packed);
+    }
+
+    #[test]
+    #[should_panic]
+    fn unpack_rfft_checks_length() {
+        unpack_rfft_f32(&[0.0; 32], &mut [Complex32::new(0.0, 0.0); 16]);
+    }
+
+    /// Returns uniformly distributed pseudo-random noise in the range [-amplitude, amplitude)
+    #[cfg(feature = "alloc")]
+    fn noise(length: usize, amplitude: f32) -> Vec<f32> {
+        let mut state = 12345u32;
+        (0..length)
+            .map(|_| {
+                state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                amplitude * ((state >> 8) as f32 / (1 << 23) as f32 - 1.0)
+            })
+            .collect()
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn welch_sinusoid_peaks_at_its_bin() {
+        let mut welch = Welch::new(64, 0.5, crate::window::Window::Hanning).unwrap();
+        let mut signal = vec![0.0; 1024];
+        // Bin 10 of a 64-point FFT
+        sine_f32(&mut signal, 1250.0, 8000.0, 1.0, 0.3);
+        assert_eq!(welch.segments(signal.len()), 31);
+        let mut psd = [0.0; 33];
+        welch.process(&signal, &mut psd);
+
+        let peak = (0..psd.len())
+            .max_by(|&a, &b| psd[a].partial_cmp(&psd[b]).unwrap())
+            .unwrap();
+        assert_eq!(peak, 10);
+        // The powers add up to segment_length times the mean square of 0.5
+        let total: f32 = psd.iter().sum();
+        assert!((total - 32.0).abs() < 0.5, "{}", total);
+        // A Hanning window leaks only into the neighbouring bins
+        for (bin, value) in psd.iter().enumerate() {
+            if !(9..=11).contains(&bin) {
+                assert!(*value < 1e-3 * psd[10], "{}: {}", bin, value);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn welch_white_noise_is_flat() {
+        let mut welch = Welch::new(64, 0.5, crate::window::Window::Hanning).unwrap();
+        // Uniform noise in [-1, 1) has a variance of 1 / 3, so each bin other than DC and
+        // Nyquist is about 2 / 3
+        let signal = noise(16384, 1.0);
+        let mut psd = [0.0; 33];
+        welch.process(&signal, &mut psd);
+
+        let expected = 2.0 / 3.0;
+        let interior = &psd[1..32];
+        let mean = interior.iter().sum::<f32>() / interior.len() as f32;
+        assert!((mean - expected).abs() < 0.05 * expected, "{}", mean);
+        for value in interior {
+            assert!((value - expected).abs() < 0.25 * expected, "{:?}", psd);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn spectral_subtractor_attenuates_noise_to_the_floor() {
+        let mut subtractor = SpectralSubtractor::new(64, 2.0, 0.1).unwrap();
+        let noise = noise(64, 0.5);
+        subtractor.learn_noise(&noise);
+        // The profile matches the frame exactly, so every bin is reduced to the floor
+        let mut frame = noise.clone();
+        subtractor.process(&mut frame);
+        for (value, original) in frame.iter().zip(noise.iter()) {
+            assert!((value - 0.1 * original).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn spectral_subtractor_keeps_a_tone_above_the_noise() {
+        let mut subtractor = SpectralSubtractor::new(64, 2.0, 0.05).unwrap();
+        let noise = noise(64 * 33, 0.1);
+        for frame in noise.chunks_exact(64).take(32) {
+            subtractor.learn_noise(frame);
+        }
+        assert_eq!(subtractor.noise_profile().len(), 33);
+
+        // A noise frame that was not in the profile is attenuated by more than 20 dB
+        let unseen = &noise[64 * 32..];
+        let mut frame = unseen.to_vec();
+        subtractor.process(&mut frame);
+        let energy = |values: &[f32]| values.iter().map(|x| x * x).sum::<f32>();
+        assert!(energy(&frame) < 0.01 * energy(unseen));
+
+        // A tone in bin 8 passes with little change
+        let mut tone = [0.0; 64];
+        sine_f32(&mut tone, 1000.0, 8000.0, 1.0, 0.0);
+        let mut frame: Vec<f32> = tone.iter().zip(unseen).map(|(t, n)| t + n).collect();
+        subtractor.process(&mut frame);
+        let error: Vec<f32> = frame.iter().zip(tone.iter()).map(|(x, t)| x - t).collect();
+        assert!(energy(&error) < 0.01 * energy(&tone));
+
+        subtractor.clear_noise();
+        assert!(subtractor.noise_profile().iter().all(|n| *n == 0.0));
+    }
+
+    /// Returns a deterministic complex test signal
+    fn complex_signal<const N: usize>() -> [Complex32; N] {
+        let mut signal = [Complex32::new(0.0, 0.0); N];
+        for (n, value) in signal.iter_mut().enumerate() {
+            *value = Complex32::new(
+                ((n * 37) % 17) as f32 / 17.0 - 0.5,
+                ((n * 11) % 13) as f32 / 13.0 - 0.5,
+            );
+        }
+        signal
+    }
+
+    #[test]
+    fn dynamic_complex_fft_matches_float_fft() {
+        for &order in [OutputOrder::Standard, OutputOrder::Raw].iter() {
+            for &direction in [Direction::Forward, Direction::Inverse].iter() {
+                let fixed = FloatFft::new(64).unwrap();
+                let dynamic = DynamicComplexFft::new(64).unwrap();
+                assert_eq!(dynamic.size(), 64);
+                let mut expected = complex_signal::<64>();
+                fixed.run(&mut expected, direction, order);
+                let mut actual = complex_signal::<64>();
+                dynamic.run(&mut actual, direction, order);
+                assert_eq!(actual, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn dynamic_complex_fft_rejects_unsupported_lengths() {
+        for &length in [0, 8, 17, 100, 8192].iter() {
+            assert!(matches!(
+                DynamicComplexFft::new(length),
+                Err(Error::Unsupported)
+            ));
+        }
+    }
+
+    #[test]
+    fn analytic_signal_of_a_cosine() {
+        // 5 cycles in 128 samples, so the tone is in bin 5
+        let mut input = [0.0; 128];
+        sine_f32(&mut input, 5.0, 128.0, 0.8, PI / 2.0);
+        let mut output = [Complex32::new(0.0, 0.0); 128];
+        analytic_signal_f32(&input, &mut output).unwrap();
+        for (n, value) in output.iter().enumerate().take(120).skip(8) {
+            let phase = 2.0 * PI * 5.0 * n as f32 / 128.0;
+            assert!((value.re - input[n]).abs() < 1e-4);
+            assert!(
+                (value.im - 0.8 * phase.sin()).abs() < 1e-4,
+                "{}: {}",
+                n,
+                value
+            );
+            assert!((value.norm_sqr() - 0.64).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn analytic_signal_checks_length() {
+        let mut output = [Complex32::new(0.0, 0.0); 100];
+        assert!(matches!(
+            analytic_signal_f32(&[0.0; 100], &mut output),
+            Err(Error::Argument)
+        ));
+    }
+
+    const FFT_LENGTHS: [FftLength; 9] = [
+        FftLength::N16,
+        FftLength::N32,
+        FftLength::N64,
+        FftLength::N128,
+        FftLength::N256,
+        FftLength::N512,
+        FftLength::N1024,
+        FftLength::N2048,
+        FftLength::N4096,
+    ];
+
+    #[test]
+    fn fft_length_from_usize() {
+        for length in FFT_LENGTHS.iter() {
+            assert_eq!(FftLength::from_usize(length.as_usize()).unwrap(), *length);
+        }
+        for &n in [0, 1, 8, 15, 17, 48, 100, 1000, 4095, 8192].iter() {
+            assert!(matches!(FftLength::from_usize(n), Err(Error::Argument)));
+        }
+    }
+
+    #[test]
+    fn with_length_agrees_with_new() {
+        for length in FFT_LENGTHS.iter() {
+            let size = length.as_usize() as u16;
+            let fixed = FloatFft::with_length(*length);
+            assert!(core::ptr::eq(
+                fixed.instance,
+                FloatFft::new(size).unwrap().instance
+            ));
+            assert_eq!(unsafe { (*fixed.instance).fftLen }, size);
+
+            match FloatRealFft::with_length(*length) {
+                Ok(real) => {
+                    let new = FloatRealFft::new(size).unwrap();
+                    assert_eq!(real.0.fftLenRFFT, size);
+                    assert_eq!(real.0.fftLenRFFT, new.0.fftLenRFFT);
+                }
+                Err(error) => {
+                    assert_eq!(*length, FftLength::N16);
+                    assert!(matches!(error, Error::Argument));
+                    assert!(FloatRealFft::new(size).is_err());
+                }
+            }
+        }
+        assert!(FloatFft::new(100).is_err());
+    }
+
+    #[test]
+    fn bin_frequencies_round_trip() {
+        let mut frequencies = [0.0; 33];
+        fft_bin_frequencies(64, 8000.0, &mut frequencies);
+        assert_eq!(frequencies[0], 0.0);
+        assert_eq!(frequencies[1], 125.0);
+        assert_eq!(frequencies[32], 4000.0);
+        for (bin, frequency) in frequencies.iter().enumerate() {
+            assert_eq!(frequency_to_bin(*frequency, 64, 8000.0), bin);
+        }
+        // Frequencies between bins go to the closest one
+        assert_eq!(frequency_to_bin(187.0, 64, 8000.0), 1);
+        assert_eq!(frequency_to_bin(188.0, 64, 8000.0), 2);
+        // Frequencies outside the range go to DC or Nyquist
+        assert_eq!(frequency_to_bin(-100.0, 64, 8000.0), 0);
+        assert_eq!(frequency_to_bin(5000.0, 64, 8000.0), 32);
+    }
+
+    #[test]
+    fn bin_frequencies_of_odd_size() {
+        let mut frequencies = [0.0; 3];
+        fft_bin_frequencies(5, 10.0, &mut frequencies);
+        assert_eq!(frequencies, [0.0, 2.0, 4.0]);
+        // The highest bin of an odd size is below the Nyquist frequency
+        assert_eq!(frequency_to_bin(5.0, 5, 10.0), 2);
+    }
+
+    #[test]
+    fn bin_magnitude_matches_fft() {
+        let mut signal = [0.0; 64];
+        for (n, value) in signal.iter_mut().enumerate() {
+            *value = ((n * 37) % 17) as f32 / 17.0 - 0.3;
+        }
+        let fft = FloatRealFft::new(64).unwrap();
+        let mut packed = [0.0; 64];
+        fft.run(&signal, &mut packed);
+        let mut spectrum = [Complex32::new(0.0, 0.0); 33];
+        unpack_rfft_f32(&packed, &mut spectrum);
+        for (bin, value) in spectrum.iter().enumerate() {
+            let magnitude = value.norm_sqr().sqrt();
+            let goertzel = fft.bin_magnitude(&signal, bin);
+            assert!(
+                (goertzel - magnitude).abs() < 1e-4 * (1.0 + magnitude),
+                "bin {}",
+                bin
+            );
+        }
+    }
+
+    #[test]
+    fn goertzel_matches_fft_bin() {
+        // 1 kHz is bin 8 of a 64-point FFT at 8 kHz
+        let mut signal = [0.0; 64];
+        sine_f32(&mut signal, 1000.0, 8000.0, 0.5, 0.7);
+        let fft = FloatRealFft::new(64).unwrap();
+        let mut packed = [0.0; 64];
+        fft.run(&signal, &mut packed);
+        let bin = Complex32::new(packed[16], packed[17]).norm_sqr().sqrt();
+
+        let magnitude = goertzel_f32(&signal, 1000.0, 8000.0);
+        assert!((magnitude - bin).abs() < 1e-3);
+        assert!((magnitude - 0.5 * 64.0 / 2.0).abs() < 1e-3);
+        // Other bins contain no energy
+        assert!(goertzel_f32(&signal, 1250.0, 8000.0) < 1e-3);
+    }
+
+    #[test]
+    fn goertzel_off_bin_tone() {
+        // 1062.5 Hz is halfway between bins 8 and 9
+        let mut signal = [0.0; 64];
+        sine_f32(&mut signal, 1062.5, 8000.0, 0.5, 0.0);
+        let full = 0.5 * 64.0 / 2.0;
+        // At the tone frequency the result is close to the full amplitude, apart from a little
+        // leakage from the negative frequency
+        let at_tone = goertzel_f32(&signal, 1062.5, 8000.0);
+        assert!((at_tone - full).abs() < 0.05 * full, "{}", at_tone);
+        // The neighbouring bins pick up about 2 / pi of it
+        for &frequency in [1000.0, 1125.0].iter() {
+            let neighbour = goertzel_f32(&signal, frequency, 8000.0);
+            assert!(
+                (neighbour - 2.0 / PI * full).abs() < 0.05 * full,
+                "{}",
+                neighbour
+            );
+        }
+        // Bins further away pick up less
+        assert!(goertzel_f32(&signal, 1500.0, 8000.0) < 0.2 * full);
+    }
+
+    const WAVELETS: [Wavelet; 3] = [Wavelet::Haar, Wavelet::Daubechies2, Wavelet::Daubechies3];
+
+    /// Returns a deterministic test signal
+    fn wavelet_signal() -> [f32; 32] {
+        let mut signal = [0.0; 32];
+        for (n, value) in signal.iter_mut().enumerate() {
+            *value = ((n * 37) % 17) as f32 / 17.0 - 0.5 + 0.01 * n as f32;
+        }
+        signal
+    }
+
+    #[test]
+    fn dwt_haar_coefficients() {
+        let dwt = Dwt::new(Wavelet::Haar);
+        let (mut approx, mut detail) = ([0.0; 2], [0.0; 2]);
+        dwt.forward(&[1.0, 3.0, 4.0, 4.0], &mut approx, &mut detail);
+        let s = FRAC_1_SQRT_2;
+        assert!((approx[0] - 4.0 * s).abs() < 1e-6 && (approx[1] - 8.0 * s).abs() < 1e-6);
+        assert!((detail[0] + 2.0 * s).abs() < 1e-6 && detail[1].abs() < 1e-6);
+    }
+
+    #[test]
+    fn dwt_perfect_reconstruction() {
+        let signal = wavelet_signal();
+        for wavelet in WAVELETS.iter() {
+            let dwt = Dwt::new(*wavelet);
+            let (mut approx, mut detail) = ([0.0; 16], [0.0; 16]);
+            dwt.forward(&signal, &mut approx, &mut detail);
+            // The transform is orthogonal, so it preserves energy
+            let energy = |values: &[f32]| values.iter().map(|x| x * x).sum::<f32>();
+            let coefficients = energy(&approx) + energy(&detail);
+            assert!(
+                (coefficients - energy(&signal)).abs() < 1e-4,
+                "{:?}",
+                wavelet
+            );
+
+            let mut output = [0.0; 32];
+            dwt.inverse(&approx, &detail, &mut output);
+            for (value, expected) in output.iter().zip(signal.iter()) {
+                assert!((value - expected).abs() < 1e-5, "{:?}", wavelet);
+            }
+        }
+    }
+
+    #[test]
+    fn dwt_multilevel_perfect_reconstruction() {
+        let signal = wavelet_signal();
+        let mut scratch = [0.0; 32];
+        for wavelet in WAVELETS.iter() {
+            let dwt = Dwt::new(*wavelet);
+            let mut data = signal;
+            dwt.forward_multilevel(&mut data, 3, &mut scratch);
+            // The last 16 values are the detail coefficients of the first level
+            let (mut approx, mut detail) = ([0.0; 16], [0.0; 16]);
+            dwt.forward(&signal, &mut approx, &mut detail);
+            assert_eq!(data[16..], detail);
+
+            dwt.inverse_multilevel(&mut data, 3, &mut scratch);
+            for (value, expected) in data.iter().zip(signal.iter()) {
+                assert!((value - expected).abs() < 1e-5, "{:?}", wavelet);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn dwt_multilevel_checks_length() {
+        let mut data = [0.0; 12];
+        Dwt::new(Wavelet::Haar).forward_multilevel(&mut data, 3, &mut [0.0; 12]);
+    }
 }
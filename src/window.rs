@@ -0,0 +1,192 @@
+//! Window functions
+//!
+//! Each function fills a destination slice with an n-point window, where n is the length of
+//! the slice. Like the CMSIS-DSP window functions (`arm_hanning_f32` and others), these windows
+//! are periodic: they are designed for spectral analysis with an n-point FFT, so
+//! `dst[i] == dst[n - i]` for `0 < i < n`, and the final point is not repeated. They use the same
+//! formulas as the CMSIS-DSP functions.
+
+use core::f32::consts::PI;
+
+use crate::fast_math::cos_f32;
+
+/// A window function, for functions that take the window as a parameter
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Window {
+    /// A rectangular window, which is equivalent to no window
+    Rectangular,
+    /// A Hanning (Hann) window, as produced by `hanning_f32`
+    Hanning,
+    /// A Hamming window, as produced by `hamming_f32`
+    Hamming,
+    /// A Blackman-Harris window, as produced by `blackman_harris_92db_f32`
+    BlackmanHarris92dB,
+    /// An HFT90D flat-top window, as produced by `hft90d_f32`
+    Hft90d,
+    /// An HFT95 flat-top window, as produced by `hft95_f32`
+    Hft95,
+    /// A Bartlett (triangular) window, as produced by `bartlett_f32`
+    Bartlett,
+    /// A Welch (parabolic) window, as produced by `welch_f32`
+    Welch,
+}
+
+impl Window {
+    /// Fills dst with this window
+    pub fn fill(&self, dst: &mut [f32]) {
+        match self {
+            Window::Rectangular => {
+                for value in dst.iter_mut() {
+                    *value = 1.0;
+                }
+            }
+            Window::Hanning => hanning_f32(dst),
+            Window::Hamming => hamming_f32(dst),
+            Window::BlackmanHarris92dB => blackman_harris_92db_f32(dst),
+            Window::Hft90d => hft90d_f32(dst),
+            Window::Hft95 => hft95_f32(dst),
+            Window::Bartlett => bartlett_f32(dst),
+            Window::Welch => welch_f32(dst),
+        }
+    }
+}
+
+/// Fills dst with a Hanning (Hann) window
+pub fn hanning_f32(dst: &mut [f32]) {
+    cosine_sum(dst, &[0.5, -0.5]);
+}
+
+/// Fills dst with a Hamming window
+pub fn hamming_f32(dst: &mut [f32]) {
+    cosine_sum(dst, &[0.54, -0.46]);
+}
+
+/// Fills dst with a 4-term Blackman-Harris window with 92 dB sidelobe attenuation
+pub fn blackman_harris_92db_f32(dst: &mut [f32]) {
+    cosine_sum(dst, &[0.35875, -0.48829, 0.14128, -0.01168]);
+}
+
+/// Fills dst with the HFT90D flat-top window
+///
+/// The window is not normalized. Its maximum value is about 4.8.
+pub fn hft90d_f32(dst: &mut [f32]) {
+    cosine_sum(dst, &[1.0, -1.942604, 1.340318, -0.440811, 0.043097]);
+}
+
+/// Fills dst with the HFT95 flat-top window
+///
+/// The window is not normalized. Its maximum value is about 4.7.
+pub fn hft95_f32(dst: &mut [f32]) {
+    cosine_sum(dst, &[1.0, -1.9383379, 1.3045202, -0.402_827, 0.035_066_5]);
+}
+
+/// Fills dst with a Bartlett (triangular) window
+pub fn bartlett_f32(dst: &mut [f32]) {
+    let k = 2.0 / dst.len() as f32;
+    for (i, value) in dst.iter_mut().enumerate() {
+        let w = i as f32 * k - 1.0;
+        *value = 1.0 - if w < 0.0 { -w } else { w };
+    }
+}
+
+/// Fills dst with a Welch (parabolic) window
+pub fn welch_f32(dst: &mut [f32]) {
+    let k = 2.0 / dst.len() as f32;
+    for (i, value) in dst.iter_mut().enumerate() {
+        let w = i as f32 * k - 1.0;
+        *value = 1.0 - w * w;
+    }
+}
+
+/// Fills dst with a generalized cosine window, `sum(coefficients[j] * cos(2 * pi * j * i / n))`
+fn cosine_sum(dst: &mut [f32], coefficients: &[f32]) {
+    let k = 2.0 * PI / dst.len() as f32;
+    for (i, value) in dst.iter_mut().enumerate() {
+        let w = i as f32 * k;
+        *value = coefficients
+            .iter()
+            .enumerate()
+            .map(|(j, coefficient)| coefficient * cos_f32(j as f32 * w))
+            .sum();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WINDOWS: [Window; 8] = [
+        Window::Rectangular,
+        Window::Hanning,
+        Window::Hamming,
+        Window::BlackmanHarris92dB,
+        Window::Hft90d,
+        Window::Hft95,
+        Window::Bartlett,
+        Window::Welch,
+    ];
+
+    /// Checks that a window is periodic: `dst[i] == dst[n - i]` for `0 < i < n`
+    fn assert_periodic(window: &[f32]) {
+        let n = window.len();
+        for i in 1..n {
+            assert!(
+                (window[i] - window[n - i]).abs() < 1e-5,
+                "{} != {} at {}",
+                window[i],
+                window[n - i],
+                i
+            );
+        }
+    }
+
+    #[test]
+    fn hanning_is_symmetric_with_zero_endpoint() {
+        for &n in &[16, 17, 64] {
+            let mut window = [0.0; 64];
+            let window = &mut window[..n];
+            hanning_f32(window);
+            assert_periodic(window);
+            assert!(window[0].abs() < 1e-6);
+            // sin^2(pi * i / n) is the same as 0.5 - 0.5 * cos(2 * pi * i / n)
+            for (i, value) in window.iter().enumerate() {
+                let expected = (PI * i as f32 / n as f32).sin().powi(2);
+                assert!((value - expected).abs() < 1e-5);
+            }
+        }
+    }
+
+    #[test]
+    fn windows_are_periodic_with_peak_at_center() {
+        for window_type in WINDOWS.iter() {
+            let mut window = [0.0; 32];
+            window_type.fill(&mut window);
+            assert_periodic(&window);
+            let peak = window.iter().cloned().fold(f32::MIN, f32::max);
+            assert_eq!(window[16], peak, "{:?}", window_type);
+        }
+    }
+
+    #[test]
+    fn window_values() {
+        let mut window = [0.0; 8];
+        hamming_f32(&mut window);
+        assert!((window[0] - 0.08).abs() < 1e-6 && (window[4] - 1.0).abs() < 1e-6);
+        blackman_harris_92db_f32(&mut window);
+        assert!((window[0] - 6e-5).abs() < 1e-6 && (window[4] - 1.0).abs() < 1e-6);
+        hft90d_f32(&mut window);
+        assert!((window[4] - 4.76683).abs() < 1e-4);
+        bartlett_f32(&mut window);
+        assert_eq!(window, [0.0, 0.25, 0.5, 0.75, 1.0, 0.75, 0.5, 0.25]);
+        welch_f32(&mut window);
+        assert_eq!(
+            window,
+            [0.0, 0.4375, 0.75, 0.9375, 1.0, 0.9375, 0.75, 0.4375]
+        );
+        Window::Rectangular.fill(&mut window);
+        assert_eq!(window, [1.0; 8]);
+        // An empty window does nothing
+        hanning_f32(&mut []);
+        bartlett_f32(&mut []);
+    }
+}